@@ -0,0 +1,52 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{ArraySize, Expression, PredefinedType, PrimaryType, Type};
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_array(
+        &mut self,
+        elements: &[Positioned<Expression>],
+        is_dynamic: bool,
+        expected: Option<&Type>,
+    ) -> Type {
+        let expected = expected.map(|ty| self.resolve_type(ty));
+        let expected = match expected.as_ref().and_then(Type::as_primary) {
+            Some(PrimaryType::ArrayType(ty, _)) => Some(Type::from((**ty).clone())),
+            _ => None,
+        };
+
+        let types = elements
+            .iter()
+            .map(|element| {
+                let expected = expected.as_ref().filter(|_| !is_spread(element));
+
+                let ty = self.check_expression(element, expected);
+
+                self.widen_to(ty, expected)
+            })
+            .collect::<Vec<_>>();
+
+        let ty = match types.is_empty() {
+            true => expected.unwrap_or(PredefinedType::Any.into()),
+            false => Type::union(types),
+        };
+
+        PrimaryType::ArrayType(
+            Box::new(match ty.as_primary() {
+                Some(ty) => ty.clone(),
+                None => PrimaryType::ParenthesizedType(Box::new(ty)),
+            }),
+            // The length of an array spread into isn't written out, so it isn't fixed
+            match is_dynamic || elements.iter().any(is_spread) {
+                true => ArraySize::Dynamic,
+                false => ArraySize::Fixed(elements.len()),
+            },
+        )
+        .into()
+    }
+}
+
+pub(crate) fn is_spread(element: &Positioned<Expression>) -> bool {
+    matches!(element.value, Expression::SpreadElement(_))
+}
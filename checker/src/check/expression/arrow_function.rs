@@ -0,0 +1,119 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    ArrowFunction, Expression, Ident, Parameter, PredefinedType, PrimaryType, Type, TypeParameter,
+};
+
+use crate::{
+    check::{statement::function::is_async, widen},
+    Checker, ReturnContext,
+};
+
+impl Checker {
+    pub fn check_arrow_function(
+        &mut self,
+        function: &Positioned<ArrowFunction>,
+        expected: Option<&Type>,
+    ) -> Type {
+        let function = &function.value;
+        let contextual = expected.and_then(|ty| self.call_signature(ty));
+        let type_parameters = function
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.value.clone())
+            .collect::<Vec<TypeParameter>>();
+
+        self.add_scope();
+        self.set_type_parameters(&type_parameters);
+
+        let mut parameters = vec![];
+
+        for (index, parameter) in function.parameters.iter().enumerate() {
+            let parameter = &parameter.value;
+            let ty = match &parameter.ty {
+                Some(ty) => {
+                    self.validate_type(ty.span, &ty.value);
+
+                    ty.value.clone()
+                }
+                None => contextual
+                    .as_ref()
+                    .and_then(|(_, parameters, _)| parameters.get(index))
+                    .map_or(PredefinedType::Any.into(), |parameter| {
+                        parameter.ty.value.clone()
+                    }),
+            };
+
+            if let Some(default) = &parameter.default {
+                let default_ty = self.check_expression(default, Some(&ty));
+
+                self.expect_assignable(default.span, &default_ty, &ty);
+            }
+
+            self.set_binding(
+                &parameter.name.value.0,
+                match parameter.nullable.value {
+                    true => Type::union(vec![ty.clone(), PredefinedType::Null.into()]),
+                    false => ty.clone(),
+                },
+                true,
+            );
+
+            parameters.push(Parameter {
+                name: parameter.name.clone(),
+                nullable: parameter.nullable.clone(),
+                ty: parameter.name.wrap(ty),
+                default: parameter.default.clone(),
+            });
+        }
+
+        let declared = match &function.ty {
+            Some(ty) => {
+                self.validate_type(ty.span, &ty.value);
+
+                Some(ty.value.clone())
+            }
+            None => None,
+        };
+        let is_async = is_async(&function.modifiers);
+        // The body of an async arrow function returns what its promise fulfills with
+        let expected = match (&function.ty, is_async) {
+            (Some(ty), true) => Some(self.promised_type(ty.span, &ty.value)),
+            (None, true) => contextual.map(|(_, _, ty)| self.awaited_type(&ty)),
+            (_, false) => declared.clone().or(contextual.map(|(_, _, ty)| ty)),
+        };
+
+        self.returns.push(ReturnContext {
+            expected: expected.clone(),
+            found: vec![],
+            yields: None,
+            is_async,
+        });
+
+        let body = self.check_expression(&function.body, expected.as_ref());
+        let context = self.returns.pop().unwrap();
+
+        let inferred = match &function.body.value {
+            Expression::Block(_) => Type::union(context.found.into_iter().map(widen).collect()),
+            _ => {
+                let body = match is_async {
+                    true => self.awaited_type(&body),
+                    false => body,
+                };
+
+                if let Some(expected) = &expected {
+                    self.expect_assignable(function.body.span, &body, expected);
+                }
+
+                widen(body)
+            }
+        };
+        let ty = declared.unwrap_or(match is_async {
+            true => PrimaryType::TypeReference(Ident::new("Promise"), vec![inferred]).into(),
+            false => inferred,
+        });
+
+        self.remove_scope();
+
+        Type::FunctionType(type_parameters, parameters, Box::new(ty))
+    }
+}
@@ -0,0 +1,64 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{Expression, PredefinedType, Type, TypeAssertion};
+
+use crate::{check::widen, Checker};
+
+impl Checker {
+    /// `expression as T` is allowed when either type is assignable to the other.
+    pub fn check_as_expression(&mut self, assertion: &Positioned<TypeAssertion>) -> Type {
+        let assertion = &assertion.value;
+        let ty = &assertion.ty;
+
+        self.validate_type(ty.span, &ty.value);
+
+        let source = self.check_expression(&assertion.expression, None);
+
+        if !self.is_assignable(&source, &ty.value) && !self.is_assignable(&ty.value, &source) {
+            self.error(
+                assertion.expression.span,
+                format!(
+                    "conversion of type \"{}\" to type \"{}\" may be a mistake",
+                    widen(source),
+                    ty.value
+                ),
+            );
+        }
+
+        ty.value.clone()
+    }
+
+    /// `expression satisfies T` checks the expression against `T` but keeps its own type.
+    pub fn check_satisfies_expression(&mut self, assertion: &Positioned<TypeAssertion>) -> Type {
+        let assertion = &assertion.value;
+        let ty = &assertion.ty;
+
+        self.validate_type(ty.span, &ty.value);
+
+        let source = self.check_expression(&assertion.expression, Some(&ty.value));
+
+        self.expect_assignable(assertion.expression.span, &source, &ty.value);
+
+        source
+    }
+
+    pub fn check_non_null_expression(&mut self, expression: &Positioned<Expression>) -> Type {
+        let ty = self.check_expression(expression, None);
+        let members = self.resolve_type(&ty).union_members();
+        let present = members
+            .iter()
+            .filter(|member| {
+                !matches!(
+                    self.resolve_type(member).as_predefined(),
+                    Some(PredefinedType::Null | PredefinedType::Undefined | PredefinedType::Void)
+                )
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match present.len() {
+            0 => PredefinedType::Never.into(),
+            length if length == members.len() => ty,
+            _ => Type::union(present),
+        }
+    }
+}
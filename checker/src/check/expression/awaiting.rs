@@ -0,0 +1,25 @@
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{Expression, Type};
+
+use crate::Checker;
+
+impl Checker {
+    /// `await` evaluates to what the promise it is given fulfills with. It is allowed in
+    /// async functions and at the top level of the program.
+    pub fn check_await_expression(
+        &mut self,
+        span: Span,
+        argument: &Positioned<Expression>,
+    ) -> Type {
+        if self.returns.last().is_some_and(|context| !context.is_async) {
+            self.error(
+                span,
+                "await is only valid in async functions and at the top level",
+            );
+        }
+
+        let ty = self.check_expression(argument, None);
+
+        self.awaited_type(&ty)
+    }
+}
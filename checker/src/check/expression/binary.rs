@@ -0,0 +1,220 @@
+use tsr_lexer::{globals::Positioned, token::Operator};
+use tsr_parser::ast::{BinaryExpression, Expression, PredefinedType, Type};
+
+use crate::{check::widen, types::is_any, Checker};
+
+fn symbol(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::And => "&",
+        Operator::AndAnd => "&&",
+        Operator::Plus => "+",
+        Operator::Star => "*",
+        Operator::Slash => "/",
+        Operator::Or => "|",
+        Operator::OrOr => "||",
+        Operator::PlusPlus => "++",
+        Operator::Minus => "-",
+        Operator::MinusMinus => "--",
+        Operator::EqEq => "==",
+        Operator::Eq => "=",
+        Operator::Ne => "!=",
+        Operator::Le => "<=",
+        Operator::Ge => ">=",
+        Operator::Lt => "<",
+        Operator::Gt => ">",
+        Operator::Not => "!",
+        Operator::EqEqEq => "===",
+        Operator::NeEq => "!==",
+        Operator::TypeOf => "typeof",
+        Operator::InstanceOf => "instanceof",
+        Operator::In => "in",
+    }
+}
+
+impl Checker {
+    pub fn check_binary_expression(&mut self, expression: &Positioned<BinaryExpression>) -> Type {
+        let (span, expression) = (expression.span, &expression.value);
+        let operator = &expression.operator.value;
+
+        match operator {
+            Operator::Eq => return self.check_assignment(&expression.left, &expression.right),
+            Operator::PlusPlus | Operator::MinusMinus => {
+                let ty = self.check_assignment_target(&expression.left);
+
+                if !self.is_numeric(&ty) {
+                    self.error(
+                        span,
+                        format!(
+                            "operator \"{}\" cannot be applied to type \"{}\"",
+                            symbol(operator),
+                            widen(ty.clone())
+                        ),
+                    );
+                }
+
+                return ty;
+            }
+            Operator::Not => {
+                self.check_expression(&expression.left, None);
+
+                return PredefinedType::Boolean.into();
+            }
+            Operator::TypeOf => {
+                self.check_expression(&expression.left, None);
+
+                return PredefinedType::String.into();
+            }
+            Operator::AndAnd | Operator::OrOr => {
+                self.check_expression(&expression.left, None);
+
+                let narrowings = self.narrowings(&expression.left, operator == &Operator::AndAnd);
+
+                self.add_scope();
+                self.apply_narrowings(&narrowings);
+                self.check_expression(&expression.right, None);
+                self.remove_scope();
+
+                return PredefinedType::Boolean.into();
+            }
+            _ => {}
+        }
+
+        let left = widen(self.check_expression(&expression.left, None));
+        let right = widen(self.check_expression(&expression.right, None));
+
+        if is_any(&self.resolve_type(&left)) || is_any(&self.resolve_type(&right)) {
+            return match operator {
+                Operator::Plus | Operator::Star | Operator::Minus | Operator::Slash => {
+                    PredefinedType::Any.into()
+                }
+                _ => PredefinedType::Boolean.into(),
+            };
+        }
+
+        let string: Type = PredefinedType::String.into();
+        let numeric = self.is_numeric(&left) && self.is_numeric(&right);
+
+        let ty = match operator {
+            Operator::Plus | Operator::Star | Operator::Minus | Operator::Slash if numeric => {
+                Some(self.numeric_result(&left, &right))
+            }
+            Operator::Plus
+                if self.is_assignable(&left, &string) && self.is_assignable(&right, &string) =>
+            {
+                Some(string)
+            }
+            Operator::Star if self.is_assignable(&left, &string) && self.is_numeric(&right) => {
+                Some(string)
+            }
+            Operator::Le | Operator::Ge | Operator::Lt | Operator::Gt if numeric => {
+                Some(PredefinedType::Boolean.into())
+            }
+            Operator::EqEq
+            | Operator::Ne
+            | Operator::EqEqEq
+            | Operator::NeEq
+            | Operator::And
+            | Operator::Or
+            | Operator::InstanceOf
+            | Operator::In => Some(PredefinedType::Boolean.into()),
+            _ => None,
+        };
+
+        ty.unwrap_or_else(|| {
+            self.error(
+                span,
+                format!(
+                    "operator \"{}\" cannot be applied to types \"{left}\" and \"{right}\"",
+                    symbol(operator)
+                ),
+            );
+
+            PredefinedType::Any.into()
+        })
+    }
+
+    fn check_assignment(
+        &mut self,
+        left: &Positioned<Expression>,
+        right: &Positioned<Expression>,
+    ) -> Type {
+        let target = self.check_assignment_target(left);
+        let ty = self.check_expression(right, Some(&target));
+        let constant = matches!(
+            &left.value,
+            Expression::Ident(ident) if self.get_binding(&ident.value.0).is_some_and(|binding| !binding.mutable)
+        );
+
+        if !constant {
+            self.expect_assignable(right.span, &ty, &target);
+        }
+
+        if let Expression::Ident(ident) = &left.value {
+            self.forget_narrowings(&ident.value.0);
+        }
+
+        target
+    }
+
+    fn check_assignment_target(&mut self, target: &Positioned<Expression>) -> Type {
+        match &target.value {
+            Expression::Ident(ident) => match self.get_declared_binding(&ident.value.0) {
+                Some(binding) => {
+                    let binding = binding.clone();
+
+                    if !binding.mutable {
+                        self.error(
+                            ident.span,
+                            format!(
+                                "cannot assign to \"{}\" because it is a constant",
+                                ident.value
+                            ),
+                        );
+                    }
+
+                    binding.ty
+                }
+                None => self.check_expression(target, None),
+            },
+            Expression::IndexExpression(expression) => self.check_index_expression(expression),
+            _ => {
+                self.check_expression(target, None);
+                self.error(
+                    target.span,
+                    "the left-hand side of an assignment must be a variable or a property access",
+                );
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+
+    /// Whether `ty` is a number or a float, or a union of them like what `Math.floor` gives.
+    fn is_numeric(&self, ty: &Type) -> bool {
+        self.resolve_type(ty).union_members().iter().all(|member| {
+            matches!(
+                widen(self.resolve_type(member)).as_predefined(),
+                Some(PredefinedType::Number | PredefinedType::Float | PredefinedType::Any)
+            )
+        })
+    }
+
+    fn numeric_result(&self, left: &Type, right: &Type) -> Type {
+        let is_float = |ty: &Type| {
+            matches!(
+                self.resolve_type(ty).as_predefined(),
+                Some(PredefinedType::Float)
+            )
+        };
+        let may_be_float = |ty: &Type| self.resolve_type(ty).union_members().iter().any(&is_float);
+
+        match [left, right] {
+            operands if operands.into_iter().any(is_float) => PredefinedType::Float.into(),
+            operands if operands.into_iter().any(may_be_float) => Type::union(vec![
+                PredefinedType::Number.into(),
+                PredefinedType::Float.into(),
+            ]),
+            _ => PredefinedType::Number.into(),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{
+    Block, Expression, FunctionCallExpression, Parameter, PredefinedType, Type, TypeParameter,
+};
+
+use super::array::is_spread;
+use crate::{
+    check::widen,
+    types::{is_any, type_arguments_count, FunctionSignature},
+    Checker, ReturnContext,
+};
+
+impl Checker {
+    pub fn check_call(&mut self, call: &Positioned<FunctionCallExpression>) -> Type {
+        let (span, call) = (call.span, &call.value);
+        let function = self.check_expression(&call.function, None);
+
+        if is_any(&self.resolve_type(&function)) {
+            for argument in &call.arguments {
+                self.check_expression(argument, None);
+            }
+
+            if let Some(lambda) = &call.lambda {
+                self.check_block(&lambda.value);
+            }
+
+            return PredefinedType::Any.into();
+        }
+
+        let signatures = self.call_signatures(&function);
+
+        match &signatures[..] {
+            [] => {
+                self.error(
+                    call.function.span,
+                    format!("type \"{}\" has no call signatures", widen(function)),
+                );
+
+                for argument in &call.arguments {
+                    self.check_expression(argument, None);
+                }
+
+                PredefinedType::Any.into()
+            }
+            [signature] => self.check_signature_call(span, signature.clone(), call),
+            _ => {
+                // Overloads are tried in declaration order, the first one without errors wins
+                for signature in signatures {
+                    let length = self.diagnostics.len();
+                    let ty = self.check_signature_call(span, signature, call);
+
+                    if self.diagnostics.len() == length {
+                        return ty;
+                    }
+
+                    self.diagnostics.truncate(length);
+                }
+
+                self.error(span, "no overload matches this call");
+
+                for argument in &call.arguments {
+                    self.check_expression(argument, None);
+                }
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+
+    fn check_signature_call(
+        &mut self,
+        span: Span,
+        signature: FunctionSignature,
+        call: &FunctionCallExpression,
+    ) -> Type {
+        let (parameters, ty) =
+            self.instantiate(span, signature, &call.type_arguments, &call.arguments);
+
+        self.check_arguments(
+            span,
+            (&parameters, ty),
+            &call.arguments,
+            call.lambda.as_ref(),
+        )
+    }
+
+    /// Binds the type parameters of a generic signature to the explicit type arguments, or
+    /// infers them from the arguments, and substitutes them into its parameters and result.
+    pub(crate) fn instantiate(
+        &mut self,
+        span: Span,
+        (type_parameters, parameters, ty): FunctionSignature,
+        type_arguments: &[Positioned<Type>],
+        arguments: &[Positioned<Expression>],
+    ) -> (Vec<Parameter>, Type) {
+        if type_parameters.is_empty() && type_arguments.is_empty() {
+            return (parameters, ty);
+        }
+
+        let mut bindings = HashMap::new();
+
+        if type_arguments.is_empty() {
+            let names = type_parameters
+                .iter()
+                .map(|parameter| parameter.name.value.0.clone())
+                .collect::<Vec<_>>();
+
+            // Arrow functions go last so that their parameters are typed by what the other
+            // arguments have bound
+            for arrow_functions in [false, true] {
+                for (parameter, argument) in parameters.iter().zip(arguments) {
+                    if matches!(argument.value, Expression::ArrowFunction(_)) != arrow_functions {
+                        continue;
+                    }
+
+                    let expected = parameter.ty.value.substitute(&bindings);
+                    let argument = widen(self.infer_silently(argument, Some(&expected)));
+
+                    parameter.ty.value.infer(&argument, &names, &mut bindings);
+                }
+            }
+        } else {
+            if let Some(message) = type_arguments_count(&type_parameters, type_arguments.len()) {
+                self.error(span, message);
+            }
+
+            for argument in type_arguments {
+                self.validate_type(argument.span, &argument.value);
+            }
+
+            bindings = TypeParameter::zip(
+                &type_parameters,
+                &type_arguments
+                    .iter()
+                    .map(|argument| argument.value.clone())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let bindings = TypeParameter::bind(&type_parameters, bindings);
+
+        for (index, parameter) in type_parameters.iter().enumerate() {
+            let Some(constraint) = &parameter.constraint else {
+                continue;
+            };
+
+            let constraint = constraint.value.substitute(&bindings);
+            let ty = &bindings[&parameter.name.value.0];
+
+            if !self.is_assignable(ty, &constraint) {
+                self.error(
+                    type_arguments
+                        .get(index)
+                        .map_or(span, |argument| argument.span),
+                    format!(
+                        "type \"{}\" does not satisfy the constraint \"{constraint}\"",
+                        widen(ty.clone())
+                    ),
+                );
+            }
+        }
+
+        (
+            parameters
+                .into_iter()
+                .map(|parameter| Parameter {
+                    ty: parameter.ty.wrap(parameter.ty.value.substitute(&bindings)),
+                    ..parameter
+                })
+                .collect(),
+            ty.substitute(&bindings),
+        )
+    }
+
+    /// Checks arguments (and a trailing lambda) against a signature, returns its result type.
+    pub(crate) fn check_arguments(
+        &mut self,
+        span: Span,
+        (parameters, ty): (&[Parameter], Type),
+        arguments: &[Positioned<Expression>],
+        lambda: Option<&Block>,
+    ) -> Type {
+        let count = arguments.len() + lambda.is_some() as usize;
+        let required = parameters
+            .iter()
+            .filter(|parameter| !parameter.nullable.value && parameter.default.is_none())
+            .count();
+
+        // Which parameters spread arguments end up in is only known once they are run
+        let spread = arguments.iter().position(is_spread);
+
+        if spread.is_none() && (count < required || count > parameters.len()) {
+            let expected = match required == parameters.len() {
+                true => required.to_string(),
+                false => format!("{required}-{}", parameters.len()),
+            };
+
+            self.error(
+                span,
+                format!("expected {expected} arguments, but got {count}"),
+            );
+        }
+
+        for (index, argument) in arguments.iter().enumerate() {
+            let expected = parameters
+                .get(index)
+                .filter(|_| spread.is_none_or(|spread| index < spread))
+                .map(|parameter| &parameter.ty.value);
+            let argument_ty = self.check_expression(argument, expected);
+
+            if let Some(expected) = expected {
+                self.expect_assignable(argument.span, &argument_ty, expected);
+            }
+        }
+
+        if let Some(lambda) = lambda {
+            let signature = parameters
+                .get(arguments.len())
+                .and_then(|parameter| self.call_signature(&parameter.ty.value));
+
+            self.add_scope();
+
+            let expected = match signature {
+                Some((_, parameters, ty)) => {
+                    for parameter in parameters {
+                        self.set_binding(&parameter.name.value.0, parameter.ty.value, true);
+                    }
+
+                    Some(ty)
+                }
+                None => None,
+            };
+
+            self.returns.push(ReturnContext {
+                expected,
+                found: vec![],
+                yields: None,
+                is_async: false,
+            });
+            self.check_statements(&lambda.value);
+            self.returns.pop();
+            self.remove_scope();
+        }
+
+        ty
+    }
+}
@@ -0,0 +1,301 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    ArraySize, Expression, Ident, IndexExpression, Literal, Parameter, PredefinedType, PrimaryType,
+    Type, TypeMember, TypeParameter,
+};
+
+use crate::{check::widen, types::is_any, Checker};
+
+/// Function type of a built-in method, parameters named with a trailing `?` are optional.
+fn method(parameters: &[(&str, Type)], ty: Type) -> Type {
+    let span = Default::default();
+
+    Type::FunctionType(
+        vec![],
+        parameters
+            .iter()
+            .map(|(name, ty)| Parameter {
+                name: Positioned::new(Ident::new(name.trim_end_matches('?')), span),
+                nullable: Positioned::new(name.ends_with('?'), span),
+                ty: Positioned::new(ty.clone(), span),
+                default: None,
+            })
+            .collect(),
+        Box::new(ty),
+    )
+}
+
+/// Makes a built-in method generic over `U`.
+fn generic(method: Type) -> Type {
+    match method {
+        Type::FunctionType(_, parameters, ty) => Type::FunctionType(
+            vec![TypeParameter {
+                name: Positioned::new(Ident::new("U"), Default::default()),
+                constraint: None,
+                default: None,
+            }],
+            parameters,
+            ty,
+        ),
+        method => method,
+    }
+}
+
+fn array_of(element: Type) -> Type {
+    PrimaryType::ArrayType(
+        Box::new(match element.as_primary() {
+            Some(element) => element.clone(),
+            None => PrimaryType::ParenthesizedType(Box::new(element)),
+        }),
+        ArraySize::Dynamic,
+    )
+    .into()
+}
+
+impl Checker {
+    pub fn check_index_expression(&mut self, expression: &Positioned<IndexExpression>) -> Type {
+        let (span, expression) = (expression.span, &expression.value);
+        let target = self.check_expression(&expression.target, None);
+
+        if let Expression::Literal(Positioned {
+            value: Literal::String(name),
+            ..
+        }) = &expression.index.value
+        {
+            return match self.property(&target, &name.value) {
+                Some(ty) => ty,
+                None => {
+                    self.error(
+                        span,
+                        format!(
+                            "property \"{}\" does not exist on type \"{}\"",
+                            name.value,
+                            widen(target)
+                        ),
+                    );
+
+                    PredefinedType::Any.into()
+                }
+            };
+        }
+
+        let index = self.check_expression(&expression.index, None);
+        let resolved = self.resolve_type(&target);
+
+        if is_any(&resolved) {
+            return resolved;
+        }
+
+        if !self.is_assignable(&index, &PredefinedType::Number.into()) {
+            return match self.members_of(&target) {
+                Some(members) if self.is_assignable(&index, &PredefinedType::String.into()) => {
+                    members
+                        .into_iter()
+                        .find_map(|member| match member {
+                            TypeMember::IndexSignature(signature) => Some(signature.value.2.value),
+                            _ => None,
+                        })
+                        .unwrap_or(PredefinedType::Any.into())
+                }
+                _ => {
+                    self.error(
+                        expression.index.span,
+                        format!("type \"{}\" cannot be used as an index type", widen(index)),
+                    );
+
+                    PredefinedType::Any.into()
+                }
+            };
+        }
+
+        match resolved.as_primary() {
+            Some(PrimaryType::ArrayType(ty, _)) => (**ty).clone().into(),
+            Some(PrimaryType::TupleType(types)) => match &expression.index.value {
+                Expression::Literal(Positioned {
+                    value: Literal::Number(index),
+                    ..
+                }) => match types.get(index.value as usize) {
+                    Some(ty) => ty.clone(),
+                    None => {
+                        self.error(
+                            expression.index.span,
+                            format!(
+                                "tuple type \"{resolved}\" of length \"{}\" has no element at index \"{}\"",
+                                types.len(),
+                                index.value
+                            ),
+                        );
+
+                        PredefinedType::Any.into()
+                    }
+                },
+                _ => Type::union(types.clone()),
+            },
+            Some(PrimaryType::PredefinedType(
+                PredefinedType::String | PredefinedType::StringLiteral(_),
+            )) => PredefinedType::String.into(),
+            _ => {
+                self.error(
+                    span,
+                    format!(
+                        "element implicitly has an \"any\" type because type \"{}\" has no index signature",
+                        widen(target)
+                    ),
+                );
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+
+    /// Type of a named property, including the built-in members of strings and arrays.
+    pub(crate) fn property(&self, target: &Type, name: &str) -> Option<Type> {
+        let resolved = self.resolve_type(target);
+
+        match resolved.as_primary() {
+            Some(PrimaryType::PredefinedType(PredefinedType::Any)) => {
+                Some(PredefinedType::Any.into())
+            }
+            Some(PrimaryType::PredefinedType(
+                PredefinedType::String | PredefinedType::StringLiteral(_),
+            )) => string_property(name),
+            Some(PrimaryType::ArrayType(..) | PrimaryType::TupleType(_)) => {
+                array_property(target, &resolved, name)
+            }
+            _ => {
+                let members = resolved.union_members();
+
+                if members.len() > 1 {
+                    return members
+                        .iter()
+                        .map(|member| self.property(member, name))
+                        .collect::<Option<Vec<_>>>()
+                        .map(Type::union);
+                }
+
+                self.property_type(&resolved, name)
+            }
+        }
+    }
+}
+
+/// Type of a built-in member of a string.
+fn string_property(name: &str) -> Option<Type> {
+    let number: Type = PredefinedType::Number.into();
+    let boolean: Type = PredefinedType::Boolean.into();
+    let string: Type = PredefinedType::String.into();
+
+    Some(match name {
+        "length" => number,
+        "at" => method(
+            &[("index", number)],
+            Type::union(vec![string, PredefinedType::Undefined.into()]),
+        ),
+        "charCodeAt" => method(&[("index?", number.clone())], number),
+        "slice" | "substring" => method(&[("start?", number.clone()), ("end?", number)], string),
+        "indexOf" => method(&[("search", string), ("position?", number.clone())], number),
+        "includes" | "startsWith" => method(&[("search", string), ("position?", number)], boolean),
+        "endsWith" => method(&[("search", string), ("end?", number)], boolean),
+        "replace" | "replaceAll" => method(
+            &[("pattern", string.clone()), ("replacement", string.clone())],
+            string,
+        ),
+        "trim" | "trimStart" | "trimEnd" | "toUpperCase" | "toLowerCase" => method(&[], string),
+        "padStart" | "padEnd" => {
+            method(&[("length", number), ("padding?", string.clone())], string)
+        }
+        "repeat" => method(&[("count", number)], string),
+        "split" => method(&[("pattern", string.clone())], array_of(string)),
+        _ => return None,
+    })
+}
+
+/// Type of a built-in member of an array. Methods changing the length only exist on dynamic
+/// arrays, and nothing mutates a readonly one.
+fn array_property(target: &Type, resolved: &Type, name: &str) -> Option<Type> {
+    let (element, dynamic): (Type, bool) = match resolved.as_primary() {
+        Some(PrimaryType::ArrayType(element, size)) => {
+            ((**element).clone().into(), *size == ArraySize::Dynamic)
+        }
+        Some(PrimaryType::TupleType(types)) => (Type::union(types.clone()), false),
+        _ => return None,
+    };
+    let readonly = matches!(target.as_primary(), Some(PrimaryType::ReadonlyType(_)));
+
+    let number: Type = PredefinedType::Number.into();
+    let boolean: Type = PredefinedType::Boolean.into();
+    let any: Type = PredefinedType::Any.into();
+    let result: Type = PrimaryType::TypeReference(Ident::new("U"), vec![]).into();
+    let array = array_of(element.clone());
+    let optional = Type::union(vec![element.clone(), PredefinedType::Undefined.into()]);
+    let callback = |ty: Type| {
+        method(
+            &[
+                ("value", element.clone()),
+                ("index", number.clone()),
+                ("array", array.clone()),
+            ],
+            ty,
+        )
+    };
+
+    Some(match name {
+        "length" => number,
+        "push" if dynamic && !readonly => method(&[("item", element)], number),
+        "pop" if dynamic && !readonly => method(&[], optional),
+        "splice" if dynamic && !readonly => method(
+            &[
+                ("start", number.clone()),
+                ("deleteCount?", number),
+                ("item?", element),
+            ],
+            array,
+        ),
+        "sort" if !readonly => method(
+            &[(
+                "compare?",
+                method(&[("first", element.clone()), ("second", element)], number),
+            )],
+            resolved.clone(),
+        ),
+        "map" => generic(method(
+            &[("callback", callback(result.clone()))],
+            array_of(result),
+        )),
+        "filter" => method(&[("predicate", callback(any))], array),
+        "reduce" => generic(method(
+            &[
+                (
+                    "callback",
+                    method(
+                        &[
+                            ("accumulator", result.clone()),
+                            ("value", element),
+                            ("index", number),
+                            ("array", array),
+                        ],
+                        result.clone(),
+                    ),
+                ),
+                ("initial?", result.clone()),
+            ],
+            result,
+        )),
+        "find" => method(&[("predicate", callback(any))], optional),
+        "some" | "every" => method(&[("predicate", callback(any))], boolean),
+        "forEach" => method(
+            &[("callback", callback(PredefinedType::Void.into()))],
+            PredefinedType::Void.into(),
+        ),
+        "slice" => method(&[("start?", number.clone()), ("end?", number)], array),
+        "join" => method(
+            &[("separator?", PredefinedType::String.into())],
+            PredefinedType::String.into(),
+        ),
+        "includes" => method(&[("item", element)], boolean),
+        "indexOf" => method(&[("item", element)], number),
+        "flat" => method(&[("depth?", number)], array_of(any)),
+        "flatMap" => method(&[("callback", callback(any.clone()))], array_of(any)),
+        _ => return None,
+    })
+}
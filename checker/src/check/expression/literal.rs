@@ -0,0 +1,15 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{Literal, PredefinedType, Type};
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_literal(&mut self, literal: &Positioned<Literal>) -> Type {
+        match &literal.value {
+            Literal::String(string) => PredefinedType::StringLiteral(string.value.clone()).into(),
+            Literal::Number(number) => PredefinedType::NumberLiteral(number.value).into(),
+            Literal::Float(_) => PredefinedType::Float.into(),
+            Literal::Boolean(boolean) => PredefinedType::BooleanLiteral(boolean.value).into(),
+        }
+    }
+}
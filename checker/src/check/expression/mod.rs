@@ -0,0 +1,101 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{Expression, Ident, PredefinedType, PrimaryType, Type};
+
+use crate::Checker;
+
+pub mod array;
+pub mod arrow_function;
+pub mod assertion;
+pub mod awaiting;
+pub mod binary;
+pub mod call;
+pub mod index;
+pub mod literal;
+pub mod new;
+pub mod object;
+pub mod switch;
+pub mod yielding;
+
+impl Checker {
+    /// Infers the type of `expression`. `expected` is the contextual type, it lets
+    /// arrow functions and array literals pick up types that were not written out.
+    pub fn check_expression(
+        &mut self,
+        expression: &Positioned<Expression>,
+        expected: Option<&Type>,
+    ) -> Type {
+        match &expression.value {
+            Expression::BinaryExpression(expression) => self.check_binary_expression(expression),
+            Expression::IndexExpression(expression) => self.check_index_expression(expression),
+            Expression::MatchExpression(expression) => self.check_match_expression(expression),
+            Expression::AsExpression(assertion) => self.check_as_expression(assertion),
+            Expression::SatisfiesExpression(assertion) => {
+                self.check_satisfies_expression(assertion)
+            }
+            Expression::NonNullExpression(expression) => self.check_non_null_expression(expression),
+            Expression::FunctionCallExpression(call) => self.check_call(call),
+            Expression::NewExpression(expression) => self.check_new_expression(expression),
+            // Modules are provided by the host, so their namespace is only known to exist
+            Expression::ImportExpression(specifier) => {
+                let string = PredefinedType::String.into();
+                let ty = self.check_expression(specifier, Some(&string));
+
+                self.expect_assignable(specifier.span, &ty, &string);
+
+                PrimaryType::TypeReference(Ident::new("Promise"), vec![PredefinedType::Any.into()])
+                    .into()
+            }
+            Expression::Block(block) => {
+                self.check_block(&block.value);
+
+                PredefinedType::Void.into()
+            }
+            Expression::Literal(literal) => self.check_literal(literal),
+            Expression::Ident(ident) => match self.get_binding(&ident.value.0) {
+                Some(binding) => binding.ty.clone(),
+                None => {
+                    self.error(ident.span, format!("cannot find name \"{}\"", ident.value));
+
+                    PredefinedType::Any.into()
+                }
+            },
+            Expression::Array {
+                elements,
+                is_dynamic,
+            } => self.check_array(elements, is_dynamic.value, expected),
+            Expression::ArrowFunction(function) => self.check_arrow_function(function, expected),
+            Expression::Object(properties) => self.check_object(properties, expected),
+            Expression::This => self
+                .this
+                .last()
+                .cloned()
+                .unwrap_or(PredefinedType::Any.into()),
+            Expression::Null => PredefinedType::Null.into(),
+            Expression::YieldExpression(expression) => self.check_yield_expression(expression),
+            Expression::AwaitExpression(argument) => {
+                self.check_await_expression(expression.span, argument)
+            }
+            // Spread elements stand for each value of their iterable
+            Expression::SpreadElement(iterable) => {
+                let ty = self.check_expression(iterable, None);
+
+                self.iterated_type(iterable.span, &ty)
+            }
+        }
+    }
+
+    /// Infers a type without reporting anything, used while declarations are hoisted
+    /// and while type arguments are inferred.
+    pub(crate) fn infer_silently(
+        &mut self,
+        expression: &Positioned<Expression>,
+        expected: Option<&Type>,
+    ) -> Type {
+        let length = self.diagnostics.len();
+        let ty = self.check_expression(expression, expected);
+
+        self.diagnostics.truncate(length);
+
+        ty
+    }
+}
@@ -0,0 +1,44 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{NewExpression, PredefinedType, Type};
+
+use crate::{check::widen, types::is_any, Checker};
+
+impl Checker {
+    pub fn check_new_expression(&mut self, expression: &Positioned<NewExpression>) -> Type {
+        let (span, expression) = (expression.span, &expression.value);
+        let class = self.check_expression(&expression.expression, None);
+
+        if is_any(&self.resolve_type(&class)) {
+            for argument in &expression.arguments {
+                self.check_expression(argument, None);
+            }
+
+            return PredefinedType::Any.into();
+        }
+
+        match self.construct_signature(&class) {
+            Some(signature) => {
+                let (parameters, ty) = self.instantiate(
+                    span,
+                    signature,
+                    &expression.type_arguments,
+                    &expression.arguments,
+                );
+
+                self.check_arguments(span, (&parameters, ty), &expression.arguments, None)
+            }
+            None => {
+                self.error(
+                    expression.expression.span,
+                    format!("type \"{}\" has no construct signatures", widen(class)),
+                );
+
+                for argument in &expression.arguments {
+                    self.check_expression(argument, None);
+                }
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    Ident, PrimaryType, PropertyAssignment, PropertyName, PropertySignature, Type, TypeMember,
+};
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_object(
+        &mut self,
+        properties: &[Positioned<PropertyAssignment>],
+        expected: Option<&Type>,
+    ) -> Type {
+        let expected = expected.map(|ty| self.resolve_type(ty));
+        let mut members = vec![];
+
+        for property in properties {
+            let property = &property.value;
+            let name = match &property.name.value {
+                PropertyName::LiteralPropertyName(literal) => Some(literal.value.to_string()),
+                PropertyName::ComputedPropertyName(expression) => {
+                    self.check_expression(expression, None);
+
+                    None
+                }
+            };
+            let expected = expected
+                .as_ref()
+                .zip(name.as_ref())
+                .and_then(|(ty, name)| self.expected_property_type(ty, name));
+            let ty = self.check_expression(&property.initializer, expected.as_ref());
+            let ty = self.widen_to(ty, expected.as_ref());
+
+            if let Some(name) = name {
+                members.push(TypeMember::PropertySignature(property.name.wrap(
+                    PropertySignature {
+                        modifiers: vec![],
+                        name: property.name.wrap(Ident(name)),
+                        nullable: property.name.wrap(false),
+                        ty: property.initializer.wrap(ty),
+                    },
+                )));
+            }
+        }
+
+        PrimaryType::ObjectType(members).into()
+    }
+
+    /// Type the property `name` of an object literal is expected to have, each member of a
+    /// union contributes the type it gives the property.
+    fn expected_property_type(&self, ty: &Type, name: &str) -> Option<Type> {
+        let types = ty
+            .union_members()
+            .iter()
+            .filter_map(|ty| self.property_type(ty, name))
+            .collect::<Vec<_>>();
+
+        (!types.is_empty()).then(|| Type::union(types))
+    }
+}
@@ -0,0 +1,47 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{MatchExpression, PredefinedType, Statement, Type};
+
+use crate::{check::widen, Checker};
+
+impl Checker {
+    pub fn check_match_expression(&mut self, expression: &Positioned<MatchExpression>) -> Type {
+        let expression = &expression.value;
+        let target = self.check_expression(&expression.target, None);
+        let mut types = vec![];
+
+        for variant in &expression.variants {
+            let value = self.check_expression(&variant.value.value, None);
+
+            if !self.is_assignable(&value, &target) && !self.is_assignable(&target, &value) {
+                self.error(
+                    variant.value.value.span,
+                    format!(
+                        "type \"{}\" is not comparable to type \"{}\"",
+                        widen(value),
+                        widen(target.clone())
+                    ),
+                );
+            }
+
+            let narrowings =
+                self.narrow_equal(&expression.target, &variant.value.value, true, false);
+
+            self.add_scope();
+            self.apply_narrowings(&narrowings);
+
+            match &variant.value.callback.value {
+                Statement::Expression(expression) => {
+                    types.push(widen(self.check_expression(expression, None)))
+                }
+                _ => self.check_statement(&variant.value.callback),
+            }
+
+            self.remove_scope();
+        }
+
+        match types.len() == expression.variants.len() {
+            true => Type::union(types),
+            false => PredefinedType::Any.into(),
+        }
+    }
+}
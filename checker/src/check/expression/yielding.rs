@@ -0,0 +1,43 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{PredefinedType, Type, YieldExpression};
+
+use crate::Checker;
+
+impl Checker {
+    /// Checks what is yielded against the type the generator yields, `yield` evaluates to
+    /// whatever the generator is resumed with.
+    pub fn check_yield_expression(&mut self, expression: &Positioned<YieldExpression>) -> Type {
+        let (span, expression) = (expression.span, &expression.value);
+        let yields = self
+            .returns
+            .last()
+            .and_then(|context| context.yields.clone());
+
+        let Some(yields) = yields else {
+            self.error(span, "yield is only valid in generator functions");
+
+            if let Some(argument) = &expression.argument {
+                self.check_expression(argument, None);
+            }
+
+            return PredefinedType::Any.into();
+        };
+
+        let (argument_span, ty) = match &expression.argument {
+            Some(argument) if expression.delegate => {
+                let ty = self.check_expression(argument, None);
+
+                (argument.span, self.iterated_type(argument.span, &ty))
+            }
+            Some(argument) => (
+                argument.span,
+                self.check_expression(argument, Some(&yields)),
+            ),
+            None => (span, PredefinedType::Undefined.into()),
+        };
+
+        self.expect_assignable(argument_span, &ty, &yields);
+
+        PredefinedType::Any.into()
+    }
+}
@@ -0,0 +1,142 @@
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{
+    ExportDeclaration, ExportDefaultElement, ExportSingleElement, PredefinedType, Statement, Type,
+};
+
+use crate::Checker;
+
+pub mod expression;
+pub mod statement;
+
+/// Literal types only survive where they were written, everything else sees the primitive.
+pub fn widen(ty: Type) -> Type {
+    match ty.as_predefined() {
+        Some(PredefinedType::StringLiteral(_)) => PredefinedType::String.into(),
+        Some(PredefinedType::NumberLiteral(_)) => PredefinedType::Number.into(),
+        Some(PredefinedType::BooleanLiteral(_)) => PredefinedType::Boolean.into(),
+        _ => ty,
+    }
+}
+
+impl Checker {
+    /// Checks statements of one scope. Declarations are registered first so they can be
+    /// referenced before the place they are written at.
+    pub fn check_statements(&mut self, statements: &[Positioned<Statement>]) {
+        for statement in statements {
+            self.hoist_statement(statement);
+        }
+
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    pub fn check_block(&mut self, statements: &[Positioned<Statement>]) {
+        self.add_scope();
+        self.check_statements(statements);
+        self.remove_scope();
+    }
+
+    fn hoist_statement(&mut self, statement: &Positioned<Statement>) {
+        match &statement.value {
+            Statement::TypeAliasDeclaration(declaration) => self.hoist_type_alias(declaration),
+            Statement::InterfaceDeclaration(declaration) => self.hoist_interface(declaration),
+            Statement::FunctionDeclaration(declaration) => self.hoist_function(declaration),
+            Statement::EnumDeclaration(declaration) => self.hoist_enum(declaration),
+            Statement::ClassDeclaration(declaration) => self.hoist_class(declaration),
+            Statement::AmbientDeclaration(declaration) => self.hoist_ambient(declaration),
+            Statement::ExportDeclaration(export) => match &export.value {
+                ExportDeclaration::Default(element) => match &element.value {
+                    ExportDefaultElement::FunctionDeclaration(declaration) => {
+                        self.hoist_function(declaration)
+                    }
+                    ExportDefaultElement::ClassDeclaration(declaration) => {
+                        self.hoist_class(declaration)
+                    }
+                    _ => {}
+                },
+                ExportDeclaration::Single(element) => match &element.value {
+                    ExportSingleElement::FunctionDeclaration(declaration) => {
+                        self.hoist_function(declaration)
+                    }
+                    ExportSingleElement::ClassDeclaration(declaration) => {
+                        self.hoist_class(declaration)
+                    }
+                    ExportSingleElement::InterfaceDeclaration(declaration) => {
+                        self.hoist_interface(declaration)
+                    }
+                    ExportSingleElement::TypeAliasDeclaration(declaration) => {
+                        self.hoist_type_alias(declaration)
+                    }
+                    ExportSingleElement::EnumDeclaration(declaration) => {
+                        self.hoist_enum(declaration)
+                    }
+                    ExportSingleElement::VariableStatement(_) => {}
+                },
+                ExportDeclaration::List(_) => {}
+            },
+            _ => {}
+        }
+    }
+
+    pub fn check_statement(&mut self, statement: &Positioned<Statement>) {
+        match &statement.value {
+            Statement::ImportDeclaration(import) => self.check_import(import),
+            Statement::TypeAliasDeclaration(declaration) => self.check_type_alias(declaration),
+            Statement::InterfaceDeclaration(declaration) => self.check_interface(declaration),
+            Statement::FunctionDeclaration(declaration) => self.check_function(declaration),
+            Statement::EnumDeclaration(declaration) => self.check_enum(declaration),
+            Statement::ExportDeclaration(export) => self.check_export(export),
+            Statement::ClassDeclaration(declaration) => self.check_class(declaration),
+            Statement::NamespaceDeclaration(declaration) => self.check_namespace(declaration),
+            Statement::AmbientDeclaration(declaration) => self.check_ambient(declaration),
+            Statement::VariableStatement(variable) => self.check_variable(variable),
+            Statement::IfStatement(statement) => self.check_if(statement),
+            Statement::ForOfStatement(statement) => self.check_for_of(statement),
+            Statement::ReturnStatement(expression) => self.check_return(expression),
+            Statement::Expression(expression) => {
+                self.check_expression(expression, None);
+            }
+        }
+    }
+
+    /// Widens the type of an element or property of a literal, unless the contextual type
+    /// only accepts the literal itself, like the tag of a discriminated union.
+    pub(crate) fn widen_to(&self, ty: Type, expected: Option<&Type>) -> Type {
+        let widened = widen(ty.clone());
+
+        match expected {
+            Some(expected)
+                if !self.is_assignable(&widened, expected) && self.is_assignable(&ty, expected) =>
+            {
+                ty
+            }
+            _ => widened,
+        }
+    }
+
+    /// Reports `source` not being assignable to `target`, returns whether it was.
+    pub(crate) fn expect_assignable(&mut self, span: Span, source: &Type, target: &Type) -> bool {
+        let assignable = self.is_assignable(source, target);
+
+        if !assignable {
+            // Literal sources are only shown as such when the target expects literals
+            let literal = self
+                .resolve_type(target)
+                .union_members()
+                .iter()
+                .any(|ty| widen(ty.clone()) != *ty);
+            let source = match literal {
+                true => source.clone(),
+                false => widen(source.clone()),
+            };
+
+            self.error(
+                span,
+                format!("type \"{source}\" is not assignable to type \"{target}\""),
+            );
+        }
+
+        assignable
+    }
+}
@@ -0,0 +1,258 @@
+use tsr_lexer::{globals::Positioned, token::Modifier};
+use tsr_parser::ast::{
+    AccessorKind, CallSignature, ClassDeclaration, ClassElement, ConstructSignature, Ident,
+    MethodSignature, Parameter, PredefinedType, PrimaryType, PropertyMemberDeclaration,
+    PropertyName, PropertySignature, Type, TypeMember,
+};
+
+use super::function::is_async;
+use crate::{check::widen, environment::TypeDeclaration, Checker};
+
+fn is_static(modifiers: &[Positioned<Modifier>]) -> bool {
+    modifiers
+        .iter()
+        .any(|modifier| modifier.value == Modifier::Static)
+}
+
+fn property_ident(name: &Positioned<PropertyName>) -> Option<Positioned<Ident>> {
+    match &name.value {
+        PropertyName::LiteralPropertyName(literal) => {
+            Some(name.wrap(Ident(literal.value.to_string())))
+        }
+        PropertyName::ComputedPropertyName(_) => None,
+    }
+}
+
+impl Checker {
+    /// Registers the instance type of a class and binds its name to the constructor.
+    pub(crate) fn hoist_class(&mut self, declaration: &Positioned<ClassDeclaration>) {
+        let class = &declaration.value;
+        let name = &class.name.value;
+        let mut constructors = vec![];
+        let mut instance = vec![];
+        let mut statics = vec![];
+
+        for element in &class.body {
+            match &element.value {
+                ClassElement::ConstructorDeclaration(constructor) => constructors.push(
+                    TypeMember::ConstructSignature(constructor.wrap(ConstructSignature(
+                        class.type_parameters.clone(),
+                        constructor.value.parameters.clone(),
+                        constructor.wrap(PrimaryType::TypeReference(name.clone(), vec![]).into()),
+                    ))),
+                ),
+                ClassElement::PropertyMemberDeclaration(member) => {
+                    let (modifiers, member) = match &member.value {
+                        PropertyMemberDeclaration::MemberVariableDeclaration(variable) => {
+                            let variable = &variable.value;
+                            let Some(name) = property_ident(&variable.name) else {
+                                continue;
+                            };
+                            let ty = match (&variable.ty, &variable.initializer) {
+                                (Some(ty), _) => ty.value.clone(),
+                                (None, Some(initializer)) => {
+                                    widen(self.infer_silently(initializer, None))
+                                }
+                                (None, None) => PredefinedType::Any.into(),
+                            };
+
+                            (
+                                &variable.modifiers,
+                                TypeMember::PropertySignature(name.wrap(PropertySignature {
+                                    modifiers: vec![],
+                                    nullable: name.wrap(false),
+                                    ty: name.wrap(ty),
+                                    name: name.clone(),
+                                })),
+                            )
+                        }
+                        PropertyMemberDeclaration::MemberFunctionDeclaration(function) => (
+                            &function.value.modifiers,
+                            TypeMember::MethodSignature(function.wrap(MethodSignature(
+                                function.value.name.clone(),
+                                function.wrap(false),
+                                Box::new(function.wrap(CallSignature(
+                                    function.value.type_parameters.clone(),
+                                    function.value.parameters.clone(),
+                                    function.value.ty.clone(),
+                                ))),
+                            ))),
+                        ),
+                        PropertyMemberDeclaration::MemberAccessorDeclaration(accessor) => {
+                            let Some(name) = property_ident(&accessor.value.name) else {
+                                continue;
+                            };
+
+                            (
+                                &accessor.value.modifiers,
+                                TypeMember::PropertySignature(name.wrap(PropertySignature {
+                                    modifiers: vec![],
+                                    nullable: name.wrap(false),
+                                    ty: accessor.value.ty.clone(),
+                                    name: name.clone(),
+                                })),
+                            )
+                        }
+                    };
+
+                    match is_static(modifiers) {
+                        true => statics.push(member),
+                        false => instance.push(member),
+                    }
+                }
+                ClassElement::IndexMemberDeclaration(signature) => {
+                    instance.push(TypeMember::IndexSignature(signature.clone()))
+                }
+            }
+        }
+
+        if constructors.is_empty() {
+            constructors.push(TypeMember::ConstructSignature(declaration.wrap(
+                ConstructSignature(
+                    class.type_parameters.clone(),
+                    vec![],
+                    declaration.wrap(PrimaryType::TypeReference(name.clone(), vec![]).into()),
+                ),
+            )));
+        }
+
+        self.set_type(
+            &name.0,
+            TypeDeclaration::Class {
+                type_parameters: class
+                    .type_parameters
+                    .iter()
+                    .map(|parameter| parameter.value.clone())
+                    .collect(),
+                extends: class
+                    .extends
+                    .iter()
+                    .map(|extend| extend.value.0.clone())
+                    .collect(),
+                members: instance,
+            },
+        );
+
+        self.set_binding(
+            &name.0,
+            PrimaryType::ObjectType([constructors, statics].concat()).into(),
+            false,
+        );
+    }
+
+    pub fn check_class(&mut self, declaration: &Positioned<ClassDeclaration>) {
+        let class = &declaration.value;
+        let this: Type = PrimaryType::TypeReference(class.name.value.clone(), vec![]).into();
+
+        for parent in class.extends.iter().chain(&class.implements) {
+            if self.get_type(&parent.value.0).is_none() {
+                self.error(
+                    parent.span,
+                    format!("cannot find name \"{}\"", parent.value),
+                );
+            }
+        }
+
+        for implement in &class.implements {
+            let interface = PrimaryType::TypeReference(implement.value.clone(), vec![]).into();
+
+            if self.get_type(&implement.value.0).is_some() && !self.is_assignable(&this, &interface)
+            {
+                self.error(
+                    implement.span,
+                    format!(
+                        "class \"{}\" incorrectly implements interface \"{}\"",
+                        class.name.value, implement.value
+                    ),
+                );
+            }
+        }
+
+        self.add_scope();
+        self.set_type_parameters(
+            &class
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect::<Vec<_>>(),
+        );
+        self.this.push(this);
+
+        for element in &class.body {
+            match &element.value {
+                ClassElement::ConstructorDeclaration(constructor) => self.check_function_body(
+                    &[],
+                    &constructor.value.parameters,
+                    &constructor.wrap(PredefinedType::Void.into()),
+                    &constructor.value.body,
+                    false,
+                ),
+                ClassElement::PropertyMemberDeclaration(member) => match &member.value {
+                    PropertyMemberDeclaration::MemberVariableDeclaration(variable) => {
+                        let variable = &variable.value;
+
+                        if let Some(ty) = &variable.ty {
+                            self.validate_type(ty.span, &ty.value);
+                        }
+
+                        if let Some(initializer) = &variable.initializer {
+                            let expected = variable.ty.as_ref().map(|ty| &ty.value);
+                            let ty = self.check_expression(initializer, expected);
+
+                            if let Some(expected) = expected {
+                                self.expect_assignable(initializer.span, &ty, expected);
+                            }
+                        }
+                    }
+                    PropertyMemberDeclaration::MemberFunctionDeclaration(function) => self
+                        .check_function_body(
+                            &function.value.type_parameters,
+                            &function.value.parameters,
+                            &function.value.ty,
+                            &function.value.body,
+                            is_async(&function.value.modifiers),
+                        ),
+                    PropertyMemberDeclaration::MemberAccessorDeclaration(accessor) => {
+                        let accessor = &accessor.value;
+
+                        match accessor.kind.value {
+                            AccessorKind::Getter => self.check_function_body(
+                                &[],
+                                &[],
+                                &accessor.ty,
+                                &accessor.body,
+                                false,
+                            ),
+                            AccessorKind::Setter => self.check_function_body(
+                                &[],
+                                &accessor
+                                    .parameter
+                                    .iter()
+                                    .map(|name| {
+                                        name.wrap(Parameter {
+                                            name: name.clone(),
+                                            nullable: name.wrap(false),
+                                            ty: accessor.ty.clone(),
+                                            default: None,
+                                        })
+                                    })
+                                    .collect::<Vec<_>>(),
+                                &accessor.kind.wrap(PredefinedType::Void.into()),
+                                &accessor.body,
+                                false,
+                            ),
+                        }
+                    }
+                },
+                ClassElement::IndexMemberDeclaration(signature) => self.validate_type(
+                    signature.span,
+                    &PrimaryType::ObjectType(vec![TypeMember::IndexSignature(signature.clone())])
+                        .into(),
+                ),
+            }
+        }
+
+        self.this.pop();
+        self.remove_scope();
+    }
+}
@@ -0,0 +1,59 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    EnumDeclaration, Expression, Literal, PredefinedType, PrimaryType, PropertySignature, Type,
+    TypeMember,
+};
+
+use crate::{environment::TypeDeclaration, Checker};
+
+impl Checker {
+    pub(crate) fn hoist_enum(&mut self, declaration: &Positioned<EnumDeclaration>) {
+        let declaration = &declaration.value;
+        let name = &declaration.name.value;
+
+        let members = declaration
+            .members
+            .iter()
+            .map(|member| match &member.value.initializer {
+                Some(Positioned {
+                    value: Expression::Literal(literal),
+                    ..
+                }) => match &literal.value {
+                    Literal::String(string) => {
+                        PredefinedType::StringLiteral(string.value.clone()).into()
+                    }
+                    Literal::Float(_) => PredefinedType::Float.into(),
+                    Literal::Boolean(_) => PredefinedType::Boolean.into(),
+                    Literal::Number(_) => PredefinedType::Number.into(),
+                },
+                Some(_) => PredefinedType::Any.into(),
+                None => PredefinedType::Number.into(),
+            })
+            .collect::<Vec<Type>>();
+
+        self.set_type(&name.0, TypeDeclaration::Enum { members });
+
+        let properties = declaration
+            .members
+            .iter()
+            .map(|member| {
+                TypeMember::PropertySignature(member.wrap(PropertySignature {
+                    modifiers: vec![],
+                    name: member.value.name.clone(),
+                    nullable: member.wrap(false),
+                    ty: member.wrap(PrimaryType::TypeReference(name.clone(), vec![]).into()),
+                }))
+            })
+            .collect();
+
+        self.set_binding(&name.0, PrimaryType::ObjectType(properties).into(), false);
+    }
+
+    pub fn check_enum(&mut self, declaration: &Positioned<EnumDeclaration>) {
+        for member in &declaration.value.members {
+            if let Some(initializer) = &member.value.initializer {
+                self.check_expression(initializer, None);
+            }
+        }
+    }
+}
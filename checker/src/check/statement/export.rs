@@ -0,0 +1,63 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    ExportDeclaration, ExportDefaultElement, ExportListElement, ExportSingleElement,
+};
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_export(&mut self, export: &Positioned<ExportDeclaration>) {
+        match &export.value {
+            ExportDeclaration::Default(element) => match &element.value {
+                ExportDefaultElement::FunctionDeclaration(declaration) => {
+                    self.check_function(declaration)
+                }
+                ExportDefaultElement::ClassDeclaration(declaration) => {
+                    self.check_class(declaration)
+                }
+                ExportDefaultElement::Expression(expression) => {
+                    self.check_expression(expression, None);
+                }
+                ExportDefaultElement::IdentifierReference(name) => {
+                    if self.get_binding(&name.value.0).is_none() {
+                        self.error(name.span, format!("cannot find name \"{}\"", name.value));
+                    }
+                }
+            },
+            ExportDeclaration::Single(element) => match &element.value {
+                ExportSingleElement::VariableStatement(variable) => self.check_variable(variable),
+                ExportSingleElement::FunctionDeclaration(declaration) => {
+                    self.check_function(declaration)
+                }
+                ExportSingleElement::ClassDeclaration(declaration) => self.check_class(declaration),
+                ExportSingleElement::InterfaceDeclaration(declaration) => {
+                    self.check_interface(declaration)
+                }
+                ExportSingleElement::TypeAliasDeclaration(declaration) => {
+                    self.check_type_alias(declaration)
+                }
+                ExportSingleElement::EnumDeclaration(declaration) => self.check_enum(declaration),
+            },
+            ExportDeclaration::List(element) => {
+                // Re-exports are resolved by the module they come from
+                let ExportListElement::NamedExports(specifiers) = &element.value else {
+                    return;
+                };
+
+                for specifier in &specifiers.value {
+                    let local = specifier
+                        .value
+                        .property_name
+                        .as_ref()
+                        .unwrap_or(&specifier.value.name);
+
+                    if self.get_binding(&local.value.0).is_none()
+                        && self.get_type(&local.value.0).is_none()
+                    {
+                        self.error(local.span, format!("cannot find name \"{}\"", local.value));
+                    }
+                }
+            }
+        }
+    }
+}
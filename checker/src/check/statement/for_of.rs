@@ -0,0 +1,17 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::ForOfStatement;
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_for_of(&mut self, statement: &Positioned<ForOfStatement>) {
+        let statement = &statement.value;
+        let iterable = self.check_expression(&statement.iterable, None);
+        let element = self.iterated_type(statement.iterable.span, &iterable);
+
+        self.add_scope();
+        self.bind_pattern(&statement.binding, element, statement.mutable.value);
+        self.check_statement(&statement.body);
+        self.remove_scope();
+    }
+}
@@ -0,0 +1,246 @@
+use tsr_lexer::{globals::Positioned, token::Modifier};
+use tsr_parser::ast::{
+    Block, CallSignature, FunctionDeclaration, Parameter, PredefinedType, PrimaryType, Type,
+    TypeMember, TypeParameter,
+};
+
+use crate::{iteration::GeneratorTypes, Checker, ReturnContext};
+
+/// Whether a function is marked `async`, calls to it return a promise.
+pub(crate) fn is_async(modifiers: &[Positioned<Modifier>]) -> bool {
+    modifiers
+        .iter()
+        .any(|modifier| modifier.value == Modifier::Async)
+}
+
+impl Checker {
+    pub(crate) fn hoist_function(&mut self, declaration: &Positioned<FunctionDeclaration>) {
+        let (span, declaration) = (declaration.span, &declaration.value);
+        let name = &declaration.name.value.0;
+        let overloads = self.overloads_of(name);
+
+        match &declaration.body {
+            // Signatures without a body are overloads of the implementation that follows
+            None => {
+                let signature = TypeMember::CallSignature(span.wrap(CallSignature(
+                    declaration.type_parameters.clone(),
+                    declaration.parameters.clone(),
+                    declaration.ty.clone(),
+                )));
+
+                self.set_binding(
+                    name,
+                    PrimaryType::ObjectType([overloads, vec![signature]].concat()).into(),
+                    false,
+                );
+            }
+            Some(_) if !overloads.is_empty() => {}
+            Some(_) => self.set_binding(
+                name,
+                Type::FunctionType(
+                    declaration
+                        .type_parameters
+                        .iter()
+                        .map(|parameter| parameter.value.clone())
+                        .collect(),
+                    declaration
+                        .parameters
+                        .iter()
+                        .map(|parameter| parameter.value.clone())
+                        .collect(),
+                    Box::new(declaration.ty.value.clone()),
+                ),
+                false,
+            ),
+        }
+    }
+
+    /// Overload signatures declared for `name` in the current scope.
+    fn overloads_of(&self, name: &str) -> Vec<TypeMember> {
+        let binding = self
+            .get_binding(name)
+            .filter(|_| self.is_declared_here(name));
+
+        match binding.and_then(|binding| binding.ty.as_primary()) {
+            Some(PrimaryType::ObjectType(members))
+                if members
+                    .iter()
+                    .all(|member| matches!(member, TypeMember::CallSignature(_))) =>
+            {
+                members.clone()
+            }
+            _ => vec![],
+        }
+    }
+
+    pub fn check_function(&mut self, declaration: &Positioned<FunctionDeclaration>) {
+        let declaration = &declaration.value;
+
+        if let Some(body) = &declaration.body {
+            for overload in self.overloads_of(&declaration.name.value.0) {
+                let TypeMember::CallSignature(overload) = overload else {
+                    continue;
+                };
+
+                if !self.is_implementation_compatible(&overload.value, declaration) {
+                    self.error(
+                        overload.span,
+                        "this overload signature is not compatible with its implementation",
+                    );
+                }
+            }
+
+            match declaration.generator {
+                true => self.check_generator_body(declaration, body),
+                false => self.check_function_body(
+                    &declaration.type_parameters,
+                    &declaration.parameters,
+                    &declaration.ty,
+                    body,
+                    is_async(&declaration.modifiers),
+                ),
+            }
+        } else {
+            self.add_scope();
+            self.check_signature(&declaration.type_parameters, &declaration.parameters);
+            self.validate_type(declaration.ty.span, &declaration.ty.value);
+            self.remove_scope();
+        }
+    }
+
+    /// An implementation has to accept the parameters of each overload and return a type
+    /// related to the overload's result.
+    fn is_implementation_compatible(
+        &mut self,
+        overload: &CallSignature,
+        implementation: &FunctionDeclaration,
+    ) -> bool {
+        let CallSignature(type_parameters, parameters, ty) = overload;
+
+        self.add_scope();
+        self.set_type_parameters(
+            &[&type_parameters[..], &implementation.type_parameters[..]]
+                .concat()
+                .into_iter()
+                .map(|parameter| parameter.value)
+                .collect::<Vec<_>>(),
+        );
+
+        let compatible = parameters.len() <= implementation.parameters.len()
+            && parameters
+                .iter()
+                .zip(&implementation.parameters)
+                .all(|(parameter, target)| {
+                    self.is_assignable(&parameter.value.ty.value, &target.value.ty.value)
+                })
+            && (self.is_assignable(&implementation.ty.value, &ty.value)
+                || self.is_assignable(&ty.value, &implementation.ty.value));
+
+        self.remove_scope();
+
+        compatible
+    }
+
+    /// Checks a body against its signature, used by functions, methods and constructors.
+    /// The body of an async function returns what its promise fulfills with.
+    pub(crate) fn check_function_body(
+        &mut self,
+        type_parameters: &[Positioned<TypeParameter>],
+        parameters: &[Positioned<Parameter>],
+        ty: &Positioned<Type>,
+        body: &Block,
+        is_async: bool,
+    ) {
+        self.add_scope();
+        self.check_signature(type_parameters, parameters);
+        self.validate_type(ty.span, &ty.value);
+
+        let expected = match is_async {
+            true => self.promised_type(ty.span, &ty.value),
+            false => ty.value.clone(),
+        };
+
+        self.returns.push(ReturnContext {
+            expected: Some(expected),
+            found: vec![],
+            yields: None,
+            is_async,
+        });
+
+        self.check_statements(&body.value);
+        self.returns.pop();
+        self.remove_scope();
+    }
+
+    /// Checks the body of a `function*`, its annotation gives the type of what it yields and
+    /// of what it returns.
+    fn check_generator_body(&mut self, declaration: &FunctionDeclaration, body: &Block) {
+        let ty = &declaration.ty;
+
+        self.add_scope();
+        self.check_signature(&declaration.type_parameters, &declaration.parameters);
+        self.validate_type(ty.span, &ty.value);
+
+        let types = self.generator_types(&ty.value).unwrap_or_else(|| {
+            self.error(
+                ty.span,
+                format!(
+                    "type \"{}\" is not a valid return type for a generator",
+                    ty.value
+                ),
+            );
+
+            GeneratorTypes {
+                yields: PredefinedType::Any.into(),
+                returns: PredefinedType::Any.into(),
+            }
+        });
+
+        self.returns.push(ReturnContext {
+            expected: Some(types.returns),
+            found: vec![],
+            yields: Some(types.yields),
+            is_async: false,
+        });
+
+        self.check_statements(&body.value);
+        self.returns.pop();
+        self.remove_scope();
+    }
+
+    /// Declares type parameters and parameters in the current scope.
+    pub(crate) fn check_signature(
+        &mut self,
+        type_parameters: &[Positioned<TypeParameter>],
+        parameters: &[Positioned<Parameter>],
+    ) {
+        self.set_type_parameters(
+            &type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect::<Vec<_>>(),
+        );
+
+        for parameter in parameters {
+            let parameter = &parameter.value;
+
+            self.validate_type(parameter.ty.span, &parameter.ty.value);
+
+            if let Some(default) = &parameter.default {
+                let ty = self.check_expression(default, Some(&parameter.ty.value));
+
+                self.expect_assignable(default.span, &ty, &parameter.ty.value);
+            }
+
+            let ty = match parameter.nullable.value {
+                true => Type::union(vec![
+                    parameter.ty.value.clone(),
+                    PredefinedType::Undefined.into(),
+                ]),
+                false => parameter.ty.value.clone(),
+            };
+
+            self.set_binding(&parameter.name.value.0, ty, true);
+        }
+    }
+}
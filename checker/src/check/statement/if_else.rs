@@ -0,0 +1,54 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{Expression, IfStatement, Statement};
+
+use crate::Checker;
+
+/// Whether running `statement` always ends in a `return`.
+fn returns(statement: &Positioned<Statement>) -> bool {
+    match &statement.value {
+        Statement::ReturnStatement(_) => true,
+        Statement::Expression(Positioned {
+            value: Expression::Block(block),
+            ..
+        }) => block.value.iter().any(returns),
+        Statement::IfStatement(statement) => {
+            statement
+                .value
+                .else_statement
+                .as_ref()
+                .is_some_and(|else_statement| {
+                    returns(&statement.value.then_statement) && returns(else_statement)
+                })
+        }
+        _ => false,
+    }
+}
+
+impl Checker {
+    pub fn check_if(&mut self, statement: &Positioned<IfStatement>) {
+        let statement = &statement.value;
+
+        self.check_expression(&statement.expression, None);
+
+        let narrowings = self.narrowings(&statement.expression, true);
+
+        self.add_scope();
+        self.apply_narrowings(&narrowings);
+        self.check_statement(&statement.then_statement);
+        self.remove_scope();
+
+        let narrowings = self.narrowings(&statement.expression, false);
+
+        match &statement.else_statement {
+            Some(else_statement) => {
+                self.add_scope();
+                self.apply_narrowings(&narrowings);
+                self.check_statement(else_statement);
+                self.remove_scope();
+            }
+            // Code after an `if` that always returns only runs when the condition failed
+            None if returns(&statement.then_statement) => self.apply_narrowings(&narrowings),
+            None => {}
+        }
+    }
+}
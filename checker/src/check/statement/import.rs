@@ -0,0 +1,121 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    ImportClause, ImportDeclaration, Literal, PredefinedType, PrimaryType, Type, TypeMember,
+};
+
+use crate::{environment::TypeDeclaration, Checker};
+
+impl Checker {
+    /// Modules described by `declare module` type what is imported from them, other
+    /// modules are provided by the host, so imported names are only known to exist.
+    pub fn check_import(&mut self, import: &Positioned<ImportDeclaration>) {
+        let Some(clause) = &import.value.import_clause else {
+            return;
+        };
+        let type_only = import.value.is_type_only.value;
+        let module = match &import.value.module_specifier.value {
+            Literal::String(specifier) => self
+                .modules
+                .get(&specifier.value)
+                .cloned()
+                .map(|members| (specifier.value.clone(), members)),
+            _ => None,
+        };
+
+        self.check_import_clause(&clause.value, &module, type_only);
+    }
+
+    /// Binds the names `clause` imports, typed by the members of `module` when it is known.
+    fn check_import_clause(
+        &mut self,
+        clause: &ImportClause,
+        module: &Option<(String, Vec<TypeMember>)>,
+        type_only: bool,
+    ) {
+        match clause {
+            ImportClause::Named(name) => {
+                let ty = match &module {
+                    Some((specifier, members)) => match find_member(members, "default") {
+                        Some(ty) => ty,
+                        None => {
+                            self.error(
+                                name.span,
+                                format!("module \"{specifier}\" has no default export"),
+                            );
+
+                            PredefinedType::Any.into()
+                        }
+                    },
+                    None => PredefinedType::Any.into(),
+                };
+
+                self.import_name(&name.value.0, ty, type_only)
+            }
+            ImportClause::NamespaceImport(name) => {
+                let ty = match &module {
+                    Some((_, members)) => PrimaryType::ObjectType(members.clone()).into(),
+                    None => PredefinedType::Any.into(),
+                };
+
+                self.import_name(&name.value.0, ty, type_only)
+            }
+            ImportClause::NamedImports(specifiers) => {
+                for specifier in specifiers {
+                    let specifier = &specifier.value;
+                    let type_only = type_only || specifier.is_type_only.value;
+                    let imported = specifier.property_name.as_ref().unwrap_or(&specifier.name);
+                    let ty = match &module {
+                        // Types declared by the module aren't tracked
+                        Some(_) if type_only => PredefinedType::Any.into(),
+                        Some((specifier, members)) => {
+                            match find_member(members, &imported.value.0) {
+                                Some(ty) => ty,
+                                None => {
+                                    self.error(
+                                        imported.span,
+                                        format!(
+                                            "module \"{specifier}\" has no exported member \"{}\"",
+                                            imported.value
+                                        ),
+                                    );
+
+                                    PredefinedType::Any.into()
+                                }
+                            }
+                        }
+                        None => PredefinedType::Any.into(),
+                    };
+
+                    self.import_name(&specifier.name.value.0, ty, type_only);
+                }
+            }
+            ImportClause::Combined(name, bindings) => {
+                self.check_import_clause(&ImportClause::Named(name.clone()), module, type_only);
+                self.check_import_clause(&bindings.value, module, type_only);
+            }
+        }
+    }
+
+    /// Type-only imports can only be used as types, they don't exist at runtime.
+    fn import_name(&mut self, name: &str, ty: Type, type_only: bool) {
+        match type_only {
+            true => self.set_type(
+                name,
+                TypeDeclaration::Alias {
+                    type_parameters: vec![],
+                    ty: PredefinedType::Any.into(),
+                },
+            ),
+            false => self.set_binding(name, ty, false),
+        }
+    }
+}
+
+fn find_member(members: &[TypeMember], name: &str) -> Option<Type> {
+    members.iter().find_map(|member| match member {
+        TypeMember::PropertySignature(signature) if signature.value.name.value.0 == name => {
+            Some(signature.value.ty.value.clone())
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,61 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{InterfaceDeclaration, PrimaryType};
+
+use crate::{environment::TypeDeclaration, Checker};
+
+impl Checker {
+    pub(crate) fn hoist_interface(&mut self, declaration: &Positioned<InterfaceDeclaration>) {
+        let declaration = &declaration.value;
+
+        self.set_type(
+            &declaration.name.value.0,
+            TypeDeclaration::Interface {
+                type_parameters: declaration
+                    .type_parameters
+                    .iter()
+                    .map(|parameter| parameter.value.clone())
+                    .collect(),
+                extends: declaration
+                    .extends
+                    .iter()
+                    .map(|extend| extend.value.0.clone())
+                    .collect(),
+                members: declaration
+                    .members
+                    .iter()
+                    .map(|member| member.value.clone())
+                    .collect(),
+            },
+        );
+    }
+
+    pub fn check_interface(&mut self, declaration: &Positioned<InterfaceDeclaration>) {
+        let declaration = &declaration.value;
+        let type_parameters = declaration
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.value.clone())
+            .collect::<Vec<_>>();
+
+        for extend in &declaration.extends {
+            if self.get_type(&extend.value.0).is_none() {
+                self.error(
+                    extend.span,
+                    format!("cannot find name \"{}\"", extend.value),
+                );
+            }
+        }
+
+        self.add_scope();
+        self.set_type_parameters(&type_parameters);
+
+        for member in &declaration.members {
+            self.validate_type(
+                member.span,
+                &PrimaryType::ObjectType(vec![member.value.clone()]).into(),
+            );
+        }
+
+        self.remove_scope();
+    }
+}
@@ -0,0 +1,12 @@
+pub mod class;
+pub mod enumeration;
+pub mod export;
+pub mod for_of;
+pub mod function;
+pub mod if_else;
+pub mod import;
+pub mod interface;
+pub mod namespace;
+pub mod returning;
+pub mod type_alias;
+pub mod variable;
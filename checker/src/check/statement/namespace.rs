@@ -0,0 +1,229 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    AmbientDeclaration, BindingPattern, ExportDeclaration, ExportListElement, ExportSingleElement,
+    Ident, NamespaceDeclaration, PredefinedType, PrimaryType, PropertySignature, Statement, Type,
+    TypeMember,
+};
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_namespace(&mut self, declaration: &Positioned<NamespaceDeclaration>) {
+        let declaration = &declaration.value;
+
+        self.add_scope();
+        self.check_statements(&declaration.body.value);
+
+        let members = self.namespace_members(&declaration.body.value);
+
+        self.remove_scope();
+        self.merge_namespace(&declaration.name, members);
+    }
+
+    /// Properties for the bindings a namespace body exports, read before its scope ends.
+    fn namespace_members(&self, body: &[Positioned<Statement>]) -> Vec<TypeMember> {
+        let mut exported = vec![];
+
+        for statement in body {
+            let Statement::ExportDeclaration(export) = &statement.value else {
+                continue;
+            };
+
+            match &export.value {
+                ExportDeclaration::Single(element) => match &element.value {
+                    ExportSingleElement::VariableStatement(variable) => exported.extend(
+                        variable
+                            .value
+                            .declarations
+                            .iter()
+                            .flat_map(|declaration| BindingPattern::names(&declaration.value.name))
+                            .map(|name| (name, None)),
+                    ),
+                    ExportSingleElement::FunctionDeclaration(declaration) => {
+                        exported.push((declaration.value.name.clone(), None))
+                    }
+                    ExportSingleElement::ClassDeclaration(declaration) => {
+                        exported.push((declaration.value.name.clone(), None))
+                    }
+                    ExportSingleElement::EnumDeclaration(declaration) => {
+                        exported.push((declaration.value.name.clone(), None))
+                    }
+                    _ => {}
+                },
+                ExportDeclaration::List(element) => {
+                    if let ExportListElement::NamedExports(specifiers) = &element.value {
+                        exported.extend(specifiers.value.iter().map(|specifier| {
+                            (
+                                specifier.value.name.clone(),
+                                specifier.value.property_name.clone(),
+                            )
+                        }))
+                    }
+                }
+                ExportDeclaration::Default(_) => {}
+            }
+        }
+
+        exported
+            .into_iter()
+            .filter_map(|(name, local)| {
+                let local = local.as_ref().unwrap_or(&name);
+                let binding = self.get_declared_binding(&local.value.0)?;
+
+                Some(property(&name, binding.ty.clone()))
+            })
+            .collect()
+    }
+
+    /// Adds `members` to the object type bound at `path`, so namespaces declared in parts
+    /// share one binding.
+    fn merge_namespace(&mut self, path: &[Positioned<Ident>], members: Vec<TypeMember>) {
+        let name = &path[0];
+        let existing = match self.get_declared_binding(&name.value.0) {
+            Some(binding) => match binding.ty.as_primary() {
+                Some(PrimaryType::ObjectType(existing)) => existing.clone(),
+                _ if self.is_declared_here(&name.value.0) => {
+                    return self.error(
+                        name.span,
+                        format!("namespace \"{}\" cannot merge with a value", name.value),
+                    );
+                }
+                _ => vec![],
+            },
+            None => vec![],
+        };
+
+        self.set_binding(
+            &name.value.0,
+            PrimaryType::ObjectType(merge_members(existing, &path[1..], members)).into(),
+            false,
+        );
+    }
+
+    pub(crate) fn hoist_ambient(&mut self, declaration: &Positioned<AmbientDeclaration>) {
+        match &declaration.value {
+            AmbientDeclaration::VariableStatement(variable) => {
+                for declaration in &variable.value.declarations {
+                    let declaration = &declaration.value;
+                    let ty = declaration
+                        .ty
+                        .as_ref()
+                        .map(|ty| ty.value.clone())
+                        .unwrap_or(PredefinedType::Any.into());
+                    let ty = match declaration.nullable.value {
+                        true => Type::union(vec![ty, PredefinedType::Undefined.into()]),
+                        false => ty,
+                    };
+
+                    self.bind_pattern(&declaration.name, ty, variable.value.mutable.value);
+                }
+            }
+            AmbientDeclaration::FunctionDeclaration(declaration) => {
+                self.hoist_function(declaration)
+            }
+            AmbientDeclaration::NamespaceDeclaration(namespace) => self.merge_namespace(
+                &namespace.value.name,
+                AmbientDeclaration::members(&namespace.value.body.value),
+            ),
+            AmbientDeclaration::ModuleDeclaration(module) => {
+                self.modules.insert(
+                    module.value.name.value.clone(),
+                    AmbientDeclaration::members(&module.value.body.value),
+                );
+            }
+        }
+    }
+
+    pub fn check_ambient(&mut self, declaration: &Positioned<AmbientDeclaration>) {
+        match &declaration.value {
+            AmbientDeclaration::VariableStatement(variable) => {
+                for declaration in &variable.value.declarations {
+                    if let Some(ty) = &declaration.value.ty {
+                        self.validate_type(ty.span, &ty.value);
+                    }
+                }
+            }
+            AmbientDeclaration::FunctionDeclaration(declaration) => {
+                if declaration.value.body.is_some() {
+                    self.error(
+                        declaration.span,
+                        "an implementation cannot be declared in ambient contexts",
+                    );
+                }
+
+                self.check_function(declaration)
+            }
+            AmbientDeclaration::NamespaceDeclaration(Positioned { span, value }) => self
+                .validate_type(
+                    *span,
+                    &PrimaryType::ObjectType(AmbientDeclaration::members(&value.body.value)).into(),
+                ),
+            AmbientDeclaration::ModuleDeclaration(Positioned { span, value }) => self
+                .validate_type(
+                    *span,
+                    &PrimaryType::ObjectType(AmbientDeclaration::members(&value.body.value)).into(),
+                ),
+        }
+    }
+}
+
+fn property(name: &Positioned<Ident>, ty: Type) -> TypeMember {
+    TypeMember::PropertySignature(name.wrap(PropertySignature {
+        modifiers: vec![],
+        name: name.clone(),
+        nullable: name.wrap(false),
+        ty: name.wrap(ty),
+    }))
+}
+
+/// Adds `members` to the object type nested at `path` inside `existing`, later members
+/// replace earlier ones of the same name.
+fn merge_members(
+    existing: Vec<TypeMember>,
+    path: &[Positioned<Ident>],
+    members: Vec<TypeMember>,
+) -> Vec<TypeMember> {
+    let name_of = |member: &TypeMember| match member {
+        TypeMember::PropertySignature(signature) => Some(signature.value.name.value.0.clone()),
+        _ => None,
+    };
+
+    match path.split_first() {
+        Some((name, path)) => {
+            let mut nested = vec![];
+            let mut existing = existing
+                .into_iter()
+                .filter(|member| match member {
+                    TypeMember::PropertySignature(signature)
+                        if signature.value.name.value == name.value =>
+                    {
+                        if let Some(PrimaryType::ObjectType(members)) =
+                            signature.value.ty.value.as_primary()
+                        {
+                            nested = members.clone();
+                        }
+
+                        false
+                    }
+                    _ => true,
+                })
+                .collect::<Vec<_>>();
+
+            existing.push(property(
+                name,
+                PrimaryType::ObjectType(merge_members(nested, path, members)).into(),
+            ));
+
+            existing
+        }
+        None => {
+            let names = members.iter().filter_map(name_of).collect::<Vec<_>>();
+
+            existing
+                .into_iter()
+                .filter(|member| name_of(member).is_none_or(|name| !names.contains(&name)))
+                .chain(members)
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::Expression;
+
+use crate::Checker;
+
+impl Checker {
+    pub fn check_return(&mut self, expression: &Positioned<Expression>) {
+        let expected = self
+            .returns
+            .last()
+            .and_then(|context| context.expected.clone());
+        let ty = self.check_expression(expression, expected.as_ref());
+        // An async function may return a promise, its own promise follows it
+        let ty = match self.returns.last().is_some_and(|context| context.is_async) {
+            true => self.awaited_type(&ty),
+            false => ty,
+        };
+
+        if let Some(expected) = &expected {
+            self.expect_assignable(expression.span, &ty, expected);
+        }
+
+        if let Some(context) = self.returns.last_mut() {
+            context.found.push(ty);
+        }
+    }
+}
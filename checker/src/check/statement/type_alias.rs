@@ -0,0 +1,36 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::TypeAliasDeclaration;
+
+use crate::{environment::TypeDeclaration, Checker};
+
+impl Checker {
+    pub(crate) fn hoist_type_alias(&mut self, declaration: &Positioned<TypeAliasDeclaration>) {
+        let declaration = &declaration.value;
+
+        self.set_type(
+            &declaration.name.value.0,
+            TypeDeclaration::Alias {
+                type_parameters: declaration
+                    .type_parameters
+                    .iter()
+                    .map(|parameter| parameter.value.clone())
+                    .collect(),
+                ty: declaration.ty.value.clone(),
+            },
+        );
+    }
+
+    pub fn check_type_alias(&mut self, declaration: &Positioned<TypeAliasDeclaration>) {
+        let declaration = &declaration.value;
+        let type_parameters = declaration
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.value.clone())
+            .collect::<Vec<_>>();
+
+        self.add_scope();
+        self.set_type_parameters(&type_parameters);
+        self.validate_type(declaration.ty.span, &declaration.ty.value);
+        self.remove_scope();
+    }
+}
@@ -0,0 +1,119 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{
+    ArraySize, BindingPattern, Ident, PredefinedType, PrimaryType, Type, VariableStatement,
+};
+
+use crate::{check::widen, Checker};
+
+impl Checker {
+    pub fn check_variable(&mut self, variable: &Positioned<VariableStatement>) {
+        let mutable = variable.value.mutable.value;
+
+        for declaration in &variable.value.declarations {
+            let (span, declaration) = (declaration.span, &declaration.value);
+            if let Some(ty) = &declaration.ty {
+                self.validate_type(ty.span, &ty.value);
+            }
+
+            let initializer = declaration.initializer.as_ref().map(|initializer| {
+                (
+                    initializer.span,
+                    self.check_expression(initializer, declaration.ty.as_ref().map(|ty| &ty.value)),
+                )
+            });
+
+            let ty = match (&declaration.ty, initializer) {
+                (Some(ty), Some((span, initializer))) => {
+                    self.expect_assignable(span, &initializer, &ty.value);
+
+                    ty.value.clone()
+                }
+                (Some(ty), None) => ty.value.clone(),
+                (None, Some((_, initializer))) => match mutable {
+                    true => widen(initializer),
+                    false => initializer,
+                },
+                (None, None) => PredefinedType::Any.into(),
+            };
+
+            if !mutable && declaration.initializer.is_none() {
+                match &declaration.name.value {
+                    BindingPattern::Ident(name) => self.error(
+                        span,
+                        format!("const declaration \"{name}\" must be initialized"),
+                    ),
+                    BindingPattern::Array { .. } => {
+                        self.error(span, "destructuring declaration must be initialized")
+                    }
+                }
+            }
+
+            let ty = match declaration.nullable.value {
+                true => Type::union(vec![ty, PredefinedType::Null.into()]),
+                false => ty,
+            };
+
+            self.bind_pattern(&declaration.name, ty, mutable);
+        }
+    }
+
+    /// Declares the names of `pattern` in the current scope, names taken out of an array
+    /// pattern get the type of the element they are bound to.
+    pub(crate) fn bind_pattern(
+        &mut self,
+        pattern: &Positioned<BindingPattern>,
+        ty: Type,
+        mutable: bool,
+    ) {
+        let (elements, rest) = match &pattern.value {
+            BindingPattern::Ident(name) => {
+                return self.bind_name(&pattern.wrap(name.clone()), ty, mutable)
+            }
+            BindingPattern::Array { elements, rest } => (elements, rest),
+        };
+        let element = self.iterated_type(pattern.span, &ty);
+        let tuple = match self.resolve_type(&ty).as_primary() {
+            Some(PrimaryType::TupleType(types)) => Some(types.clone()),
+            _ => None,
+        };
+
+        for (index, pattern) in elements.iter().enumerate() {
+            let Some(pattern) = pattern else {
+                continue;
+            };
+
+            let ty = match &tuple {
+                Some(types) => types
+                    .get(index)
+                    .cloned()
+                    .unwrap_or(PredefinedType::Undefined.into()),
+                None => element.clone(),
+            };
+
+            self.bind_pattern(pattern, ty, mutable);
+        }
+
+        if let Some(rest) = rest {
+            let ty = PrimaryType::ArrayType(
+                Box::new(match element.as_primary() {
+                    Some(element) => element.clone(),
+                    None => PrimaryType::ParenthesizedType(Box::new(element)),
+                }),
+                ArraySize::Dynamic,
+            );
+
+            self.bind_name(rest, ty.into(), mutable);
+        }
+    }
+
+    fn bind_name(&mut self, name: &Positioned<Ident>, ty: Type, mutable: bool) {
+        if self.is_declared_here(&name.value.0) {
+            self.error(
+                name.span,
+                format!("cannot redeclare block-scoped variable \"{}\"", name.value),
+            );
+        }
+
+        self.set_binding(&name.value.0, ty, mutable);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use tsr_parser::ast::{Type, TypeMember, TypeParameter};
+
+use crate::Checker;
+
+#[derive(Clone, Debug)]
+pub struct Binding {
+    pub ty: Type,
+    pub mutable: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum TypeDeclaration {
+    Alias {
+        type_parameters: Vec<TypeParameter>,
+        ty: Type,
+    },
+    Interface {
+        type_parameters: Vec<TypeParameter>,
+        extends: Vec<String>,
+        members: Vec<TypeMember>,
+    },
+    Class {
+        type_parameters: Vec<TypeParameter>,
+        extends: Vec<String>,
+        members: Vec<TypeMember>,
+    },
+    Enum {
+        members: Vec<Type>,
+    },
+    Parameter {
+        constraint: Option<Type>,
+    },
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct Scope {
+    pub values: HashMap<String, Binding>,
+    pub types: HashMap<String, TypeDeclaration>,
+    /// Bindings whose type was narrowed by a condition guarding this scope.
+    pub narrowed: HashMap<String, Binding>,
+}
+
+impl Checker {
+    /// Looks a binding up, taking the narrowings of enclosing conditions into account.
+    pub fn get_binding<N: AsRef<str>>(&self, name: N) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| {
+            scope
+                .narrowed
+                .get(name.as_ref())
+                .or_else(|| scope.values.get(name.as_ref()))
+        })
+    }
+
+    /// Looks a binding up with the type it was declared with.
+    pub fn get_declared_binding<N: AsRef<str>>(&self, name: N) -> Option<&Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.values.get(name.as_ref()))
+    }
+
+    pub(crate) fn narrow<N: AsRef<str>>(&mut self, name: N, ty: Type) {
+        let Some(binding) = self.get_declared_binding(&name) else {
+            return;
+        };
+        let binding = Binding {
+            ty,
+            mutable: binding.mutable,
+        };
+
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .narrowed
+            .insert(name.as_ref().into(), binding);
+    }
+
+    /// Drops the narrowings of `name`, used once it is assigned to.
+    pub(crate) fn forget_narrowings<N: AsRef<str>>(&mut self, name: N) {
+        for scope in &mut self.scopes {
+            scope.narrowed.remove(name.as_ref());
+        }
+    }
+
+    pub fn get_type<N: AsRef<str>>(&self, name: N) -> Option<&TypeDeclaration> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.types.get(name.as_ref()))
+    }
+
+    pub(crate) fn set_binding<N: AsRef<str>>(&mut self, name: N, ty: Type, mutable: bool) {
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .values
+            .insert(name.as_ref().into(), Binding { ty, mutable });
+    }
+
+    pub(crate) fn set_type<N: AsRef<str>>(&mut self, name: N, declaration: TypeDeclaration) {
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .types
+            .insert(name.as_ref().into(), declaration);
+    }
+
+    pub(crate) fn is_declared_here<N: AsRef<str>>(&self, name: N) -> bool {
+        self.scopes
+            .last()
+            .is_some_and(|scope| scope.values.contains_key(name.as_ref()))
+    }
+
+    pub(crate) fn set_type_parameters(&mut self, type_parameters: &[TypeParameter]) {
+        for parameter in type_parameters {
+            self.set_type(
+                &parameter.name.value.0,
+                TypeDeclaration::Parameter {
+                    constraint: parameter.constraint.as_ref().map(|ty| ty.value.clone()),
+                },
+            );
+        }
+    }
+}
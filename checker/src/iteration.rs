@@ -0,0 +1,144 @@
+use tsr_lexer::{globals::Span, Lexer};
+use tsr_parser::{
+    ast::{PredefinedType, PrimaryType, Statement, Type},
+    Parser,
+};
+
+use crate::{check::widen, types::is_any, Checker};
+
+/// Interfaces of the iterators scripts get from generators and built-in iterables.
+const DECLARATIONS: &str = r#"
+interface IteratorResult<T> {
+    done: boolean;
+    value: T;
+}
+
+interface Iterator<T, TReturn = any, TNext = any> {
+    next(value?: TNext): IteratorResult<T>;
+    'return'(value?: TReturn): IteratorResult<T>;
+}
+
+interface IterableIterator<T, TReturn = any, TNext = any> {
+    next(value?: TNext): IteratorResult<T>;
+    'return'(value?: TReturn): IteratorResult<T>;
+}
+
+interface Generator<T = any, TReturn = any, TNext = any> {
+    next(value?: TNext): IteratorResult<T>;
+    'return'(value?: TReturn): IteratorResult<T>;
+}
+
+// Anything with a `[Symbol.iterator]` method is iterable, which types can't express yet
+type Iterable<T> = any;
+"#;
+
+/// Types a generator function declares: what it yields and what it returns.
+pub(crate) struct GeneratorTypes {
+    pub yields: Type,
+    pub returns: Type,
+}
+
+impl Checker {
+    /// Declares the built-in iterator interfaces in the root scope.
+    pub(crate) fn declare_iterators(&mut self) {
+        let (_, tokens) = Lexer::lex_tokens(DECLARATIONS.as_bytes().into())
+            .expect("built-in declarations are lexed");
+        let (_, declarations) =
+            Parser::parse_tokens(&tokens).expect("built-in declarations are parsed");
+
+        for statement in &declarations.value {
+            match &statement.value {
+                Statement::InterfaceDeclaration(declaration) => self.hoist_interface(declaration),
+                Statement::TypeAliasDeclaration(declaration) => self.hoist_type_alias(declaration),
+                _ => {}
+            }
+        }
+    }
+
+    /// Type of the values of an iterable, reports types that can't be iterated.
+    pub(crate) fn iterated_type(&mut self, span: Span, ty: &Type) -> Type {
+        match self.element_type(ty) {
+            Some(element) => element,
+            None => {
+                self.error(
+                    span,
+                    format!("type \"{}\" is not iterable", widen(ty.clone())),
+                );
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+
+    fn element_type(&self, ty: &Type) -> Option<Type> {
+        if let Some(PrimaryType::TypeReference(name, generics)) = ty.as_primary() {
+            let argument = |index: usize| {
+                generics
+                    .get(index)
+                    .cloned()
+                    .unwrap_or(PredefinedType::Any.into())
+            };
+
+            match name.0.as_str() {
+                "Iterator" | "IterableIterator" | "Iterable" | "Generator" | "Set" => {
+                    return Some(argument(0))
+                }
+                "Map" => {
+                    return Some(PrimaryType::TupleType(vec![argument(0), argument(1)]).into())
+                }
+                _ => {}
+            }
+        }
+
+        let resolved = self.resolve_type(ty);
+        let members = resolved.union_members();
+
+        if members.len() > 1 {
+            return members
+                .iter()
+                .map(|member| self.element_type(member))
+                .collect::<Option<Vec<_>>>()
+                .map(Type::union);
+        }
+
+        match resolved.as_primary()? {
+            PrimaryType::ArrayType(element, _) => Some((**element).clone().into()),
+            PrimaryType::TupleType(types) => Some(Type::union(types.clone())),
+            PrimaryType::PredefinedType(
+                PredefinedType::String | PredefinedType::StringLiteral(_),
+            ) => Some(PredefinedType::String.into()),
+            // Objects may be iterable through a `[Symbol.iterator]` method
+            PrimaryType::ObjectType(_) => Some(PredefinedType::Any.into()),
+            _ if is_any(&resolved) => Some(PredefinedType::Any.into()),
+            _ => None,
+        }
+    }
+
+    /// What a generator function annotated with `ty` yields and returns, `None` when `ty`
+    /// doesn't describe an iterator.
+    pub(crate) fn generator_types(&self, ty: &Type) -> Option<GeneratorTypes> {
+        let any = || Type::from(PredefinedType::Any);
+
+        match ty.as_primary() {
+            Some(PrimaryType::TypeReference(name, generics))
+                if matches!(
+                    name.0.as_str(),
+                    "Generator" | "Iterator" | "IterableIterator" | "Iterable"
+                ) =>
+            {
+                Some(GeneratorTypes {
+                    yields: generics.first().cloned().unwrap_or_else(any),
+                    returns: match name.0.as_str() {
+                        "Iterable" => any(),
+                        _ => generics.get(1).cloned().unwrap_or_else(any),
+                    },
+                })
+            }
+            _ if is_any(&self.resolve_type(ty)) => Some(GeneratorTypes {
+                yields: any(),
+                returns: any(),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use self::environment::{Binding, Scope};
+use std::collections::HashMap;
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{Block, Statement, Type, TypeMember};
+
+pub mod check;
+pub mod environment;
+pub mod iteration;
+pub mod narrowing;
+pub mod prelude;
+pub mod promise;
+pub mod types;
+
+/// A type error found before the program is run.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Diagnostic {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new<T: Into<String>>(span: Span, message: T) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn format<T: Into<String>>(&self, file: T, source: T) -> String {
+        self.span
+            .report("TypeError", &file.into(), &source.into(), &self.message)
+    }
+}
+
+/// Return types collected while checking a function body.
+#[derive(Debug, Clone)]
+pub struct ReturnContext {
+    pub expected: Option<Type>,
+    pub found: Vec<Type>,
+    /// Type of the values a generator body yields, `None` outside generators
+    pub yields: Option<Type>,
+    /// Whether the body is the body of an async function, where `await` is allowed
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Checker {
+    scopes: Vec<Scope>,
+    returns: Vec<ReturnContext>,
+    this: Vec<Type>,
+    /// Exports of the modules described by `declare module` blocks
+    modules: HashMap<String, Vec<TypeMember>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        let mut checker = Self {
+            scopes: vec![Scope::default()],
+            returns: Default::default(),
+            this: Default::default(),
+            modules: Default::default(),
+            diagnostics: Default::default(),
+        };
+
+        checker.declare_iterators();
+        checker.declare_promises();
+
+        checker
+    }
+}
+
+impl Checker {
+    /// Declares a host-provided binding (like `console`) so scripts can use it.
+    pub fn declare<N: AsRef<str>, T: Into<Type>>(&mut self, name: N, ty: T) {
+        self.scopes[0].values.insert(
+            name.as_ref().into(),
+            Binding {
+                ty: ty.into(),
+                mutable: false,
+            },
+        );
+    }
+
+    pub fn check_program(&mut self, program: &Block) -> Vec<Diagnostic> {
+        self.check_statements(&program.value);
+
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Checks a `.d.ts` declaration file, keeping what it declares for the programs
+    /// checked afterwards so their use of host APIs is typed.
+    pub fn load_declarations(&mut self, declarations: &Block) -> Vec<Diagnostic> {
+        for statement in &declarations.value {
+            if !matches!(
+                statement.value,
+                Statement::AmbientDeclaration(_)
+                    | Statement::InterfaceDeclaration(_)
+                    | Statement::TypeAliasDeclaration(_)
+                    | Statement::ImportDeclaration(_)
+                    | Statement::ExportDeclaration(_)
+            ) {
+                self.error(
+                    statement.span,
+                    "only declarations are allowed in a declaration file",
+                );
+            }
+        }
+
+        self.check_program(declarations)
+    }
+
+    fn error<T: Into<String>>(&mut self, span: Span, message: T) {
+        self.diagnostics.push(Diagnostic::new(span, message));
+    }
+
+    fn add_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    fn remove_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
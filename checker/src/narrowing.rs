@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use tsr_lexer::{globals::Positioned, token::Operator};
+use tsr_parser::ast::{Expression, Literal, PredefinedType, Type};
+
+use crate::{
+    types::{is_any, is_never},
+    Checker,
+};
+
+/// Types of bindings that hold while a condition has a known value.
+pub type Narrowings = Vec<(String, Type)>;
+
+/// Literal types, `null` and `undefined` only have a single value.
+fn is_unit(ty: &Type) -> bool {
+    matches!(
+        ty.as_predefined(),
+        Some(
+            PredefinedType::StringLiteral(_)
+                | PredefinedType::NumberLiteral(_)
+                | PredefinedType::BooleanLiteral(_)
+                | PredefinedType::Null
+                | PredefinedType::Undefined
+        )
+    )
+}
+
+fn is_nullish(ty: &Type) -> bool {
+    matches!(
+        ty.as_predefined(),
+        Some(PredefinedType::Null | PredefinedType::Undefined | PredefinedType::Void)
+    )
+}
+
+/// Keeps the narrowings that hold on both sides of an `||`, their types are joined.
+fn either(left: Narrowings, right: Narrowings) -> Narrowings {
+    let right = right.into_iter().collect::<HashMap<_, _>>();
+
+    left.into_iter()
+        .filter_map(|(name, ty)| {
+            right
+                .get(&name)
+                .map(|other| (name, Type::union(vec![ty, other.clone()])))
+        })
+        .collect()
+}
+
+impl Checker {
+    /// Narrowings implied by `condition` evaluating to `assume`.
+    pub(crate) fn narrowings(
+        &mut self,
+        condition: &Positioned<Expression>,
+        assume: bool,
+    ) -> Narrowings {
+        let expression = match &condition.value {
+            Expression::Ident(ident) => {
+                let name = &ident.value.0;
+
+                return match assume {
+                    true => self.narrowed(name, |_, ty| !is_nullish(ty)),
+                    false => vec![],
+                };
+            }
+            Expression::BinaryExpression(expression) => &expression.value,
+            _ => return vec![],
+        };
+        let (left, right) = (&expression.left, &expression.right);
+
+        match expression.operator.value {
+            Operator::Not => self.narrowings(left, !assume),
+            Operator::AndAnd | Operator::OrOr => {
+                // The right side only runs when the left one did not decide the result
+                let and = expression.operator.value == Operator::AndAnd;
+                let left_narrowings = self.narrowings(left, and);
+
+                self.add_scope();
+                self.apply_narrowings(&left_narrowings);
+
+                let right_narrowings = self.narrowings(right, assume);
+
+                self.remove_scope();
+
+                match assume == and {
+                    true => [left_narrowings, right_narrowings].concat(),
+                    false => either(
+                        self.narrowings(left, assume),
+                        [left_narrowings, right_narrowings].concat(),
+                    ),
+                }
+            }
+            Operator::EqEq | Operator::EqEqEq | Operator::Ne | Operator::NeEq => {
+                let equal = matches!(expression.operator.value, Operator::EqEq | Operator::EqEqEq)
+                    == assume;
+                let loose = matches!(expression.operator.value, Operator::EqEq | Operator::Ne);
+
+                match self.narrow_equal(left, right, equal, loose) {
+                    narrowings if narrowings.is_empty() => {
+                        self.narrow_equal(right, left, equal, loose)
+                    }
+                    narrowings => narrowings,
+                }
+            }
+            Operator::InstanceOf => {
+                let Expression::Ident(ident) = &left.value else {
+                    return vec![];
+                };
+                let class = self.infer_silently(right, None);
+                let Some((_, _, instance)) = self.construct_signature(&class) else {
+                    return vec![];
+                };
+
+                match assume {
+                    true => match self.narrowed(&ident.value.0, |checker, ty| {
+                        checker.is_assignable(ty, &instance)
+                    }) {
+                        narrowings if narrowings.iter().all(|(_, ty)| is_never(ty)) => {
+                            vec![(ident.value.0.clone(), instance)]
+                        }
+                        narrowings => narrowings,
+                    },
+                    false => self.narrowed(&ident.value.0, |checker, ty| {
+                        !checker.is_assignable(ty, &instance)
+                    }),
+                }
+            }
+            Operator::In => match (&left.value, &right.value) {
+                (Expression::Literal(literal), Expression::Ident(ident)) => {
+                    let Literal::String(key) = &literal.value else {
+                        return vec![];
+                    };
+
+                    self.narrowed(&ident.value.0, |checker, ty| {
+                        checker.property_type(ty, &key.value).is_some() == assume
+                    })
+                }
+                _ => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    pub(crate) fn apply_narrowings(&mut self, narrowings: &[(String, Type)]) {
+        for (name, ty) in narrowings {
+            self.narrow(name, ty.clone());
+        }
+    }
+
+    /// Narrows `target` knowing whether it equals the value of `other`. Besides plain
+    /// bindings, `target` may be a `typeof` of a binding or a property of one, which
+    /// narrows a discriminated union.
+    pub(crate) fn narrow_equal(
+        &mut self,
+        target: &Positioned<Expression>,
+        other: &Positioned<Expression>,
+        equal: bool,
+        loose: bool,
+    ) -> Narrowings {
+        let other = self.infer_silently(other, None);
+        let others = match loose && is_nullish(&other) {
+            true => vec![
+                PredefinedType::Null.into(),
+                PredefinedType::Undefined.into(),
+                PredefinedType::Void.into(),
+            ],
+            false => vec![other],
+        };
+
+        match &target.value {
+            Expression::Ident(ident) => {
+                let name = &ident.value.0;
+
+                match equal {
+                    true => self.narrowed_map(name, |checker, ty| {
+                        others.iter().find_map(|other| {
+                            match (
+                                checker.is_assignable(ty, other),
+                                checker.is_assignable(other, ty),
+                            ) {
+                                (true, _) => Some(ty.clone()),
+                                (false, true) => Some(other.clone()),
+                                (false, false) => None,
+                            }
+                        })
+                    }),
+                    false => self.narrowed(name, |checker, ty| {
+                        !(is_unit(ty) && others.iter().any(|other| checker.same_type(ty, other)))
+                    }),
+                }
+            }
+            Expression::IndexExpression(expression) => {
+                let (Expression::Ident(ident), Expression::Literal(literal)) = (
+                    &expression.value.target.value,
+                    &expression.value.index.value,
+                ) else {
+                    return vec![];
+                };
+                let Literal::String(key) = &literal.value else {
+                    return vec![];
+                };
+
+                self.narrowed(&ident.value.0, |checker, ty| {
+                    let Some(property) = checker.property_type(ty, &key.value) else {
+                        return true;
+                    };
+                    let property = checker.resolve_type(&property);
+
+                    match equal {
+                        true => others.iter().any(|other| {
+                            checker.is_assignable(&property, other)
+                                || checker.is_assignable(other, &property)
+                        }),
+                        false => {
+                            !(is_unit(&property)
+                                && others
+                                    .iter()
+                                    .any(|other| checker.same_type(&property, other)))
+                        }
+                    }
+                })
+            }
+            Expression::BinaryExpression(expression)
+                if expression.value.operator.value == Operator::TypeOf =>
+            {
+                let Expression::Ident(ident) = &expression.value.left.value else {
+                    return vec![];
+                };
+                let Some(PredefinedType::StringLiteral(name)) =
+                    others.first().and_then(Type::as_predefined).cloned()
+                else {
+                    return vec![];
+                };
+                let primitive = match name.as_str() {
+                    "string" => Some(PredefinedType::String),
+                    "number" => Some(PredefinedType::Number),
+                    "boolean" => Some(PredefinedType::Boolean),
+                    "undefined" => Some(PredefinedType::Undefined),
+                    _ => None,
+                };
+
+                match self.get_binding(&ident.value.0) {
+                    Some(binding)
+                        if equal
+                            && primitive.is_some()
+                            && matches!(
+                                self.resolve_type(&binding.ty).as_predefined(),
+                                Some(PredefinedType::Any | PredefinedType::Unknown)
+                            ) =>
+                    {
+                        vec![(ident.value.0.clone(), primitive.unwrap().into())]
+                    }
+                    _ => self.narrowed(&ident.value.0, |checker, ty| {
+                        (checker.type_of(ty) == name) == equal
+                    }),
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The result of the `typeof` operator for values of `ty`.
+    fn type_of(&self, ty: &Type) -> &'static str {
+        match ty.as_predefined() {
+            Some(
+                PredefinedType::Number | PredefinedType::Float | PredefinedType::NumberLiteral(_),
+            ) => "number",
+            Some(PredefinedType::String | PredefinedType::StringLiteral(_)) => "string",
+            Some(PredefinedType::Boolean | PredefinedType::BooleanLiteral(_)) => "boolean",
+            Some(PredefinedType::Undefined | PredefinedType::Void) => "undefined",
+            _ if matches!(ty, Type::FunctionType(..) | Type::ConstructorType(..))
+                || self.call_signature(ty).is_some()
+                || self.construct_signature(ty).is_some() =>
+            {
+                "function"
+            }
+            _ => "object",
+        }
+    }
+
+    fn same_type(&self, first: &Type, second: &Type) -> bool {
+        self.is_assignable(first, second) && self.is_assignable(second, first)
+    }
+
+    /// Members of the union type of `name` for which `keep` holds.
+    fn narrowed<F: Fn(&Self, &Type) -> bool>(&self, name: &str, keep: F) -> Narrowings {
+        self.narrowed_map(name, |checker, ty| keep(checker, ty).then(|| ty.clone()))
+    }
+
+    fn narrowed_map<F: Fn(&Self, &Type) -> Option<Type>>(&self, name: &str, map: F) -> Narrowings {
+        let Some(binding) = self.get_binding(name) else {
+            return vec![];
+        };
+        let ty = self.resolve_type(&binding.ty);
+
+        if is_any(&ty) {
+            return vec![];
+        }
+
+        // Members that are kept as they were keep the name they were written with
+        let members = ty.union_members();
+        let narrowed_members = members
+            .iter()
+            .filter_map(|member| {
+                let resolved = self.resolve_type(member);
+
+                map(self, &resolved).map(|ty| match ty == resolved {
+                    true => member.clone(),
+                    false => ty,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if narrowed_members == members {
+            return vec![];
+        }
+
+        let members = narrowed_members;
+        let narrowed = match members.is_empty() {
+            true => PredefinedType::Never.into(),
+            false => Type::union(members),
+        };
+
+        vec![(name.into(), narrowed)]
+    }
+}
@@ -0,0 +1,180 @@
+use tsr_lexer::Lexer;
+use tsr_parser::Parser;
+
+use crate::Checker;
+
+/// Types of the standard globals the runtime installs with `Runtime::with_std`.
+const DECLARATIONS: &str = r#"
+interface Map<K, V> {
+    size: number;
+    'get'(key: K): V | undefined;
+    'set'(key: K, value: V): Map<K, V>;
+    has(key: K): boolean;
+    delete(key: K): boolean;
+    clear(): void;
+    forEach(callback: (value: V, key: K) => void): void;
+    keys(): IterableIterator<K>;
+    values(): IterableIterator<V>;
+    entries(): IterableIterator<[K, V]>;
+}
+
+interface Set<T> {
+    size: number;
+    add(value: T): Set<T>;
+    has(value: T): boolean;
+    delete(value: T): boolean;
+    clear(): void;
+    forEach(callback: (value: T, key: T) => void): void;
+    keys(): IterableIterator<T>;
+    values(): IterableIterator<T>;
+    entries(): IterableIterator<[T, T]>;
+}
+
+interface WeakMap<K, V> {
+    'get'(key: K): V | undefined;
+    'set'(key: K, value: V): WeakMap<K, V>;
+    has(key: K): boolean;
+    delete(key: K): boolean;
+}
+
+declare const NaN: float;
+declare const Infinity: float;
+
+// Functions taking any number of arguments can't be declared yet, so they are `any`
+declare const console: {
+    log: any;
+    error: any;
+    warn: any;
+    table(data: any): void;
+};
+
+declare const Math: {
+    PI: float;
+    E: float;
+    LN2: float;
+    LN10: float;
+    LOG2E: float;
+    LOG10E: float;
+    SQRT2: float;
+    abs(x: number | float): number | float;
+    floor(x: number | float): number | float;
+    ceil(x: number | float): number | float;
+    trunc(x: number | float): number | float;
+    round(x: number | float): number | float;
+    sign(x: number | float): number | float;
+    sqrt(x: number | float): float;
+    cbrt(x: number | float): float;
+    exp(x: number | float): float;
+    log(x: number | float): float;
+    log2(x: number | float): float;
+    log10(x: number | float): float;
+    sin(x: number | float): float;
+    cos(x: number | float): float;
+    tan(x: number | float): float;
+    asin(x: number | float): float;
+    acos(x: number | float): float;
+    atan(x: number | float): float;
+    atan2(y: number | float, x: number | float): float;
+    fround(x: number | float): float;
+    pow(base: number | float, exponent: number | float): number | float;
+    min: any;
+    max: any;
+    hypot: any;
+    random(): float;
+};
+
+declare const Number: {
+    (value?: any): number | float;
+    MAX_SAFE_INTEGER: number;
+    MIN_SAFE_INTEGER: number;
+    EPSILON: float;
+    MAX_VALUE: float;
+    POSITIVE_INFINITY: float;
+    NEGATIVE_INFINITY: float;
+    NaN: float;
+    isInteger(value: any): boolean;
+    isSafeInteger(value: any): boolean;
+    isFinite(value: any): boolean;
+    isNaN(value: any): boolean;
+    parseInt(text: string, radix?: number): number | float;
+    parseFloat(text: string): float;
+};
+
+declare function parseInt(text: string, radix?: number): number | float;
+declare function parseFloat(text: string): float;
+
+declare const Object: {
+    keys(object: any): any;
+    values(object: any): any;
+    entries(object: any): any;
+    assign: any;
+};
+
+declare const Array: {
+    isArray(value: any): boolean;
+    'from'(source: any): any;
+    // `of` can't be named in a type yet
+    [name: string]: any;
+};
+
+declare const JSON: {
+    parse(text: string): any;
+    stringify(value: any, replacer?: null | undefined, space?: any): string | undefined;
+};
+
+declare const Map: {
+    new <K = any, V = any>(entries?: any): Map<K, V>;
+};
+
+declare const Set: {
+    new <T = any>(values?: any): Set<T>;
+};
+
+declare const WeakMap: {
+    new <K = any, V = any>(entries?: any): WeakMap<K, V>;
+};
+
+declare const Symbol: {
+    (description?: any): symbol;
+    iterator: symbol;
+    asyncIterator: symbol;
+    hasInstance: symbol;
+    toPrimitive: symbol;
+    toStringTag: symbol;
+    'for'(key: string): symbol;
+    keyFor(key: symbol): string | undefined;
+};
+
+declare const Promise: {
+    new <T = any>(executor: any): Promise<T>;
+    resolve<T = any>(value?: T): Promise<T>;
+    reject(reason?: any): Promise<any>;
+    all(values: any): Promise<any>;
+    race(values: any): Promise<any>;
+};
+
+declare const setTimeout: any;
+declare const setInterval: any;
+declare function clearTimeout(id?: number): void;
+declare function clearInterval(id?: number): void;
+"#;
+
+impl Checker {
+    /// A checker that knows the standard globals, for programs run with
+    /// `Runtime::with_std`.
+    pub fn with_std() -> Self {
+        let mut checker = Self::default();
+        let (_, tokens) = Lexer::lex_tokens(DECLARATIONS.as_bytes().into())
+            .expect("built-in declarations are lexed");
+        let (_, declarations) =
+            Parser::parse_tokens(&tokens).expect("built-in declarations are parsed");
+        let diagnostics = checker.load_declarations(&declarations);
+
+        debug_assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+        // Programs may declare names of their own shadowing the globals
+        checker.add_scope();
+
+        checker
+    }
+}
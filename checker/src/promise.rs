@@ -0,0 +1,75 @@
+use tsr_lexer::{globals::Span, Lexer};
+use tsr_parser::{
+    ast::{PredefinedType, PrimaryType, Statement, Type},
+    Parser,
+};
+
+use crate::{types::is_any, Checker};
+
+/// Interface of the promises async functions and the `Promise` global give scripts.
+const DECLARATIONS: &str = r#"
+interface Promise<T> {
+    then(onFulfilled?: (value: T) => any, onRejected?: (reason: any) => any): Promise<any>;
+    catch(onRejected?: (reason: any) => any): Promise<any>;
+    finally(onFinally?: () => any): Promise<T>;
+}
+"#;
+
+impl Checker {
+    /// Declares the built-in promise interface in the root scope.
+    pub(crate) fn declare_promises(&mut self) {
+        let (_, tokens) = Lexer::lex_tokens(DECLARATIONS.as_bytes().into())
+            .expect("built-in declarations are lexed");
+        let (_, declarations) =
+            Parser::parse_tokens(&tokens).expect("built-in declarations are parsed");
+
+        for statement in &declarations.value {
+            if let Statement::InterfaceDeclaration(declaration) = &statement.value {
+                self.hoist_interface(declaration);
+            }
+        }
+    }
+
+    /// Type `await` evaluates to: what a promise fulfills with, other values as they are.
+    pub(crate) fn awaited_type(&self, ty: &Type) -> Type {
+        if let Some(PrimaryType::TypeReference(name, generics)) = ty.as_primary() {
+            if name.0 == "Promise" {
+                return generics
+                    .first()
+                    .cloned()
+                    .unwrap_or(PredefinedType::Any.into());
+            }
+        }
+
+        let members = ty.union_members();
+
+        match members.len() > 1 {
+            true => Type::union(
+                members
+                    .iter()
+                    .map(|member| self.awaited_type(member))
+                    .collect(),
+            ),
+            false => ty.clone(),
+        }
+    }
+
+    /// What the body of an async function annotated with `ty` returns, reports annotations
+    /// that aren't promises.
+    pub(crate) fn promised_type(&mut self, span: Span, ty: &Type) -> Type {
+        match ty.as_primary() {
+            Some(PrimaryType::TypeReference(name, _)) if name.0 == "Promise" => {
+                self.awaited_type(ty)
+            }
+            _ if is_any(&self.resolve_type(ty)) => PredefinedType::Any.into(),
+            _ => {
+                self.error(
+                    span,
+                    format!("type \"{ty}\" is not a valid return type for an async function"),
+                );
+
+                PredefinedType::Any.into()
+            }
+        }
+    }
+}
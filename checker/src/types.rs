@@ -0,0 +1,854 @@
+use std::collections::HashMap;
+
+use tsr_lexer::{globals::Span, token::Modifier};
+use tsr_parser::ast::{
+    ArraySize, CallSignature, ConditionalType, Ident, IndexSignature, IntersectionOrPrimaryType,
+    MappedType, MethodSignature, Parameter, PredefinedType, PrimaryType, PropertyName,
+    PropertySignature, Type, TypeMember, TypeParameter, UnionOrIntersectionOrPrimaryType,
+};
+
+use crate::{environment::TypeDeclaration, Checker};
+
+/// Recursive types (`interface Node { next: Node }`) are only expanded this deep.
+const MAX_DEPTH: usize = 32;
+
+pub type FunctionSignature = (Vec<TypeParameter>, Vec<Parameter>, Type);
+
+pub fn is_any(ty: &Type) -> bool {
+    matches!(ty.as_predefined(), Some(PredefinedType::Any))
+}
+
+pub fn is_void(ty: &Type) -> bool {
+    matches!(ty.as_predefined(), Some(PredefinedType::Void))
+}
+
+pub fn is_never(ty: &Type) -> bool {
+    matches!(ty.as_predefined(), Some(PredefinedType::Never))
+}
+
+pub fn member_name(member: &TypeMember) -> Option<String> {
+    match member {
+        TypeMember::PropertySignature(signature) => Some(signature.value.name.value.0.clone()),
+        TypeMember::MethodSignature(signature) => match &signature.value.0.value {
+            PropertyName::LiteralPropertyName(literal) => Some(literal.value.to_string()),
+            PropertyName::ComputedPropertyName(_) => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn signature_type(signature: &CallSignature) -> Type {
+    let CallSignature(type_parameters, parameters, ty) = signature;
+
+    Type::FunctionType(
+        type_parameters.iter().map(|p| p.value.clone()).collect(),
+        parameters.iter().map(|p| p.value.clone()).collect(),
+        Box::new(ty.value.clone()),
+    )
+}
+
+/// Binds the generic arguments of a type reference to the declaration's type parameters.
+fn type_arguments(type_parameters: &[TypeParameter], generics: &[Type]) -> HashMap<String, Type> {
+    TypeParameter::bind(
+        type_parameters,
+        TypeParameter::zip(type_parameters, generics),
+    )
+}
+
+/// Reports a wrong number of type arguments, parameters with a default may be left out.
+pub fn type_arguments_count(type_parameters: &[TypeParameter], count: usize) -> Option<String> {
+    let required = type_parameters
+        .iter()
+        .filter(|parameter| parameter.default.is_none())
+        .count();
+
+    if count >= required && count <= type_parameters.len() {
+        return None;
+    }
+
+    let expected = match required == type_parameters.len() {
+        true => required.to_string(),
+        false => format!("{required}-{}", type_parameters.len()),
+    };
+
+    Some(format!(
+        "expected {expected} type arguments, but got {count}"
+    ))
+}
+
+fn declared_type_parameters(declaration: &TypeDeclaration) -> &[TypeParameter] {
+    match declaration {
+        TypeDeclaration::Alias {
+            type_parameters, ..
+        }
+        | TypeDeclaration::Interface {
+            type_parameters, ..
+        }
+        | TypeDeclaration::Class {
+            type_parameters, ..
+        } => type_parameters,
+        TypeDeclaration::Enum { .. } | TypeDeclaration::Parameter { .. } => &[],
+    }
+}
+
+fn required_parameters(parameters: &[Parameter]) -> usize {
+    parameters
+        .iter()
+        .filter(|parameter| !parameter.nullable.value && parameter.default.is_none())
+        .count()
+}
+
+impl Checker {
+    /// Expands type references, parentheses and `this` until a structural type is reached.
+    /// Unknown references resolve to `any` since they are reported separately.
+    pub fn resolve_type(&self, ty: &Type) -> Type {
+        self.resolve_type_depth(ty, 0)
+    }
+
+    fn resolve_type_depth(&self, ty: &Type, depth: usize) -> Type {
+        if depth > MAX_DEPTH {
+            return PredefinedType::Any.into();
+        }
+
+        if let Type::ConditionalType(conditional) = ty {
+            return self.resolve_conditional(conditional, depth + 1);
+        }
+
+        match ty.as_primary() {
+            Some(PrimaryType::ParenthesizedType(ty)) => self.resolve_type_depth(ty, depth + 1),
+            Some(PrimaryType::TypeReference(name, generics)) => match self.get_type(&name.0) {
+                Some(TypeDeclaration::Alias {
+                    type_parameters,
+                    ty,
+                }) => self.resolve_type_depth(
+                    &ty.substitute(&type_arguments(type_parameters, generics)),
+                    depth + 1,
+                ),
+                Some(
+                    TypeDeclaration::Interface {
+                        type_parameters, ..
+                    }
+                    | TypeDeclaration::Class {
+                        type_parameters, ..
+                    },
+                ) => Type::from(PrimaryType::ObjectType(
+                    self.declaration_members(&name.0, depth),
+                ))
+                .substitute(&type_arguments(type_parameters, generics)),
+                Some(TypeDeclaration::Enum { members }) => Type::union(members.clone()),
+                Some(TypeDeclaration::Parameter {
+                    constraint: Some(constraint),
+                }) => self.resolve_type_depth(constraint, depth + 1),
+                Some(TypeDeclaration::Parameter { constraint: None }) | None => {
+                    PredefinedType::Any.into()
+                }
+            },
+            Some(PrimaryType::ThisType) => match self.this.last() {
+                Some(this) => self.resolve_type_depth(this, depth + 1),
+                None => PredefinedType::Any.into(),
+            },
+            Some(PrimaryType::TypeQuery(path)) => {
+                let ty = self.get_binding(&path[0].0).and_then(|binding| {
+                    path[1..].iter().try_fold(binding.ty.clone(), |ty, name| {
+                        self.property_type(&ty, &name.0)
+                    })
+                });
+
+                match ty {
+                    Some(ty) => self.resolve_type_depth(&ty, depth + 1),
+                    None => PredefinedType::Any.into(),
+                }
+            }
+            Some(PrimaryType::KeyOfType(ty)) => self.keys_of(&(**ty).clone().into()),
+            Some(PrimaryType::IndexedAccessType(ty, index)) => {
+                let object = (**ty).clone().into();
+                let index = self.resolve_type_depth(index, depth + 1);
+
+                self.resolve_type_depth(
+                    &Type::union(
+                        index
+                            .union_members()
+                            .iter()
+                            .map(|index| self.indexed_type(&object, index))
+                            .collect(),
+                    ),
+                    depth + 1,
+                )
+            }
+            Some(PrimaryType::MappedType(mapped)) => self.resolve_mapped(mapped, depth + 1),
+            Some(PrimaryType::ReadonlyType(ty)) => {
+                self.resolve_type_depth(&(**ty).clone().into(), depth + 1)
+            }
+            Some(PrimaryType::InferType(_)) => PredefinedType::Any.into(),
+            Some(primary) => primary.clone().into(),
+            None => ty.clone(),
+        }
+    }
+
+    /// Property names of `ty` as a union of string literals, index signatures contribute
+    /// their key type.
+    fn keys_of(&self, ty: &Type) -> Type {
+        if is_any(&self.resolve_type(ty)) {
+            return PredefinedType::String.into();
+        }
+
+        let keys = self
+            .members_of(ty)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|member| match member {
+                TypeMember::IndexSignature(signature) => Some(signature.value.1.value.clone()),
+                member => {
+                    member_name(member).map(|name| PredefinedType::StringLiteral(name).into())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match keys.is_empty() {
+            true => PredefinedType::Never.into(),
+            false => Type::union(keys),
+        }
+    }
+
+    /// Type of `object[index]` for a single (non-union) index type.
+    fn indexed_type(&self, object: &Type, index: &Type) -> Type {
+        let resolved = self.resolve_type(object);
+
+        match (resolved.as_primary(), index.as_predefined()) {
+            (Some(PrimaryType::PredefinedType(PredefinedType::Any)), _) => {
+                PredefinedType::Any.into()
+            }
+            (
+                Some(PrimaryType::ArrayType(element, _)),
+                Some(PredefinedType::Number | PredefinedType::NumberLiteral(_)),
+            ) => (**element).clone().into(),
+            (Some(PrimaryType::TupleType(types)), Some(PredefinedType::NumberLiteral(index))) => {
+                types
+                    .get(*index as usize)
+                    .cloned()
+                    .unwrap_or(PredefinedType::Undefined.into())
+            }
+            (Some(PrimaryType::TupleType(types)), Some(PredefinedType::Number)) => {
+                Type::union(types.clone())
+            }
+            (_, Some(PredefinedType::StringLiteral(name))) => self
+                .property_type(object, name)
+                .unwrap_or(PredefinedType::Any.into()),
+            (_, Some(PredefinedType::String)) => self
+                .members_of(object)
+                .unwrap_or_default()
+                .into_iter()
+                .find_map(|member| match member {
+                    TypeMember::IndexSignature(signature) => Some(signature.value.2.value),
+                    _ => None,
+                })
+                .unwrap_or(PredefinedType::Any.into()),
+            _ => PredefinedType::Any.into(),
+        }
+    }
+
+    /// Expands `{ [K in Keys]: T }` into one property per literal key, other keys become
+    /// index signatures.
+    fn resolve_mapped(&self, mapped: &MappedType, depth: usize) -> Type {
+        let span = Span::default();
+        let modifiers = match mapped.readonly {
+            true => vec![span.wrap(Modifier::Readonly)],
+            false => vec![],
+        };
+        let keys = self.resolve_type_depth(&mapped.constraint, depth + 1);
+
+        PrimaryType::ObjectType(
+            keys.union_members()
+                .into_iter()
+                .filter(|key| !is_never(key))
+                .map(|key| {
+                    let ty = mapped
+                        .ty
+                        .substitute(&HashMap::from([(mapped.parameter.0.clone(), key.clone())]));
+
+                    match key.as_predefined() {
+                        Some(PredefinedType::StringLiteral(name)) => {
+                            TypeMember::PropertySignature(span.wrap(PropertySignature {
+                                modifiers: modifiers.clone(),
+                                name: span.wrap(Ident(name.clone())),
+                                nullable: span.wrap(mapped.optional),
+                                ty: span.wrap(ty),
+                            }))
+                        }
+                        _ => TypeMember::IndexSignature(span.wrap(IndexSignature(
+                            span.wrap(mapped.parameter.clone()),
+                            span.wrap(key),
+                            span.wrap(ty),
+                        ))),
+                    }
+                })
+                .collect(),
+        )
+        .into()
+    }
+
+    /// Picks the branch of a conditional type. The check distributes over unions, `any`
+    /// and unresolved type parameters take both branches.
+    fn resolve_conditional(&self, conditional: &ConditionalType, depth: usize) -> Type {
+        let check = self.resolve_type_depth(&conditional.check, depth + 1);
+        let checks = check.union_members();
+
+        if checks.len() > 1 {
+            return Type::union(
+                checks
+                    .into_iter()
+                    .map(|check| {
+                        self.resolve_conditional(
+                            &ConditionalType {
+                                check,
+                                ..conditional.clone()
+                            },
+                            depth + 1,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        if is_any(&check) {
+            return Type::union(vec![
+                self.resolve_type_depth(&conditional.true_type, depth + 1),
+                self.resolve_type_depth(&conditional.false_type, depth + 1),
+            ]);
+        }
+
+        let mut bindings = HashMap::new();
+
+        conditional
+            .extends
+            .infer(&conditional.check, &[], &mut bindings);
+        conditional.extends.infer(&check, &[], &mut bindings);
+
+        let branch = match self.is_assignable(&check, &conditional.extends.substitute(&bindings)) {
+            true => conditional.true_type.substitute(&bindings),
+            false => conditional.false_type.clone(),
+        };
+
+        self.resolve_type_depth(&branch, depth + 1)
+    }
+
+    /// Members of an interface or class including everything it inherits.
+    fn declaration_members(&self, name: &str, depth: usize) -> Vec<TypeMember> {
+        let (extends, members) = match self.get_type(name) {
+            Some(
+                TypeDeclaration::Interface {
+                    extends, members, ..
+                }
+                | TypeDeclaration::Class {
+                    extends, members, ..
+                },
+            ) => (extends.clone(), members.clone()),
+            _ => return vec![],
+        };
+
+        if depth > MAX_DEPTH {
+            return members;
+        }
+
+        let mut result = vec![];
+
+        for parent in extends {
+            for member in self.declaration_members(&parent, depth + 1) {
+                let name = member_name(&member);
+
+                if name.is_none()
+                    || !members
+                        .iter()
+                        .any(|own| member_name(own).is_some_and(|own| Some(own) == name))
+                {
+                    result.push(member);
+                }
+            }
+        }
+
+        result.extend(members);
+
+        result
+    }
+
+    /// Object members of `ty`, if it describes an object.
+    pub fn members_of(&self, ty: &Type) -> Option<Vec<TypeMember>> {
+        let ty = self.resolve_type(ty);
+
+        match ty {
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                    IntersectionOrPrimaryType::IntersectionType(types),
+                ),
+            ) => {
+                let mut members = vec![];
+
+                for ty in types {
+                    members.extend(self.members_of(&ty.into())?);
+                }
+
+                Some(members)
+            }
+            ty => match ty.as_primary() {
+                Some(PrimaryType::ObjectType(members)) => Some(members.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn property_type(&self, ty: &Type, name: &str) -> Option<Type> {
+        let members = self.members_of(ty)?;
+
+        for member in &members {
+            if member_name(member).as_deref() == Some(name) {
+                return Some(match member {
+                    TypeMember::PropertySignature(signature) => signature.value.ty.value.clone(),
+                    TypeMember::MethodSignature(signature) => {
+                        let MethodSignature(_, _, signature) = &signature.value;
+
+                        signature_type(&signature.value)
+                    }
+                    _ => unreachable!(),
+                });
+            }
+        }
+
+        members.iter().find_map(|member| match member {
+            TypeMember::IndexSignature(signature) => Some(signature.value.2.value.clone()),
+            _ => None,
+        })
+    }
+
+    pub fn call_signature(&self, ty: &Type) -> Option<FunctionSignature> {
+        self.call_signatures(ty).into_iter().next()
+    }
+
+    /// Every call signature of `ty`, overloaded functions have more than one.
+    pub fn call_signatures(&self, ty: &Type) -> Vec<FunctionSignature> {
+        match self.resolve_type(ty) {
+            Type::FunctionType(type_parameters, parameters, ty) => {
+                vec![(type_parameters, parameters, *ty)]
+            }
+            ty => self
+                .members_of(&ty)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|member| match member {
+                    TypeMember::CallSignature(signature) => {
+                        let CallSignature(type_parameters, parameters, ty) = signature.value;
+
+                        Some((
+                            type_parameters.into_iter().map(|p| p.value).collect(),
+                            parameters.into_iter().map(|p| p.value).collect(),
+                            ty.value,
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn construct_signature(&self, ty: &Type) -> Option<FunctionSignature> {
+        match self.resolve_type(ty) {
+            Type::ConstructorType(type_parameters, parameters, ty) => {
+                Some((type_parameters, parameters, *ty))
+            }
+            ty => self
+                .members_of(&ty)?
+                .into_iter()
+                .find_map(|member| match member {
+                    TypeMember::ConstructSignature(signature) => Some((
+                        signature.value.0.into_iter().map(|p| p.value).collect(),
+                        signature.value.1.into_iter().map(|p| p.value).collect(),
+                        signature.value.2.value,
+                    )),
+                    _ => None,
+                }),
+        }
+    }
+
+    pub fn is_assignable(&self, source: &Type, target: &Type) -> bool {
+        self.is_assignable_depth(source, target, 0)
+    }
+
+    fn is_assignable_depth(&self, source: &Type, target: &Type, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return true;
+        }
+
+        let source = self.resolve_type(source);
+        let target = self.resolve_type(target);
+
+        if is_any(&source) || is_any(&target) || is_never(&source) || source == target {
+            return true;
+        }
+
+        if matches!(target.as_predefined(), Some(PredefinedType::Unknown)) {
+            return true;
+        }
+
+        let sources = source.union_members();
+
+        if sources.len() > 1 {
+            return sources
+                .iter()
+                .all(|source| self.is_assignable_depth(source, &target, depth + 1));
+        }
+
+        let targets = target.union_members();
+
+        if targets.len() > 1 {
+            return targets
+                .iter()
+                .any(|target| self.is_assignable_depth(&source, target, depth + 1));
+        }
+
+        if let Type::UnionOrIntersectionOrPrimaryType(
+            UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                IntersectionOrPrimaryType::IntersectionType(types),
+            ),
+        ) = &target
+        {
+            return types.iter().all(|target| {
+                self.is_assignable_depth(&source, &target.clone().into(), depth + 1)
+            });
+        }
+
+        match (&source, &target) {
+            (
+                Type::FunctionType(_, source_parameters, source_ty),
+                Type::FunctionType(_, target_parameters, target_ty),
+            )
+            | (
+                Type::ConstructorType(_, source_parameters, source_ty),
+                Type::ConstructorType(_, target_parameters, target_ty),
+            ) => self.is_function_assignable(
+                (source_parameters, source_ty),
+                (target_parameters, target_ty),
+                depth,
+            ),
+            _ => match (source.as_primary(), target.as_primary()) {
+                (
+                    Some(PrimaryType::PredefinedType(source)),
+                    Some(PrimaryType::PredefinedType(target)),
+                ) => matches!(
+                    (source, target),
+                    (PredefinedType::StringLiteral(_), PredefinedType::String)
+                        | (
+                            PredefinedType::Number | PredefinedType::NumberLiteral(_),
+                            PredefinedType::Number | PredefinedType::Float
+                        )
+                        | (PredefinedType::BooleanLiteral(_), PredefinedType::Boolean)
+                        | (PredefinedType::Undefined, PredefinedType::Void)
+                ),
+                (_, Some(PrimaryType::ObjectType(members))) => {
+                    self.is_object_assignable(&source, members, depth)
+                }
+                (
+                    Some(PrimaryType::ArrayType(source, source_size)),
+                    Some(PrimaryType::ArrayType(target, target_size)),
+                ) => {
+                    (matches!(target_size, ArraySize::Dynamic) || source_size == target_size)
+                        && self.is_assignable_depth(
+                            &(**source).clone().into(),
+                            &(**target).clone().into(),
+                            depth + 1,
+                        )
+                }
+                (
+                    Some(PrimaryType::TupleType(sources)),
+                    Some(PrimaryType::ArrayType(target, size)),
+                ) => {
+                    (match size {
+                        ArraySize::Fixed(size) => sources.len() == *size,
+                        ArraySize::Dynamic => true,
+                    }) && sources.iter().all(|source| {
+                        self.is_assignable_depth(source, &(**target).clone().into(), depth + 1)
+                    })
+                }
+                (Some(PrimaryType::TupleType(sources)), Some(PrimaryType::TupleType(targets))) => {
+                    sources.len() == targets.len()
+                        && sources.iter().zip(targets).all(|(source, target)| {
+                            self.is_assignable_depth(source, target, depth + 1)
+                        })
+                }
+                _ => match &source {
+                    Type::UnionOrIntersectionOrPrimaryType(
+                        UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                            IntersectionOrPrimaryType::IntersectionType(types),
+                        ),
+                    ) => types.iter().any(|source| {
+                        self.is_assignable_depth(&source.clone().into(), &target, depth + 1)
+                    }),
+                    _ => false,
+                },
+            },
+        }
+    }
+
+    fn is_function_assignable(
+        &self,
+        (source_parameters, source_ty): (&[Parameter], &Type),
+        (target_parameters, target_ty): (&[Parameter], &Type),
+        depth: usize,
+    ) -> bool {
+        if required_parameters(source_parameters) > target_parameters.len() {
+            return false;
+        }
+
+        let parameters = source_parameters
+            .iter()
+            .zip(target_parameters)
+            .all(|(source, target)| {
+                self.is_assignable_depth(&target.ty.value, &source.ty.value, depth + 1)
+                    || self.is_assignable_depth(&source.ty.value, &target.ty.value, depth + 1)
+            });
+
+        parameters
+            && (is_void(target_ty) || self.is_assignable_depth(source_ty, target_ty, depth + 1))
+    }
+
+    fn is_object_assignable(&self, source: &Type, targets: &[TypeMember], depth: usize) -> bool {
+        if targets.is_empty() {
+            return !matches!(
+                source.as_predefined(),
+                Some(PredefinedType::Null | PredefinedType::Void)
+            );
+        }
+
+        if let Type::FunctionType(..) = source {
+            return targets.iter().all(|member| match member {
+                TypeMember::CallSignature(signature) => {
+                    self.is_assignable_depth(source, &signature_type(&signature.value), depth + 1)
+                }
+                _ => false,
+            });
+        }
+
+        let sources = match self.members_of(source) {
+            Some(sources) => sources,
+            // Strings and arrays aren't object types, but have members of their own
+            None if matches!(
+                source.as_primary(),
+                Some(
+                    PrimaryType::PredefinedType(
+                        PredefinedType::String | PredefinedType::StringLiteral(_)
+                    ) | PrimaryType::ArrayType(..)
+                        | PrimaryType::TupleType(_)
+                )
+            ) =>
+            {
+                vec![]
+            }
+            None => return false,
+        };
+
+        targets.iter().all(|member| match member {
+            TypeMember::PropertySignature(signature) => {
+                match self.property(source, &signature.value.name.value.0) {
+                    Some(ty) => self.is_assignable_depth(&ty, &signature.value.ty.value, depth + 1),
+                    None => signature.value.nullable.value,
+                }
+            }
+            TypeMember::MethodSignature(signature) => {
+                let MethodSignature(_, optional, call) = &signature.value;
+
+                match member_name(member).and_then(|name| self.property(source, &name)) {
+                    Some(ty) => {
+                        self.is_assignable_depth(&ty, &signature_type(&call.value), depth + 1)
+                    }
+                    None => optional.value,
+                }
+            }
+            TypeMember::CallSignature(signature) => {
+                self.call_signature(source)
+                    .is_some_and(|(type_parameters, parameters, ty)| {
+                        self.is_assignable_depth(
+                            &Type::FunctionType(type_parameters, parameters, Box::new(ty)),
+                            &signature_type(&signature.value),
+                            depth + 1,
+                        )
+                    })
+            }
+            TypeMember::ConstructSignature(signature) => self
+                .construct_signature(source)
+                .is_some_and(|(_, parameters, ty)| {
+                    let target = &signature.value;
+
+                    self.is_function_assignable(
+                        (&parameters, &ty),
+                        (
+                            &target.1.iter().map(|p| p.value.clone()).collect::<Vec<_>>(),
+                            &target.2.value,
+                        ),
+                        depth,
+                    )
+                }),
+            TypeMember::IndexSignature(signature) => sources.iter().all(|member| {
+                let ty = match member {
+                    TypeMember::PropertySignature(property) => property.value.ty.value.clone(),
+                    TypeMember::MethodSignature(method) => signature_type(&method.value.2.value),
+                    TypeMember::IndexSignature(index) => index.value.2.value.clone(),
+                    _ => return true,
+                };
+
+                self.is_assignable_depth(&ty, &signature.value.2.value, depth + 1)
+            }),
+        })
+    }
+
+    /// Reports every type name in `ty` that is not declared.
+    pub(crate) fn validate_type(&mut self, span: Span, ty: &Type) {
+        match ty {
+            Type::UnionOrIntersectionOrPrimaryType(ty) => match ty {
+                UnionOrIntersectionOrPrimaryType::UnionType(types) => {
+                    for ty in types {
+                        self.validate_type(span, &ty.clone().into());
+                    }
+                }
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(ty) => match ty {
+                    IntersectionOrPrimaryType::IntersectionType(types) => {
+                        for ty in types {
+                            self.validate_primary_type(span, ty);
+                        }
+                    }
+                    IntersectionOrPrimaryType::PrimaryType(ty) => {
+                        self.validate_primary_type(span, ty)
+                    }
+                },
+            },
+            Type::FunctionType(type_parameters, parameters, ty)
+            | Type::ConstructorType(type_parameters, parameters, ty) => {
+                self.add_scope();
+                self.set_type_parameters(type_parameters);
+
+                for parameter in parameters {
+                    self.validate_type(span, &parameter.ty.value);
+                }
+
+                self.validate_type(span, ty);
+                self.remove_scope();
+            }
+            Type::ConditionalType(conditional) => {
+                self.validate_type(span, &conditional.check);
+                self.add_scope();
+                self.validate_type(span, &conditional.extends);
+                self.validate_type(span, &conditional.true_type);
+                self.remove_scope();
+                self.validate_type(span, &conditional.false_type);
+            }
+        }
+    }
+
+    fn validate_primary_type(&mut self, span: Span, ty: &PrimaryType) {
+        match ty {
+            PrimaryType::ParenthesizedType(ty) => self.validate_type(span, ty),
+            PrimaryType::TypeReference(name, generics) => {
+                if self.get_type(&name.0).is_none() {
+                    self.error(span, format!("cannot find name \"{name}\""));
+                }
+
+                for generic in generics {
+                    self.validate_type(span, generic);
+                }
+
+                if let Some(declaration) = self.get_type(&name.0).filter(|_| !generics.is_empty()) {
+                    let message =
+                        type_arguments_count(declared_type_parameters(declaration), generics.len());
+
+                    if let Some(message) = message {
+                        self.error(span, message);
+                    }
+                }
+            }
+            PrimaryType::ObjectType(members) => {
+                for member in members {
+                    match member {
+                        TypeMember::PropertySignature(signature) => {
+                            self.validate_type(span, &signature.value.ty.value)
+                        }
+                        TypeMember::CallSignature(signature) => {
+                            self.validate_type(span, &signature_type(&signature.value))
+                        }
+                        TypeMember::ConstructSignature(signature) => {
+                            let signature = &signature.value;
+
+                            self.validate_type(
+                                span,
+                                &Type::ConstructorType(
+                                    signature.0.iter().map(|p| p.value.clone()).collect(),
+                                    signature.1.iter().map(|p| p.value.clone()).collect(),
+                                    Box::new(signature.2.value.clone()),
+                                ),
+                            )
+                        }
+                        TypeMember::IndexSignature(signature) => {
+                            self.validate_type(span, &signature.value.1.value);
+                            self.validate_type(span, &signature.value.2.value);
+                        }
+                        TypeMember::MethodSignature(signature) => {
+                            self.validate_type(span, &signature_type(&signature.value.2.value))
+                        }
+                    }
+                }
+            }
+            PrimaryType::ArrayType(ty, _) => self.validate_primary_type(span, ty),
+            PrimaryType::TupleType(types) => {
+                for ty in types {
+                    self.validate_type(span, ty);
+                }
+            }
+            PrimaryType::MappedType(mapped) => {
+                self.validate_type(span, &mapped.constraint);
+                self.add_scope();
+                self.set_type(
+                    &mapped.parameter.0,
+                    TypeDeclaration::Parameter {
+                        constraint: Some(mapped.constraint.clone()),
+                    },
+                );
+                self.validate_type(span, &mapped.ty);
+                self.remove_scope();
+            }
+            PrimaryType::TypeQuery(path) => {
+                if self.get_binding(&path[0].0).is_none() {
+                    self.error(span, format!("cannot find name \"{}\"", path[0]));
+                }
+            }
+            PrimaryType::KeyOfType(ty) | PrimaryType::ReadonlyType(ty) => {
+                self.validate_primary_type(span, ty)
+            }
+            PrimaryType::IndexedAccessType(ty, index) => {
+                self.validate_primary_type(span, ty);
+                self.validate_type(span, index);
+
+                let object: Type = (**ty).clone().into();
+
+                if let Some(members) = self.members_of(&object) {
+                    for key in self.resolve_type(index).union_members() {
+                        if let Some(PredefinedType::StringLiteral(name)) = key.as_predefined() {
+                            let found = members.iter().any(|member| {
+                                matches!(member, TypeMember::IndexSignature(_))
+                                    || member_name(member).as_deref() == Some(name)
+                            });
+
+                            if !found {
+                                self.error(
+                                    span,
+                                    format!(
+                                        "property \"{name}\" does not exist on type \"{object}\""
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            PrimaryType::InferType(name) => {
+                self.set_type(&name.0, TypeDeclaration::Parameter { constraint: None })
+            }
+            PrimaryType::PredefinedType(_) | PrimaryType::ThisType => {}
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::tokens::Tokens;
 use nom::{IResult, InputLength};
 use nom_locate::LocatedSpan;
+use owo_colors::{colors, Color};
 use std::fmt::Debug;
 
 #[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, Default)]
@@ -24,6 +25,24 @@ impl Span {
     pub fn wrap<A>(self, value: A) -> Positioned<A> {
         Positioned { value, span: self }
     }
+
+    /// Reports `message` under the line of `source` the span starts at, headed by the
+    /// `kind` of error and where it happened in `file`.
+    pub fn report(&self, kind: &str, file: &str, source: &str, message: &str) -> String {
+        format!(
+            "==> {red}{kind}{reset} at {green}{file}:{line}:{column}{reset}\n{gray}{pline:>3} |{reset}\n{gray}{line:>3} |{reset} {data}\n{gray}... |{reset}{nspace}{red}{length} {message}{reset}",
+            pline = self.line - 1,
+            line = self.line,
+            column = self.column,
+            length = "-".repeat(self.end - self.start),
+            data = source.lines().nth((self.line - 1) as usize).unwrap_or_default(),
+            nspace = " ".repeat(self.column + 2 - (self.line.to_string().len())),
+            gray = colors::css::DimGray::ANSI_FG,
+            green = colors::css::LightSeaGreen::ANSI_FG,
+            red = colors::css::IndianRed::ANSI_FG,
+            reset = colors::Default::ANSI_FG
+        )
+    }
 }
 
 impl From<BytesSpan<'_>> for Span {
@@ -5,8 +5,10 @@ use nom::character::complete::digit1;
 use nom::combinator::map;
 use nom::combinator::map_res;
 use nom::combinator::opt;
+use nom::combinator::recognize;
 use nom::sequence::delimited;
 use nom::sequence::pair;
+use nom::sequence::tuple;
 use nom::AsBytes;
 use nom_locate::position;
 
@@ -49,29 +51,73 @@ pub fn lex_integer(input: BytesSpan) -> ByteResult<Positioned<Token>> {
     Ok((input, start.between(end).wrap(Token::Literal(value))))
 }
 
-fn pis(input: BytesSpan) -> ByteResult<Vec<u8>> {
+/// Numbers with a fraction, an exponent or both, like `2.5`, `-0.1` or `1.5e-3`. Digits are
+/// required after the point so that `2...` still spreads the integer `2`.
+pub fn lex_float(input: BytesSpan) -> ByteResult<Positioned<Token>> {
+    let (input, start) = position(input)?;
+    let exponent = || {
+        tuple((
+            alt((tag("e"), tag("E"))),
+            opt(alt((tag("+"), tag("-")))),
+            digit1,
+        ))
+    };
+    let (input, value) = map_res(
+        map_res(
+            recognize(tuple((
+                opt(tag("-")),
+                digit1,
+                alt((
+                    recognize(pair(pair(tag("."), digit1), opt(exponent()))),
+                    recognize(exponent()),
+                )),
+            ))),
+            complete_byte_slice_str_from_utf8,
+        ),
+        complete_str_from_str,
+    )(input)?;
+    let (input, end) = position(input)?;
+    let start: Span = start.into();
+    let end: Span = end.into();
+
+    Ok((
+        input,
+        start
+            .between(end)
+            .wrap(Token::Literal(Literal::Float(value))),
+    ))
+}
+
+fn pis<'a>(input: BytesSpan<'a>, quote: &[u8]) -> ByteResult<'a, Vec<u8>> {
     use std::result::Result::*;
 
     let (second_input, character) = take(1usize)(input)?;
 
     match character.as_bytes() {
-        b"\"" => Ok((input, vec![])),
+        c if c == quote => Ok((input, vec![])),
         b"\\" => {
             let (i2, c2) = take(1usize)(second_input)?;
 
-            pis(i2).map(|(slice, done)| (slice, concat_slice_vec(c2.fragment(), done)))
+            pis(i2, quote).map(|(slice, done)| (slice, concat_slice_vec(c2.fragment(), done)))
         }
-        c => pis(second_input).map(|(slice, done)| (slice, concat_slice_vec(c, done))),
+        c => pis(second_input, quote).map(|(slice, done)| (slice, concat_slice_vec(c, done))),
     }
 }
 
 pub fn lex_string(input: BytesSpan) -> ByteResult<Positioned<Token>> {
     let (input, start) = position(input)?;
-    let (input, value) = delimited(
-        double_quote_punctuation,
-        map_res(pis, convert_vec_utf8),
-        double_quote_punctuation,
-    )(input)?;
+    let (input, value) = alt((
+        delimited(
+            double_quote_punctuation,
+            map_res(|input| pis(input, b"\""), convert_vec_utf8),
+            double_quote_punctuation,
+        ),
+        delimited(
+            tag("'"),
+            map_res(|input| pis(input, b"'"), convert_vec_utf8),
+            tag("'"),
+        ),
+    ))(input)?;
     let (input, end) = position(input)?;
     let start: Span = start.into();
     let end: Span = end.into();
@@ -85,5 +131,5 @@ pub fn lex_string(input: BytesSpan) -> ByteResult<Positioned<Token>> {
 }
 
 pub fn lex_literal(input: BytesSpan) -> ByteResult<Positioned<Token>> {
-    alt((lex_string, lex_integer))(input)
+    alt((lex_string, lex_float, lex_integer))(input)
 }
@@ -13,7 +13,9 @@ syntax! {
     and_operator: "&" => Token::Operator(Operator::And);
     and_and_operator: "&&" => Token::Operator(Operator::AndAnd);
     equal_operator: "==" => Token::Operator(Operator::EqEq);
+    strict_equal_operator: "===" => Token::Operator(Operator::EqEqEq);
     not_equal_operator: "!=" => Token::Operator(Operator::Ne);
+    strict_not_equal_operator: "!==" => Token::Operator(Operator::NeEq);
     or_operator: "|" => Token::Operator(Operator::Or);
     or_or_operator: "||" => Token::Operator(Operator::OrOr);
     assign_operator: "=" => Token::Operator(Operator::Eq);
@@ -32,12 +34,14 @@ syntax! {
 
 pub fn lex_operator(input: BytesSpan) -> ByteResult<Positioned<Token>> {
     alt((
-        and_operator,
         and_and_operator,
+        and_operator,
+        strict_equal_operator,
         equal_operator,
+        strict_not_equal_operator,
         not_equal_operator,
-        or_operator,
         or_or_operator,
+        or_operator,
         assign_operator,
         plus_plus_operator,
         plus_operator,
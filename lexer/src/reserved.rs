@@ -40,6 +40,7 @@ pub fn lex_reserved_ident(input: BytesSpan) -> ByteResult<Positioned<Token>> {
                     "protected" => Token::Modifier(Modifier::Protected),
                     "static" => Token::Modifier(Modifier::Static),
                     "async" => Token::Modifier(Modifier::Async),
+                    "readonly" => Token::Modifier(Modifier::Readonly),
 
                     "const" => Token::ReservedWord(ReservedWord::Const),
                     "let" => Token::ReservedWord(ReservedWord::Let),
@@ -67,12 +68,18 @@ pub fn lex_reserved_ident(input: BytesSpan) -> ByteResult<Positioned<Token>> {
                     "get" => Token::ReservedWord(ReservedWord::Get),
                     "set" => Token::ReservedWord(ReservedWord::Set),
                     "type" => Token::ReservedWord(ReservedWord::Type),
-                    "typeOf" => Token::ReservedWord(ReservedWord::TypeOf),
+                    "typeof" => Token::ReservedWord(ReservedWord::TypeOf),
+                    "keyof" => Token::ReservedWord(ReservedWord::KeyOf),
+                    "infer" => Token::ReservedWord(ReservedWord::Infer),
                     "for" => Token::ReservedWord(ReservedWord::For),
                     "in" => Token::ReservedWord(ReservedWord::In),
+                    "instanceof" => Token::ReservedWord(ReservedWord::InstanceOf),
                     "of" => Token::ReservedWord(ReservedWord::Of),
                     "as" => Token::ReservedWord(ReservedWord::As),
+                    "satisfies" => Token::ReservedWord(ReservedWord::Satisfies),
                     "from" => Token::ReservedWord(ReservedWord::From),
+                    "yield" => Token::ReservedWord(ReservedWord::Yield),
+                    "await" => Token::ReservedWord(ReservedWord::Await),
 
                     "any" => Token::BuiltInType(BuiltInType::Any),
                     "number" => Token::BuiltInType(BuiltInType::Number),
@@ -81,6 +88,9 @@ pub fn lex_reserved_ident(input: BytesSpan) -> ByteResult<Positioned<Token>> {
                     "string" => Token::BuiltInType(BuiltInType::String),
                     "symbol" => Token::BuiltInType(BuiltInType::Symbol),
                     "void" => Token::BuiltInType(BuiltInType::Void),
+                    "unknown" => Token::BuiltInType(BuiltInType::Unknown),
+                    "never" => Token::BuiltInType(BuiltInType::Never),
+                    "undefined" => Token::BuiltInType(BuiltInType::Undefined),
 
                     "true" => Token::Literal(Literal::Boolean(true)),
                     "false" => Token::Literal(Literal::Boolean(false)),
@@ -10,6 +10,9 @@ pub enum BuiltInType {
     String,
     Symbol,
     Void,
+    Unknown,
+    Never,
+    Undefined,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -48,12 +51,18 @@ pub enum ReservedWord {
     Set,
     Type,
     TypeOf,
+    KeyOf,
+    Infer,
     For,
     In,
+    InstanceOf,
     Of,
     As,
+    Satisfies,
     From,
-    When
+    When,
+    Yield,
+    Await,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -83,13 +92,18 @@ pub enum Operator {
     Minus,
     MinusMinus,
     EqEq,
+    EqEqEq,
     Eq,
     Ne,
+    NeEq,
     Le,
     Ge,
     Lt,
     Gt,
     Not,
+    TypeOf,
+    InstanceOf,
+    In,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -99,6 +113,7 @@ pub enum Modifier {
     Protected,
     Async,
     Static,
+    Readonly,
 }
 
 #[derive(Clone, Debug, PartialEq)]
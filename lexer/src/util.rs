@@ -20,7 +20,7 @@ pub fn convert_vec_utf8(v: Vec<u8>) -> Result<String, Utf8Error> {
     str::from_utf8(slice).map(|s| s.to_owned())
 }
 
-pub fn complete_byte_slice_str_from_utf8(c: BytesSpan) -> Result<&str, Utf8Error> {
+pub fn complete_byte_slice_str_from_utf8(c: BytesSpan<'_>) -> Result<&str, Utf8Error> {
     str::from_utf8(c.fragment())
 }
 
@@ -23,6 +23,8 @@ struct Attributes {
     name: Option<Ident>,
     args: Vec<(String, Expr, Option<Expr>)>,
     returns: Option<Expr>,
+    /// Also exports the function as the default export of a module
+    is_default: bool,
 }
 
 trait VecExt<T> {
@@ -71,6 +73,12 @@ fn generate_member(func: ImplItemFn, is_module: bool) -> [proc_macro2::TokenStre
 
         for tokens in tokens {
             if let TokenTree::Ident(name) = &tokens[0] {
+                if name == "default" && tokens.len() == 1 {
+                    attributes.is_default = true;
+
+                    continue;
+                }
+
                 match (name.to_string().as_str(), &tokens[2..]) {
                     ("name", [TokenTree::Literal(literal)]) => {
                         let literal = literal.to_string();
@@ -187,17 +195,27 @@ fn generate_member(func: ImplItemFn, is_module: bool) -> [proc_macro2::TokenStre
             quote!()
         };
 
+        let export_default = if attributes.is_default {
+            quote!(module.export_default(function.clone());)
+        } else {
+            quote!()
+        };
+
         let object_func = if is_module {
             quote! {
-                module.export(#name, FunctionBuilder::new(#name)
-                    #(#args)*
-                    #returns
-                    .build(move |args| {
-                        let result = self.#original_name(args);
-
-                        #returning
-                    })
-                );
+                {
+                    let function = FunctionBuilder::new(#name)
+                        #(#args)*
+                        #returns
+                        .build(move |args| {
+                            let result = self.#original_name(args);
+
+                            #returning
+                        });
+
+                    #export_default
+                    module.export(#name, function);
+                }
             }
         } else {
             quote! {
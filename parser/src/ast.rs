@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::HashMap;
 
 use tsr_lexer::globals::Positioned;
 use tsr_lexer::token::Modifier;
@@ -13,13 +14,21 @@ pub enum Infix {
     Minus,
     Divide,
     Multiply,
-    Inverse,
+    NonNull,
     Equal,
     NotEqual,
+    StrictEqual,
+    StrictNotEqual,
     GreaterThanEqual,
     LessThanEqual,
     GreaterThan,
     LessThan,
+    LogicalAnd,
+    LogicalOr,
+    InstanceOf,
+    In,
+    As,
+    Satisfies,
 }
 
 #[derive(PartialEq, Hash, Debug, Eq, Clone)]
@@ -40,6 +49,9 @@ impl fmt::Display for Ident {
 #[derive(PartialEq, PartialOrd, Debug, Clone)]
 pub enum Precedence {
     PLowest,
+    PAssign,
+    POr,
+    PAnd,
     PEquals,
     PLessGreater,
     PSum,
@@ -59,8 +71,11 @@ pub enum Statement {
     EnumDeclaration(Positioned<EnumDeclaration>),
     ExportDeclaration(Positioned<ExportDeclaration>),
     ClassDeclaration(Positioned<ClassDeclaration>),
+    NamespaceDeclaration(Positioned<NamespaceDeclaration>),
+    AmbientDeclaration(Box<Positioned<AmbientDeclaration>>),
     VariableStatement(Positioned<VariableStatement>),
     IfStatement(Box<Positioned<IfStatement>>),
+    ForOfStatement(Box<Positioned<ForOfStatement>>),
     ReturnStatement(Positioned<Expression>),
     Expression(Positioned<Expression>),
 }
@@ -102,7 +117,7 @@ pub struct ClassDeclaration {
 #[derive(PartialEq, Debug, Clone)]
 pub enum ClassElement {
     ConstructorDeclaration(Positioned<ConstructorDeclaration>),
-    PropertyMemberDeclaration(Positioned<PropertyMemberDeclaration>),
+    PropertyMemberDeclaration(Box<Positioned<PropertyMemberDeclaration>>),
     IndexMemberDeclaration(Positioned<IndexSignature>),
 }
 
@@ -191,12 +206,37 @@ pub struct ArrowParameter {
 pub struct FunctionDeclaration {
     pub name: Positioned<Ident>,
     pub modifiers: Vec<Positioned<Modifier>>,
+    /// `function*`, calls return a generator running the body as it is iterated
+    pub generator: bool,
     pub type_parameters: Vec<Positioned<TypeParameter>>,
     pub parameters: Vec<Positioned<Parameter>>,
     pub ty: Positioned<Type>,
     pub body: Option<Block>,
 }
 
+/// `namespace A.B { ... }`, members marked with `export` become properties of `A.B`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct NamespaceDeclaration {
+    pub name: Vec<Positioned<Ident>>,
+    pub body: Block,
+}
+
+/// `declare ...`, bindings provided by the host rather than the program.
+#[derive(PartialEq, Debug, Clone)]
+pub enum AmbientDeclaration {
+    VariableStatement(Positioned<VariableStatement>),
+    FunctionDeclaration(Positioned<FunctionDeclaration>),
+    NamespaceDeclaration(Positioned<NamespaceDeclaration>),
+    ModuleDeclaration(Positioned<ModuleDeclaration>),
+}
+
+/// `declare module "name" { ... }`, the exports of a native module.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModuleDeclaration {
+    pub name: Positioned<String>,
+    pub body: Block,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct EnumDeclaration {
     pub name: Positioned<Ident>,
@@ -224,8 +264,45 @@ pub struct IfStatement {
     pub else_statement: Option<Positioned<Statement>>,
 }
 
+/// `for (const binding of iterable) body`
+#[derive(PartialEq, Debug, Clone)]
+pub struct ForOfStatement {
+    pub mutable: Positioned<bool>,
+    pub binding: Positioned<BindingPattern>,
+    pub iterable: Positioned<Expression>,
+    pub body: Positioned<Statement>,
+}
+
+/// Target of a variable declaration, a name or a pattern taking values apart.
+#[derive(PartialEq, Debug, Clone)]
+pub enum BindingPattern {
+    Ident(Ident),
+    /// `[first, , third, ...rest]`, bound to the values of an iterable in order
+    Array {
+        elements: Vec<Option<Positioned<BindingPattern>>>,
+        rest: Option<Positioned<Ident>>,
+    },
+}
+
+impl BindingPattern {
+    /// Names bound by the pattern, in the order they are written.
+    pub fn names(pattern: &Positioned<BindingPattern>) -> Vec<Positioned<Ident>> {
+        match &pattern.value {
+            BindingPattern::Ident(name) => vec![pattern.wrap(name.clone())],
+            BindingPattern::Array { elements, rest } => elements
+                .iter()
+                .flatten()
+                .flat_map(BindingPattern::names)
+                .chain(rest.clone())
+                .collect(),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct ImportDeclaration {
+    /// `import type`, erased at runtime
+    pub is_type_only: Positioned<bool>,
     pub import_clause: Option<Positioned<ImportClause>>,
     pub module_specifier: Positioned<Literal>,
 }
@@ -235,6 +312,8 @@ pub enum ImportClause {
     Named(Positioned<Ident>),
     NamedImports(Vec<Positioned<ImportSpecifier>>),
     NamespaceImport(Positioned<Ident>),
+    /// `import name, { a }` and `import name, * as names`
+    Combined(Positioned<Ident>, Box<Positioned<ImportClause>>),
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -298,6 +377,26 @@ pub enum Type {
     UnionOrIntersectionOrPrimaryType(UnionOrIntersectionOrPrimaryType),
     FunctionType(Vec<TypeParameter>, Vec<Parameter>, Box<Type>),
     ConstructorType(Vec<TypeParameter>, Vec<Parameter>, Box<Type>),
+    ConditionalType(Box<ConditionalType>),
+}
+
+/// `check extends extends ? true_type : false_type`
+#[derive(PartialEq, Debug, Clone)]
+pub struct ConditionalType {
+    pub check: Type,
+    pub extends: Type,
+    pub true_type: Type,
+    pub false_type: Type,
+}
+
+/// `{ readonly [parameter in constraint]?: ty }`
+#[derive(PartialEq, Debug, Clone)]
+pub struct MappedType {
+    pub readonly: bool,
+    pub parameter: Ident,
+    pub constraint: Type,
+    pub optional: bool,
+    pub ty: Type,
 }
 
 impl Default for Type {
@@ -306,6 +405,542 @@ impl Default for Type {
     }
 }
 
+impl Type {
+    /// Builds a union of `types`, flattening nested unions and dropping duplicates.
+    pub fn union(types: Vec<Type>) -> Type {
+        let mut members: Vec<Type> = vec![];
+
+        for ty in types.iter().flat_map(Type::union_members) {
+            if !members.contains(&ty) {
+                members.push(ty);
+            }
+        }
+
+        match members.len() {
+            0 => PredefinedType::Void.into(),
+            1 => members.remove(0),
+            _ => {
+                Type::UnionOrIntersectionOrPrimaryType(UnionOrIntersectionOrPrimaryType::UnionType(
+                    members.into_iter().map(Into::into).collect(),
+                ))
+            }
+        }
+    }
+
+    /// Splits a union into its members, any other type is returned as is.
+    pub fn union_members(&self) -> Vec<Type> {
+        match self {
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::UnionType(types),
+            ) => types.iter().cloned().map(Into::into).collect(),
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                    IntersectionOrPrimaryType::PrimaryType(PrimaryType::ParenthesizedType(ty)),
+                ),
+            ) => ty.union_members(),
+            ty => vec![ty.clone()],
+        }
+    }
+
+    pub fn as_primary(&self) -> Option<&PrimaryType> {
+        match self {
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                    IntersectionOrPrimaryType::PrimaryType(PrimaryType::ParenthesizedType(ty)),
+                ),
+            ) => ty.as_primary(),
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                    IntersectionOrPrimaryType::PrimaryType(ty),
+                ),
+            ) => Some(ty),
+            _ => None,
+        }
+    }
+
+    pub fn as_predefined(&self) -> Option<&PredefinedType> {
+        match self.as_primary() {
+            Some(PrimaryType::PredefinedType(ty)) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Replaces references to type parameters with the types bound to them in `arguments`.
+    pub fn substitute(&self, arguments: &HashMap<String, Type>) -> Type {
+        if arguments.is_empty() {
+            return self.clone();
+        }
+
+        match self {
+            Type::UnionOrIntersectionOrPrimaryType(ty) => match ty {
+                UnionOrIntersectionOrPrimaryType::UnionType(types) => {
+                    Type::UnionOrIntersectionOrPrimaryType(
+                        UnionOrIntersectionOrPrimaryType::UnionType(
+                            types.iter().map(|ty| ty.substitute(arguments)).collect(),
+                        ),
+                    )
+                }
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(ty) => {
+                    match ty.substitute(arguments) {
+                        IntersectionOrPrimaryType::PrimaryType(PrimaryType::ParenthesizedType(
+                            ty,
+                        )) => *ty,
+                        ty => ty.into(),
+                    }
+                }
+            },
+            Type::FunctionType(type_parameters, parameters, ty) => {
+                let arguments = shadow(arguments, type_parameters);
+
+                Type::FunctionType(
+                    type_parameters.clone(),
+                    substitute_parameters(parameters, &arguments),
+                    Box::new(ty.substitute(&arguments)),
+                )
+            }
+            Type::ConstructorType(type_parameters, parameters, ty) => {
+                let arguments = shadow(arguments, type_parameters);
+
+                Type::ConstructorType(
+                    type_parameters.clone(),
+                    substitute_parameters(parameters, &arguments),
+                    Box::new(ty.substitute(&arguments)),
+                )
+            }
+            Type::ConditionalType(conditional) => {
+                Type::ConditionalType(Box::new(ConditionalType {
+                    check: conditional.check.substitute(arguments),
+                    extends: conditional.extends.substitute(arguments),
+                    true_type: conditional.true_type.substitute(arguments),
+                    false_type: conditional.false_type.substitute(arguments),
+                }))
+            }
+        }
+    }
+
+    /// Binds the type parameters named in `names` by matching `self` against the type of an
+    /// actual value, parameters that are already bound are left untouched. `infer X`
+    /// declarations are always bound.
+    pub fn infer(&self, from: &Type, names: &[String], bindings: &mut HashMap<String, Type>) {
+        match (self, from) {
+            (
+                Type::FunctionType(_, parameters, ty),
+                Type::FunctionType(_, from_parameters, from_ty),
+            ) => {
+                for (parameter, from) in parameters.iter().zip(from_parameters) {
+                    parameter.ty.value.infer(&from.ty.value, names, bindings);
+                }
+
+                ty.infer(from_ty, names, bindings);
+            }
+            _ => {
+                if let (Some(ty), Some(from)) = (self.as_primary(), from.as_primary()) {
+                    ty.infer(from, names, bindings);
+                }
+            }
+        }
+    }
+}
+
+impl IntersectionOrPrimaryType {
+    fn substitute(&self, arguments: &HashMap<String, Type>) -> IntersectionOrPrimaryType {
+        match self {
+            IntersectionOrPrimaryType::IntersectionType(types) => {
+                IntersectionOrPrimaryType::IntersectionType(
+                    types.iter().map(|ty| ty.substitute(arguments)).collect(),
+                )
+            }
+            IntersectionOrPrimaryType::PrimaryType(ty) => {
+                IntersectionOrPrimaryType::PrimaryType(ty.substitute(arguments))
+            }
+        }
+    }
+}
+
+impl PrimaryType {
+    fn substitute(&self, arguments: &HashMap<String, Type>) -> PrimaryType {
+        match self {
+            PrimaryType::ParenthesizedType(ty) => {
+                PrimaryType::ParenthesizedType(Box::new(ty.substitute(arguments)))
+            }
+            PrimaryType::TypeReference(Ident(name), generics) if generics.is_empty() => {
+                match arguments.get(name) {
+                    Some(ty) => match ty.as_primary() {
+                        Some(ty) => ty.clone(),
+                        None => PrimaryType::ParenthesizedType(Box::new(ty.clone())),
+                    },
+                    None => self.clone(),
+                }
+            }
+            PrimaryType::InferType(Ident(name)) => match arguments.get(name) {
+                Some(ty) => PrimaryType::ParenthesizedType(Box::new(ty.clone())),
+                None => self.clone(),
+            },
+            PrimaryType::TypeReference(name, generics) => PrimaryType::TypeReference(
+                name.clone(),
+                generics
+                    .iter()
+                    .map(|generic| generic.substitute(arguments))
+                    .collect(),
+            ),
+            PrimaryType::MappedType(mapped) => {
+                let mut inner = arguments.clone();
+
+                inner.remove(&mapped.parameter.0);
+
+                PrimaryType::MappedType(Box::new(MappedType {
+                    constraint: mapped.constraint.substitute(arguments),
+                    ty: mapped.ty.substitute(&inner),
+                    ..(**mapped).clone()
+                }))
+            }
+            PrimaryType::KeyOfType(ty) => {
+                PrimaryType::KeyOfType(Box::new(ty.substitute(arguments)))
+            }
+            PrimaryType::IndexedAccessType(ty, index) => PrimaryType::IndexedAccessType(
+                Box::new(ty.substitute(arguments)),
+                Box::new(index.substitute(arguments)),
+            ),
+            PrimaryType::ReadonlyType(ty) => {
+                PrimaryType::ReadonlyType(Box::new(ty.substitute(arguments)))
+            }
+            PrimaryType::ObjectType(members) => PrimaryType::ObjectType(
+                members
+                    .iter()
+                    .map(|member| member.substitute(arguments))
+                    .collect(),
+            ),
+            PrimaryType::ArrayType(ty, size) => {
+                PrimaryType::ArrayType(Box::new(ty.substitute(arguments)), *size)
+            }
+            PrimaryType::TupleType(types) => {
+                PrimaryType::TupleType(types.iter().map(|ty| ty.substitute(arguments)).collect())
+            }
+            ty => ty.clone(),
+        }
+    }
+
+    fn infer(&self, from: &PrimaryType, names: &[String], bindings: &mut HashMap<String, Type>) {
+        match (self, from) {
+            (PrimaryType::TypeReference(Ident(name), generics), from)
+                if generics.is_empty() && names.contains(name) =>
+            {
+                bindings
+                    .entry(name.clone())
+                    .or_insert_with(|| from.clone().into());
+            }
+            (PrimaryType::InferType(Ident(name)), from) => {
+                bindings
+                    .entry(name.clone())
+                    .or_insert_with(|| from.clone().into());
+            }
+            (
+                PrimaryType::TypeReference(name, generics),
+                PrimaryType::TypeReference(from_name, from_generics),
+            ) if name == from_name => {
+                for (generic, from) in generics.iter().zip(from_generics) {
+                    generic.infer(from, names, bindings);
+                }
+            }
+            (PrimaryType::ParenthesizedType(ty), _) => {
+                ty.infer(&from.clone().into(), names, bindings)
+            }
+            (_, PrimaryType::ParenthesizedType(from)) => {
+                Type::from(self.clone()).infer(from, names, bindings)
+            }
+            (PrimaryType::ReadonlyType(ty), from) => ty.infer(from, names, bindings),
+            (ty, PrimaryType::ReadonlyType(from)) => ty.infer(from, names, bindings),
+            (PrimaryType::ArrayType(ty, _), PrimaryType::ArrayType(from, _)) => {
+                ty.infer(from, names, bindings)
+            }
+            (PrimaryType::ArrayType(ty, _), PrimaryType::TupleType(types)) => Type::from(
+                IntersectionOrPrimaryType::PrimaryType((**ty).clone()),
+            )
+            .infer(&Type::union(types.clone()), names, bindings),
+            (PrimaryType::TupleType(types), PrimaryType::TupleType(from)) => {
+                for (ty, from) in types.iter().zip(from) {
+                    ty.infer(from, names, bindings);
+                }
+            }
+            (PrimaryType::ObjectType(members), PrimaryType::ObjectType(from)) => {
+                for member in members {
+                    let TypeMember::PropertySignature(signature) = member else {
+                        continue;
+                    };
+
+                    let found = from.iter().find_map(|member| match member {
+                        TypeMember::PropertySignature(from)
+                            if from.value.name.value == signature.value.name.value =>
+                        {
+                            Some(&from.value.ty.value)
+                        }
+                        _ => None,
+                    });
+
+                    if let Some(from) = found {
+                        signature.value.ty.value.infer(from, names, bindings);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl FunctionDeclaration {
+    /// Type of a function with the signature of the declaration.
+    pub fn ty(&self) -> Type {
+        Type::FunctionType(
+            self.type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect(),
+            self.parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect(),
+            Box::new(self.ty.value.clone()),
+        )
+    }
+}
+
+impl AmbientDeclaration {
+    /// Members of the object described by the body of `declare namespace` or
+    /// `declare module`. Every declaration is a member unless the body exports some
+    /// explicitly, then only those are.
+    pub fn members(body: &[Positioned<Statement>]) -> Vec<TypeMember> {
+        let property = |name: &Positioned<Ident>, nullable: bool, ty: Type| {
+            TypeMember::PropertySignature(name.wrap(PropertySignature {
+                modifiers: vec![],
+                name: name.clone(),
+                nullable: name.wrap(nullable),
+                ty: name.wrap(ty),
+            }))
+        };
+        let explicit = body
+            .iter()
+            .any(|statement| matches!(statement.value, Statement::ExportDeclaration(_)));
+        let mut members: Vec<(TypeMember, bool)> = vec![];
+
+        for statement in body {
+            let (declaration, exported) = match &statement.value {
+                Statement::ExportDeclaration(export) => match &export.value {
+                    ExportDeclaration::Single(element) => match &element.value {
+                        ExportSingleElement::VariableStatement(variable) => {
+                            (Statement::VariableStatement(variable.clone()), true)
+                        }
+                        ExportSingleElement::FunctionDeclaration(function) => {
+                            (Statement::FunctionDeclaration(function.clone()), true)
+                        }
+                        _ => continue,
+                    },
+                    ExportDeclaration::Default(element) => {
+                        let ty = match &element.value {
+                            ExportDefaultElement::FunctionDeclaration(function) => {
+                                Some(function.value.ty())
+                            }
+                            ExportDefaultElement::IdentifierReference(name)
+                            | ExportDefaultElement::Expression(Positioned {
+                                value: Expression::Ident(name),
+                                ..
+                            }) => members.iter().find_map(|(member, _)| match member {
+                                TypeMember::PropertySignature(signature)
+                                    if signature.value.name.value == name.value =>
+                                {
+                                    Some(signature.value.ty.value.clone())
+                                }
+                                _ => None,
+                            }),
+                            _ => None,
+                        };
+
+                        if let Some(ty) = ty {
+                            let name = element.span.wrap(Ident::new("default"));
+
+                            members.push((property(&name, false, ty), true));
+                        }
+
+                        continue;
+                    }
+                    ExportDeclaration::List(_) => continue,
+                },
+                statement => (statement.clone(), false),
+            };
+
+            match declaration {
+                Statement::VariableStatement(variable) => {
+                    for declaration in &variable.value.declarations {
+                        let declaration = &declaration.value;
+                        // The type of a pattern describes the value taken apart, not its names
+                        let ty = match &declaration.name.value {
+                            BindingPattern::Ident(_) => declaration.ty.as_ref(),
+                            BindingPattern::Array { .. } => None,
+                        }
+                        .map(|ty| ty.value.clone())
+                        .unwrap_or(PredefinedType::Any.into());
+
+                        for name in BindingPattern::names(&declaration.name) {
+                            members.push((
+                                property(&name, declaration.nullable.value, ty.clone()),
+                                exported,
+                            ));
+                        }
+                    }
+                }
+                Statement::FunctionDeclaration(function) => members.push((
+                    property(&function.value.name, false, function.value.ty()),
+                    exported,
+                )),
+                Statement::NamespaceDeclaration(namespace) => {
+                    let (first, rest) = namespace.value.name.split_first().unwrap();
+                    let ty = rest.iter().rev().fold(
+                        PrimaryType::ObjectType(Self::members(&namespace.value.body.value)),
+                        |ty, name| PrimaryType::ObjectType(vec![property(name, false, ty.into())]),
+                    );
+
+                    members.push((property(first, false, ty.into()), exported));
+                }
+                _ => {}
+            }
+        }
+
+        members
+            .into_iter()
+            .filter(|(_, exported)| *exported || !explicit)
+            .map(|(member, _)| member)
+            .collect()
+    }
+}
+
+impl TypeMember {
+    fn substitute(&self, arguments: &HashMap<String, Type>) -> TypeMember {
+        match self {
+            TypeMember::PropertySignature(signature) => TypeMember::PropertySignature(
+                signature.wrap(PropertySignature {
+                    ty: signature
+                        .value
+                        .ty
+                        .wrap(signature.value.ty.value.substitute(arguments)),
+                    ..signature.value.clone()
+                }),
+            ),
+            TypeMember::CallSignature(signature) => {
+                TypeMember::CallSignature(signature.wrap(signature.value.substitute(arguments)))
+            }
+            TypeMember::ConstructSignature(signature) => {
+                let ConstructSignature(type_parameters, parameters, ty) = &signature.value;
+                let CallSignature(type_parameters, parameters, ty) =
+                    CallSignature(type_parameters.clone(), parameters.clone(), ty.clone())
+                        .substitute(arguments);
+
+                TypeMember::ConstructSignature(signature.wrap(ConstructSignature(
+                    type_parameters,
+                    parameters,
+                    ty,
+                )))
+            }
+            TypeMember::IndexSignature(signature) => {
+                let IndexSignature(name, index, ty) = &signature.value;
+
+                TypeMember::IndexSignature(signature.wrap(IndexSignature(
+                    name.clone(),
+                    index.wrap(index.value.substitute(arguments)),
+                    ty.wrap(ty.value.substitute(arguments)),
+                )))
+            }
+            TypeMember::MethodSignature(signature) => {
+                let MethodSignature(name, optional, call) = &signature.value;
+
+                TypeMember::MethodSignature(signature.wrap(MethodSignature(
+                    name.clone(),
+                    optional.clone(),
+                    Box::new(call.wrap(call.value.substitute(arguments))),
+                )))
+            }
+        }
+    }
+}
+
+impl CallSignature {
+    fn substitute(&self, arguments: &HashMap<String, Type>) -> CallSignature {
+        let CallSignature(type_parameters, parameters, ty) = self;
+        let arguments = shadow(
+            arguments,
+            &type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect::<Vec<_>>(),
+        );
+
+        CallSignature(
+            type_parameters.clone(),
+            parameters
+                .iter()
+                .map(|parameter| {
+                    parameter.wrap(Parameter {
+                        ty: parameter
+                            .value
+                            .ty
+                            .wrap(parameter.value.ty.value.substitute(&arguments)),
+                        ..parameter.value.clone()
+                    })
+                })
+                .collect(),
+            ty.wrap(ty.value.substitute(&arguments)),
+        )
+    }
+}
+
+/// Drops the arguments hidden by type parameters declared on an inner signature.
+fn shadow(
+    arguments: &HashMap<String, Type>,
+    type_parameters: &[TypeParameter],
+) -> HashMap<String, Type> {
+    arguments
+        .iter()
+        .filter(|(name, _)| {
+            !type_parameters
+                .iter()
+                .any(|parameter| &&parameter.name.value.0 == name)
+        })
+        .map(|(name, ty)| (name.clone(), ty.clone()))
+        .collect()
+}
+
+fn substitute_parameters(
+    parameters: &[Parameter],
+    arguments: &HashMap<String, Type>,
+) -> Vec<Parameter> {
+    parameters
+        .iter()
+        .map(|parameter| Parameter {
+            ty: parameter.ty.wrap(parameter.ty.value.substitute(arguments)),
+            ..parameter.clone()
+        })
+        .collect()
+}
+
+impl From<Type> for IntersectionOrPrimaryType {
+    fn from(value: Type) -> Self {
+        match value {
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(ty),
+            ) => ty,
+            ty => {
+                IntersectionOrPrimaryType::PrimaryType(PrimaryType::ParenthesizedType(Box::new(ty)))
+            }
+        }
+    }
+}
+
+impl From<IntersectionOrPrimaryType> for Type {
+    fn from(value: IntersectionOrPrimaryType) -> Self {
+        Self::UnionOrIntersectionOrPrimaryType(
+            UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(value),
+        )
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum UnionOrIntersectionOrPrimaryType {
     UnionType(Vec<IntersectionOrPrimaryType>),
@@ -322,11 +957,21 @@ pub enum IntersectionOrPrimaryType {
 pub enum PrimaryType {
     ParenthesizedType(Box<Type>),
     PredefinedType(PredefinedType),
-    TypeReference(Ident, Vec<Ident>),
+    TypeReference(Ident, Vec<Type>),
     ObjectType(Vec<TypeMember>),
+    MappedType(Box<MappedType>),
     ArrayType(Box<PrimaryType>, ArraySize),
     TupleType(Vec<Type>),
-    TypeQuery,
+    /// `typeof a.b`
+    TypeQuery(Vec<Ident>),
+    /// `keyof T`
+    KeyOfType(Box<PrimaryType>),
+    /// `T[K]`
+    IndexedAccessType(Box<PrimaryType>, Box<Type>),
+    /// `readonly T[]`
+    ReadonlyType(Box<PrimaryType>),
+    /// `infer U`, only meaningful in the `extends` clause of a conditional type
+    InferType(Ident),
     ThisType,
 }
 
@@ -338,9 +983,14 @@ pub enum PredefinedType {
     Boolean,
     String,
     StringLiteral(String),
+    NumberLiteral(i64),
+    BooleanLiteral(bool),
     Symbol,
     Null,
     Void,
+    Unknown,
+    Never,
+    Undefined,
 }
 
 impl From<PredefinedType> for PrimaryType {
@@ -388,9 +1038,14 @@ impl fmt::Display for PredefinedType {
             PredefinedType::Boolean => write!(f, "boolean"),
             PredefinedType::String => write!(f, "string"),
             PredefinedType::StringLiteral(literal) => write!(f, "\"{literal}\""),
+            PredefinedType::NumberLiteral(literal) => write!(f, "{literal}"),
+            PredefinedType::BooleanLiteral(literal) => write!(f, "{literal}"),
             PredefinedType::Symbol => write!(f, "symbol"),
             PredefinedType::Null => write!(f, "null"),
             PredefinedType::Void => write!(f, "void"),
+            PredefinedType::Unknown => write!(f, "unknown"),
+            PredefinedType::Never => write!(f, "never"),
+            PredefinedType::Undefined => write!(f, "undefined"),
         }
     }
 }
@@ -406,14 +1061,12 @@ impl fmt::Display for PrimaryType {
                 if !generics.is_empty() {
                     write!(f, "<")?;
 
-                    let last = generics.last();
-
-                    for generic in generics {
-                        generic.fmt(f)?;
-
-                        if !last.is_some_and(|g| g == generic) {
+                    for (index, generic) in generics.iter().enumerate() {
+                        if index > 0 {
                             write!(f, ", ")?;
                         }
+
+                        generic.fmt(f)?;
                     }
 
                     write!(f, ">")?;
@@ -444,6 +1097,7 @@ impl fmt::Display for PrimaryType {
                                     Modifier::Protected => "protected",
                                     Modifier::Async => "async",
                                     Modifier::Static => "static",
+                                    Modifier::Readonly => "readonly",
                                 })
                                 .collect::<Vec<_>>();
                             let modifiers = if !modifiers.is_empty() {
@@ -461,8 +1115,15 @@ impl fmt::Display for PrimaryType {
 
                             write!(f, "{modifiers}{name}{nullable}: {ty}")?;
                         }
-                        TypeMember::CallSignature(_) => todo!(),
-                        TypeMember::ConstructSignature(_) => todo!(),
+                        TypeMember::CallSignature(signature) => signature.value.fmt(f)?,
+                        TypeMember::ConstructSignature(signature) => {
+                            let ConstructSignature(generics, parameters, ty) = &signature.value;
+
+                            write!(f, "new ")?;
+
+                            CallSignature(generics.clone(), parameters.clone(), ty.clone())
+                                .fmt(f)?;
+                        }
                         TypeMember::IndexSignature(signature) => {
                             let signature = &signature.value;
 
@@ -472,7 +1133,17 @@ impl fmt::Display for PrimaryType {
                                 signature.0.value, signature.1.value, signature.2.value
                             )?;
                         }
-                        TypeMember::MethodSignature(_) => todo!(),
+                        TypeMember::MethodSignature(signature) => {
+                            let MethodSignature(name, nullable, signature) = &signature.value;
+
+                            write!(f, "{}", name.value)?;
+
+                            if nullable.value {
+                                write!(f, "?")?;
+                            }
+
+                            signature.value.fmt(f)?;
+                        }
                     }
 
                     if !last.is_some_and(|p| p == member) {
@@ -511,12 +1182,109 @@ impl fmt::Display for PrimaryType {
 
                 write!(f, "]")
             }
-            PrimaryType::TypeQuery => write!(f, "unknown"),
+            PrimaryType::MappedType(mapped) => {
+                let MappedType {
+                    readonly,
+                    parameter,
+                    constraint,
+                    optional,
+                    ty,
+                } = &**mapped;
+
+                write!(f, "{{ ")?;
+
+                if *readonly {
+                    write!(f, "readonly ")?;
+                }
+
+                write!(f, "[{parameter} in {constraint}]")?;
+
+                if *optional {
+                    write!(f, "?")?;
+                }
+
+                write!(f, ": {ty} }}")
+            }
+            PrimaryType::TypeQuery(path) => {
+                write!(f, "typeof ")?;
+
+                let last = path.last();
+
+                for name in path {
+                    name.fmt(f)?;
+
+                    if !last.is_some_and(|n| n == name) {
+                        write!(f, ".")?;
+                    }
+                }
+
+                Ok(())
+            }
+            PrimaryType::KeyOfType(ty) => write!(f, "keyof {ty}"),
+            PrimaryType::IndexedAccessType(ty, index) => write!(f, "{ty}[{index}]"),
+            PrimaryType::ReadonlyType(ty) => write!(f, "readonly {ty}"),
+            PrimaryType::InferType(name) => write!(f, "infer {name}"),
             PrimaryType::ThisType => write!(f, "this"),
         }
     }
 }
 
+impl fmt::Display for CallSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let CallSignature(generics, parameters, ty) = self;
+
+        if !generics.is_empty() {
+            write!(f, "<")?;
+
+            let last = generics.last();
+
+            for generic in generics {
+                generic.value.fmt(f)?;
+
+                if !last.is_some_and(|g| g == generic) {
+                    write!(f, ", ")?;
+                }
+            }
+
+            write!(f, ">")?;
+        }
+
+        write!(f, "(")?;
+
+        let last = parameters.last();
+
+        for parameter in parameters {
+            parameter.value.fmt(f)?;
+
+            if !last.is_some_and(|p| p == parameter) {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, "): {}", ty.value)
+    }
+}
+
+impl fmt::Display for PropertyName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyName::LiteralPropertyName(literal) => literal.value.fmt(f),
+            PropertyName::ComputedPropertyName(_) => write!(f, "[computed]"),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::String(string) => write!(f, "{}", string.value),
+            Literal::Number(number) => write!(f, "{}", number.value),
+            Literal::Float(float) => write!(f, "{}", float.value),
+            Literal::Boolean(boolean) => write!(f, "{}", boolean.value),
+        }
+    }
+}
+
 impl fmt::Display for IntersectionOrPrimaryType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -595,14 +1363,12 @@ impl fmt::Display for Type {
                 if !generics.is_empty() {
                     write!(f, "<")?;
 
-                    let last = generics.last();
-
-                    for generic in generics {
-                        generic.fmt(f)?;
-
-                        if !last.is_some_and(|g| g == generic) {
+                    for (index, generic) in generics.iter().enumerate() {
+                        if index > 0 {
                             write!(f, ", ")?;
                         }
+
+                        generic.fmt(f)?;
                     }
 
                     write!(f, ">")?;
@@ -630,14 +1396,12 @@ impl fmt::Display for Type {
                 if !generics.is_empty() {
                     write!(f, "<")?;
 
-                    let last = generics.last();
-
-                    for generic in generics {
-                        generic.fmt(f)?;
-
-                        if !last.is_some_and(|g| g == generic) {
+                    for (index, generic) in generics.iter().enumerate() {
+                        if index > 0 {
                             write!(f, ", ")?;
                         }
+
+                        generic.fmt(f)?;
                     }
 
                     write!(f, ">")?;
@@ -659,6 +1423,16 @@ impl fmt::Display for Type {
 
                 write!(f, "): {ty}")
             }
+            Type::ConditionalType(conditional) => {
+                let ConditionalType {
+                    check,
+                    extends,
+                    true_type,
+                    false_type,
+                } = &**conditional;
+
+                write!(f, "{check} extends {extends} ? {true_type} : {false_type}")
+            }
         }
     }
 }
@@ -678,6 +1452,39 @@ pub struct TypeParameter {
     pub default: Option<Positioned<Type>>,
 }
 
+impl TypeParameter {
+    /// Completes `bindings` for every parameter that was neither given nor inferred, using
+    /// its default, then its constraint, then `any`.
+    pub fn bind(
+        type_parameters: &[TypeParameter],
+        mut bindings: HashMap<String, Type>,
+    ) -> HashMap<String, Type> {
+        for parameter in type_parameters {
+            if bindings.contains_key(&parameter.name.value.0) {
+                continue;
+            }
+
+            let ty = match (&parameter.default, &parameter.constraint) {
+                (Some(ty), _) | (None, Some(ty)) => ty.value.substitute(&bindings),
+                (None, None) => PredefinedType::Any.into(),
+            };
+
+            bindings.insert(parameter.name.value.0.clone(), ty);
+        }
+
+        bindings
+    }
+
+    /// Binds explicit type arguments to `type_parameters` in order.
+    pub fn zip(type_parameters: &[TypeParameter], arguments: &[Type]) -> HashMap<String, Type> {
+        type_parameters
+            .iter()
+            .zip(arguments)
+            .map(|(parameter, ty)| (parameter.name.value.0.clone(), ty.clone()))
+            .collect()
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct TypeAliasDeclaration {
     pub name: Positioned<Ident>,
@@ -693,7 +1500,7 @@ pub struct VariableStatement {
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct VariableDeclaration {
-    pub name: Positioned<Ident>,
+    pub name: Positioned<BindingPattern>,
     pub ty: Option<Positioned<Type>>,
     pub nullable: Positioned<bool>,
     pub initializer: Option<Positioned<Expression>>,
@@ -714,6 +1521,7 @@ pub struct MatchExpression {
 #[derive(PartialEq, Debug, Clone)]
 pub struct FunctionCallExpression {
     pub function: Box<Positioned<Expression>>,
+    pub type_arguments: Vec<Positioned<Type>>,
     pub arguments: Vec<Positioned<Expression>>,
     pub lambda: Option<Block>,
 }
@@ -721,7 +1529,7 @@ pub struct FunctionCallExpression {
 #[derive(PartialEq, Debug, Clone)]
 pub struct NewExpression {
     pub expression: Box<Positioned<Expression>>,
-    pub type_parameters: Vec<Positioned<TypeParameter>>,
+    pub type_arguments: Vec<Positioned<Type>>,
     pub arguments: Vec<Positioned<Expression>>,
 }
 
@@ -730,8 +1538,19 @@ pub enum Expression {
     BinaryExpression(Box<Positioned<BinaryExpression>>),
     IndexExpression(Box<Positioned<IndexExpression>>),
     MatchExpression(Box<Positioned<MatchExpression>>),
+    AsExpression(Box<Positioned<TypeAssertion>>),
+    SatisfiesExpression(Box<Positioned<TypeAssertion>>),
+    NonNullExpression(Box<Positioned<Expression>>),
     FunctionCallExpression(Box<Positioned<FunctionCallExpression>>),
     NewExpression(Positioned<NewExpression>),
+    /// `import(specifier)`
+    ImportExpression(Box<Positioned<Expression>>),
+    /// `yield value` and `yield* iterable`
+    YieldExpression(Box<Positioned<YieldExpression>>),
+    /// `await value`, which waits for a promise to settle
+    AwaitExpression(Box<Positioned<Expression>>),
+    /// `...iterable`, only valid among the elements of an array or the arguments of a call
+    SpreadElement(Box<Positioned<Expression>>),
     Block(Block),
     Literal(Positioned<Literal>),
     Ident(Positioned<Ident>),
@@ -740,10 +1559,31 @@ pub enum Expression {
         is_dynamic: Positioned<bool>,
     },
     ArrowFunction(Box<Positioned<ArrowFunction>>),
+    Object(Vec<Positioned<PropertyAssignment>>),
     This,
     Null,
 }
 
+#[derive(PartialEq, Debug, Clone)]
+pub struct YieldExpression {
+    /// `yield*`, which yields every value of `argument` in turn
+    pub delegate: bool,
+    pub argument: Option<Positioned<Expression>>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct PropertyAssignment {
+    pub name: Positioned<PropertyName>,
+    pub initializer: Positioned<Expression>,
+}
+
+/// The expression and type of `expression as T` and `expression satisfies T`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TypeAssertion {
+    pub expression: Positioned<Expression>,
+    pub ty: Positioned<Type>,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct IndexExpression {
     pub target: Positioned<Expression>,
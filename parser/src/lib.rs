@@ -21,7 +21,7 @@ pub mod tags;
 pub struct Parser;
 
 impl Parser {
-    pub fn parse_tokens(tokens: &[Positioned<Token>]) -> TokenResult<Block> {
+    pub fn parse_tokens(tokens: &[Positioned<Token>]) -> TokenResult<'_, Block> {
         map(
             tuple((position, many0(parse_program_statement), eof_tag)),
             |(start, program, end)| start.between(end.span).wrap(program),
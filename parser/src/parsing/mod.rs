@@ -55,6 +55,20 @@ pub fn parse_ident(input: Tokens) -> TokenResult<Positioned<Ident>> {
     }
 }
 
+/// Name of a property after a dot, where reserved words like `from` or `of` are plain names.
+pub fn parse_property_name(input: Tokens) -> TokenResult<Positioned<Ident>> {
+    let (i1, t1) = take(1usize)(input)?;
+
+    match t1.tok.first().map(|token| &token.value) {
+        // The variants are named after their keywords, `TypeOf` for `typeof` and so on
+        Some(Token::ReservedWord(word)) => Ok((
+            i1,
+            t1.tok[0].wrap(Ident(format!("{word:?}").to_lowercase())),
+        )),
+        _ => parse_ident(input),
+    }
+}
+
 pub fn parse_from_clause(input: Tokens) -> TokenResult<Positioned<String>> {
     map_res(preceded(from_tag, parse_literal), |literal| {
         match literal.value {
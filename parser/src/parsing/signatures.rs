@@ -8,18 +8,19 @@ use crate::{
     ast::{CallSignature, ConstructSignature, IndexSignature, MethodSignature, PropertySignature},
     tags::{
         bracket_close_tag, bracket_open_tag, colon_tag, comma_tag, gt_tag, lt_tag, new_tag,
-        paren_close_tag, paren_open_tag, positioned, question_tag,
+        paren_close_tag, paren_open_tag, positioned, question_tag, readonly_tag,
     },
 };
 
 use nom::{
-    combinator::{map, opt},
+    combinator::{map, opt, value},
     multi::{separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, tuple},
 };
 
 use tsr_lexer::{
     globals::{Positioned, TokenResult},
+    token::Modifier,
     tokens::Tokens,
 };
 
@@ -84,13 +85,14 @@ pub fn parse_method_signature(input: Tokens) -> TokenResult<Positioned<MethodSig
 pub fn parse_property_signature(input: Tokens) -> TokenResult<Positioned<PropertySignature>> {
     positioned(map(
         tuple((
+            opt(positioned(value(Modifier::Readonly, readonly_tag))),
             parse_ident,
             positioned(opt(question_tag)),
             colon_tag,
             parse_type,
         )),
-        |(name, nullable, _, ty)| PropertySignature {
-            modifiers: Default::default(),
+        |(readonly, name, nullable, _, ty)| PropertySignature {
+            modifiers: readonly.into_iter().collect(),
             name,
             nullable: nullable.wrap(nullable.value.is_some()),
             ty,
@@ -2,10 +2,12 @@ pub mod class;
 pub mod enumeration;
 pub mod export;
 pub mod expression;
+pub mod for_of;
 pub mod function;
 pub mod if_else;
 pub mod import;
 pub mod interface;
+pub mod namespace;
 pub mod returning;
 pub mod type_alias;
 pub mod variable;
@@ -153,6 +155,9 @@ pub fn parse_statement(input: Tokens) -> TokenResult<Positioned<Statement>> {
         map(if_else::parse_if_statement, |statement| {
             Statement::IfStatement(Box::new(statement))
         }),
+        map(for_of::parse_for_of_statement, |statement| {
+            Statement::ForOfStatement(Box::new(statement))
+        }),
         returning::parse_return_statement,
         map(expression::parse_expression, Statement::Expression),
     )))(input)
@@ -173,6 +178,13 @@ pub fn parse_program_statement(input: Tokens) -> TokenResult<Positioned<Statemen
                 Statement::TypeAliasDeclaration,
             ),
             map(class::parse_class_declaration, Statement::ClassDeclaration),
+            map(
+                namespace::parse_namespace_declaration,
+                Statement::NamespaceDeclaration,
+            ),
+            map(namespace::parse_ambient_declaration, |declaration| {
+                Statement::AmbientDeclaration(Box::new(declaration))
+            }),
             map(
                 interface::parse_interface_declaration,
                 Statement::InterfaceDeclaration,
@@ -192,6 +204,9 @@ pub fn parse_program_statement(input: Tokens) -> TokenResult<Positioned<Statemen
             map(if_else::parse_if_statement, |statement| {
                 Statement::IfStatement(Box::new(statement))
             }),
+            map(for_of::parse_for_of_statement, |statement| {
+                Statement::ForOfStatement(Box::new(statement))
+            }),
             returning::parse_return_statement,
             map(expression::parse_expression, Statement::Expression),
         ))),
@@ -13,7 +13,7 @@ use crate::{
     tags::{
         brace_close_tag, brace_open_tag, class_tag, colon_tag, comma_tag, constructor_tag, eq_tag,
         extends_tag, fat_arrow_tag, get_tag, gt_tag, implements_tag, lt_tag, paren_close_tag,
-        paren_open_tag, positioned, semi_tag, set_tag, static_tag,
+        paren_open_tag, positioned, readonly_tag, semi_tag, set_tag, static_tag,
     },
 };
 
@@ -69,23 +69,25 @@ pub fn parse_member_variable_declaration(
             tuple((
                 many0(parse_access_modifier),
                 opt(positioned(value(Modifier::Static, static_tag))),
+                opt(positioned(value(Modifier::Readonly, readonly_tag))),
                 parse_property_name,
                 opt(preceded(colon_tag, parse_type)),
                 opt(preceded(eq_tag, parse_expression)),
             )),
             semi_tag,
         ),
-        |(modifiers, static_modifier, name, ty, initializer)| MemberVariableDeclaration {
-            modifiers: [
-                modifiers,
-                static_modifier
-                    .map(|modifier| vec![modifier])
-                    .unwrap_or_default(),
-            ]
-            .concat(),
-            name,
-            ty,
-            initializer,
+        |(modifiers, static_modifier, readonly_modifier, name, ty, initializer)| {
+            MemberVariableDeclaration {
+                modifiers: [
+                    modifiers,
+                    static_modifier.into_iter().collect(),
+                    readonly_modifier.into_iter().collect(),
+                ]
+                .concat(),
+                name,
+                ty,
+                initializer,
+            }
         },
     ))(input)
 }
@@ -221,10 +223,9 @@ pub fn parse_class_element(input: Tokens) -> TokenResult<Positioned<ClassElement
             parse_constructor_declaration,
             ClassElement::ConstructorDeclaration,
         ),
-        map(
-            parse_property_member_declaration,
-            ClassElement::PropertyMemberDeclaration,
-        ),
+        map(parse_property_member_declaration, |declaration| {
+            ClassElement::PropertyMemberDeclaration(Box::new(declaration))
+        }),
         map(
             parse_index_member_declaration,
             ClassElement::IndexMemberDeclaration,
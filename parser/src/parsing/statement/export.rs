@@ -10,10 +10,10 @@ use crate::{
         ExportDeclaration, ExportDefaultElement, ExportListElement, ExportSingleElement,
         ExportSpecifier,
     },
-    parsing::{parse_from_clause, parse_ident},
+    parsing::{parse_from_clause, parse_ident, parse_property_name},
     tags::{
-        as_tag, brace_close_tag, brace_open_tag, comma_tag, export_tag, positioned, semi_tag,
-        star_tag,
+        as_tag, brace_close_tag, brace_open_tag, comma_tag, default_tag, export_tag, positioned,
+        semi_tag, star_tag,
     },
 };
 
@@ -94,7 +94,11 @@ pub fn parse_export_clause(
         separated_list0(
             comma_tag,
             map(
-                pair(parse_ident, opt(tuple((as_tag, parse_ident)))),
+                // Exported names may be reserved words, like in `export { value as default }`
+                pair(
+                    parse_property_name,
+                    opt(tuple((as_tag, parse_property_name))),
+                ),
                 |(ident, alias)| {
                     alias
                         .as_ref()
@@ -117,7 +121,10 @@ pub fn parse_export_declaration(input: Tokens) -> TokenResult<Positioned<ExportD
     positioned(delimited(
         export_tag,
         alt((
-            map(parse_export_default_element, ExportDeclaration::Default),
+            map(
+                preceded(default_tag, parse_export_default_element),
+                ExportDeclaration::Default,
+            ),
             map(parse_export_single_element, ExportDeclaration::Single),
             map(parse_export_list_element, ExportDeclaration::List),
         )),
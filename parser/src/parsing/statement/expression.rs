@@ -1,26 +1,39 @@
 pub mod array;
 pub mod arrow_function;
+pub mod awaiting;
 pub mod call;
 pub mod code_block;
 pub mod index;
 pub mod new;
+pub mod object;
 pub mod primitives;
 pub mod simple_binary;
+pub mod spread;
 pub mod switch;
+pub mod yielding;
 
 use self::{
     array::parse_array,
     arrow_function::parse_arrow_function_expression,
+    awaiting::parse_await_expression,
     call::parse_call_expression,
     code_block::parse_code_block_expression,
     index::parse_index_expression,
     new::parse_new_expression,
-    primitives::{parse_ident_expression, parse_literal_expression, parse_null, parse_this},
+    object::parse_object,
+    primitives::{
+        parse_ident_expression, parse_import_expression, parse_literal_expression, parse_null,
+        parse_parenthesized_expression, parse_this,
+    },
     simple_binary::parse_simple_binary_expression,
     switch::parse_switch_expression,
+    yielding::parse_yield_expression,
 };
 
-use crate::ast::{BinaryExpression, Expression, Infix, Precedence};
+use crate::{
+    ast::{BinaryExpression, Expression, Infix, Precedence, TypeAssertion},
+    parsing::types::parse_type,
+};
 
 use nom::{
     branch::alt,
@@ -31,7 +44,7 @@ use nom::{
 
 use tsr_lexer::{
     globals::{Positioned, TokenResult},
-    token::{Delimiter, Operator, Punctuation, Token},
+    token::{Delimiter, Operator, Punctuation, ReservedWord, Token},
     tokens::Tokens,
 };
 
@@ -45,9 +58,13 @@ impl FromRef<Positioned<Token>> for PrecedencedOperation {
     fn from_ref(token: &Positioned<Token>) -> Self {
         match &token.value {
             Token::Operator(operator) => match operator {
-                Operator::Eq => (Precedence::PEquals, Some(token.wrap(Infix::Assign))),
+                Operator::Eq => (Precedence::PAssign, Some(token.wrap(Infix::Assign))),
+                Operator::OrOr => (Precedence::POr, Some(token.wrap(Infix::LogicalOr))),
+                Operator::AndAnd => (Precedence::PAnd, Some(token.wrap(Infix::LogicalAnd))),
                 Operator::EqEq => (Precedence::PEquals, Some(token.wrap(Infix::Equal))),
                 Operator::Ne => (Precedence::PEquals, Some(token.wrap(Infix::NotEqual))),
+                Operator::EqEqEq => (Precedence::PEquals, Some(token.wrap(Infix::StrictEqual))),
+                Operator::NeEq => (Precedence::PEquals, Some(token.wrap(Infix::StrictNotEqual))),
                 Operator::Le => (
                     Precedence::PLessGreater,
                     Some(token.wrap(Infix::LessThanEqual)),
@@ -67,9 +84,22 @@ impl FromRef<Positioned<Token>> for PrecedencedOperation {
                 Operator::Slash => (Precedence::PProduct, Some(token.wrap(Infix::Divide))),
                 Operator::PlusPlus => (Precedence::PProduct, Some(token.wrap(Infix::Increment))),
                 Operator::MinusMinus => (Precedence::PProduct, Some(token.wrap(Infix::Decrement))),
-                Operator::Not => (Precedence::PProduct, Some(token.wrap(Infix::Inverse))),
+                Operator::Not => (Precedence::PIndex, Some(token.wrap(Infix::NonNull))),
                 _ => (Precedence::PLowest, None),
             },
+            Token::ReservedWord(ReservedWord::InstanceOf) => (
+                Precedence::PLessGreater,
+                Some(token.wrap(Infix::InstanceOf)),
+            ),
+            Token::ReservedWord(ReservedWord::In) => {
+                (Precedence::PLessGreater, Some(token.wrap(Infix::In)))
+            }
+            Token::ReservedWord(ReservedWord::As) => {
+                (Precedence::PLessGreater, Some(token.wrap(Infix::As)))
+            }
+            Token::ReservedWord(ReservedWord::Satisfies) => {
+                (Precedence::PLessGreater, Some(token.wrap(Infix::Satisfies)))
+            }
             Token::Delimiter(Delimiter::ParenOpen) => (Precedence::PCall, None),
             Token::Delimiter(Delimiter::BracketOpen) => (Precedence::PIndex, None),
             Token::Punctuation(Punctuation::Dot) => (Precedence::PIndex, None),
@@ -91,6 +121,30 @@ pub fn parse_binary_operation_expression(
 
         match maybe_op {
             None => Err(Err::Error(Error::new(input, ErrorKind::Tag))),
+            Some(operation) if matches!(operation.value, Infix::As | Infix::Satisfies) => {
+                let (input, ty) = parse_type(input)?;
+                let distance = left.between(&ty);
+                let assertion = Box::new(distance.wrap(TypeAssertion {
+                    expression: left,
+                    ty,
+                }));
+
+                Ok((
+                    input,
+                    distance.wrap(match operation.value {
+                        Infix::As => Expression::AsExpression(assertion),
+                        _ => Expression::SatisfiesExpression(assertion),
+                    }),
+                ))
+            }
+            Some(operation) if operation.value == Infix::NonNull => {
+                let distance = left.between(&operation);
+
+                Ok((
+                    input,
+                    distance.wrap(Expression::NonNullExpression(Box::new(left))),
+                ))
+            }
             Some(operation) => {
                 let operator = operation.wrap(match &operation.value {
                     Infix::Increment => Operator::PlusPlus,
@@ -100,25 +154,23 @@ pub fn parse_binary_operation_expression(
                     Infix::Divide => Operator::Slash,
                     Infix::Multiply => Operator::Star,
                     Infix::Assign => Operator::Eq,
-                    Infix::Inverse => Operator::Not,
                     Infix::Equal => Operator::EqEq,
                     Infix::NotEqual => Operator::Ne,
-                    Infix::GreaterThanEqual => Operator::Gt,
-                    Infix::LessThanEqual => Operator::Lt,
-                    Infix::GreaterThan => Operator::Ge,
-                    Infix::LessThan => Operator::Le,
+                    Infix::StrictEqual => Operator::EqEqEq,
+                    Infix::StrictNotEqual => Operator::NeEq,
+                    Infix::GreaterThanEqual => Operator::Ge,
+                    Infix::LessThanEqual => Operator::Le,
+                    Infix::GreaterThan => Operator::Gt,
+                    Infix::LessThan => Operator::Lt,
+                    Infix::LogicalAnd => Operator::AndAnd,
+                    Infix::LogicalOr => Operator::OrOr,
+                    Infix::InstanceOf => Operator::InstanceOf,
+                    Infix::In => Operator::In,
+                    Infix::As | Infix::Satisfies | Infix::NonNull => unreachable!(),
                 });
 
-                if matches!(
-                    operation.value,
-                    Infix::Increment | Infix::Decrement | Infix::Inverse
-                ) {
-                    let distance = if matches!(operation.value, Infix::Increment | Infix::Decrement)
-                    {
-                        left.between(&operation)
-                    } else {
-                        operation.between(&left)
-                    };
+                if matches!(operation.value, Infix::Increment | Infix::Decrement) {
+                    let distance = left.between(&operation);
 
                     Ok((
                         input,
@@ -171,13 +223,20 @@ pub fn go_parse_pratt_expr(
     } else {
         let p = PrecedencedOperation::from_ref(&tokens.tok[0]);
 
+        // `f<T>(x)` is a call with type arguments, anything else starting with `<` is a comparison
+        if precedence < Precedence::PCall && tokens.tok[0].value == Token::Operator(Operator::Lt) {
+            if let Ok((input, left)) = parse_call_expression(input, left.clone()) {
+                return go_parse_pratt_expr(input, precedence, left);
+            }
+        }
+
         match p {
             (Precedence::PCall, _) if precedence < Precedence::PCall => {
                 let (input, left) = parse_call_expression(input, left)?;
 
                 go_parse_pratt_expr(input, precedence, left)
             }
-            (Precedence::PIndex, _) if precedence < Precedence::PIndex => {
+            (Precedence::PIndex, None) if precedence < Precedence::PIndex => {
                 let (input, left) = parse_index_expression(input, left)?;
 
                 go_parse_pratt_expr(input, precedence, left)
@@ -199,9 +258,14 @@ pub fn parse_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
 pub fn parse_atom(input: Tokens) -> TokenResult<Positioned<Expression>> {
     alt((
         parse_new_expression,
+        parse_import_expression,
+        parse_yield_expression,
+        parse_await_expression,
         parse_switch_expression,
         parse_arrow_function_expression,
+        parse_parenthesized_expression,
         parse_array,
+        parse_object,
         parse_code_block_expression,
         parse_literal_expression,
         parse_this,
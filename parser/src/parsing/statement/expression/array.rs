@@ -1,4 +1,4 @@
-use super::parse_expression;
+use super::spread::parse_element;
 use crate::{
     ast::Expression,
     tags::{bracket_close_tag, bracket_open_tag, comma_tag, ellipsis_tag, positioned},
@@ -20,7 +20,7 @@ pub fn parse_array(input: Tokens) -> TokenResult<Positioned<Expression>> {
         delimited(
             bracket_open_tag,
             pair(
-                separated_list0(comma_tag, parse_expression),
+                separated_list0(comma_tag, parse_element),
                 positioned(opt(ellipsis_tag)),
             ),
             bracket_close_tag,
@@ -2,23 +2,27 @@ use super::parse_expression;
 use crate::{
     ast::{ArrowFunction, Expression},
     parsing::statement::{parse_arrow_parameter, parse_type},
-    tags::{colon_tag, comma_tag, fat_arrow_tag, paren_close_tag, paren_open_tag, positioned},
+    tags::{
+        async_tag, colon_tag, comma_tag, fat_arrow_tag, paren_close_tag, paren_open_tag, positioned,
+    },
 };
 
 use nom::{
-    combinator::{map, opt},
+    combinator::{map, opt, value},
     multi::separated_list0,
     sequence::{delimited, preceded, tuple},
 };
 
 use tsr_lexer::{
     globals::{Positioned, TokenResult},
+    token::Modifier,
     tokens::Tokens,
 };
 
 pub fn parse_arrow_function_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
     positioned(map(
         positioned(tuple((
+            opt(positioned(value(Modifier::Async, async_tag))),
             delimited(
                 paren_open_tag,
                 separated_list0(comma_tag, parse_arrow_parameter),
@@ -28,7 +32,7 @@ pub fn parse_arrow_function_expression(input: Tokens) -> TokenResult<Positioned<
             preceded(fat_arrow_tag, parse_expression),
         ))),
         |Positioned {
-             value: (parameters, ty, body),
+             value: (modifier, parameters, ty, body),
              span,
          }| {
             Expression::ArrowFunction(Box::new(span.wrap(ArrowFunction {
@@ -36,7 +40,7 @@ pub fn parse_arrow_function_expression(input: Tokens) -> TokenResult<Positioned<
                 parameters,
                 ty,
                 body,
-                modifiers: Default::default(),
+                modifiers: modifier.into_iter().collect(),
             })))
         },
     ))(input)
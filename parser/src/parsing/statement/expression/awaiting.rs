@@ -0,0 +1,22 @@
+use super::parse_pratt_expr;
+use crate::{
+    ast::{Expression, Precedence},
+    tags::{await_tag, positioned},
+};
+
+use nom::{combinator::map, sequence::preceded};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    tokens::Tokens,
+};
+
+/// `await value`, binding as tightly as `typeof` so that `await a + b` awaits only `a`.
+pub fn parse_await_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    positioned(map(
+        preceded(await_tag, |input| {
+            parse_pratt_expr(input, Precedence::PProduct)
+        }),
+        |argument| Expression::AwaitExpression(Box::new(argument)),
+    ))(input)
+}
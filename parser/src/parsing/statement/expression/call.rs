@@ -1,14 +1,14 @@
-use super::parse_expression;
+use super::spread::parse_element;
 use crate::{
     ast::{Expression, FunctionCallExpression},
-    parsing::parse_code_block,
-    tags::{comma_tag, paren_close_tag, paren_open_tag, positioned},
+    parsing::{parse_code_block, types::parse_type},
+    tags::{comma_tag, gt_tag, lt_tag, paren_close_tag, paren_open_tag, positioned},
 };
 
 use nom::{
     combinator::{map, opt},
-    multi::separated_list0,
-    sequence::{delimited, pair},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, tuple},
 };
 
 use tsr_lexer::{
@@ -21,21 +21,27 @@ pub fn parse_call_expression(
     fn_handle: Positioned<Expression>,
 ) -> TokenResult<Positioned<Expression>> {
     map(
-        positioned(pair(
+        positioned(tuple((
+            opt(delimited(
+                lt_tag,
+                separated_list1(comma_tag, parse_type),
+                gt_tag,
+            )),
             delimited(
                 paren_open_tag,
-                separated_list0(comma_tag, parse_expression),
+                separated_list0(comma_tag, parse_element),
                 paren_close_tag,
             ),
             opt(parse_code_block),
-        )),
+        ))),
         |Positioned {
-             value: (arguments, lambda),
+             value: (type_arguments, arguments, lambda),
              span,
          }| {
             span.wrap(Expression::FunctionCallExpression(Box::new(span.wrap(
                 FunctionCallExpression {
                     function: Box::new(fn_handle.clone()),
+                    type_arguments: type_arguments.unwrap_or_default(),
                     arguments,
                     lambda,
                 },
@@ -1,6 +1,7 @@
-use super::{parse_expression, primitives::parse_ident_expression};
+use super::parse_expression;
 use crate::{
-    ast::{Expression, IndexExpression},
+    ast::{Expression, IndexExpression, Literal},
+    parsing::parse_property_name,
     tags::{bracket_close_tag, bracket_open_tag, dot_tag},
 };
 
@@ -22,7 +23,13 @@ pub fn parse_index_expression(
     map(
         alt((
             delimited(bracket_open_tag, parse_expression, bracket_close_tag),
-            preceded(dot_tag, parse_ident_expression),
+            map(preceded(dot_tag, parse_property_name), |ident| {
+                ident.span.wrap(Expression::Literal(
+                    ident
+                        .span
+                        .wrap(Literal::String(ident.span.wrap(ident.value.0))),
+                ))
+            }),
         )),
         |index| {
             let distance = target.between(&index);
@@ -1,7 +1,7 @@
-use super::{parse_expression, primitives::parse_ident_expression};
+use super::{primitives::parse_ident_expression, spread::parse_element};
 use crate::{
     ast::{Expression, NewExpression},
-    parsing::statement::parse_type_parameter,
+    parsing::types::parse_type,
     tags::{comma_tag, gt_tag, lt_tag, new_tag, paren_close_tag, paren_open_tag, positioned},
 };
 
@@ -25,19 +25,19 @@ pub fn parse_new_expression(input: Tokens) -> TokenResult<Positioned<Expression>
                     parse_ident_expression,
                     opt(delimited(
                         lt_tag,
-                        separated_list1(comma_tag, parse_type_parameter),
+                        separated_list1(comma_tag, parse_type),
                         gt_tag,
                     )),
                     delimited(
                         paren_open_tag,
-                        separated_list0(comma_tag, parse_expression),
+                        separated_list0(comma_tag, parse_element),
                         paren_close_tag,
                     ),
                 )),
             ),
-            |(expression, type_parameters, arguments)| NewExpression {
+            |(expression, type_arguments, arguments)| NewExpression {
                 expression: Box::new(expression),
-                type_parameters: type_parameters.unwrap_or_default(),
+                type_arguments: type_arguments.unwrap_or_default(),
                 arguments,
             },
         )),
@@ -0,0 +1,37 @@
+use super::{super::parse_property_name, parse_expression};
+use crate::{
+    ast::{Expression, PropertyAssignment},
+    tags::{brace_close_tag, brace_open_tag, colon_tag, comma_tag, positioned},
+};
+
+use nom::{
+    combinator::{map, opt},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, terminated},
+};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    tokens::Tokens,
+};
+
+pub fn parse_property_assignment(input: Tokens) -> TokenResult<Positioned<PropertyAssignment>> {
+    positioned(map(
+        separated_pair(parse_property_name, colon_tag, parse_expression),
+        |(name, initializer)| PropertyAssignment { name, initializer },
+    ))(input)
+}
+
+pub fn parse_object(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    positioned(map(
+        delimited(
+            brace_open_tag,
+            terminated(
+                separated_list1(comma_tag, parse_property_assignment),
+                opt(comma_tag),
+            ),
+            brace_close_tag,
+        ),
+        Expression::Object,
+    ))(input)
+}
@@ -1,10 +1,14 @@
+use super::parse_expression;
 use crate::{
     ast::Expression,
     parsing::{parse_ident, parse_literal},
-    tags::{null_tag, positioned, this_tag},
+    tags::{import_tag, null_tag, paren_close_tag, paren_open_tag, positioned, this_tag},
 };
 
-use nom::combinator::map;
+use nom::{
+    combinator::map,
+    sequence::{delimited, tuple},
+};
 use tsr_lexer::{
     globals::{Positioned, TokenResult},
     tokens::Tokens,
@@ -25,3 +29,25 @@ pub fn parse_this(input: Tokens) -> TokenResult<Positioned<Expression>> {
 pub fn parse_null(input: Tokens) -> TokenResult<Positioned<Expression>> {
     map(null_tag, |tag| tag.wrap(Expression::Null))(input)
 }
+
+/// `(expression)`, only groups the inner expression.
+pub fn parse_parenthesized_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    delimited(paren_open_tag, parse_expression, paren_close_tag)(input)
+}
+
+/// `import(specifier)`, loads a module while the program runs.
+pub fn parse_import_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    map(
+        tuple((
+            import_tag,
+            paren_open_tag,
+            parse_expression,
+            paren_close_tag,
+        )),
+        |(start, _, specifier, end)| {
+            start
+                .between(&end)
+                .wrap(Expression::ImportExpression(Box::new(specifier)))
+        },
+    )(input)
+}
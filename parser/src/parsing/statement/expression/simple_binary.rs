@@ -1,10 +1,14 @@
-use super::parse_expression;
+use super::parse_pratt_expr;
 use crate::{
-    ast::{BinaryExpression, Expression},
-    tags::{not_tag, positioned},
+    ast::{BinaryExpression, Expression, Precedence},
+    tags::{not_tag, positioned, typeof_tag},
 };
 
-use nom::{combinator::map, sequence::pair};
+use nom::{
+    branch::alt,
+    combinator::{map, value},
+    sequence::pair,
+};
 use tsr_lexer::{
     globals::{Positioned, TokenResult},
     token::Operator,
@@ -12,13 +16,22 @@ use tsr_lexer::{
 };
 
 pub fn parse_simple_binary_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
-    positioned(map(pair(not_tag, parse_expression), |(operator, left)| {
-        Expression::BinaryExpression(Box::new(operator.span.between(left.span).wrap(
-            BinaryExpression {
-                operator: operator.span.wrap(Operator::Not),
-                right: left.span.wrap(Expression::Null),
-                left,
-            },
-        )))
-    }))(input)
+    positioned(map(
+        pair(
+            positioned(alt((
+                value(Operator::Not, not_tag),
+                value(Operator::TypeOf, typeof_tag),
+            ))),
+            |input| parse_pratt_expr(input, Precedence::PProduct),
+        ),
+        |(operator, left)| {
+            Expression::BinaryExpression(Box::new(operator.span.between(left.span).wrap(
+                BinaryExpression {
+                    operator,
+                    right: left.span.wrap(Expression::Null),
+                    left,
+                },
+            )))
+        },
+    ))(input)
 }
@@ -0,0 +1,25 @@
+use super::parse_expression;
+use crate::{
+    ast::Expression,
+    tags::{ellipsis_tag, positioned},
+};
+
+use nom::{branch::alt, combinator::map, sequence::preceded};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    tokens::Tokens,
+};
+
+/// `...iterable`, which expands to the values of `iterable`.
+pub fn parse_spread_element(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    positioned(map(
+        preceded(ellipsis_tag, parse_expression),
+        |expression| Expression::SpreadElement(Box::new(expression)),
+    ))(input)
+}
+
+/// An element of an array or an argument of a call, either of which may be spread.
+pub fn parse_element(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    alt((parse_spread_element, parse_expression))(input)
+}
@@ -0,0 +1,30 @@
+use super::parse_expression;
+use crate::{
+    ast::{Expression, YieldExpression},
+    tags::{positioned, star_tag, yield_tag},
+};
+
+use nom::{
+    combinator::{map, opt},
+    sequence::tuple,
+};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    tokens::Tokens,
+};
+
+pub fn parse_yield_expression(input: Tokens) -> TokenResult<Positioned<Expression>> {
+    positioned(map(
+        positioned(tuple((yield_tag, opt(star_tag), opt(parse_expression)))),
+        |Positioned {
+             value: (_, delegate, argument),
+             span,
+         }| {
+            Expression::YieldExpression(Box::new(span.wrap(YieldExpression {
+                delegate: delegate.is_some(),
+                argument,
+            })))
+        },
+    ))(input)
+}
@@ -0,0 +1,42 @@
+use super::{expression::parse_expression, parse_statement, variable::parse_binding_pattern};
+use crate::{
+    ast::ForOfStatement,
+    tags::{const_tag, for_tag, let_tag, of_tag, paren_close_tag, paren_open_tag, positioned},
+};
+
+use nom::{
+    branch::alt,
+    combinator::map,
+    sequence::{delimited, tuple},
+};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    token::{ReservedWord, Token},
+    tokens::Tokens,
+};
+
+pub fn parse_for_of_statement(input: Tokens) -> TokenResult<Positioned<ForOfStatement>> {
+    positioned(map(
+        tuple((
+            for_tag,
+            delimited(
+                paren_open_tag,
+                tuple((
+                    alt((let_tag, const_tag)),
+                    parse_binding_pattern,
+                    of_tag,
+                    parse_expression,
+                )),
+                paren_close_tag,
+            ),
+            parse_statement,
+        )),
+        |(_, (kind, binding, _, iterable), body)| ForOfStatement {
+            mutable: kind.wrap(kind.value.tok[0].value == Token::ReservedWord(ReservedWord::Let)),
+            binding,
+            iterable,
+            body,
+        },
+    ))(input)
+}
@@ -1,12 +1,12 @@
 use super::{super::parse_code_block, parse_call_signature, parse_ident};
 use crate::{
     ast::FunctionDeclaration,
-    tags::{async_tag, function_tag, positioned},
+    tags::{async_tag, function_tag, positioned, star_tag},
 };
 
 use nom::{
     combinator::{map, opt, value},
-    sequence::{preceded, tuple},
+    sequence::{pair, preceded, tuple},
 };
 
 use tsr_lexer::{
@@ -19,12 +19,13 @@ pub fn parse_function_declaration(input: Tokens) -> TokenResult<Positioned<Funct
     positioned(map(
         tuple((
             opt(positioned(value(Modifier::Async, async_tag))),
-            preceded(function_tag, parse_ident),
+            preceded(function_tag, pair(opt(star_tag), parse_ident)),
             parse_call_signature,
             opt(parse_code_block),
         )),
-        |(async_modifier, name, signature, body)| FunctionDeclaration {
+        |(async_modifier, (generator, name), signature, body)| FunctionDeclaration {
             name,
+            generator: generator.is_some(),
             type_parameters: signature.value.0,
             parameters: signature.value.1,
             ty: signature.value.2,
@@ -1,6 +1,7 @@
 use super::{parse_ident, parse_literal};
 use crate::{
     ast::{ImportClause, ImportDeclaration, ImportSpecifier},
+    parsing::parse_property_name,
     tags::{
         as_tag, brace_close_tag, brace_open_tag, comma_tag, from_tag, import_tag, positioned,
         semi_tag, star_tag, type_tag,
@@ -11,7 +12,7 @@ use nom::{
     branch::alt,
     combinator::{map, opt},
     multi::separated_list0,
-    sequence::{delimited, pair, terminated, tuple},
+    sequence::{delimited, terminated, tuple},
 };
 
 use tsr_lexer::{
@@ -23,63 +24,23 @@ pub fn parse_import_declaration(input: Tokens) -> TokenResult<Positioned<ImportD
     map(
         positioned(delimited(
             import_tag,
-            pair(
+            tuple((
+                opt(type_tag),
                 opt(terminated(
                     alt((
                         map(
                             tuple((
-                                brace_open_tag,
-                                separated_list0(
-                                    comma_tag,
-                                    map(
-                                        tuple((
-                                            opt(type_tag),
-                                            parse_ident,
-                                            opt(tuple((as_tag, parse_ident))),
-                                        )),
-                                        |(type_only, ident, alias)| {
-                                            type_only
-                                                .as_ref()
-                                                .map(|ty| ty.span)
-                                                .unwrap_or(ident.span)
-                                                .between(
-                                                    alias
-                                                        .as_ref()
-                                                        .map(|(_, alias)| alias.span)
-                                                        .unwrap_or(ident.span),
-                                                )
-                                                .wrap(ImportSpecifier {
-                                                    is_type_only: type_only
-                                                        .map(|ty| ty.wrap(true))
-                                                        .unwrap_or(ident.wrap(false)),
-                                                    property_name: alias
-                                                        .as_ref()
-                                                        .map(|_| ident.clone()),
-                                                    name: alias
-                                                        .map(|(start, alias)| {
-                                                            start.between(&alias).wrap(alias.value)
-                                                        })
-                                                        .unwrap_or(ident),
-                                                })
-                                        },
-                                    ),
-                                ),
-                                brace_close_tag,
+                                parse_ident,
+                                comma_tag,
+                                alt((parse_named_imports, parse_namespace_import)),
                             )),
-                            |(start, imports, end)| {
-                                start
-                                    .between(&end)
-                                    .wrap(ImportClause::NamedImports(imports))
-                            },
-                        ),
-                        map(
-                            tuple((star_tag, as_tag, parse_ident)),
-                            |(start, _, ident)| {
-                                start
-                                    .between(&ident)
-                                    .wrap(ImportClause::NamespaceImport(ident))
+                            |(name, _, bindings)| {
+                                name.between(&bindings)
+                                    .wrap(ImportClause::Combined(name, Box::new(bindings)))
                             },
                         ),
+                        parse_named_imports,
+                        parse_namespace_import,
                         map(parse_ident, |ident| {
                             ident.span.wrap(ImportClause::Named(ident))
                         }),
@@ -87,17 +48,83 @@ pub fn parse_import_declaration(input: Tokens) -> TokenResult<Positioned<ImportD
                     from_tag,
                 )),
                 parse_literal,
-            ),
+            )),
             semi_tag,
         )),
         |Positioned {
-             value: (import_clause, module_specifier),
+             value: (type_only, import_clause, module_specifier),
              span,
          }| {
             span.wrap(ImportDeclaration {
+                is_type_only: type_only
+                    .map(|ty| ty.wrap(true))
+                    .unwrap_or(span.wrap(false)),
                 import_clause,
                 module_specifier,
             })
         },
     )(input)
 }
+
+pub fn parse_named_imports(input: Tokens) -> TokenResult<Positioned<ImportClause>> {
+    map(
+        tuple((
+            brace_open_tag,
+            separated_list0(comma_tag, parse_import_specifier),
+            brace_close_tag,
+        )),
+        |(start, imports, end)| {
+            start
+                .between(&end)
+                .wrap(ImportClause::NamedImports(imports))
+        },
+    )(input)
+}
+
+pub fn parse_namespace_import(input: Tokens) -> TokenResult<Positioned<ImportClause>> {
+    map(
+        tuple((star_tag, as_tag, parse_ident)),
+        |(start, _, ident)| {
+            start
+                .between(&ident)
+                .wrap(ImportClause::NamespaceImport(ident))
+        },
+    )(input)
+}
+
+pub fn parse_import_specifier(input: Tokens) -> TokenResult<Positioned<ImportSpecifier>> {
+    map(
+        tuple((
+            opt(type_tag),
+            alt((
+                // Only a renamed import may name a reserved word, like `default as value`
+                map(
+                    tuple((parse_property_name, as_tag, parse_ident)),
+                    |(ident, start, alias)| (ident, Some((start, alias))),
+                ),
+                map(parse_ident, |ident| (ident, None)),
+            )),
+        )),
+        |(type_only, (ident, alias))| {
+            type_only
+                .as_ref()
+                .map(|ty| ty.span)
+                .unwrap_or(ident.span)
+                .between(
+                    alias
+                        .as_ref()
+                        .map(|(_, alias)| alias.span)
+                        .unwrap_or(ident.span),
+                )
+                .wrap(ImportSpecifier {
+                    is_type_only: type_only
+                        .map(|ty| ty.wrap(true))
+                        .unwrap_or(ident.wrap(false)),
+                    property_name: alias.as_ref().map(|_| ident.clone()),
+                    name: alias
+                        .map(|(start, alias)| start.between(&alias).wrap(alias.value))
+                        .unwrap_or(ident),
+                })
+        },
+    )(input)
+}
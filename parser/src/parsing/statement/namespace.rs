@@ -0,0 +1,79 @@
+use super::{
+    function::parse_function_declaration, parse_ident, parse_program_statement,
+    variable::parse_variable_statement,
+};
+use crate::{
+    ast::{AmbientDeclaration, Block, Literal, ModuleDeclaration, NamespaceDeclaration},
+    parsing::parse_literal,
+    tags::{brace_close_tag, brace_open_tag, declare_tag, dot_tag, namespace_tag, positioned},
+};
+
+use nom::{
+    branch::alt,
+    combinator::{map, map_opt, verify},
+    multi::{many0, separated_list1},
+    sequence::{delimited, preceded, tuple},
+};
+
+use tsr_lexer::{
+    globals::{Positioned, TokenResult},
+    tokens::Tokens,
+};
+
+pub fn parse_namespace_body(input: Tokens) -> TokenResult<Block> {
+    positioned(delimited(
+        brace_open_tag,
+        many0(parse_program_statement),
+        brace_close_tag,
+    ))(input)
+}
+
+pub fn parse_namespace_declaration(input: Tokens) -> TokenResult<Positioned<NamespaceDeclaration>> {
+    positioned(map(
+        tuple((
+            preceded(namespace_tag, separated_list1(dot_tag, parse_ident)),
+            parse_namespace_body,
+        )),
+        |(name, body)| NamespaceDeclaration { name, body },
+    ))(input)
+}
+
+pub fn parse_module_declaration(input: Tokens) -> TokenResult<Positioned<ModuleDeclaration>> {
+    positioned(map(
+        tuple((
+            preceded(
+                verify(parse_ident, |ident| ident.value.0 == "module"),
+                map_opt(parse_literal, |literal| match literal.value {
+                    Literal::String(name) => Some(name),
+                    _ => None,
+                }),
+            ),
+            parse_namespace_body,
+        )),
+        |(name, body)| ModuleDeclaration { name, body },
+    ))(input)
+}
+
+pub fn parse_ambient_declaration(input: Tokens) -> TokenResult<Positioned<AmbientDeclaration>> {
+    preceded(
+        declare_tag,
+        positioned(alt((
+            map(
+                parse_variable_statement,
+                AmbientDeclaration::VariableStatement,
+            ),
+            map(
+                parse_function_declaration,
+                AmbientDeclaration::FunctionDeclaration,
+            ),
+            map(
+                parse_namespace_declaration,
+                AmbientDeclaration::NamespaceDeclaration,
+            ),
+            map(
+                parse_module_declaration,
+                AmbientDeclaration::ModuleDeclaration,
+            ),
+        ))),
+    )(input)
+}
@@ -1,14 +1,17 @@
 use super::{expression::parse_expression, parse_ident, parse_type};
 use crate::{
-    ast::{VariableDeclaration, VariableStatement},
-    tags::{colon_tag, comma_tag, const_tag, eq_tag, let_tag, positioned, question_tag, semi_tag},
+    ast::{BindingPattern, VariableDeclaration, VariableStatement},
+    tags::{
+        bracket_close_tag, bracket_open_tag, colon_tag, comma_tag, const_tag, ellipsis_tag, eq_tag,
+        let_tag, positioned, question_tag, semi_tag,
+    },
 };
 
 use nom::{
     branch::alt,
     combinator::{map, opt},
-    multi::separated_list1,
-    sequence::{preceded, terminated, tuple},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
 };
 
 use tsr_lexer::{
@@ -26,7 +29,7 @@ pub fn parse_variable_statement(input: Tokens) -> TokenResult<Positioned<Variabl
                     comma_tag,
                     positioned(map(
                         tuple((
-                            parse_ident,
+                            parse_binding_pattern,
                             positioned(opt(question_tag)),
                             opt(preceded(colon_tag, parse_type)),
                             opt(preceded(eq_tag, parse_expression)),
@@ -48,3 +51,28 @@ pub fn parse_variable_statement(input: Tokens) -> TokenResult<Positioned<Variabl
         },
     ))(input)
 }
+
+/// A name, or `[first, , third, ...rest]` with nested patterns and holes for skipped values.
+pub fn parse_binding_pattern(input: Tokens) -> TokenResult<Positioned<BindingPattern>> {
+    positioned(alt((
+        map(parse_ident, |name| BindingPattern::Ident(name.value)),
+        map(
+            delimited(
+                bracket_open_tag,
+                pair(
+                    separated_list0(comma_tag, opt(parse_binding_pattern)),
+                    opt(preceded(ellipsis_tag, parse_ident)),
+                ),
+                bracket_close_tag,
+            ),
+            |(mut elements, rest)| {
+                // A trailing comma, or the one before the rest element, leaves no hole
+                if let Some(None) = elements.last() {
+                    elements.pop();
+                }
+
+                BindingPattern::Array { elements, rest }
+            },
+        ),
+    )))(input)
+}
@@ -5,14 +5,15 @@ use super::{
 
 use crate::{
     ast::{
-        ArraySize, IntersectionOrPrimaryType, Literal, PredefinedType, PrimaryType, Type,
-        UnionOrIntersectionOrPrimaryType,
+        ArraySize, ConditionalType, IntersectionOrPrimaryType, Literal, MappedType, PredefinedType,
+        PrimaryType, Type, UnionOrIntersectionOrPrimaryType,
     },
     tags::{
         and_tag, any_tag, boolean_tag, brace_close_tag, brace_open_tag, bracket_close_tag,
-        bracket_open_tag, comma_tag, fat_arrow_tag, float_tag, gt_tag, lt_tag, new_tag, number_tag,
-        or_tag, paren_close_tag, paren_open_tag, positioned, semi_tag, string_tag, symbol_tag,
-        this_tag, void_tag,
+        bracket_open_tag, colon_tag, comma_tag, dot_tag, extends_tag, fat_arrow_tag, float_tag,
+        gt_tag, in_tag, infer_tag, keyof_tag, lt_tag, never_tag, new_tag, null_tag, number_tag,
+        or_tag, paren_close_tag, paren_open_tag, positioned, question_tag, readonly_tag, semi_tag,
+        string_tag, symbol_tag, this_tag, typeof_tag, undefined_tag, unknown_tag, void_tag,
     },
 };
 
@@ -20,7 +21,7 @@ use nom::{
     branch::alt,
     combinator::{map, map_res, opt, value},
     error::{Error, ErrorKind},
-    multi::{separated_list0, separated_list1},
+    multi::{many0, separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, terminated, tuple},
 };
 
@@ -82,9 +83,27 @@ pub fn parse_type(input: Tokens) -> TokenResult<Positioned<Type>> {
                 )
             },
         ),
-        map(parse_union_or_intersection_or_primary_type, |ty| {
-            Type::UnionOrIntersectionOrPrimaryType(ty)
-        }),
+        map(
+            pair(
+                parse_union_or_intersection_or_primary_type,
+                opt(tuple((
+                    preceded(extends_tag, parse_union_or_intersection_or_primary_type),
+                    preceded(question_tag, parse_type),
+                    preceded(colon_tag, parse_type),
+                ))),
+            ),
+            |(check, conditional)| match conditional {
+                Some((extends, true_type, false_type)) => {
+                    Type::ConditionalType(Box::new(ConditionalType {
+                        check: Type::UnionOrIntersectionOrPrimaryType(check),
+                        extends: Type::UnionOrIntersectionOrPrimaryType(extends),
+                        true_type: true_type.value,
+                        false_type: false_type.value,
+                    }))
+                }
+                None => Type::UnionOrIntersectionOrPrimaryType(check),
+            },
+        ),
     )))(input)
 }
 
@@ -137,30 +156,70 @@ pub fn parse_intersection_or_primary_type(input: Tokens) -> TokenResult<Intersec
 }
 
 pub fn parse_primary_type(input: Tokens) -> TokenResult<PrimaryType> {
+    map(
+        pair(
+            parse_non_array_type,
+            many0(delimited(
+                bracket_open_tag,
+                opt(parse_type),
+                bracket_close_tag,
+            )),
+        ),
+        |(ty, suffixes)| {
+            suffixes.into_iter().fold(ty, |ty, suffix| match suffix {
+                Some(index) => match index.value.as_primary() {
+                    Some(PrimaryType::PredefinedType(PredefinedType::NumberLiteral(size))) => {
+                        PrimaryType::ArrayType(Box::new(ty), ArraySize::Fixed(*size as usize))
+                    }
+                    _ => PrimaryType::IndexedAccessType(Box::new(ty), Box::new(index.value)),
+                },
+                None => PrimaryType::ArrayType(Box::new(ty), ArraySize::Dynamic),
+            })
+        },
+    )(input)
+}
+
+fn parse_non_array_type(input: Tokens) -> TokenResult<PrimaryType> {
     alt((
         map(
             delimited(paren_open_tag, parse_type, paren_close_tag),
             |ty| PrimaryType::ParenthesizedType(Box::new(ty.value)),
         ),
+        map(preceded(keyof_tag, parse_primary_type), |ty| {
+            PrimaryType::KeyOfType(Box::new(ty))
+        }),
+        map(preceded(readonly_tag, parse_primary_type), |ty| {
+            PrimaryType::ReadonlyType(Box::new(ty))
+        }),
+        map(
+            preceded(typeof_tag, separated_list1(dot_tag, parse_ident)),
+            |path| PrimaryType::TypeQuery(path.into_iter().map(|name| name.value).collect()),
+        ),
+        map(preceded(infer_tag, parse_ident), |name| {
+            PrimaryType::InferType(name.value)
+        }),
         map(parse_predefined_type, PrimaryType::PredefinedType),
         map(
             pair(
                 parse_ident,
                 opt(delimited(
                     lt_tag,
-                    separated_list1(comma_tag, parse_ident),
+                    separated_list1(comma_tag, parse_type),
                     gt_tag,
                 )),
             ),
-            |(ty, type_parameters)| {
+            |(ty, type_arguments)| {
                 PrimaryType::TypeReference(
                     ty.value,
-                    type_parameters
+                    type_arguments
                         .map(|p| p.into_iter().map(|p| p.value).collect())
                         .unwrap_or_default(),
                 )
             },
         ),
+        map(parse_mapped_type, |mapped| {
+            PrimaryType::MappedType(Box::new(mapped))
+        }),
         map(
             delimited(
                 brace_open_tag,
@@ -180,26 +239,35 @@ pub fn parse_primary_type(input: Tokens) -> TokenResult<PrimaryType> {
             ),
             |tuple| PrimaryType::TupleType(tuple.into_iter().map(|ty| ty.value).collect()),
         ),
-        map(
+        map(this_tag, |_| PrimaryType::ThisType),
+    ))(input)
+}
+
+pub fn parse_mapped_type(input: Tokens) -> TokenResult<MappedType> {
+    map(
+        delimited(
+            brace_open_tag,
             tuple((
-                parse_primary_type,
-                delimited(bracket_open_tag, opt(parse_literal), bracket_close_tag),
+                opt(readonly_tag),
+                delimited(
+                    bracket_open_tag,
+                    pair(parse_ident, preceded(in_tag, parse_type)),
+                    bracket_close_tag,
+                ),
+                opt(question_tag),
+                preceded(colon_tag, parse_type),
+                opt(alt((comma_tag, semi_tag))),
             )),
-            |(ty, size)| {
-                PrimaryType::ArrayType(
-                    Box::new(ty),
-                    match size {
-                        Some(size) => match size.value {
-                            Literal::Number(value) => ArraySize::Fixed(value.value as usize),
-                            _ => ArraySize::Dynamic,
-                        },
-                        None => ArraySize::Dynamic,
-                    },
-                )
-            },
+            brace_close_tag,
         ),
-        map(this_tag, |_| PrimaryType::ThisType),
-    ))(input)
+        |(readonly, (parameter, constraint), optional, ty, _)| MappedType {
+            readonly: readonly.is_some(),
+            parameter: parameter.value,
+            constraint: constraint.value,
+            optional: optional.is_some(),
+            ty: ty.value,
+        },
+    )(input)
 }
 
 pub fn parse_predefined_type(input: Tokens) -> TokenResult<PredefinedType> {
@@ -211,9 +279,15 @@ pub fn parse_predefined_type(input: Tokens) -> TokenResult<PredefinedType> {
         value(PredefinedType::String, string_tag),
         map_res(parse_literal, |literal| match literal.value {
             Literal::String(string) => Ok(PredefinedType::StringLiteral(string.value)),
+            Literal::Number(number) => Ok(PredefinedType::NumberLiteral(number.value)),
+            Literal::Boolean(boolean) => Ok(PredefinedType::BooleanLiteral(boolean.value)),
             _ => Err(Error::new(input, ErrorKind::Not)),
         }),
         value(PredefinedType::Symbol, symbol_tag),
         value(PredefinedType::Void, void_tag),
+        value(PredefinedType::Unknown, unknown_tag),
+        value(PredefinedType::Never, never_tag),
+        value(PredefinedType::Undefined, undefined_tag),
+        value(PredefinedType::Null, null_tag),
     ))(input)
 }
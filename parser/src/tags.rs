@@ -15,7 +15,7 @@ use tsr_lexer::{
 macro_rules! tokens {
     ($($func_name:ident => $tag:expr;)*) => {
         $(
-            pub fn $func_name(tokens: Tokens<'_>) -> TokenResult<Positioned<Tokens<'_>>> {
+            pub fn $func_name(tokens: Tokens) -> TokenResult<Positioned<Tokens>> {
                 verify(map(take(1usize), |s: Tokens| s.tok[0].wrap(s)), |t| {
                     // if t.value.tok[0].value != $tag {
                     //     println!(
@@ -49,7 +49,7 @@ tokens! {
     null_tag => Token::ReservedWord(ReservedWord::Null);
     enum_tag => Token::ReservedWord(ReservedWord::Enum);
     namespace_tag => Token::ReservedWord(ReservedWord::Namespace);
-    decalre_tag => Token::ReservedWord(ReservedWord::Declare);
+    declare_tag => Token::ReservedWord(ReservedWord::Declare);
     export_tag => Token::ReservedWord(ReservedWord::Export);
     import_tag => Token::ReservedWord(ReservedWord::Import);
     default_tag => Token::ReservedWord(ReservedWord::Default);
@@ -59,12 +59,18 @@ tokens! {
     set_tag => Token::ReservedWord(ReservedWord::Set);
     type_tag => Token::ReservedWord(ReservedWord::Type);
     typeof_tag => Token::ReservedWord(ReservedWord::TypeOf);
+    keyof_tag => Token::ReservedWord(ReservedWord::KeyOf);
+    infer_tag => Token::ReservedWord(ReservedWord::Infer);
     for_tag => Token::ReservedWord(ReservedWord::For);
     in_tag => Token::ReservedWord(ReservedWord::In);
+    instanceof_tag => Token::ReservedWord(ReservedWord::InstanceOf);
     of_tag => Token::ReservedWord(ReservedWord::Of);
     as_tag => Token::ReservedWord(ReservedWord::As);
+    satisfies_tag => Token::ReservedWord(ReservedWord::Satisfies);
     from_tag => Token::ReservedWord(ReservedWord::From);
     when_tag => Token::ReservedWord(ReservedWord::When);
+    yield_tag => Token::ReservedWord(ReservedWord::Yield);
+    await_tag => Token::ReservedWord(ReservedWord::Await);
 
     comma_tag => Token::Punctuation(Punctuation::Comma);
     dot_tag => Token::Punctuation(Punctuation::Dot);
@@ -88,8 +94,10 @@ tokens! {
     minus_tag => Token::Operator(Operator::Minus);
     minus_minus_tag => Token::Operator(Operator::MinusMinus);
     eq_eq_tag => Token::Operator(Operator::EqEq);
+    eq_eq_eq_tag => Token::Operator(Operator::EqEqEq);
     eq_tag => Token::Operator(Operator::Eq);
     ne_tag => Token::Operator(Operator::Ne);
+    ne_eq_tag => Token::Operator(Operator::NeEq);
     le_tag => Token::Operator(Operator::Le);
     ge_tag => Token::Operator(Operator::Ge);
     lt_tag => Token::Operator(Operator::Lt);
@@ -101,6 +109,7 @@ tokens! {
     protected_tag => Token::Modifier(Modifier::Protected);
     static_tag => Token::Modifier(Modifier::Static);
     async_tag => Token::Modifier(Modifier::Async);
+    readonly_tag => Token::Modifier(Modifier::Readonly);
 
     any_tag => Token::BuiltInType(BuiltInType::Any);
     number_tag => Token::BuiltInType(BuiltInType::Number);
@@ -109,6 +118,9 @@ tokens! {
     string_tag => Token::BuiltInType(BuiltInType::String);
     symbol_tag => Token::BuiltInType(BuiltInType::Symbol);
     void_tag => Token::BuiltInType(BuiltInType::Void);
+    unknown_tag => Token::BuiltInType(BuiltInType::Unknown);
+    never_tag => Token::BuiltInType(BuiltInType::Never);
+    undefined_tag => Token::BuiltInType(BuiltInType::Undefined);
 
     brace_open_tag => Token::Delimiter(Delimiter::BraceOpen);
     brace_close_tag => Token::Delimiter(Delimiter::BraceClose);
@@ -1,3 +1,4 @@
 pub mod events;
+pub mod prelude;
 pub mod reflection;
 pub mod util;
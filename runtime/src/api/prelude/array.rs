@@ -0,0 +1,61 @@
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{ArraySize, PredefinedType};
+
+use super::elements;
+use crate::{
+    value::{builders::ObjectBuilder, ErrorCode, Value},
+    FunctionBuilder,
+};
+
+pub fn array() -> Value {
+    ObjectBuilder::default()
+        .prop(
+            "isArray",
+            FunctionBuilder::new("isArray")
+                .param("value", PredefinedType::Any)
+                .returns(PredefinedType::Boolean)
+                .build(|args| {
+                    args.returns(args.get("value").is_some_and(|value| value.is_array()));
+                }),
+        )
+        .prop(
+            "from",
+            FunctionBuilder::new("from")
+                .param("source", PredefinedType::Any)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    let source = args.get("source").unwrap_or(Value::None);
+                    let elements = match elements(&source) {
+                        Some(Ok(elements)) => elements,
+                        Some(Err(message)) => {
+                            return args.returns(Value::error(
+                                Span::default(),
+                                ErrorCode::Type,
+                                message,
+                            ))
+                        }
+                        // Array-likes give their indexed properties up to their length
+                        None => match source.get_member("length") {
+                            Some(Value::Number(length)) => (0..length.max(0))
+                                .map(|index| {
+                                    source.get_member(index.to_string()).unwrap_or(Value::None)
+                                })
+                                .collect(),
+                            _ => vec![],
+                        },
+                    };
+
+                    args.returns(Value::array(elements, ArraySize::Dynamic));
+                }),
+        )
+        .prop(
+            "of",
+            FunctionBuilder::new("of")
+                .rest("items", PredefinedType::Any)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    args.returns(args.get("items").unwrap_or(Value::None));
+                }),
+        )
+        .build()
+}
@@ -0,0 +1,98 @@
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::PredefinedType;
+
+use super::elements;
+use crate::{
+    value::{
+        collection::{Entries, WeakEntries},
+        heap::Shared,
+        ErrorCode, Value,
+    },
+    FArguments, FunctionBuilder,
+};
+
+fn initial(args: &FArguments) -> Result<Vec<Value>, String> {
+    match args.get("iterable") {
+        None | Some(Value::None | Value::Null) => Ok(vec![]),
+        Some(iterable) => elements(&iterable)
+            .unwrap_or_else(|| Err(format!("{} is not iterable", iterable.type_of()))),
+    }
+}
+
+fn pair(entry: Value) -> Result<(Value, Value), String> {
+    match &entry {
+        Value::Array(elements, _) => {
+            let elements = elements.read();
+            let element = |index: usize| elements.get(index).cloned().unwrap_or(Value::None);
+
+            Ok((element(0), element(1)))
+        }
+        entry => Err(format!("iterator value {entry} is not an entry object")),
+    }
+}
+
+fn constructed(result: Result<Value, String>) -> Value {
+    result.unwrap_or_else(|message| Value::error(Span::default(), ErrorCode::Type, message))
+}
+
+pub fn map() -> Value {
+    FunctionBuilder::new("Map")
+        .param_default("iterable", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Any)
+        .build(|args| {
+            let entries = initial(args).and_then(|entries| {
+                entries
+                    .into_iter()
+                    .map(pair)
+                    .collect::<Result<Entries, _>>()
+            });
+
+            args.returns(constructed(
+                entries.map(|entries| Value::Map(Shared::new(entries))),
+            ));
+        })
+}
+
+pub fn set() -> Value {
+    FunctionBuilder::new("Set")
+        .param_default("iterable", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Any)
+        .build(|args| {
+            let values = initial(args).map(|values| {
+                Value::Set(Shared::new(
+                    values
+                        .into_iter()
+                        .map(|value| (value, Value::None))
+                        .collect(),
+                ))
+            });
+
+            args.returns(constructed(values));
+        })
+}
+
+pub fn weak_map() -> Value {
+    FunctionBuilder::new("WeakMap")
+        .param_default("iterable", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Any)
+        .build(|args| {
+            let mut entries = WeakEntries::default();
+            let inserted = initial(args).and_then(|initial| {
+                initial.into_iter().try_for_each(|entry| {
+                    let (key, value) = pair(entry)?;
+
+                    match entries.insert(&key, value) {
+                        true => Ok(()),
+                        false => Err(format!(
+                            "invalid value used as weak map key: {}",
+                            key.type_of()
+                        )),
+                    }
+                })
+            });
+
+            args.returns(constructed(
+                inserted.map(|()| Value::WeakMap(Shared::new(entries))),
+            ));
+        })
+}
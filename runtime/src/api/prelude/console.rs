@@ -0,0 +1,146 @@
+use tsr_parser::ast::PredefinedType;
+
+use super::{entries, key_string};
+use crate::{
+    value::{builders::ObjectBuilder, Value},
+    FunctionBuilder,
+};
+
+pub fn console() -> Value {
+    ObjectBuilder::default()
+        .prop("log", printer("log", false))
+        .prop("error", printer("error", true))
+        .prop("warn", printer("warn", true))
+        .prop(
+            "table",
+            FunctionBuilder::new("table")
+                .param("data", PredefinedType::Any)
+                .returns(PredefinedType::Void)
+                .build(|args| {
+                    if let Some(data) = args.get("data") {
+                        println!("{}", table(&data));
+                    }
+                }),
+        )
+        .build()
+}
+
+/// Prints its arguments separated by spaces, strings as they are and anything else inspected.
+fn printer(name: &str, stderr: bool) -> Value {
+    FunctionBuilder::new(name)
+        .rest("data", PredefinedType::Any)
+        .returns(PredefinedType::Void)
+        .build(move |args| {
+            let Some(Value::Array(data, _)) = args.get("data") else {
+                return;
+            };
+
+            let message = data
+                .read()
+                .iter()
+                .map(|value| match value {
+                    Value::String(string) => string.clone(),
+                    value => value.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            match stderr {
+                true => eprintln!("{message}"),
+                false => println!("{message}"),
+            }
+        })
+}
+
+/// Formats the entries of an object or array as a table, the way `console.table` prints
+/// them. Rows that are objects get a column per key, anything else goes in `Values`.
+pub fn table(data: &Value) -> String {
+    let Some(rows) = entries(data) else {
+        return data.to_string();
+    };
+
+    let mut columns: Vec<String> = vec![];
+    let mut primitive = false;
+
+    for (_, row) in &rows {
+        match entries(row) {
+            Some(cells) => {
+                for (key, _) in cells {
+                    let key = key_string(&key);
+
+                    if !columns.contains(&key) {
+                        columns.push(key);
+                    }
+                }
+            }
+            None => primitive = true,
+        }
+    }
+
+    let mut header = vec!["(index)".to_string()];
+
+    header.extend(columns.iter().cloned());
+
+    if primitive {
+        header.push("Values".into());
+    }
+
+    let body = rows
+        .iter()
+        .map(|(index, row)| {
+            let cells = entries(row);
+            let mut line = vec![key_string(index)];
+
+            line.extend(columns.iter().map(|column| {
+                cells
+                    .iter()
+                    .flatten()
+                    .find(|(key, _)| key_string(key) == *column)
+                    .map_or(String::new(), |(_, value)| value.to_string())
+            }));
+
+            if primitive {
+                line.push(match cells {
+                    Some(_) => String::new(),
+                    None => row.to_string(),
+                });
+            }
+
+            line
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&body)
+                .map(|line| line[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let border = |left: &str, middle: &str, right: &str| {
+        let lines = widths
+            .iter()
+            .map(|width| "─".repeat(width + 2))
+            .collect::<Vec<_>>();
+
+        format!("{left}{}{right}", lines.join(middle))
+    };
+    let line = |cells: &Vec<String>| {
+        let cells = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {cell}{} ", " ".repeat(width - cell.chars().count())))
+            .collect::<Vec<_>>();
+
+        format!("│{}│", cells.join("│"))
+    };
+
+    let mut lines = vec![border("┌", "┬", "┐"), line(&header), border("├", "┼", "┤")];
+
+    lines.extend(body.iter().map(line));
+    lines.push(border("└", "┴", "┘"));
+
+    lines.join("\n")
+}
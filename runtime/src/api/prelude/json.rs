@@ -0,0 +1,399 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{ArraySize, PredefinedType, Type};
+
+use super::{entries, key_string};
+use crate::value::{builders::ObjectBuilder, ErrorCode, Properties, Value};
+use crate::FunctionBuilder;
+
+pub fn json() -> Value {
+    ObjectBuilder::default()
+        .prop(
+            "parse",
+            FunctionBuilder::new("parse")
+                .param("text", PredefinedType::String)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    let text = args.get_string("text");
+
+                    args.returns(parse(&text).unwrap_or_else(|message| {
+                        Value::error(Span::default(), ErrorCode::Type, message)
+                    }));
+                }),
+        )
+        .prop(
+            "stringify",
+            FunctionBuilder::new("stringify")
+                .param("value", PredefinedType::Any)
+                // Replacer functions can't be called back from here, only `null` is accepted
+                .param_default(
+                    "replacer",
+                    Type::union(vec![
+                        PredefinedType::Null.into(),
+                        PredefinedType::Undefined.into(),
+                    ]),
+                    Value::Null,
+                )
+                .param_default("space", PredefinedType::Any, Value::None)
+                .returns(Type::union(vec![
+                    PredefinedType::String.into(),
+                    PredefinedType::Undefined.into(),
+                ]))
+                .build(|args| {
+                    let indent = match args.get("space") {
+                        Some(Value::Number(spaces)) => " ".repeat(spaces.clamp(0, 10) as usize),
+                        Some(Value::String(space)) => space.chars().take(10).collect(),
+                        _ => String::new(),
+                    };
+                    let value = args.get("value").unwrap_or(Value::None);
+
+                    args.returns(match stringify(&value, &indent) {
+                        Ok(Some(text)) => Value::String(text),
+                        Ok(None) => Value::None,
+                        Err(message) => Value::error(Span::default(), ErrorCode::Type, message),
+                    });
+                }),
+        )
+        .build()
+}
+
+/// Serializes `value` like `JSON.stringify`, indenting nested entries with `indent` when
+/// it is not empty. Values without a JSON form, like `undefined` and functions, give `None`.
+pub fn stringify(value: &Value, indent: &str) -> Result<Option<String>, String> {
+    Serializer {
+        indent,
+        seen: vec![],
+    }
+    .value(value, 0)
+}
+
+struct Serializer<'a> {
+    indent: &'a str,
+    /// Heap ids of the objects and arrays being serialized, to catch cycles
+    seen: Vec<usize>,
+}
+
+impl Serializer<'_> {
+    fn value(&mut self, value: &Value, depth: usize) -> Result<Option<String>, String> {
+        Ok(Some(match value {
+            Value::Null => "null".into(),
+            Value::Boolean(boolean) => boolean.to_string(),
+            Value::Number(number) => number.to_string(),
+            Value::Float(float) if float.is_finite() => float.to_string(),
+            Value::Float(_) => "null".into(),
+            Value::String(string) => quote(string),
+            // Entries of collections aren't properties, they serialize as empty objects
+            Value::Map(_)
+            | Value::Set(_)
+            | Value::WeakMap(_)
+            | Value::Iterator(_)
+            | Value::Promise(_) => "{}".into(),
+            Value::Array(..) | Value::Object(_) | Value::ClassInstance(_) => {
+                let id = value.heap_id().unwrap_or_default();
+
+                if self.seen.contains(&id) {
+                    return Err("converting circular structure to JSON".into());
+                }
+
+                self.seen.push(id);
+
+                let entries = entries(value).unwrap_or_default();
+                let array = value.is_array();
+                let mut items = vec![];
+
+                for (key, value) in entries {
+                    match (self.value(&value, depth + 1)?, array) {
+                        (Some(value), true) => items.push(value),
+                        (None, true) => items.push("null".into()),
+                        (Some(value), false) => {
+                            let separator = if self.indent.is_empty() { ":" } else { ": " };
+
+                            items.push(format!("{}{separator}{value}", quote(&key_string(&key))));
+                        }
+                        (None, false) => {}
+                    }
+                }
+
+                self.seen.pop();
+
+                let (open, close) = if array { ("[", "]") } else { ("{", "}") };
+
+                match (items.is_empty(), self.indent.is_empty()) {
+                    (true, _) => format!("{open}{close}"),
+                    (false, true) => format!("{open}{}{close}", items.join(",")),
+                    (false, false) => {
+                        let inner = format!("\n{}", self.indent.repeat(depth + 1));
+                        let outer = format!("\n{}", self.indent.repeat(depth));
+
+                        format!(
+                            "{open}{inner}{}{outer}{close}",
+                            items.join(&format!(",{inner}"))
+                        )
+                    }
+                }
+            }
+            _ => return Ok(None),
+        }))
+    }
+}
+
+fn quote(string: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for char in string.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            char if (char as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", char as u32)),
+            char => quoted.push(char),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Parses JSON text into values like `JSON.parse`: objects and dynamic arrays, integers as
+/// numbers and anything with a fraction or exponent as floats.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: text.char_indices().peekable(),
+    };
+    let value = parser.value()?;
+
+    parser.whitespace();
+
+    match parser.chars.next() {
+        Some((position, char)) => Err(unexpected(char, position)),
+        None => Ok(value),
+    }
+}
+
+fn unexpected(char: char, position: usize) -> String {
+    format!("unexpected token {char} in JSON at position {position}")
+}
+
+const END: &str = "unexpected end of JSON input";
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn whitespace(&mut self) {
+        while self
+            .chars
+            .next_if(|(_, char)| matches!(char, ' ' | '\t' | '\n' | '\r'))
+            .is_some()
+        {}
+    }
+
+    fn next(&mut self) -> Result<(usize, char), String> {
+        self.chars.next().ok_or_else(|| END.to_string())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next()? {
+            (_, char) if char == expected => Ok(()),
+            (position, char) => Err(unexpected(char, position)),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.whitespace();
+
+        match self.chars.peek().copied() {
+            None => Err(END.into()),
+            Some((_, '{')) => self.object(),
+            Some((_, '[')) => self.array(),
+            Some((_, '"')) => self.string().map(Value::String),
+            Some((_, 't')) => self.keyword("true", Value::Boolean(true)),
+            Some((_, 'f')) => self.keyword("false", Value::Boolean(false)),
+            Some((_, 'n')) => self.keyword("null", Value::Null),
+            Some((_, '-' | '0'..='9')) => self.number(),
+            Some((position, char)) => Err(unexpected(char, position)),
+        }
+    }
+
+    /// Items between `open` and `close` separated by commas, each read by `item`.
+    fn sequence(
+        &mut self,
+        open: char,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<(), String>,
+    ) -> Result<(), String> {
+        self.expect(open)?;
+        self.whitespace();
+
+        if self.chars.next_if(|(_, char)| *char == close).is_some() {
+            return Ok(());
+        }
+
+        loop {
+            item(self)?;
+            self.whitespace();
+
+            match self.next()? {
+                (_, ',') => {}
+                (_, char) if char == close => return Ok(()),
+                (position, char) => return Err(unexpected(char, position)),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        let mut properties = Properties::new();
+
+        self.sequence('{', '}', |parser| {
+            parser.whitespace();
+
+            let key = parser.string()?;
+
+            parser.whitespace();
+            parser.expect(':')?;
+            properties.insert(Value::String(key), parser.value()?);
+
+            Ok(())
+        })?;
+
+        Ok(Value::object(properties))
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        let mut elements = vec![];
+
+        self.sequence('[', ']', |parser| {
+            elements.push(parser.value()?);
+
+            Ok(())
+        })?;
+
+        Ok(Value::array(elements, ArraySize::Dynamic))
+    }
+
+    fn hex(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let (position, char) = self.next()?;
+
+            code = code * 16
+                + char
+                    .to_digit(16)
+                    .ok_or_else(|| unexpected(char, position))?;
+        }
+
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+
+        loop {
+            match self.next()? {
+                (_, '"') => return Ok(string),
+                (_, '\\') => string.push(match self.next()? {
+                    (_, '"') => '"',
+                    (_, '\\') => '\\',
+                    (_, '/') => '/',
+                    (_, 'b') => '\u{8}',
+                    (_, 'f') => '\u{c}',
+                    (_, 'n') => '\n',
+                    (_, 'r') => '\r',
+                    (_, 't') => '\t',
+                    (_, 'u') => {
+                        let code = self.hex()?;
+
+                        // Characters outside the basic plane are escaped as surrogate pairs
+                        let code = match (0xd800..0xdc00).contains(&code)
+                            && self.chars.next_if(|(_, char)| *char == '\\').is_some()
+                        {
+                            true => {
+                                self.expect('u')?;
+
+                                let low = self.hex()?;
+
+                                0x10000
+                                    + ((code - 0xd800) << 10)
+                                    + (low.wrapping_sub(0xdc00) & 0x3ff)
+                            }
+                            false => code,
+                        };
+
+                        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                    }
+                    (position, char) => return Err(unexpected(char, position)),
+                }),
+                (position, char) if (char as u32) < 0x20 => return Err(unexpected(char, position)),
+                (_, char) => string.push(char),
+            }
+        }
+    }
+
+    fn digits(&mut self, number: &mut String) -> Result<(), String> {
+        let start = number.len();
+
+        while let Some((_, digit)) = self.chars.next_if(|(_, char)| char.is_ascii_digit()) {
+            number.push(digit);
+        }
+
+        match (number.len() > start, self.chars.peek()) {
+            (true, _) => Ok(()),
+            (false, Some((position, char))) => Err(unexpected(*char, *position)),
+            (false, None) => Err(END.into()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let mut number = String::new();
+
+        if let Some((_, sign)) = self.chars.next_if(|(_, char)| *char == '-') {
+            number.push(sign);
+        }
+
+        self.digits(&mut number)?;
+
+        let mut integer = true;
+
+        if let Some((_, point)) = self.chars.next_if(|(_, char)| *char == '.') {
+            number.push(point);
+            integer = false;
+            self.digits(&mut number)?;
+        }
+
+        if let Some((_, exponent)) = self.chars.next_if(|(_, char)| matches!(char, 'e' | 'E')) {
+            number.push(exponent);
+            integer = false;
+
+            if let Some((_, sign)) = self.chars.next_if(|(_, char)| matches!(char, '+' | '-')) {
+                number.push(sign);
+            }
+
+            self.digits(&mut number)?;
+        }
+
+        Ok(match integer {
+            true => number.parse().map_or_else(
+                |_| Value::Float(number.parse().unwrap_or(f64::NAN)),
+                Value::Number,
+            ),
+            false => Value::Float(number.parse().unwrap_or(f64::NAN)),
+        })
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    cell::Cell,
+    f64::consts,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tsr_parser::ast::PredefinedType;
+
+use super::numeric;
+use crate::{
+    value::{builders::ObjectBuilder, Value},
+    FArguments, FunctionBuilder,
+};
+
+thread_local! {
+    /// State of the generator behind `Math.random`, seeded from the clock
+    static SEED: Cell<u64> = Cell::new(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0x2545_f491_4f6c_dd1d, |time| time.as_nanos() as u64)
+            | 1,
+    );
+}
+
+type Unary = fn(f64) -> f64;
+
+/// An integral result, a float when it is not finite.
+fn integral(value: f64) -> Value {
+    match value.is_finite() {
+        true => Value::Number(value as i64),
+        false => Value::Float(value),
+    }
+}
+
+fn unary(name: &str, integral_result: bool, function: Unary) -> Value {
+    FunctionBuilder::new(name)
+        .param("x", numeric())
+        .returns(match integral_result {
+            true => numeric(),
+            false => PredefinedType::Float.into(),
+        })
+        .build(move |args| {
+            let result = function(args.get_float("x"));
+
+            args.returns(match integral_result {
+                true => integral(result),
+                false => Value::Float(result),
+            });
+        })
+}
+
+/// Reads the numbers of a rest parameter, and whether they are all integers.
+fn values(args: &FArguments) -> (Vec<f64>, bool) {
+    let Some(Value::Array(values, _)) = args.get("values") else {
+        return (vec![], true);
+    };
+    let values = values.read();
+
+    (
+        values
+            .iter()
+            .map(|value| match value {
+                Value::Number(number) => *number as f64,
+                Value::Float(float) => *float,
+                _ => f64::NAN,
+            })
+            .collect(),
+        values.iter().all(|value| matches!(value, Value::Number(_))),
+    )
+}
+
+fn extremum(name: &str, initial: f64, pick: fn(f64, f64) -> f64) -> Value {
+    FunctionBuilder::new(name)
+        .rest("values", numeric())
+        .returns(numeric())
+        .build(move |args| {
+            let (values, integers) = values(args);
+            let result = values.into_iter().fold(initial, pick);
+
+            args.returns(match integers && result.is_finite() {
+                true => Value::Number(result as i64),
+                false => Value::Float(result),
+            });
+        })
+}
+
+pub fn math() -> Value {
+    let mut math = ObjectBuilder::default()
+        .prop("PI", Value::Float(consts::PI))
+        .prop("E", Value::Float(consts::E))
+        .prop("LN2", Value::Float(consts::LN_2))
+        .prop("LN10", Value::Float(consts::LN_10))
+        .prop("LOG2E", Value::Float(consts::LOG2_E))
+        .prop("LOG10E", Value::Float(consts::LOG10_E))
+        .prop("SQRT2", Value::Float(consts::SQRT_2))
+        .prop(
+            "abs",
+            FunctionBuilder::new("abs")
+                .param("x", numeric())
+                .returns(numeric())
+                .build(|args| {
+                    args.returns(match args.get("x") {
+                        Some(Value::Number(number)) => Value::Number(number.abs()),
+                        _ => Value::Float(args.get_float("x").abs()),
+                    });
+                }),
+        )
+        .prop(
+            "pow",
+            FunctionBuilder::new("pow")
+                .param("base", numeric())
+                .param("exponent", numeric())
+                .returns(numeric())
+                .build(|args| {
+                    let exact = match (args.get("base"), args.get("exponent")) {
+                        (Some(Value::Number(base)), Some(Value::Number(exponent))) => {
+                            u32::try_from(exponent)
+                                .ok()
+                                .and_then(|exponent| base.checked_pow(exponent))
+                        }
+                        _ => None,
+                    };
+
+                    args.returns(match exact {
+                        Some(power) => Value::Number(power),
+                        None => {
+                            Value::Float(args.get_float("base").powf(args.get_float("exponent")))
+                        }
+                    });
+                }),
+        )
+        .prop(
+            "atan2",
+            FunctionBuilder::new("atan2")
+                .param("y", numeric())
+                .param("x", numeric())
+                .returns(PredefinedType::Float)
+                .build(|args| {
+                    args.returns(Value::Float(args.get_float("y").atan2(args.get_float("x"))));
+                }),
+        )
+        .prop("min", extremum("min", f64::INFINITY, f64::min))
+        .prop("max", extremum("max", f64::NEG_INFINITY, f64::max))
+        .prop(
+            "hypot",
+            FunctionBuilder::new("hypot")
+                .rest("values", numeric())
+                .returns(PredefinedType::Float)
+                .build(|args| {
+                    let (values, _) = values(args);
+
+                    args.returns(Value::Float(
+                        values.iter().map(|value| value * value).sum::<f64>().sqrt(),
+                    ));
+                }),
+        )
+        .prop(
+            "random",
+            FunctionBuilder::new("random")
+                .returns(PredefinedType::Float)
+                .build(|args| {
+                    // xorshift64*, good enough for scripts and free of dependencies
+                    let state = SEED.with(|seed| {
+                        let mut state = seed.get();
+
+                        state ^= state >> 12;
+                        state ^= state << 25;
+                        state ^= state >> 27;
+                        seed.set(state);
+
+                        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+                    });
+
+                    args.returns(Value::Float((state >> 11) as f64 / (1u64 << 53) as f64));
+                }),
+        );
+
+    let integral_functions: [(&str, Unary); 5] = [
+        ("floor", f64::floor),
+        ("ceil", f64::ceil),
+        ("trunc", f64::trunc),
+        // Halves round up, like in JavaScript, rather than away from zero
+        ("round", |x| (x + 0.5).floor()),
+        ("sign", |x| match x == 0.0 || x.is_nan() {
+            true => x,
+            false => x.signum(),
+        }),
+    ];
+    let float_functions: [(&str, Unary); 13] = [
+        ("sqrt", f64::sqrt),
+        ("cbrt", f64::cbrt),
+        ("exp", f64::exp),
+        ("log", f64::ln),
+        ("log2", f64::log2),
+        ("log10", f64::log10),
+        ("sin", f64::sin),
+        ("cos", f64::cos),
+        ("tan", f64::tan),
+        ("asin", f64::asin),
+        ("acos", f64::acos),
+        ("atan", f64::atan),
+        ("fround", |x| x as f32 as f64),
+    ];
+
+    for (name, function) in integral_functions {
+        math = math.prop(name, unary(name, true, function));
+    }
+
+    for (name, function) in float_functions {
+        math = math.prop(name, unary(name, false, function));
+    }
+
+    math.build()
+}
@@ -0,0 +1,124 @@
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{PredefinedType, Type};
+
+use crate::{
+    value::{
+        iterator::{Iteration, Step},
+        Value,
+    },
+    Runtime,
+};
+
+mod array;
+mod collection;
+mod console;
+mod json;
+mod math;
+mod number;
+mod object;
+mod promise;
+mod symbol;
+mod timers;
+
+pub use self::{
+    console::table,
+    json::{parse as parse_json, stringify as stringify_json},
+};
+
+/// Installs the standard globals into the root scope of `runtime`.
+pub fn install(runtime: &Runtime) {
+    let globals = [
+        ("NaN", Value::Float(f64::NAN)),
+        ("Infinity", Value::Float(f64::INFINITY)),
+        ("console", console::console()),
+        ("Math", math::math()),
+        ("Number", number::number()),
+        ("parseInt", number::parse_int()),
+        ("parseFloat", number::parse_float()),
+        ("Object", object::object()),
+        ("Array", array::array()),
+        ("JSON", json::json()),
+        ("Map", collection::map()),
+        ("Set", collection::set()),
+        ("WeakMap", collection::weak_map()),
+        ("Symbol", symbol::symbol()),
+        ("Promise", promise::promise()),
+        ("setTimeout", timers::schedule("setTimeout", false)),
+        ("setInterval", timers::schedule("setInterval", true)),
+        ("clearTimeout", timers::clear("clearTimeout")),
+        ("clearInterval", timers::clear("clearInterval")),
+    ];
+
+    for (name, value) in globals {
+        runtime.set_variable(name, Span::default().wrap(value));
+    }
+}
+
+/// Type of parameters taking integers and floats alike.
+fn numeric() -> Type {
+    Type::union(vec![
+        PredefinedType::Number.into(),
+        PredefinedType::Float.into(),
+    ])
+}
+
+fn entries(value: &Value) -> Option<Vec<(Value, Value)>> {
+    match value {
+        Value::Object(properties) => {
+            // Like in JavaScript, properties keyed by symbols aren't listed
+            let mut entries = properties
+                .read()
+                .iter()
+                .filter(|(key, _)| !matches!(key, Value::Symbol(_)))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>();
+
+            entries.sort_by_key(|(key, _)| match key {
+                Value::Number(index) => (false, *index),
+                _ => (true, 0),
+            });
+
+            Some(entries)
+        }
+        Value::ClassInstance(instance) => Some(
+            instance
+                .fields
+                .read()
+                .iter()
+                .map(|field| (Value::String(field.prop.name.clone()), field.value.clone()))
+                .collect(),
+        ),
+        Value::Array(elements, _) => Some(
+            elements
+                .read()
+                .iter()
+                .enumerate()
+                .map(|(index, element)| (Value::Number(index as i64), element.clone()))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn elements(value: &Value) -> Option<Result<Vec<Value>, String>> {
+    let Value::Iterator(iteration) = value.values()? else {
+        return None;
+    };
+    let mut elements = vec![];
+
+    loop {
+        match Iteration::step(&iteration, Value::None) {
+            Step::Next(value) => elements.push(value),
+            Step::Done(Value::Error(_, _, message)) => return Some(Err(message)),
+            Step::Done(_) => return Some(Ok(elements)),
+        }
+    }
+}
+
+/// Property keys are strings in JavaScript, whatever they were written as.
+fn key_string(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => key.to_string(),
+    }
+}
@@ -0,0 +1,234 @@
+use tsr_parser::ast::PredefinedType;
+
+use super::numeric;
+use crate::{value::Value, FunctionBuilder};
+
+/// Largest integer a float represents exactly, like JavaScript's `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+fn predicate(name: &str, test: fn(&Value) -> bool) -> Value {
+    FunctionBuilder::new(name)
+        .param("value", PredefinedType::Any)
+        .returns(PredefinedType::Boolean)
+        .build(move |args| {
+            args.returns(args.get("value").is_some_and(|value| test(&value)));
+        })
+}
+
+pub fn number() -> Value {
+    FunctionBuilder::new("Number")
+        .param_default("value", PredefinedType::Any, 0)
+        .returns(numeric())
+        .prop("MAX_SAFE_INTEGER", Value::Number(MAX_SAFE_INTEGER))
+        .prop("MIN_SAFE_INTEGER", Value::Number(-MAX_SAFE_INTEGER))
+        .prop("EPSILON", Value::Float(f64::EPSILON))
+        .prop("MAX_VALUE", Value::Float(f64::MAX))
+        .prop("POSITIVE_INFINITY", Value::Float(f64::INFINITY))
+        .prop("NEGATIVE_INFINITY", Value::Float(f64::NEG_INFINITY))
+        .prop("NaN", Value::Float(f64::NAN))
+        .prop(
+            "isInteger",
+            predicate("isInteger", |value| match value {
+                Value::Number(_) => true,
+                Value::Float(float) => float.is_finite() && float.fract() == 0.0,
+                _ => false,
+            }),
+        )
+        .prop(
+            "isSafeInteger",
+            predicate("isSafeInteger", |value| match value {
+                Value::Number(number) => number.abs() <= MAX_SAFE_INTEGER,
+                Value::Float(float) => {
+                    float.fract() == 0.0 && float.abs() <= MAX_SAFE_INTEGER as f64
+                }
+                _ => false,
+            }),
+        )
+        .prop(
+            "isFinite",
+            predicate("isFinite", |value| match value {
+                Value::Number(_) => true,
+                Value::Float(float) => float.is_finite(),
+                _ => false,
+            }),
+        )
+        .prop(
+            "isNaN",
+            predicate(
+                "isNaN",
+                |value| matches!(value, Value::Float(float) if float.is_nan()),
+            ),
+        )
+        .prop("parseInt", parse_int())
+        .prop("parseFloat", parse_float())
+        .build(|args| {
+            let value = args.get("value").unwrap_or(Value::None);
+
+            args.returns(convert(&value));
+        })
+}
+
+pub fn parse_int() -> Value {
+    FunctionBuilder::new("parseInt")
+        .param("string", PredefinedType::String)
+        .param_default("radix", PredefinedType::Number, 10)
+        .returns(numeric())
+        .build(|args| {
+            let string = args.get_string("string");
+
+            args.returns(parse_integer(&string, args.get_number("radix")));
+        })
+}
+
+pub fn parse_float() -> Value {
+    FunctionBuilder::new("parseFloat")
+        .param("string", PredefinedType::String)
+        .returns(PredefinedType::Float)
+        .build(|args| {
+            let string = args.get_string("string");
+
+            args.returns(Value::Float(parse_decimal(&string)));
+        })
+}
+
+/// Converts `value` to a number like JavaScript's `Number`, `NaN` when it isn't one.
+fn convert(value: &Value) -> Value {
+    match value {
+        Value::Number(_) | Value::Float(_) => value.clone(),
+        Value::Boolean(value) => Value::Number(*value as i64),
+        Value::Null => Value::Number(0),
+        Value::String(string) => parse_number(string.trim()),
+        _ => Value::Float(f64::NAN),
+    }
+}
+
+/// Parses a whole string as a number, unlike `parseInt` and `parseFloat` which stop at the
+/// first character that doesn't fit.
+fn parse_number(string: &str) -> Value {
+    let radix = match string.get(..2) {
+        Some("0x" | "0X") => Some(16),
+        Some("0o" | "0O") => Some(8),
+        Some("0b" | "0B") => Some(2),
+        _ => None,
+    };
+
+    if let Some(radix) = radix {
+        let digits = &string[2..];
+
+        return match digits.starts_with(['+', '-']) {
+            true => Value::Float(f64::NAN),
+            false => {
+                i64::from_str_radix(digits, radix).map_or(Value::Float(f64::NAN), Value::Number)
+            }
+        };
+    }
+
+    if string.is_empty() {
+        return Value::Number(0);
+    }
+
+    if let Ok(integer) = string.parse() {
+        return Value::Number(integer);
+    }
+
+    match string.trim_start_matches(['+', '-']) {
+        "Infinity" => Value::Float(parse_decimal(string)),
+        // Rust also reads words like `inf` and `NaN`, which JavaScript doesn't
+        unsigned if unsigned.starts_with(|char: char| char.is_ascii_digit() || char == '.') => {
+            Value::Float(string.parse().unwrap_or(f64::NAN))
+        }
+        _ => Value::Float(f64::NAN),
+    }
+}
+
+/// Parses the integer at the start of `string` like JavaScript's `parseInt`, `NaN` when
+/// it doesn't start with one.
+fn parse_integer(string: &str, radix: i64) -> Value {
+    let string = string.trim_start();
+    let (negative, string) = match string.strip_prefix('-') {
+        Some(string) => (true, string),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+    let hexadecimal = string
+        .strip_prefix("0x")
+        .or_else(|| string.strip_prefix("0X"));
+    let (radix, string) = match (radix, hexadecimal) {
+        (10 | 16, Some(string)) => (16, string),
+        (radix, _) => (radix, string),
+    };
+
+    let Ok(radix @ 2..=36) = u32::try_from(radix) else {
+        return Value::Float(f64::NAN);
+    };
+
+    let digits = string
+        .chars()
+        .map_while(|char| char.to_digit(radix))
+        .collect::<Vec<_>>();
+
+    if digits.is_empty() {
+        return Value::Float(f64::NAN);
+    }
+
+    let sign = if negative { -1 } else { 1 };
+    let exact = digits.iter().try_fold(0i64, |value, digit| {
+        value.checked_mul(radix as i64)?.checked_add(*digit as i64)
+    });
+
+    match exact {
+        Some(value) => Value::Number(sign * value),
+        None => Value::Float(
+            sign as f64
+                * digits
+                    .iter()
+                    .fold(0.0, |value, digit| value * radix as f64 + *digit as f64),
+        ),
+    }
+}
+
+/// Parses the longest decimal number at the start of `string` like JavaScript's
+/// `parseFloat`, `NaN` when it doesn't start with one.
+fn parse_decimal(string: &str) -> f64 {
+    let string = string.trim_start();
+    let unsigned = string.strip_prefix(['+', '-']).unwrap_or(string);
+    let sign = string.len() - unsigned.len();
+
+    if unsigned.starts_with("Infinity") {
+        return match string.starts_with('-') {
+            true => f64::NEG_INFINITY,
+            false => f64::INFINITY,
+        };
+    }
+
+    let bytes = unsigned.as_bytes();
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+
+    let integer = digits(0);
+    let mut end = integer;
+    let mut fraction = 0;
+
+    if bytes.get(end) == Some(&b'.') {
+        fraction = digits(end + 1);
+        end += 1 + fraction;
+    }
+
+    if integer + fraction == 0 {
+        return f64::NAN;
+    }
+
+    if let Some(b'e' | b'E') = bytes.get(end) {
+        let signed = matches!(bytes.get(end + 1), Some(b'+' | b'-')) as usize;
+        let exponent = digits(end + 1 + signed);
+
+        if exponent > 0 {
+            end += 1 + signed + exponent;
+        }
+    }
+
+    string[..sign + end].parse().unwrap_or(f64::NAN)
+}
@@ -0,0 +1,72 @@
+use tsr_parser::ast::{ArraySize, PredefinedType};
+
+use super::{entries, key_string};
+use crate::{
+    value::{builders::ObjectBuilder, Value},
+    FunctionBuilder,
+};
+
+/// `Object.keys`, `Object.values` and `Object.entries`, which share everything but the
+/// element made of each entry.
+fn listing(name: &str, element: fn(Value, Value) -> Value) -> Value {
+    FunctionBuilder::new(name)
+        .param("object", PredefinedType::Any)
+        .returns(PredefinedType::Any)
+        .build(move |args| {
+            let elements = args
+                .get("object")
+                .and_then(|object| entries(&object))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| element(Value::String(key_string(&key)), value))
+                .collect();
+
+            args.returns(Value::array(elements, ArraySize::Dynamic));
+        })
+}
+
+pub fn object() -> Value {
+    ObjectBuilder::default()
+        .prop("keys", listing("keys", |key, _| key))
+        .prop("values", listing("values", |_, value| value))
+        .prop(
+            "entries",
+            listing("entries", |key, value| {
+                Value::array(vec![key, value], ArraySize::Fixed(2))
+            }),
+        )
+        .prop(
+            "assign",
+            FunctionBuilder::new("assign")
+                .param("target", PredefinedType::Any)
+                .rest("sources", PredefinedType::Any)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    let target = args.get("target").unwrap_or(Value::None);
+                    let Some(Value::Array(sources, _)) = args.get("sources") else {
+                        return args.returns(target);
+                    };
+
+                    for (key, value) in sources.read().iter().filter_map(entries).flatten() {
+                        match &target {
+                            Value::Object(properties) => {
+                                let key = match key {
+                                    Value::Number(_) => Value::String(key_string(&key)),
+                                    key => key,
+                                };
+
+                                properties.write().insert(key, value);
+                            }
+                            // Instances have no room for properties their class doesn't declare
+                            Value::ClassInstance(instance) => {
+                                instance.set_field(key_string(&key), value);
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    args.returns(target);
+                }),
+        )
+        .build()
+}
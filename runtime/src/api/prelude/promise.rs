@@ -0,0 +1,144 @@
+use std::{cell::RefCell, rc::Rc};
+
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::PredefinedType;
+
+use super::elements;
+use crate::{
+    value::{
+        heap::Shared,
+        promise::{Outcome, Promise},
+        ErrorCode, Value,
+    },
+    FArguments, FunctionBuilder,
+};
+
+fn promises(args: &FArguments) -> Result<Vec<Shared<Promise>>, String> {
+    let values = args.get("values").unwrap_or(Value::None);
+    let values = elements(&values)
+        .unwrap_or_else(|| Err(format!("{} is not iterable", values.type_of())))?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| Promise::from_value(args.event_loop(), value))
+        .collect())
+}
+
+fn all(args: &mut FArguments) {
+    let promises = match promises(args) {
+        Ok(promises) => promises,
+        Err(message) => {
+            return args.returns(Value::error(Span::default(), ErrorCode::Type, message))
+        }
+    };
+    let combined = Promise::pending(args.event_loop());
+    let values = Rc::new(RefCell::new(vec![Value::None; promises.len()]));
+    let remaining = Rc::new(RefCell::new(promises.len()));
+
+    if promises.is_empty() {
+        Promise::settle(
+            &combined,
+            Outcome::Fulfilled(Value::from(Vec::<Value>::new())),
+        );
+    }
+
+    for (index, promise) in promises.iter().enumerate() {
+        let (combined, values, remaining) = (combined.clone(), values.clone(), remaining.clone());
+
+        Promise::subscribe(
+            promise,
+            Box::new(move |_, outcome| match outcome {
+                Outcome::Fulfilled(value) => {
+                    values.borrow_mut()[index] = value;
+                    *remaining.borrow_mut() -= 1;
+
+                    if *remaining.borrow() == 0 {
+                        let values = values.take();
+
+                        Promise::settle(&combined, Outcome::Fulfilled(Value::from(values)));
+                    }
+                }
+                rejected => Promise::settle(&combined, rejected),
+            }),
+        );
+    }
+
+    args.returns(Value::Promise(combined));
+}
+
+fn race(args: &mut FArguments) {
+    let promises = match promises(args) {
+        Ok(promises) => promises,
+        Err(message) => {
+            return args.returns(Value::error(Span::default(), ErrorCode::Type, message))
+        }
+    };
+    let combined = Promise::pending(args.event_loop());
+
+    for promise in &promises {
+        let combined = combined.clone();
+
+        Promise::subscribe(
+            promise,
+            Box::new(move |_, outcome| Promise::settle(&combined, outcome)),
+        );
+    }
+
+    args.returns(Value::Promise(combined));
+}
+
+/// The `Promise` global.
+pub fn promise() -> Value {
+    FunctionBuilder::new("Promise")
+        .param_default("executor", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Any)
+        .prop(
+            "resolve",
+            FunctionBuilder::new("resolve")
+                .param_default("value", PredefinedType::Any, Value::None)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    let value = args.get("value").unwrap_or(Value::None);
+
+                    args.returns(Value::Promise(Promise::from_value(
+                        args.event_loop(),
+                        value,
+                    )));
+                }),
+        )
+        .prop(
+            "reject",
+            FunctionBuilder::new("reject")
+                .param_default("reason", PredefinedType::Any, Value::None)
+                .returns(PredefinedType::Any)
+                .build(|args| {
+                    let reason = args.get("reason").unwrap_or(Value::None);
+
+                    args.returns(Value::Promise(Promise::settled(
+                        args.event_loop(),
+                        Outcome::Rejected(reason),
+                    )));
+                }),
+        )
+        .prop(
+            "all",
+            FunctionBuilder::new("all")
+                .param("values", PredefinedType::Any)
+                .returns(PredefinedType::Any)
+                .build(all),
+        )
+        .prop(
+            "race",
+            FunctionBuilder::new("race")
+                .param("values", PredefinedType::Any)
+                .returns(PredefinedType::Any)
+                .build(race),
+        )
+        .build(|args| {
+            args.returns(Value::error(
+                Span::default(),
+                ErrorCode::Type,
+                "Promise constructor cannot be invoked without \"new\"",
+            ))
+        })
+}
@@ -0,0 +1,54 @@
+use tsr_parser::ast::{PredefinedType, Type};
+
+use crate::{
+    value::{symbol::Symbol, Value},
+    FunctionBuilder,
+};
+
+pub fn symbol() -> Value {
+    FunctionBuilder::new("Symbol")
+        .param_default("description", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Symbol)
+        .prop("iterator", Value::Symbol(Symbol::iterator()))
+        .prop("asyncIterator", Value::Symbol(Symbol::async_iterator()))
+        .prop("hasInstance", Value::Symbol(Symbol::has_instance()))
+        .prop("toPrimitive", Value::Symbol(Symbol::to_primitive()))
+        .prop("toStringTag", Value::Symbol(Symbol::to_string_tag()))
+        .prop(
+            "for",
+            FunctionBuilder::new("for")
+                .param("key", PredefinedType::String)
+                .returns(PredefinedType::Symbol)
+                .build(|args| {
+                    let key = args.get_string("key");
+
+                    args.returns(Value::Symbol(Symbol::registered(&key)));
+                }),
+        )
+        .prop(
+            "keyFor",
+            FunctionBuilder::new("keyFor")
+                .param("symbol", PredefinedType::Symbol)
+                .returns(Type::union(vec![
+                    PredefinedType::String.into(),
+                    PredefinedType::Undefined.into(),
+                ]))
+                .build(|args| {
+                    let key = match args.get("symbol") {
+                        Some(Value::Symbol(symbol)) => symbol.key(),
+                        _ => None,
+                    };
+
+                    args.returns(key.map_or(Value::None, Value::String));
+                }),
+        )
+        .build(|args| {
+            let description = match args.get("description") {
+                None | Some(Value::None) => None,
+                Some(Value::String(description)) => Some(description),
+                Some(description) => Some(description.to_string()),
+            };
+
+            args.returns(Value::Symbol(Symbol::new(description)));
+        })
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::PredefinedType;
+
+use super::numeric;
+use crate::{
+    value::{ErrorCode, Value},
+    FunctionBuilder,
+};
+
+/// `setTimeout` and `setInterval`.
+pub fn schedule(name: &str, repeat: bool) -> Value {
+    FunctionBuilder::new(name)
+        .param("callback", PredefinedType::Any)
+        .param_default("delay", numeric(), 0)
+        .rest("arguments", PredefinedType::Any)
+        .returns(PredefinedType::Number)
+        .build(move |args| {
+            let callback = args.get("callback").unwrap_or(Value::None);
+
+            if !matches!(
+                callback,
+                Value::Function(_) | Value::NativeFunction(_) | Value::ArrowFunction(_)
+            ) {
+                return args.returns(Value::error(
+                    Span::default(),
+                    ErrorCode::Type,
+                    format!("{} is not a function", callback.type_of()),
+                ));
+            }
+
+            let delay = match args.get_float("delay") {
+                delay if delay.is_finite() && delay > 0.0 => {
+                    Duration::from_secs_f64(delay / 1000.0)
+                }
+                _ => Duration::ZERO,
+            };
+            // Like in JavaScript, intervals wait at least a millisecond between runs
+            let delay = match repeat {
+                true => delay.max(Duration::from_millis(1)),
+                false => delay,
+            };
+            let arguments = match args.get("arguments") {
+                Some(Value::Array(arguments, _)) => arguments.read().clone(),
+                _ => vec![],
+            };
+            let id = args
+                .event_loop()
+                .add_timer(callback, arguments, delay, repeat);
+
+            args.returns(id);
+        })
+}
+
+/// `clearTimeout` and `clearInterval`, which clear timers of either kind.
+pub fn clear(name: &str) -> Value {
+    FunctionBuilder::new(name)
+        .param_default("id", PredefinedType::Any, Value::None)
+        .returns(PredefinedType::Void)
+        .build(|args| {
+            if let Some(Value::Number(id)) = args.get("id") {
+                args.event_loop().clear_timer(id as usize);
+            }
+        })
+}
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 
 use tsr_parser::ast::ArraySize;
 use tsr_parser::ast::PredefinedType;
@@ -7,6 +6,7 @@ use tsr_parser::ast::PrimaryType;
 use crate::value::native::Module;
 use crate::value::native::NativeModule;
 use crate::value::Signature;
+use crate::value::Properties;
 use crate::value::Value;
 use crate::FArguments;
 use crate::FunctionBuilder;
@@ -60,7 +60,7 @@ impl Reflection {
                     .prop("extends", extends)
                     .prop(
                         "signatures",
-                        Value::Array(
+                        Value::array(
                             signatures
                                 .iter()
                                 .map(|signature| match signature {
@@ -92,7 +92,7 @@ impl Reflection {
                                         )
                                         .prop("returnType", signature.ty.to_string())
                                         .build(),
-                                    _ => Value::Object(HashMap::default()),
+                                    _ => Value::object(Properties::default()),
                                 })
                                 .collect(),
                             ArraySize::Dynamic,
@@ -1,5 +1,6 @@
 use tsr_parser::ast::PredefinedType;
 
+use crate::value::inspect::InspectOptions;
 use crate::value::native::Module;
 use crate::value::native::NativeModule;
 use crate::value::Value;
@@ -10,13 +11,14 @@ pub struct Util;
 
 #[tsr_macro::native_module("util")]
 impl Util {
-    #[func(args = [("value", PredefinedType::Any), ("pretty", PredefinedType::Boolean, false)], returns = PredefinedType::String)]
+    #[func(args = [("value", PredefinedType::Any), ("pretty", PredefinedType::Boolean, false), ("colors", PredefinedType::Boolean, false)], returns = PredefinedType::String)]
     fn inspect(&self, args: &FArguments) -> Option<impl Into<Value>> {
-        let pretty = args.get_boolean("pretty");
+        let options = InspectOptions {
+            pretty: args.get_boolean("pretty"),
+            colors: args.get_boolean("colors"),
+            ..Default::default()
+        };
 
-        args.get("value").map(|value| match pretty {
-            true => format!("{value:#}"),
-            false => format!("{value}"),
-        })
+        args.get("value").map(|value| value.inspect(options))
     }
 }
@@ -0,0 +1,62 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Tells timers what time it is, set with [`Runtime::set_clock`](crate::Runtime::set_clock).
+pub trait Clock: Debug + Send + Sync {
+    /// Time elapsed since the clock started.
+    fn now(&self) -> Duration;
+
+    /// Waits until `duration` has elapsed and returns whether it did.
+    fn sleep(&self, duration: Duration) -> bool;
+}
+
+/// Follows the time of the system, the clock runtimes use by default.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> bool {
+        thread::sleep(duration);
+
+        true
+    }
+}
+
+/// A clock that only moves when the host advances it, clones share the time.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock(Arc<Mutex<Duration>>);
+
+impl VirtualClock {
+    /// Moves the clock forward, timers due by then run on the next turn of the event loop.
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep(&self, _: Duration) -> bool {
+        false
+    }
+}
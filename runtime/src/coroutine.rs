@@ -0,0 +1,174 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Debug, Formatter},
+    ptr::NonNull,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use corosensei::{stack::DefaultStack, CoroutineResult, Yielder};
+use tsr_parser::ast::Block;
+
+use crate::{value::Value, Runtime};
+
+static COROUTINES: AtomicUsize = AtomicUsize::new(0);
+
+// Only the pages a body touches are backed by memory
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+const POOLED_STACKS: usize = 16;
+
+thread_local! {
+    static STACKS: RefCell<Vec<DefaultStack>> = const { RefCell::new(vec![]) };
+
+    static DROPPED: RefCell<Vec<Coroutine>> = const { RefCell::new(vec![]) };
+}
+
+// Coroutines are often dropped while their storage is locked, so they are unwound later
+pub(crate) fn close_dropped() {
+    while let Some(mut coroutine) = DROPPED.with(|dropped| dropped.borrow_mut().pop()) {
+        coroutine.close();
+    }
+}
+
+pub(crate) fn scope(kind: &str, name: &str) -> String {
+    format!(
+        "{kind}:{name}#{}",
+        COROUTINES.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+pub(crate) enum Resume {
+    Next(Box<Value>),
+    Throw(Box<Value>),
+    /// Unwinds the rest of the body without running it
+    Return,
+}
+
+pub(crate) enum Suspend {
+    Yield(Value),
+    Complete(Value),
+}
+
+type Stackful = corosensei::Coroutine<Resume, Value, Value, DefaultStack>;
+
+/// Body of a generator or async function, evaluated on a stack of its own.
+pub struct Coroutine {
+    body: Option<Stackful>,
+}
+
+impl Coroutine {
+    pub(crate) fn spawn<F>(mut runtime: Runtime, body: F) -> Self
+    where
+        F: FnOnce(&mut Runtime, Rc<Suspender>) -> Value + 'static,
+    {
+        let stack = STACKS
+            .with(|stacks| stacks.borrow_mut().pop())
+            .unwrap_or_else(|| {
+                DefaultStack::new(STACK_SIZE).expect("failed to map the stack of a coroutine")
+            });
+
+        Self {
+            body: Some(Stackful::with_stack(stack, move |yielder, resume| {
+                let Resume::Next(_) = resume else {
+                    runtime.clear_scope_variables();
+
+                    return Value::None;
+                };
+                let suspender = Rc::new(Suspender {
+                    yielder: Cell::new(Some(NonNull::from(yielder))),
+                });
+                let value = body(&mut runtime, suspender.clone());
+
+                suspender.yielder.set(None);
+
+                value
+            })),
+        }
+    }
+
+    pub(crate) fn resume(&mut self, with: Resume) -> Suspend {
+        let Some(body) = &mut self.body else {
+            return Suspend::Complete(Value::None);
+        };
+
+        match body.resume(with) {
+            CoroutineResult::Yield(value) => Suspend::Yield(value),
+            CoroutineResult::Return(value) => {
+                let stack = self.body.take().map(Stackful::into_stack);
+
+                STACKS.with(|stacks| {
+                    let mut stacks = stacks.borrow_mut();
+
+                    if stacks.len() < POOLED_STACKS {
+                        stacks.extend(stack);
+                    }
+                });
+
+                Suspend::Complete(value)
+            }
+        }
+    }
+
+    pub(crate) fn close(&mut self) {
+        // The body may suspend again while it is unwound
+        while !self.is_finished() {
+            self.resume(Resume::Return);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.body.is_none()
+    }
+}
+
+impl Drop for Coroutine {
+    fn drop(&mut self) {
+        let mut body = self.body.take();
+
+        if body.is_some() {
+            let _ = DROPPED
+                .try_with(|dropped| dropped.borrow_mut().push(Coroutine { body: body.take() }));
+        }
+
+        drop(body);
+    }
+}
+
+impl Debug for Coroutine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Coroutine")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Suspender {
+    yielder: Cell<Option<NonNull<Yielder<Resume, Value>>>>,
+}
+
+impl Suspender {
+    pub(crate) fn suspend(&self, value: Value) -> Resume {
+        match self.yielder.get() {
+            // SAFETY: the yielder is set for as long as the body runs, during which it stays
+            // where it was created on the stack of the coroutine. A suspender is `!Send`, so
+            // it is only ever used on the thread running the coroutine.
+            Some(yielder) => unsafe { yielder.as_ref() }.suspend(value),
+            None => Resume::Return,
+        }
+    }
+}
+
+impl Runtime {
+    pub(crate) fn eval_coroutine_body(&mut self, body: Block) -> Value {
+        let span = body.span;
+        let value = self.eval_code_block(body);
+        let value = self.returned(value);
+        let value = self.resolve(span, value);
+
+        self.clear_scope_variables();
+
+        value
+    }
+}
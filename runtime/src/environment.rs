@@ -5,8 +5,9 @@ use std::sync::Mutex;
 use std::vec::IntoIter;
 
 use tsr_lexer::util::VecExt;
+use tsr_parser::ast::ArraySize;
 
-use super::value::Value;
+use super::{value::Value, TypeDefinition};
 
 pub type Context = Arc<Mutex<Environment>>;
 pub type Scope = Vec<String>;
@@ -18,9 +19,18 @@ pub struct Variable {
     pub value: Value,
 }
 
+/// A type declared by an alias, interface, class or enum, scoped like variables.
+#[derive(Clone, Debug)]
+pub struct TypeVariable {
+    pub name: String,
+    pub scope: Scope,
+    pub definition: TypeDefinition,
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct Environment {
     store: Vec<Variable>,
+    types: Vec<TypeVariable>,
 }
 
 unsafe impl Send for Environment {}
@@ -32,35 +42,83 @@ impl Environment {
     }
 
     pub fn extend(&mut self, context: Context) {
-        self.store.extend(context.lock().unwrap().store.clone())
+        let context = context.lock().unwrap();
+
+        self.store.extend(context.store.clone());
+        self.types.extend(context.types.clone());
+    }
+
+    /// `scope` and every scope enclosing it, innermost first.
+    fn scopes(scope: Scope) -> Vec<Scope> {
+        let mut scopes = scope
+            .into_iter()
+            .fold::<Vec<Scope>, _>(vec![], |mut acc, e| {
+                let mut arr = acc.last().cloned().unwrap_or_default();
+
+                arr.push(e);
+                acc.push(arr);
+
+                acc
+            });
+
+        scopes.sort_by_key(|scope| Reverse(scope.len()));
+
+        scopes
     }
 
-    fn modify(mut path: Peekable<IntoIter<&str>>, current_value: &mut Value, value: Value) {
+    /// Assigns `value` at `path` inside `current_value`, through the shared storage of
+    /// objects, arrays and instances so every alias sees the change.
+    fn modify(mut path: Peekable<IntoIter<&str>>, current_value: &Value, value: Value) {
         if let Some(current) = path.next() {
             let next = path.peek();
 
             match current_value {
-                Value::Array(_elements, _) => {}
+                Value::Array(elements, size) => {
+                    let Ok(index) = current.parse::<usize>() else {
+                        return;
+                    };
+
+                    if next.is_some() {
+                        let element = elements.read().get(index).cloned();
+
+                        if let Some(element) = element {
+                            Self::modify(path, &element, value)
+                        }
+                    } else {
+                        let mut elements = elements.write();
+
+                        if index < elements.len() {
+                            elements[index] = value;
+                        } else if *size == ArraySize::Dynamic {
+                            // Assigning past the end leaves undefined holes, like in JavaScript
+                            elements.resize(index, Value::None);
+                            elements.push(value);
+                        }
+                    }
+                }
                 Value::Object(properties) => {
                     let current = Value::String(current.into());
 
                     if next.is_some() {
-                        if let Some(property_value) = properties.get_mut(&current) {
-                            Self::modify(path, property_value, value)
+                        let property = properties.read().get(&current).cloned();
+
+                        if let Some(property) = property {
+                            Self::modify(path, &property, value)
                         }
                     } else {
-                        properties.insert(current, value);
+                        properties.write().insert(current, value);
                     }
                 }
-                Value::ClassInstance(instance) => {
-                    match (next.is_some(), instance.get_field_mut(current)) {
-                        (true, Some(field)) => Self::modify(path, &mut field.value, value),
-                        (false, Some(field)) => {
-                            field.value = value;
+                Value::ClassInstance(instance) => match next {
+                    Some(_) => {
+                        if let Some(field) = instance.get_field(current) {
+                            Self::modify(path, &field.value, value)
                         }
-                        _ => {}
                     }
-                }
+                    None => {
+                        instance.set_field(current, value);
+                    }
+                },
                 _ => {}
             }
         }
@@ -91,7 +149,7 @@ impl Environment {
                         .into_iter()
                         .peekable();
 
-                    Self::modify(path, &mut variable.value, value)
+                    Self::modify(path, &variable.value, value)
                 }
             }
         }
@@ -104,44 +162,47 @@ impl Environment {
             .includes(|variable| variable.name == name && variable.scope == scope)
     }
 
+    /// The variable `name` declared in `scope` or the nearest scope enclosing it.
     pub fn get_mut<N: AsRef<str>>(&mut self, name: N, scope: Scope) -> Option<&mut Variable> {
-        let mut scopes = scope
-            .into_iter()
-            .fold::<Vec<Scope>, _>(vec![], |mut acc, e| {
-                let mut arr = acc.last().cloned().unwrap_or_default();
-
-                arr.push(e);
-                acc.push(arr);
-
-                acc
-            });
-
-        scopes.sort_by_key(|scope| Reverse(scope.len()));
-
-        let mut index = None;
+        let name = name.as_ref();
+        let index = Self::scopes(scope).into_iter().find_map(|scope| {
+            self.store
+                .iter()
+                .position(|variable| variable.name == name && variable.scope == scope)
+        })?;
 
-        for scope in scopes {
-            let name = name.as_ref();
+        self.store.get_mut(index)
+    }
 
-            let position = self
-                .store
-                .iter()
-                .position(|variable| variable.name == name && variable.scope == scope);
+    /// The variable `name` declared in `scope` itself.
+    pub fn get_declared_mut<N: AsRef<str>>(
+        &mut self,
+        name: N,
+        scope: &Scope,
+    ) -> Option<&mut Variable> {
+        let name = name.as_ref();
 
-            if position.is_some() {
-                index = position;
-            }
-        }
+        self.store
+            .iter_mut()
+            .find(|variable| variable.name == name && &variable.scope == scope)
+    }
 
-        if let Some(index) = index {
-            self.store.get_mut(index)
-        } else {
-            None
+    /// Declares `name` in `scope`, hiding variables of the same name in the scopes
+    /// enclosing it instead of assigning to them.
+    pub fn declare<N: AsRef<str>>(&mut self, name: N, scope: Scope, value: Value) {
+        match self.get_declared_mut(&name, &scope) {
+            Some(variable) => variable.value = value,
+            None => self.store.push(Variable {
+                name: name.as_ref().into(),
+                scope,
+                value,
+            }),
         }
     }
 
     pub fn remove_by_scope(&mut self, scope: Scope) {
         self.store.retain(|variable| variable.scope != scope);
+        self.types.retain(|ty| ty.scope != scope);
     }
 
     pub fn remove_by_scope_filtered(&mut self, scope: Scope, variables: &[String]) {
@@ -157,20 +218,7 @@ impl Environment {
     }
 
     pub fn get<N: AsRef<str>>(&self, name: N, scope: Scope) -> Option<&Variable> {
-        let mut scopes = scope
-            .into_iter()
-            .fold::<Vec<Scope>, _>(vec![], |mut acc, e| {
-                let mut arr = acc.last().cloned().unwrap_or_default();
-
-                arr.push(e);
-                acc.push(arr);
-
-                acc
-            });
-
-        scopes.sort_by_key(|scope| Reverse(scope.len()));
-
-        for scope in scopes {
+        for scope in Self::scopes(scope) {
             let name = name.as_ref();
 
             if let Some(variable) = self
@@ -184,4 +232,35 @@ impl Environment {
 
         None
     }
+
+    /// Declares the type `name` in `scope`, replacing a type of the same name declared in
+    /// that scope.
+    pub fn set_type<N: AsRef<str>>(&mut self, name: N, scope: Scope, definition: TypeDefinition) {
+        let name = name.as_ref();
+
+        match self
+            .types
+            .iter_mut()
+            .find(|ty| ty.name == name && ty.scope == scope)
+        {
+            Some(ty) => ty.definition = definition,
+            None => self.types.push(TypeVariable {
+                name: name.into(),
+                scope,
+                definition,
+            }),
+        }
+    }
+
+    /// The type `name` declared in `scope` or the nearest scope enclosing it.
+    pub fn get_type<N: AsRef<str>>(&self, name: N, scope: Scope) -> Option<&TypeDefinition> {
+        let name = name.as_ref();
+
+        Self::scopes(scope).into_iter().find_map(|scope| {
+            self.types
+                .iter()
+                .find(|ty| ty.name == name && ty.scope == scope)
+                .map(|ty| &ty.definition)
+        })
+    }
 }
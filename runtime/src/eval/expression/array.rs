@@ -1,7 +1,21 @@
-use tsr_lexer::{globals::Positioned, util::BoolExt};
-use tsr_parser::ast::{ArraySize, Expression};
+use std::cmp::Ordering;
 
-use crate::{value::Value, Runtime};
+use tsr_lexer::{
+    globals::{Positioned, Span},
+    util::BoolExt,
+};
+use tsr_parser::ast::{ArraySize, Block, Expression};
+
+use crate::{
+    value::{heap::Shared, ArrowFunction, ArrowParameter, ErrorCode, Value},
+    Runtime,
+};
+
+/// Built-in methods of arrays, run by [`Runtime::call_array_method`].
+pub(crate) const ARRAY_METHODS: &[&str] = &[
+    "push", "pop", "map", "filter", "reduce", "find", "some", "every", "slice", "splice", "sort",
+    "join", "includes", "indexOf", "forEach", "flat", "flatMap",
+];
 
 impl Runtime {
     pub fn eval_array(
@@ -9,20 +23,456 @@ impl Runtime {
         elements: Vec<Positioned<Expression>>,
         is_dynamic: Positioned<bool>,
     ) -> Value {
-        let elements = elements
-            .into_iter()
-            .map(|expression| match self.eval_expression(expression) {
-                Value::Reference(path, scope) => todo!(),
-                value => value,
-            })
-            .collect::<Vec<_>>();
+        // The length of an array spread into isn't written out, so it isn't fixed
+        let spread = elements
+            .iter()
+            .any(|element| matches!(element.value, Expression::SpreadElement(_)));
+        let elements = match self.eval_elements(elements) {
+            Some(elements) => elements
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>(),
+            None => return self.error.clone().unwrap_or(Value::None),
+        };
         let size = elements.len();
 
-        Value::Array(
+        Value::array(
             elements,
-            is_dynamic
-                .value
-                .map(ArraySize::Dynamic, ArraySize::Fixed(size)),
+            (is_dynamic.value || spread).map(ArraySize::Dynamic, ArraySize::Fixed(size)),
         )
     }
+
+    pub(crate) fn eval_elements(
+        &mut self,
+        elements: Vec<Positioned<Expression>>,
+    ) -> Option<Vec<(Span, Value)>> {
+        let mut values = vec![];
+
+        for expression in elements {
+            let span = expression.span;
+
+            match expression.value {
+                Expression::SpreadElement(iterable) => {
+                    let iterable_span = iterable.span;
+                    let iterable = self.eval_expression(*iterable);
+
+                    let iterable = match self.resolve(iterable_span, iterable) {
+                        error @ Value::Error(..) => return self.fail(error),
+                        iterable => iterable,
+                    };
+
+                    values.extend(
+                        self.spread(span, &iterable)?
+                            .into_iter()
+                            .map(|value| (span, value)),
+                    );
+                }
+                expression => {
+                    let value = self.eval_expression(Positioned::new(expression, span));
+
+                    values.push((span, self.resolve(span, value)));
+                }
+            }
+        }
+
+        Some(values)
+    }
+
+    /// Runs the built-in method `name` of an array. Callbacks get the element, its index and
+    /// the array, which a trailing lambda binds as `value`, `index` and `array`.
+    pub(crate) fn call_array_method(
+        &mut self,
+        span: Span,
+        (elements, size): (Shared<Vec<Value>>, ArraySize),
+        name: &str,
+        mut args: Vec<(Span, Value)>,
+        lambda: Option<Block>,
+    ) -> Value {
+        if let Some(lambda) = lambda {
+            let parameters: &[&str] = match name {
+                "reduce" => &["accumulator", "value", "index", "array"],
+                "sort" => &["first", "second"],
+                _ => &["value", "index", "array"],
+            };
+
+            args.push((lambda.span, lambda_callback(parameters, lambda)));
+        }
+
+        match self.array_method(span, (elements, size), name, &args) {
+            Some(value) => value,
+            None => self.error.clone().unwrap_or(Value::None),
+        }
+    }
+
+    fn array_method(
+        &mut self,
+        span: Span,
+        (elements, size): (Shared<Vec<Value>>, ArraySize),
+        name: &str,
+        args: &[(Span, Value)],
+    ) -> Option<Value> {
+        // Callbacks may change the array, so they run over a copy of its elements
+        let items = elements.read().clone();
+        let length = items.len();
+        let array = Value::Array(elements.clone(), size);
+        let argument = |index: usize| {
+            args.get(index)
+                .map_or(Value::None, |(_, value)| value.clone())
+        };
+
+        if let (ArraySize::Fixed(size), "push" | "pop") = (size, name) {
+            return self.fail(Value::error(
+                span,
+                ErrorCode::Type,
+                format!("\"{name}\" cannot change the length of an array of fixed size {size}"),
+            ));
+        }
+
+        Some(match name {
+            "push" => {
+                let mut elements = elements.write();
+
+                elements.extend(args.iter().map(|(_, value)| value.clone()));
+
+                Value::Number(elements.len() as i64)
+            }
+            "pop" => elements.write().pop().unwrap_or(Value::None),
+            "splice" => {
+                let start = relative_index(self.integer_argument(args, 0, 0)?, length);
+                let count = self
+                    .integer_argument(args, 1, (length - start) as i64)?
+                    .clamp(0, (length - start) as i64) as usize;
+                let inserted = args
+                    .iter()
+                    .skip(2)
+                    .map(|(_, value)| value.clone())
+                    .collect::<Vec<_>>();
+
+                if let ArraySize::Fixed(size) = size {
+                    if inserted.len() != count {
+                        return self.fail(Value::error(
+                            span,
+                            ErrorCode::Type,
+                            format!(
+                                "\"splice\" cannot change the length of an array of fixed size {size}"
+                            ),
+                        ));
+                    }
+                }
+
+                let removed = elements
+                    .write()
+                    .splice(start..start + count, inserted)
+                    .collect();
+
+                Value::array(removed, ArraySize::Dynamic)
+            }
+            "map" => {
+                let mut mapped = vec![];
+
+                for (index, item) in items.into_iter().enumerate() {
+                    mapped.push(self.call_back(
+                        span,
+                        &argument(0),
+                        vec![item, Value::Number(index as i64), array.clone()],
+                    )?);
+                }
+
+                Value::array(mapped, ArraySize::Dynamic)
+            }
+            "filter" => {
+                let mut filtered = vec![];
+
+                for (index, item) in items.into_iter().enumerate() {
+                    if self
+                        .call_back(
+                            span,
+                            &argument(0),
+                            vec![item.clone(), Value::Number(index as i64), array.clone()],
+                        )?
+                        .is_truthy()
+                    {
+                        filtered.push(item);
+                    }
+                }
+
+                Value::array(filtered, ArraySize::Dynamic)
+            }
+            "find" | "some" | "every" => {
+                let mut found = None;
+
+                for (index, item) in items.into_iter().enumerate() {
+                    let matches = self
+                        .call_back(
+                            span,
+                            &argument(0),
+                            vec![item.clone(), Value::Number(index as i64), array.clone()],
+                        )?
+                        .is_truthy();
+
+                    if matches != (name == "every") {
+                        found = Some(item);
+
+                        break;
+                    }
+                }
+
+                match name {
+                    "find" => found.unwrap_or(Value::None),
+                    "some" => Value::Boolean(found.is_some()),
+                    _ => Value::Boolean(found.is_none()),
+                }
+            }
+            "forEach" => {
+                for (index, item) in items.into_iter().enumerate() {
+                    self.call_back(
+                        span,
+                        &argument(0),
+                        vec![item, Value::Number(index as i64), array.clone()],
+                    )?;
+                }
+
+                Value::None
+            }
+            "reduce" => {
+                let mut items = items.into_iter().enumerate();
+                let mut accumulator = match args.len() > 1 {
+                    true => argument(1),
+                    false => match items.next() {
+                        Some((_, item)) => item,
+                        None => {
+                            return self.fail(Value::error(
+                                span,
+                                ErrorCode::Type,
+                                "reduce of empty array with no initial value",
+                            ))
+                        }
+                    },
+                };
+
+                for (index, item) in items {
+                    accumulator = self.call_back(
+                        span,
+                        &argument(0),
+                        vec![
+                            accumulator,
+                            item,
+                            Value::Number(index as i64),
+                            array.clone(),
+                        ],
+                    )?;
+                }
+
+                accumulator
+            }
+            "slice" => {
+                let start = relative_index(self.integer_argument(args, 0, 0)?, length);
+                let end = relative_index(self.integer_argument(args, 1, length as i64)?, length);
+
+                Value::array(items[start..end.max(start)].to_vec(), ArraySize::Dynamic)
+            }
+            "sort" => {
+                let mut items = items;
+                let compare = argument(0);
+                let mut failed = false;
+
+                items.sort_by(|first, second| {
+                    if compare.is_none() {
+                        // Like in JavaScript, elements are compared as strings, undefined last
+                        return match (first, second) {
+                            (Value::None, Value::None) => Ordering::Equal,
+                            (Value::None, _) => Ordering::Greater,
+                            (_, Value::None) => Ordering::Less,
+                            (first, second) => join_element(first).cmp(&join_element(second)),
+                        };
+                    }
+
+                    if failed {
+                        return Ordering::Equal;
+                    }
+
+                    match self.call_back(span, &compare, vec![first.clone(), second.clone()]) {
+                        Some(Value::Number(order)) => order.cmp(&0),
+                        Some(Value::Float(order)) => {
+                            order.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+                        }
+                        Some(_) => Ordering::Equal,
+                        None => {
+                            failed = true;
+
+                            Ordering::Equal
+                        }
+                    }
+                });
+
+                if failed {
+                    return None;
+                }
+
+                *elements.write() = items;
+
+                array
+            }
+            "join" => {
+                let separator = match argument(0) {
+                    Value::None => ",".into(),
+                    separator => join_element(&separator),
+                };
+
+                Value::String(
+                    items
+                        .iter()
+                        .map(join_element)
+                        .collect::<Vec<_>>()
+                        .join(&separator),
+                )
+            }
+            "includes" => Value::Boolean(items.contains(&argument(0))),
+            "indexOf" => {
+                let item = argument(0);
+
+                Value::Number(
+                    items
+                        .iter()
+                        .position(|element| *element == item)
+                        .map_or(-1, |index| index as i64),
+                )
+            }
+            "flat" => {
+                let depth = self.integer_argument(args, 0, 1)?;
+
+                Value::array(flatten(items, depth), ArraySize::Dynamic)
+            }
+            "flatMap" => {
+                let mut mapped = vec![];
+
+                for (index, item) in items.into_iter().enumerate() {
+                    mapped.push(self.call_back(
+                        span,
+                        &argument(0),
+                        vec![item, Value::Number(index as i64), array.clone()],
+                    )?);
+                }
+
+                Value::array(flatten(mapped, 1), ArraySize::Dynamic)
+            }
+            name => {
+                return self.fail(Value::error(
+                    span,
+                    ErrorCode::Implementing,
+                    format!("array method \"{name}\" is not implemented"),
+                ))
+            }
+        })
+    }
+
+    /// Calls a callback given to an array method, errors stop the method.
+    pub(crate) fn call_back(
+        &mut self,
+        span: Span,
+        callback: &Value,
+        arguments: Vec<Value>,
+    ) -> Option<Value> {
+        if !matches!(
+            callback,
+            Value::Function(_) | Value::NativeFunction(_) | Value::ArrowFunction(_)
+        ) {
+            return self.fail(Value::error(
+                span,
+                ErrorCode::Type,
+                format!("{} is not a function", callback.type_of()),
+            ));
+        }
+
+        let arguments = arguments.into_iter().map(|value| (span, value)).collect();
+
+        match self.call_value(span, callback, arguments, &[], None) {
+            error @ Value::Error(..) => self.fail(error),
+            _ if self.error.is_some() => None,
+            value => Some(value),
+        }
+    }
+
+    /// Reads an integer argument, `default` when it is left out.
+    fn integer_argument(
+        &mut self,
+        args: &[(Span, Value)],
+        index: usize,
+        default: i64,
+    ) -> Option<i64> {
+        match args.get(index) {
+            None | Some((_, Value::None)) => Some(default),
+            Some((_, Value::Number(number))) => Some(*number),
+            Some((_, Value::Float(float))) => Some(*float as i64),
+            Some((span, value)) => self.fail(Value::error(
+                *span,
+                ErrorCode::Type,
+                format!("number expected but {} given", value.value_type_of()),
+            )),
+        }
+    }
+
+    pub(crate) fn fail<T>(&mut self, error: Value) -> Option<T> {
+        self.error = Some(error);
+
+        None
+    }
+}
+
+pub(crate) fn lambda_callback(parameters: &[&str], body: Block) -> Value {
+    Value::ArrowFunction(ArrowFunction {
+        is_async: false,
+        parameters: parameters
+            .iter()
+            .map(|name| ArrowParameter {
+                name: name.to_string(),
+                nullable: true,
+                ty: None,
+                default: None,
+            })
+            .collect(),
+        ty: None,
+        body,
+        scope: vec![],
+    })
+}
+
+/// Index counted from the end when negative, clamped to the array or string.
+pub(crate) fn relative_index(index: i64, length: usize) -> usize {
+    match index < 0 {
+        true => (length as i64 + index).max(0) as usize,
+        false => (index as usize).min(length),
+    }
+}
+
+fn flatten(items: Vec<Value>, depth: i64) -> Vec<Value> {
+    let mut flattened = vec![];
+
+    for item in items {
+        match item {
+            Value::Array(elements, _) if depth > 0 => {
+                let elements = elements.read().clone();
+
+                flattened.extend(flatten(elements, depth - 1))
+            }
+            item => flattened.push(item),
+        }
+    }
+
+    flattened
+}
+
+/// Text of an element joined into a string, like JavaScript's `String(value)`.
+fn join_element(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::None | Value::Null => String::new(),
+        Value::Array(elements, _) => elements
+            .read()
+            .iter()
+            .map(join_element)
+            .collect::<Vec<_>>()
+            .join(","),
+        value => value.to_string(),
+    }
 }
@@ -1,10 +1,24 @@
+use std::collections::HashMap;
+
 use tsr_lexer::token::Modifier;
-use tsr_parser::ast::{ArrowFunction, Statement};
+use tsr_parser::ast::{ArrowFunction, Statement, TypeParameter};
 
-use crate::{value::{self, ArrowParameter, Value}, Runtime};
+use crate::{
+    value::{self, ArrowParameter, ParameterDefault, Value},
+    Runtime,
+};
 
 impl Runtime {
     pub fn eval_arrow_function(&mut self, func: ArrowFunction) -> Value {
+        // Arrow functions aren't instantiated, their type parameters stand for their
+        // constraints
+        let type_parameters = func
+            .type_parameters
+            .into_iter()
+            .map(|parameter| parameter.value)
+            .collect::<Vec<_>>();
+        let bindings = TypeParameter::bind(&type_parameters, HashMap::new());
+
         Value::ArrowFunction(value::ArrowFunction {
             is_async: func
                 .modifiers
@@ -16,18 +30,16 @@ impl Runtime {
                 .map(|parameter| ArrowParameter {
                     name: parameter.value.name.value.0,
                     nullable: parameter.value.nullable.value,
-                    ty: parameter.value.ty.map(|ty| ty.value),
-                    default: parameter
-                        .value
-                        .default
-                        .map(|expression| Box::new(self.eval_expression(expression))),
+                    ty: parameter.value.ty.map(|ty| ty.value.substitute(&bindings)),
+                    default: parameter.value.default.map(ParameterDefault::Expression),
                 })
                 .collect(),
-            ty: func.ty.map(|ty| ty.value),
+            ty: func.ty.map(|ty| ty.value.substitute(&bindings)),
             body: func
                 .body
                 .span
                 .wrap(vec![func.body.span.wrap(Statement::Expression(func.body))]),
+            scope: self.scope.clone(),
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{Expression, PredefinedType, TypeAssertion};
+
+use crate::{
+    value::{ErrorCode, Value},
+    Runtime,
+};
+
+impl Runtime {
+    /// `expression as T` converts numbers between `number` and `float`, any other value
+    /// has to already be of type `T`.
+    pub fn eval_as_expression(&mut self, assertion: Positioned<TypeAssertion>) -> Value {
+        let (span, assertion) = assertion.unpack();
+        let ty = assertion.ty.value;
+        let value = match self.eval_assertion_target(assertion.expression) {
+            error @ Value::Error(..) => return error,
+            value => value,
+        };
+
+        let mismatch = match value.check_type(&ty, self) {
+            Ok(()) => return value,
+            Err(mismatch) => mismatch,
+        };
+
+        let converted = match (&value, self.evaluate_type(&ty).as_predefined()) {
+            (Value::Number(number), Some(PredefinedType::Float)) => {
+                Some(Value::Float(*number as f64))
+            }
+            (Value::Float(number), Some(PredefinedType::Number)) if number.fract() == 0.0 => {
+                Some(Value::Number(*number as i64))
+            }
+            _ => None,
+        };
+
+        converted.unwrap_or_else(|| {
+            Value::error(
+                span,
+                ErrorCode::Type,
+                format!(
+                    "cannot convert {} to {}{}",
+                    value.value_type_of(),
+                    ty,
+                    mismatch.note()
+                ),
+            )
+        })
+    }
+
+    pub fn eval_satisfies_expression(&mut self, assertion: Positioned<TypeAssertion>) -> Value {
+        let (span, assertion) = assertion.unpack();
+        let ty = assertion.ty.value;
+        let value = match self.eval_assertion_target(assertion.expression) {
+            error @ Value::Error(..) => return error,
+            value => value,
+        };
+
+        match value.check_type(&ty, self) {
+            Ok(()) => value,
+            Err(mismatch) => Value::error(
+                span,
+                ErrorCode::Type,
+                format!(
+                    "{} expected but {} given{}",
+                    ty,
+                    value.value_type_of(),
+                    mismatch.note()
+                ),
+            ),
+        }
+    }
+
+    pub fn eval_non_null_expression(&mut self, expression: Positioned<Expression>) -> Value {
+        let span = expression.span;
+
+        match self.eval_assertion_target(expression) {
+            Value::Null | Value::None => Value::error(
+                span,
+                ErrorCode::Type,
+                "non-null assertion failed: value is null or undefined",
+            ),
+            value => value,
+        }
+    }
+
+    fn eval_assertion_target(&mut self, expression: Positioned<Expression>) -> Value {
+        let span = expression.span;
+        let value = self.eval_expression(expression);
+
+        self.resolve(span, value)
+    }
+}
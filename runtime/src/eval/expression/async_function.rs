@@ -0,0 +1,136 @@
+use std::{cell::RefCell, rc::Rc};
+
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{Block, Expression};
+
+use crate::{
+    coroutine::{Coroutine, Resume, Suspend},
+    value::{
+        promise::{Outcome, Promise},
+        ErrorCode, Value,
+    },
+    Runtime,
+};
+
+impl Runtime {
+    pub fn eval_await_expression(&mut self, argument: Positioned<Expression>) -> Value {
+        let span = argument.span;
+        let value = self.eval_expression(argument);
+
+        let value = match self.resolve(span, value) {
+            error @ Value::Error(..) => return error,
+            value => value,
+        };
+
+        match self.await_value(span, value) {
+            Some(value) => value,
+            None => self.error.clone().unwrap_or(Value::None),
+        }
+    }
+
+    fn await_value(&mut self, span: Span, value: Value) -> Option<Value> {
+        if let Some(task) = self.task.clone() {
+            return match task.suspend(value) {
+                Resume::Next(value) => Some(*value),
+                Resume::Throw(reason) => {
+                    let error = Value::rejection(span, (*reason).clone());
+
+                    self.rejected = Some((error.clone(), *reason));
+                    self.fail(error)
+                }
+                Resume::Return => {
+                    self.fail(Value::error(span, ErrorCode::Type, "async function closed"))
+                }
+            };
+        }
+
+        // Outside async functions the event loop runs until the promise settles, like it does
+        // for `await` at the top level of a module. Jobs queued before still run first.
+        let promise = Promise::from_value(&self.event_loop, value);
+        let settled = Rc::new(RefCell::new(None));
+
+        Promise::subscribe(&promise, {
+            let settled = settled.clone();
+
+            Box::new(move |_, outcome| *settled.borrow_mut() = Some(outcome))
+        });
+
+        loop {
+            let outcome = settled.borrow_mut().take();
+
+            match outcome {
+                Some(Outcome::Fulfilled(value)) => return Some(value),
+                Some(Outcome::Rejected(reason)) => {
+                    return self.fail(Value::rejection(span, reason))
+                }
+                None if !self.turn_event_loop(None) => {
+                    return self.fail(Value::error(
+                        span,
+                        ErrorCode::Type,
+                        match self.event_loop.is_waiting() {
+                            // Only a clock moved by the host leaves timers waiting
+                            true => "awaited promise waits on timers the clock doesn't advance",
+                            false => "awaited promise can never settle",
+                        },
+                    ));
+                }
+                None => {}
+            }
+        }
+    }
+
+    pub(crate) fn start_async(&mut self, body: Block) -> Value {
+        let promise = Promise::pending(&self.event_loop);
+        let coroutine = Coroutine::spawn(self.fork(), {
+            let promise = promise.clone();
+
+            move |runtime, suspender| {
+                runtime.task = Some(suspender);
+
+                let outcome = match runtime.eval_coroutine_body(body) {
+                    // Failing with the error of a rejected `await` passes its reason on as is
+                    error @ Value::Error(..) => match runtime.rejected.take() {
+                        Some((rejected, reason)) if rejected == error => Outcome::Rejected(reason),
+                        _ => Outcome::Rejected(error),
+                    },
+                    value => Outcome::Fulfilled(value),
+                };
+
+                Promise::settle(&promise, outcome);
+
+                Value::None
+            }
+        });
+
+        self.drive(
+            Rc::new(RefCell::new(coroutine)),
+            Resume::Next(Box::new(Value::None)),
+        );
+
+        Value::Promise(promise)
+    }
+
+    fn drive(&mut self, task: Rc<RefCell<Coroutine>>, resume: Resume) {
+        let suspend = task.borrow_mut().resume(resume);
+
+        match suspend {
+            Suspend::Yield(awaited) => {
+                let awaited = Promise::from_value(&self.event_loop, awaited);
+
+                Promise::subscribe(
+                    &awaited,
+                    Box::new(move |runtime, outcome| {
+                        let resume = match outcome {
+                            Outcome::Fulfilled(value) => Resume::Next(Box::new(value)),
+                            Outcome::Rejected(reason) => Resume::Throw(Box::new(reason)),
+                        };
+
+                        runtime.drive(task, resume);
+                    }),
+                );
+            }
+            // The body settled its promise before completing
+            Suspend::Complete(_) => {}
+        }
+    }
+}
@@ -1,108 +1,273 @@
-use tsr_lexer::token::Operator;
-use tsr_parser::ast::BinaryExpression;
+use std::cmp::Ordering;
 
-use crate::{value::Value, Runtime};
+use tsr_lexer::{globals::Span, token::Operator};
+use tsr_parser::ast::{ArraySize, BinaryExpression, Expression};
+
+use crate::{
+    environment::Scope,
+    value::{ErrorCode, Value},
+    Runtime,
+};
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(first), Value::Number(second)) => first.partial_cmp(second),
+        (Value::Float(first), Value::Float(second)) => first.partial_cmp(second),
+        (Value::Number(first), Value::Float(second)) => (*first as f64).partial_cmp(second),
+        (Value::Float(first), Value::Number(second)) => first.partial_cmp(&(*second as f64)),
+        (Value::String(first), Value::String(second)) => first.partial_cmp(second),
+        (_, _) => None,
+    }
+}
+
+/// Operands of arithmetic done in floats, when either of them is a float.
+fn floats(left: &Value, right: &Value) -> Option<(f64, f64)> {
+    match (left, right) {
+        (Value::Float(first), Value::Float(second)) => Some((*first, *second)),
+        (Value::Number(first), Value::Float(second)) => Some((*first as f64, *second)),
+        (Value::Float(first), Value::Number(second)) => Some((*first, *second as f64)),
+        (_, _) => None,
+    }
+}
 
 impl Runtime {
     pub fn eval_binary_expression(&mut self, expression: BinaryExpression) -> Value {
-        let left = self.eval_expression(expression.left);
+        let operator = expression.operator.value;
+        let left_span = expression.left.span;
+        let left = match expression.left.value {
+            Expression::IndexExpression(index) if operator == Operator::Eq => {
+                let (target_span, target, key) = self.eval_index_operands(index.value);
+
+                match self.index_reference(target_span, target, key) {
+                    Ok(reference) => reference,
+                    // Properties a reference can't lead to are set on the storage of the target
+                    Err(operands) => {
+                        let (target, key) = *operands;
+                        let right_span = expression.right.span;
+                        let value = self.eval_expression(expression.right);
+
+                        return match self.resolve(right_span, value) {
+                            error @ Value::Error(..) => error,
+                            value => self.set_property(left_span, target, key, value),
+                        };
+                    }
+                }
+            }
+            left => self.eval_expression(left_span.wrap(left)),
+        };
+
+        match operator {
+            Operator::AndAnd | Operator::OrOr => {
+                let left = self.resolve(left_span, left);
+
+                if matches!(left, Value::Error(..))
+                    || left.is_truthy() != (operator == Operator::AndAnd)
+                {
+                    return left;
+                }
+
+                let right_span = expression.right.span;
+                let right = self.eval_expression(expression.right);
+
+                return self.resolve(right_span, right);
+            }
+            Operator::Not => {
+                return match self.resolve(left_span, left) {
+                    error @ Value::Error(..) => error,
+                    value => Value::Boolean(!value.is_truthy()),
+                }
+            }
+            Operator::TypeOf => {
+                return match self.resolve(left_span, left) {
+                    error @ Value::Error(..) => error,
+                    value => Value::String(value.type_of_operator().into()),
+                }
+            }
+            _ => {}
+        }
+
+        let right_span = expression.right.span;
         let right = self.eval_expression(expression.right);
 
-        println!("{left} {:?} {right}", expression.operator.value);
+        match operator {
+            Operator::PlusPlus => {
+                return match left {
+                    Value::Reference(path, scope) => {
+                        let mut context = self.context.lock().unwrap();
+
+                        if let Some(Value::Number(value)) =
+                            context.get(&path[0], scope.clone()).map(|var| &var.value)
+                        {
+                            let value = Value::Number(value + 1);
+
+                            context.set(&path, scope, value);
+                        }
+
+                        Value::None
+                    }
+                    _ => todo!(),
+                }
+            }
+            Operator::MinusMinus => {
+                return match left {
+                    Value::Reference(path, scope) => {
+                        let mut context = self.context.lock().unwrap();
+
+                        if let Some(Value::Number(value)) =
+                            context.get(&path[0], scope.clone()).map(|var| &var.value)
+                        {
+                            let value = Value::Number(value - 1);
+
+                            context.set(&path, scope, value);
+                        }
+
+                        Value::None
+                    }
+                    _ => todo!(),
+                }
+            }
+            Operator::Eq => {
+                return match (left, right) {
+                    (Value::Reference(path, scope), value) => {
+                        let value = self.resolve(right_span, value);
+
+                        if let Some(error) = self.check_array_bounds(left_span, &path, &scope) {
+                            self.error = Some(error.clone());
+
+                            return error;
+                        }
+
+                        self.context.lock().unwrap().set(&path, scope, value);
+
+                        Value::None
+                    }
+                    (_, _) => {
+                        let error = Value::error(
+                            left_span,
+                            ErrorCode::Reference,
+                            "invalid left-hand side in assignment",
+                        );
 
-        match expression.operator.value {
+                        self.error = Some(error.clone());
+
+                        error
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let left = self.resolve(left_span, left);
+        let right = self.resolve(right_span, right);
+
+        for value in [&left, &right] {
+            if let Value::Error(..) = value {
+                return value.clone();
+            }
+        }
+
+        match operator {
             Operator::And => todo!(),
-            Operator::AndAnd => todo!(),
             Operator::Plus => match (left, right) {
                 (Value::String(first), Value::String(second)) => Value::String(first + &second),
                 (Value::Number(first), Value::Number(second)) => Value::Number(first + second),
-                (_, _) => todo!(),
+                (first, second) => match floats(&first, &second) {
+                    Some((first, second)) => Value::Float(first + second),
+                    None => todo!(),
+                },
             },
             Operator::Star => match (left, right) {
                 (Value::String(data), Value::Number(times)) => {
                     Value::String(data.repeat(times as usize))
                 }
                 (Value::Number(first), Value::Number(second)) => Value::Number(first * second),
-                (_, _) => todo!(),
+                (first, second) => match floats(&first, &second) {
+                    Some((first, second)) => Value::Float(first * second),
+                    None => todo!(),
+                },
             },
             Operator::Slash => match (left, right) {
                 (Value::Number(first), Value::Number(second)) => Value::Number(first / second),
-                (_, _) => todo!(),
+                (first, second) => match floats(&first, &second) {
+                    Some((first, second)) => Value::Float(first / second),
+                    None => todo!(),
+                },
             },
             Operator::Or => todo!(),
-            Operator::OrOr => todo!(),
-            Operator::PlusPlus => match left {
-                Value::Reference(path, scope) => {
-                    let mut context = self.context.lock().unwrap();
-
-                    if let Some(Value::Number(value)) =
-                        context.get(&path[0], scope.clone()).map(|var| &var.value)
-                    {
-                        let value = Value::Number(value + 1);
-
-                        context.set(&path, scope, value);
-                    }
-
-                    Value::None
-                }
-                _ => todo!(),
-            },
             Operator::Minus => match (left, right) {
                 (Value::Number(first), Value::Number(second)) => Value::Number(first - second),
-                (_, _) => todo!(),
+                (first, second) => match floats(&first, &second) {
+                    Some((first, second)) => Value::Float(first - second),
+                    None => todo!(),
+                },
             },
-            Operator::MinusMinus => match left {
-                Value::Reference(path, scope) => {
-                    let mut context = self.context.lock().unwrap();
-
-                    if let Some(Value::Number(value)) =
-                        context.get(&path[0], scope.clone()).map(|var| &var.value)
-                    {
-                        let value = Value::Number(value - 1);
-
-                        context.set(&path, scope, value);
-                    }
-
-                    Value::None
+            Operator::EqEq | Operator::EqEqEq => Value::Boolean(left == right),
+            Operator::Ne | Operator::NeEq => Value::Boolean(left != right),
+            Operator::Le => Value::Boolean(compare(&left, &right).is_some_and(Ordering::is_le)),
+            Operator::Ge => Value::Boolean(compare(&left, &right).is_some_and(Ordering::is_ge)),
+            Operator::Lt => Value::Boolean(compare(&left, &right).is_some_and(Ordering::is_lt)),
+            Operator::Gt => Value::Boolean(compare(&left, &right).is_some_and(Ordering::is_gt)),
+            Operator::InstanceOf => match (left, right) {
+                (Value::ClassInstance(instance), Value::Class { name, .. }) => {
+                    Value::Boolean(self.extends_class(&instance.name, &name))
                 }
-                _ => todo!(),
+                (_, _) => Value::Boolean(false),
             },
-            Operator::EqEq => Value::Boolean(left == right),
-            Operator::Eq => match (left, right) {
-                (Value::Reference(path, scope), value) => {
-                    let value = match value {
-                        Value::Reference(path, scope) => todo!(),
-                        value => value,
-                    };
-
-                    self.context.lock().unwrap().set(&path, scope, value);
-
-                    Value::None
+            Operator::In => match (left, right) {
+                (Value::String(key), Value::Object(properties)) => {
+                    Value::Boolean(properties.read().contains_key(&Value::String(key)))
                 }
-                (_, _) => todo!(),
+                (Value::String(key), Value::ClassInstance(instance)) => Value::Boolean(
+                    instance.get_field(&key).is_some()
+                        || self.get_class_method(&instance.name, &key).is_some(),
+                ),
+                (_, _) => Value::Boolean(false),
             },
-            Operator::Ne => Value::Boolean(left != right),
-            Operator::Le => todo!(),
-            Operator::Ge => todo!(),
-            Operator::Lt => todo!(),
-            Operator::Gt => todo!(),
-            Operator::Not => match left {
-                Value::Reference(path, scope) => {
-                    if let Some(Value::Boolean(value)) = self
-                        .context
-                        .lock()
-                        .unwrap()
-                        .get(&path[0], scope.clone())
-                        .map(|var| &var.value)
-                    {
-                        return Value::Boolean(!value);
-                    }
+            Operator::AndAnd
+            | Operator::OrOr
+            | Operator::Not
+            | Operator::TypeOf
+            | Operator::PlusPlus
+            | Operator::MinusMinus
+            | Operator::Eq => unreachable!(),
+        }
+    }
 
-                    Value::None
-                }
-                Value::Boolean(value) => Value::Boolean(!value),
-                _ => todo!(),
+    /// Walks the `extends` chain of `class` looking for `target`.
+    fn extends_class(&self, class: &str, target: &str) -> bool {
+        if class == target {
+            return true;
+        }
+
+        let extends = match self.context.lock().unwrap().get(class, self.scope.clone()) {
+            Some(variable) => match &variable.value {
+                Value::Class { extends, .. } => extends.clone(),
+                _ => vec![],
             },
+            None => vec![],
+        };
+
+        extends
+            .iter()
+            .any(|parent| self.extends_class(parent, target))
+    }
+
+    /// Error for assigning past the end of a fixed-size array, which can't grow.
+    fn check_array_bounds(&self, span: Span, path: &[String], scope: &Scope) -> Option<Value> {
+        let (last, parent) = path.split_last()?;
+        let index = last.parse::<usize>().ok()?;
+
+        if parent.is_empty() {
+            return None;
+        }
+
+        match self.resolve(span, Value::Reference(parent.to_vec(), scope.clone())) {
+            Value::Array(_, ArraySize::Fixed(size)) if index >= size => Some(Value::error(
+                span,
+                ErrorCode::Reference,
+                format!("index {index} is out of bounds of an array of fixed size {size}"),
+            )),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+}
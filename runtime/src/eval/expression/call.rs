@@ -1,40 +1,91 @@
-use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::{FunctionCallExpression, Type};
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{ArraySize, Block, Expression, FunctionCallExpression, Type};
 
+use super::{
+    array::ARRAY_METHODS, collection::COLLECTION_METHODS, iterator::ITERATOR_METHODS,
+    promise::PROMISE_METHODS,
+};
 use crate::{
-    value::{self, ArrowParameter, ErrorCode, NativeFunction, Value}, FArguments, Runtime
+    coroutine,
+    value::{self, ArrowParameter, ErrorCode, NativeFunction, ParameterDefault, Value},
+    FArguments, Runtime,
 };
 
 impl Runtime {
     pub fn eval_call(&mut self, call: Positioned<FunctionCallExpression>) -> Value {
         let (span, call) = call.unpack();
-        let func = match self.eval_expression(*call.function) {
-            Value::Reference(path, scope) => match todo!() {
-                Value::Reference(path, scope) => self
-                    .context
-                    .lock()
-                    .unwrap()
-                    .get(&path[0], scope.clone())
-                    .unwrap()
-                    .value
-                    .clone(),
-                value => value,
-            },
-            value => value,
+        let function_span = call.function.span;
+        let mut method = None;
+        let func = match call.function.value {
+            // Methods of arrays and collections call back into the program, so they are run
+            // by the runtime rather than looked up as values
+            Expression::IndexExpression(expression) => {
+                let (target_span, target, index) = self.eval_index_operands(expression.value);
+
+                match (self.resolve(target_span, target), index) {
+                    (target @ Value::Array(..), Value::String(name))
+                        if ARRAY_METHODS.contains(&name.as_str()) =>
+                    {
+                        method = Some((target, name));
+
+                        Value::None
+                    }
+                    (
+                        target @ (Value::Map(_) | Value::Set(_) | Value::WeakMap(_)),
+                        Value::String(name),
+                    ) if COLLECTION_METHODS.contains(&name.as_str()) => {
+                        method = Some((target, name));
+
+                        Value::None
+                    }
+                    (target @ Value::Iterator(_), Value::String(name))
+                        if ITERATOR_METHODS.contains(&name.as_str()) =>
+                    {
+                        method = Some((target, name));
+
+                        Value::None
+                    }
+                    (target @ Value::Promise(_), Value::String(name))
+                        if PROMISE_METHODS.contains(&name.as_str()) =>
+                    {
+                        method = Some((target, name));
+
+                        Value::None
+                    }
+                    (target, index) => self.get_property(target_span, target, index),
+                }
+            }
+            function => {
+                let func = self.eval_expression(Positioned::new(function, function_span));
+
+                self.resolve(function_span, func)
+            }
+        };
+        let Some(mut args) = self.eval_elements(call.arguments) else {
+            return self.error.clone().unwrap_or(Value::None);
         };
-        let mut args = call
-            .arguments
-            .into_iter()
-            .map(|argument| {
-                (
-                    argument.span,
-                    match self.eval_expression(argument) {
-                        Value::Reference(path, scope) => todo!(),
-                        value => value,
-                    },
-                )
-            })
-            .collect::<Vec<_>>();
+
+        if let Some((target, name)) = method {
+            if let Some((_, error @ Value::Error(..))) = args
+                .iter()
+                .find(|(_, value)| matches!(value, Value::Error(..)))
+            {
+                return error.clone();
+            }
+
+            return match target {
+                Value::Array(elements, size) => {
+                    self.call_array_method(span, (elements, size), &name, args, call.lambda)
+                }
+                Value::Iterator(iteration) => {
+                    self.call_iterator_method(span, iteration, &name, args)
+                }
+                Value::Promise(promise) => {
+                    self.call_promise_method(span, promise, &name, args, call.lambda)
+                }
+                target => self.call_collection_method(span, target, &name, args, call.lambda),
+            };
+        }
 
         if let Some(lambda) = call.lambda.as_ref() {
             args.push((lambda.span, Value::None));
@@ -44,17 +95,63 @@ impl Runtime {
             return func.clone();
         }
 
-        match &func {
+        let type_arguments = call
+            .type_arguments
+            .into_iter()
+            .map(|ty| ty.value)
+            .collect::<Vec<_>>();
+
+        self.call_value(span, &func, args, &type_arguments, call.lambda)
+    }
+
+    /// Calls a function value with evaluated arguments, a trailing lambda fills the first
+    /// function-typed parameter left without an argument.
+    pub(crate) fn call_value(
+        &mut self,
+        span: Span,
+        func: &Value,
+        args: Vec<(Span, Value)>,
+        type_arguments: &[Type],
+        lambda: Option<Block>,
+    ) -> Value {
+        // A `yield` or an `await` belongs to the function it is written in, not to the
+        // functions it calls
+        let coroutine = self.coroutine.take();
+        let task = self.task.take();
+        let caller = match func {
+            Value::Function(function) => self.enter_scope(&function.scope),
+            Value::ArrowFunction(function) => self.enter_scope(&function.scope),
+            _ => None,
+        };
+        let value = self.call_function(span, func, args, type_arguments, lambda);
+
+        self.leave_scope(caller);
+        self.coroutine = coroutine;
+        self.task = task;
+
+        value
+    }
+
+    fn call_function(
+        &mut self,
+        span: Span,
+        func: &Value,
+        args: Vec<(Span, Value)>,
+        type_arguments: &[Type],
+        lambda: Option<Block>,
+    ) -> Value {
+        match func {
             Value::NativeFunction(NativeFunction {
                 name,
                 parameters,
+                rest,
                 body,
                 ty,
                 ..
             }) => {
                 let arg_count = parameters
                     .iter()
-                    .filter(|parameter| parameter.default.is_none())
+                    .filter(|parameter| parameter.default.is_none() && !parameter.nullable)
                     .count();
 
                 if args.len() < arg_count {
@@ -69,12 +166,21 @@ impl Runtime {
                     );
                 }
 
-                let zipped = parameters.iter().zip(args);
+                let mut args = args.into_iter();
 
                 self.add_scope(format!("func:{}", name));
 
-                for (argument, (span, value)) in zipped {
-                    if value.is_none() && call.lambda.is_some() {
+                for argument in parameters {
+                    let (span, value) = self.argument(span, &mut args, argument.default.as_ref());
+
+                    if let Value::Error(..) = value {
+                        self.clear_scope_variables();
+                        self.remove_scope();
+
+                        return value;
+                    }
+
+                    if let (true, Some(lambda)) = (value.is_none(), lambda.clone()) {
                         if let Type::FunctionType(_, params, ty) = argument.ty.clone() {
                             let value = span.wrap(Value::ArrowFunction(value::ArrowFunction {
                                 is_async: false,
@@ -84,13 +190,12 @@ impl Runtime {
                                         name: param.name.value.0,
                                         nullable: param.nullable.value,
                                         ty: Some(param.ty.value),
-                                        default: param
-                                            .default
-                                            .map(|default| Box::new(self.eval_expression(default))),
+                                        default: param.default.map(ParameterDefault::Expression),
                                     })
                                     .collect(),
                                 ty: Some(*ty),
-                                body: call.lambda.unwrap(),
+                                body: lambda,
+                                scope: vec![],
                             }));
 
                             self.set_variable(&argument.name, value);
@@ -98,14 +203,18 @@ impl Runtime {
                             break;
                         }
                     } else {
-                        if !value.is_type_of(&argument.ty) {
+                        if let (false, Err(mismatch)) = (
+                            argument.nullable && value.is_none(),
+                            value.check_type(&argument.ty, self),
+                        ) {
                             return Value::error(
                                 span,
                                 ErrorCode::Type,
                                 format!(
-                                    "{} expected but {} given",
+                                    "{} expected but {} given{}",
                                     argument.ty,
-                                    value.value_type_of()
+                                    value.value_type_of(),
+                                    mismatch.note()
                                 ),
                             );
                         }
@@ -114,10 +223,40 @@ impl Runtime {
                     }
                 }
 
+                if let Some(rest) = rest {
+                    let mut values = vec![];
+
+                    for (span, value) in args {
+                        if let Err(mismatch) = value.check_type(&rest.ty, self) {
+                            self.clear_scope_variables();
+                            self.remove_scope();
+
+                            return Value::error(
+                                span,
+                                ErrorCode::Type,
+                                format!(
+                                    "{} expected but {} given{}",
+                                    rest.ty,
+                                    value.value_type_of(),
+                                    mismatch.note()
+                                ),
+                            );
+                        }
+
+                        values.push(value);
+                    }
+
+                    self.set_variable(
+                        &rest.name,
+                        span.wrap(Value::array(values, ArraySize::Dynamic)),
+                    );
+                }
+
                 let mut args = FArguments {
                     context: self.get_context(),
                     scope: self.scope.clone(),
                     returns: None,
+                    event_loop: self.event_loop.clone(),
                 };
 
                 (body)(&mut args);
@@ -126,12 +265,17 @@ impl Runtime {
                 self.remove_scope();
 
                 if let Some(value) = args.returns {
-                    if value.is_type_of(&ty) {
+                    // Native functions fail by returning an error, reported at the call
+                    if let Value::Error(_, code, message) = value {
+                        return Value::Error(span, code, message);
+                    }
+
+                    if value.is_type_of(ty, self) {
                         return value;
                     }
                 }
             }
-            Value::Function(func) => match func.call(span, self, args, call.lambda) {
+            Value::Function(func) => match func.call(span, self, args, type_arguments, lambda) {
                 Value::Error(span, code, message) => {
                     self.error = Some(Value::Error(span, code, message))
                 }
@@ -141,7 +285,7 @@ impl Runtime {
                 let arg_count = func
                     .parameters
                     .iter()
-                    .filter(|parameter| parameter.default.is_none())
+                    .filter(|parameter| parameter.default.is_none() && !parameter.nullable)
                     .count();
 
                 if args.len() < arg_count {
@@ -156,19 +300,37 @@ impl Runtime {
                     );
                 }
 
-                let zipped = func.parameters.iter().zip(args);
+                let mut args = args.into_iter();
+
+                // The variables of an async function outlive the call, so they get a scope of
+                // their own
+                self.add_scope(match func.is_async {
+                    true => coroutine::scope("async", "closure"),
+                    false => "closure".into(),
+                });
 
-                self.add_scope("closure");
+                for argument in &func.parameters {
+                    let (span, value) = self.argument(span, &mut args, argument.default.as_ref());
 
-                for (argument, (span, value)) in zipped {
-                    if argument.ty.as_ref().is_some_and(|ty| !value.is_type_of(ty)) {
+                    if let Value::Error(..) = value {
+                        self.clear_scope_variables();
+                        self.remove_scope();
+
+                        return value;
+                    }
+
+                    if let (false, Some(Err(mismatch))) = (
+                        argument.nullable && value.is_none(),
+                        argument.ty.as_ref().map(|ty| value.check_type(ty, self)),
+                    ) {
                         return Value::error(
                             span,
                             ErrorCode::Type,
                             format!(
-                                "{} expected but {} given",
+                                "{} expected but {} given{}",
                                 argument.ty.as_ref().unwrap(),
-                                value.value_type_of()
+                                value.value_type_of(),
+                                mismatch.note()
                             ),
                         );
                     }
@@ -176,12 +338,27 @@ impl Runtime {
                     self.set_variable(&argument.name, span.wrap(value));
                 }
 
+                if func.is_async {
+                    let promise = self.start_async(func.body.clone());
+
+                    self.remove_scope();
+
+                    return promise;
+                }
+
                 let value = self.eval_code_block(func.body.clone());
+                let value = self.returned(value);
+                let value = self.resolve(span, value);
 
                 self.clear_scope_variables();
                 self.remove_scope();
 
-                if func.ty.is_none() || func.ty.as_ref().is_some_and(|ty| value.is_type_of(ty)) {
+                if func.ty.is_none()
+                    || func
+                        .ty
+                        .as_ref()
+                        .is_some_and(|ty| value.is_type_of(ty, self))
+                {
                     return value;
                 }
             }
@@ -190,4 +367,29 @@ impl Runtime {
 
         Value::None
     }
+
+    /// The next argument of a call, or the value of a parameter the call leaves out: its
+    /// default, evaluated in the callee scope so it sees the parameters bound before it, and
+    /// `undefined` for optional parameters.
+    pub(crate) fn argument(
+        &mut self,
+        span: Span,
+        args: &mut impl Iterator<Item = (Span, Value)>,
+        default: Option<&ParameterDefault>,
+    ) -> (Span, Value) {
+        if let Some(argument) = args.next() {
+            return argument;
+        }
+
+        match default {
+            Some(ParameterDefault::Value(value)) => (span, value.clone()),
+            Some(ParameterDefault::Expression(expression)) => {
+                let span = expression.span;
+                let value = self.eval_expression(expression.clone());
+
+                (span, self.resolve(span, value))
+            }
+            None => (span, Value::None),
+        }
+    }
 }
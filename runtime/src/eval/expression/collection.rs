@@ -0,0 +1,137 @@
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{ArraySize, Block};
+
+use super::array::lambda_callback;
+use crate::{
+    value::{ErrorCode, Value},
+    Runtime,
+};
+
+pub(crate) const COLLECTION_METHODS: &[&str] = &[
+    "get", "set", "add", "has", "delete", "clear", "forEach", "keys", "values", "entries",
+];
+
+impl Runtime {
+    pub(crate) fn call_collection_method(
+        &mut self,
+        span: Span,
+        collection: Value,
+        name: &str,
+        mut args: Vec<(Span, Value)>,
+        lambda: Option<Block>,
+    ) -> Value {
+        if let Some(lambda) = lambda {
+            args.push((
+                lambda.span,
+                lambda_callback(&["value", "key", "collection"], lambda),
+            ));
+        }
+
+        match self.collection_method(span, &collection, name, &args) {
+            Some(value) => value,
+            None => self.error.clone().unwrap_or(Value::None),
+        }
+    }
+
+    fn collection_method(
+        &mut self,
+        span: Span,
+        collection: &Value,
+        name: &str,
+        args: &[(Span, Value)],
+    ) -> Option<Value> {
+        let argument = |index: usize| {
+            args.get(index)
+                .map_or(Value::None, |(_, value)| value.clone())
+        };
+        let (key, value) = (argument(0), argument(1));
+
+        Some(match (collection, name) {
+            (Value::Map(entries), "get") => {
+                entries.read().get(&key).cloned().unwrap_or(Value::None)
+            }
+            (Value::Map(entries), "set") => {
+                entries.write().insert(key, value);
+
+                collection.clone()
+            }
+            (Value::Set(entries), "add") => {
+                entries.write().insert(key, Value::None);
+
+                collection.clone()
+            }
+            (Value::Map(entries) | Value::Set(entries), "has") => {
+                Value::Boolean(entries.read().has(&key))
+            }
+            (Value::Map(entries) | Value::Set(entries), "delete") => {
+                Value::Boolean(entries.write().remove(&key))
+            }
+            (Value::Map(entries) | Value::Set(entries), "clear") => {
+                entries.write().clear();
+
+                Value::None
+            }
+            (Value::Map(entries) | Value::Set(entries), "forEach") => {
+                let set = matches!(collection, Value::Set(_));
+                let callback = argument(0);
+                // Callbacks may change the collection, so they run over a copy of its entries
+                let items = entries.read().iter().cloned().collect::<Vec<_>>();
+
+                for (key, value) in items {
+                    let value = if set { key.clone() } else { value };
+
+                    self.call_back(span, &callback, vec![value, key, collection.clone()])?;
+                }
+
+                Value::None
+            }
+            (Value::Map(entries) | Value::Set(entries), "keys" | "values" | "entries") => {
+                let set = matches!(collection, Value::Set(_));
+                // The iterator goes over the entries as they were when it was made
+                let elements = entries
+                    .read()
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = if set { key } else { value };
+
+                        match name {
+                            "keys" => key.clone(),
+                            "values" => value.clone(),
+                            _ => {
+                                Value::array(vec![key.clone(), value.clone()], ArraySize::Fixed(2))
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                Value::iterator(elements)
+            }
+            (Value::WeakMap(entries), "get") => {
+                entries.read().get(&key).cloned().unwrap_or(Value::None)
+            }
+            (Value::WeakMap(entries), "set") => {
+                if !entries.write().insert(&key, value) {
+                    return self.fail(Value::error(
+                        span,
+                        ErrorCode::Type,
+                        format!("invalid value used as weak map key: {}", key.type_of()),
+                    ));
+                }
+
+                collection.clone()
+            }
+            (Value::WeakMap(entries), "has") => Value::Boolean(entries.read().has(&key)),
+            (Value::WeakMap(entries), "delete") => Value::Boolean(entries.write().remove(&key)),
+            (collection, name) => {
+                return self.fail(Value::error(
+                    span,
+                    ErrorCode::Reference,
+                    format!(
+                        "property \"{name}\" does not exist on {}",
+                        collection.type_of()
+                    ),
+                ))
+            }
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{Block, YieldExpression};
+
+use crate::{
+    coroutine::{Coroutine, Resume},
+    value::{
+        heap::Shared,
+        iterator::{Iteration, Step},
+        ErrorCode, Value,
+    },
+    Runtime,
+};
+
+impl Runtime {
+    pub fn eval_yield_expression(&mut self, expression: Positioned<YieldExpression>) -> Value {
+        let (span, expression) = expression.unpack();
+
+        match self.yield_value(span, expression) {
+            Some(value) => value,
+            None => self.error.clone().unwrap_or(Value::None),
+        }
+    }
+
+    fn yield_value(&mut self, span: Span, expression: YieldExpression) -> Option<Value> {
+        if self.coroutine.is_none() {
+            return self.fail(Value::error(
+                span,
+                ErrorCode::Type,
+                "yield is only valid in generator functions",
+            ));
+        }
+
+        let value = match expression.argument {
+            Some(argument) => {
+                let argument_span = argument.span;
+                let value = self.eval_expression(argument);
+
+                match self.resolve(argument_span, value) {
+                    error @ Value::Error(..) => return self.fail(error),
+                    value => value,
+                }
+            }
+            None => Value::None,
+        };
+
+        if !expression.delegate {
+            return self.suspend(span, value);
+        }
+
+        // `yield*` passes every value of the inner iterator on, its result is what the inner
+        // iterator completes with
+        let iterator = self.get_iterator(span, &value)?;
+        let mut sent = Value::None;
+
+        loop {
+            match self.step(span, &iterator, sent)? {
+                Step::Next(value) => match self.suspend(span, value) {
+                    Some(value) => sent = value,
+                    None => {
+                        self.close_iterator(&iterator);
+
+                        return None;
+                    }
+                },
+                Step::Done(value) => return Some(value),
+            }
+        }
+    }
+
+    fn suspend(&mut self, span: Span, value: Value) -> Option<Value> {
+        let coroutine = self.coroutine.clone()?;
+
+        match coroutine.suspend(value) {
+            Resume::Next(value) => Some(*value),
+            Resume::Throw(reason) => self.fail(Value::rejection(span, *reason)),
+            Resume::Return => self.fail(Value::error(span, ErrorCode::Type, "generator closed")),
+        }
+    }
+
+    pub(crate) fn start_generator(&mut self, body: Block) -> Value {
+        let coroutine = Coroutine::spawn(self.fork(), move |runtime, suspender| {
+            runtime.coroutine = Some(suspender);
+
+            runtime.eval_coroutine_body(body)
+        });
+
+        Value::Iterator(Shared::new(Iteration::Generator(coroutine)))
+    }
+}
@@ -0,0 +1,38 @@
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::Expression;
+
+use crate::{
+    value::{
+        promise::{Outcome, Promise},
+        ErrorCode, Value,
+    },
+    Runtime,
+};
+
+impl Runtime {
+    /// `import(specifier)`, a promise of the module namespace.
+    pub fn eval_import_expression(
+        &mut self,
+        span: Span,
+        specifier: Positioned<Expression>,
+    ) -> Value {
+        let specifier_span = specifier.span;
+        let specifier = self.eval_expression(specifier);
+
+        let outcome = match self.resolve(specifier_span, specifier) {
+            Value::String(specifier) => match self.import_module(span, &specifier) {
+                Some(module) => Outcome::Fulfilled(module.namespace()),
+                // A failed import rejects the promise, not the whole program
+                None => Outcome::Rejected(self.error.take().unwrap_or(Value::None)),
+            },
+            error @ Value::Error(..) => return error,
+            value => Outcome::Rejected(Value::error(
+                specifier_span,
+                ErrorCode::Type,
+                format!("string expected but {} given", value.value_type_of()),
+            )),
+        };
+
+        Value::Promise(Promise::settled(&self.event_loop, outcome))
+    }
+}
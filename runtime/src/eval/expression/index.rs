@@ -1,9 +1,93 @@
-use tsr_parser::ast::{ArraySize, IndexExpression, PredefinedType, PrimaryType};
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{IndexExpression, PredefinedType};
 
-use crate::{value::{ErrorCode, Value}, FunctionBuilder, Runtime};
+use super::string::{string_index, string_member};
+use crate::{
+    value::{symbol::Symbol, ErrorCode, Value},
+    FunctionBuilder, Runtime,
+};
 
 impl Runtime {
     pub fn eval_index_expression(&mut self, expression: IndexExpression) -> Value {
+        let (target_span, target, index) = self.eval_index_operands(expression);
+
+        match self.index_reference(target_span, target, index) {
+            Ok(reference) => reference,
+            Err(operands) => {
+                let (target, index) = *operands;
+
+                self.get_property(target_span, target, index)
+            }
+        }
+    }
+
+    pub(crate) fn index_reference(
+        &self,
+        target_span: Span,
+        target: Value,
+        index: Value,
+    ) -> Result<Value, Box<(Value, Value)>> {
+        match (target, index) {
+            (Value::Reference(path, scope), Value::String(key)) => {
+                Ok(Value::Reference([path, vec![key]].concat(), scope))
+            }
+            // Elements of arrays are referenced too, so that they can be assigned
+            (Value::Reference(path, scope), Value::Number(index))
+                if index >= 0
+                    && matches!(
+                        self.resolve(target_span, Value::Reference(path.clone(), scope.clone())),
+                        Value::Array(..)
+                    ) =>
+            {
+                Ok(Value::Reference(
+                    [path, vec![index.to_string()]].concat(),
+                    scope,
+                ))
+            }
+            (target, index) => Err(Box::new((self.resolve(target_span, target), index))),
+        }
+    }
+
+    pub(crate) fn set_property(
+        &mut self,
+        span: Span,
+        target: Value,
+        key: Value,
+        value: Value,
+    ) -> Value {
+        let error = match target {
+            error @ Value::Error(..) => error,
+            Value::Object(properties) => {
+                properties.write().insert(key, value);
+
+                return Value::None;
+            }
+            Value::ClassInstance(instance) if matches!(key, Value::String(_)) => {
+                let Value::String(name) = key else {
+                    unreachable!()
+                };
+
+                instance.set_field(name, value);
+
+                return Value::None;
+            }
+            target => Value::error(
+                span,
+                ErrorCode::Reference,
+                format!("can't set property {key} of {}", target.type_of()),
+            ),
+        };
+
+        self.error = Some(error.clone());
+
+        error
+    }
+
+    /// Evaluates the target of an index expression, left unresolved, and its resolved index.
+    pub(crate) fn eval_index_operands(
+        &mut self,
+        expression: IndexExpression,
+    ) -> (Span, Value, Value) {
         let (target_span, target) = (
             expression.target.span,
             self.eval_expression(expression.target),
@@ -13,47 +97,79 @@ impl Runtime {
             self.eval_expression(expression.index),
         );
 
-        println!("trying to access {index:?} inside {target:?}");
+        (target_span, target, self.resolve(index_span, index))
+    }
 
+    pub(crate) fn get_property(&self, target_span: Span, target: Value, index: Value) -> Value {
         match (target, index) {
-            (Value::Array(elements, _), Value::Number(index)) => {
-                elements.get(index as usize).unwrap_or(&Value::None).clone()
-            }
-            (Value::Array(..), Value::String(_)) => todo!(),
-            (Value::Object(properties), index) => {
-                properties.get(&index).unwrap_or(&Value::None).clone()
+            (Value::Error(span, code, message), _) => Value::Error(span, code, message),
+            (Value::Array(elements, _), Value::Number(index)) => usize::try_from(index)
+                .ok()
+                .and_then(|index| elements.read().get(index).cloned())
+                .unwrap_or(Value::None),
+            (Value::Array(elements, _), Value::String(key)) => match key.as_str() {
+                "length" => Value::Number(elements.read().len() as i64),
+                // Element references carry their index as a string
+                key => match key.parse::<usize>() {
+                    Ok(index) => elements.read().get(index).cloned().unwrap_or(Value::None),
+                    Err(_) => Value::error(
+                        target_span,
+                        ErrorCode::Reference,
+                        format!("property \"{key}\" does not exist on array"),
+                    ),
+                },
+            },
+            (Value::Object(properties), index) => properties
+                .read()
+                .get(&index)
+                .cloned()
+                .unwrap_or(Value::None),
+            (Value::String(string), Value::Number(index)) => string_index(&string, index),
+            (Value::String(string), Value::String(name)) => string_member(string, &name)
+                .unwrap_or_else(|| {
+                    Value::error(
+                        target_span,
+                        ErrorCode::Reference,
+                        format!("property \"{name}\" does not exist on string"),
+                    )
+                }),
+            (Value::Map(entries) | Value::Set(entries), Value::String(key)) if key == "size" => {
+                Value::Number(entries.read().len() as i64)
             }
-            (Value::String(string), Value::Number(index)) => Value::String(
-                string
-                    .get(index as usize..=index as usize)
-                    .unwrap_or_default()
-                    .to_string(),
+            (
+                collection @ (Value::Map(_) | Value::Set(_) | Value::WeakMap(_)),
+                Value::String(key),
+            ) => Value::error(
+                target_span,
+                ErrorCode::Reference,
+                format!(
+                    "property \"{key}\" does not exist on {}",
+                    collection.type_of()
+                ),
             ),
-            (Value::String(string), Value::String(name)) => match name.as_str() {
-                "length" => Value::Number(string.len() as i64),
-                "startsWith" => FunctionBuilder::new("startsWith")
-                    .param("data", PredefinedType::String)
-                    .returns(PredefinedType::Boolean)
-                    .build(move |args| {
-                        args.returns(Value::Boolean(string.starts_with(&args.get_string("data"))));
-                    }),
-                "split" => FunctionBuilder::new("split")
-                    .param("pattern", PredefinedType::String)
-                    .returns(PrimaryType::ArrayType(
-                        Box::new(PredefinedType::String.into()),
-                        ArraySize::Dynamic,
-                    ))
-                    .build(move |args| {
-                        args.returns(Value::Array(
-                            string
-                                .split(&args.get_string("pattern"))
-                                .map(|part| Value::String(part.into()))
-                                .collect(),
-                            ArraySize::Dynamic,
-                        ));
-                    }),
-                &_ => Value::error(target_span, ErrorCode::Implementing, "implementing"),
-            },
+            // The iterator of a built-in iterable is taken from the iterable when called
+            (iterable, Value::Symbol(symbol))
+                if symbol == Symbol::iterator() && iterable.values().is_some() =>
+            {
+                FunctionBuilder::new("[Symbol.iterator]")
+                    .returns(PredefinedType::Any)
+                    .build(move |args| args.returns(iterable.values().unwrap_or(Value::None)))
+            }
+            (target @ (Value::Iterator(_) | Value::Promise(_)), Value::String(key)) => {
+                Value::error(
+                    target_span,
+                    ErrorCode::Reference,
+                    format!("property \"{key}\" does not exist on {}", target.type_of()),
+                )
+            }
+            (Value::Symbol(symbol), Value::String(key)) if key == "description" => {
+                symbol.description.map_or(Value::None, Value::String)
+            }
+            (Value::NativeFunction(function), Value::String(key)) => function
+                .properties
+                .get(&key)
+                .cloned()
+                .unwrap_or(Value::None),
             (Value::ReturnValue(value), _) => *value,
             (Value::Enum(enumeration), Value::String(key)) => enumeration
                 .get_moved(key)
@@ -67,18 +183,9 @@ impl Runtime {
                 Value::error(target_span, ErrorCode::Reference, "can't index null value")
             }
             (Value::ClassInstance(instance), Value::String(key)) => instance
-                .get_field_moved(key)
+                .get_field(key)
                 .map_or(Value::None, |field| field.value),
-            (Value::Reference(path, scope), Value::Reference(second_path, _)) => {
-                if second_path[0] == "push"
-                    && todo!()
-                {
-                    Value::None
-                } else {
-                    Value::Reference([path, second_path].concat(), scope)
-                }
-            }
             _ => Value::error(target_span, ErrorCode::Reference, "can't index"),
         }
     }
-}
\ No newline at end of file
+}
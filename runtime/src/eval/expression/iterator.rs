@@ -0,0 +1,136 @@
+use tsr_lexer::globals::Span;
+
+use crate::{
+    value::{
+        heap::Shared,
+        iterator::{Iteration, Step},
+        symbol::Symbol,
+        ErrorCode, Properties, Value,
+    },
+    Runtime,
+};
+
+pub(crate) const ITERATOR_METHODS: &[&str] = &["next", "return"];
+
+impl Runtime {
+    pub(crate) fn call_iterator_method(
+        &mut self,
+        span: Span,
+        iteration: Shared<Iteration>,
+        name: &str,
+        args: Vec<(Span, Value)>,
+    ) -> Value {
+        let argument = args
+            .into_iter()
+            .next()
+            .map_or(Value::None, |(_, value)| value);
+        let iterator = Value::Iterator(iteration.clone());
+        let step = match name {
+            "next" => self.step(span, &iterator, argument),
+            _ => {
+                Iteration::close(&iteration);
+
+                Some(Step::Done(argument))
+            }
+        };
+        let (value, done) = match step {
+            Some(Step::Next(value)) => (value, false),
+            Some(Step::Done(value)) => (value, true),
+            None => return self.error.clone().unwrap_or(Value::None),
+        };
+
+        Value::object(Properties::from([
+            (Value::String("value".into()), value),
+            (Value::String("done".into()), Value::Boolean(done)),
+        ]))
+    }
+
+    pub(crate) fn get_iterator(&mut self, span: Span, iterable: &Value) -> Option<Value> {
+        if let Some(iterator) = iterable.values() {
+            return Some(iterator);
+        }
+
+        let method = match iterable {
+            Value::Object(properties) => properties
+                .read()
+                .get(&Value::Symbol(Symbol::iterator()))
+                .cloned(),
+            _ => None,
+        };
+
+        match method {
+            Some(method) => match self.call_back(span, &method, vec![])? {
+                iterator @ (Value::Object(_) | Value::ClassInstance(_) | Value::Iterator(_)) => {
+                    Some(iterator)
+                }
+                iterator => self.fail(Value::error(
+                    span,
+                    ErrorCode::Type,
+                    format!("iterator {} is not an object", iterator.value_type_of()),
+                )),
+            },
+            None => self.fail(Value::error(
+                span,
+                ErrorCode::Type,
+                format!("{} is not iterable", iterable.value_type_of()),
+            )),
+        }
+    }
+
+    pub(crate) fn step(&mut self, span: Span, iterator: &Value, sent: Value) -> Option<Step> {
+        if let Value::Iterator(iteration) = iterator {
+            return match Iteration::step(iteration, sent) {
+                Step::Done(error @ Value::Error(..)) => self.fail(error),
+                step => Some(step),
+            };
+        }
+
+        let next = iterator.get_member("next").unwrap_or(Value::None);
+        let result = self.call_back(span, &next, vec![sent])?;
+
+        if !matches!(result, Value::Object(_) | Value::ClassInstance(_)) {
+            return self.fail(Value::error(
+                span,
+                ErrorCode::Type,
+                format!(
+                    "iterator result {} is not an object",
+                    result.value_type_of()
+                ),
+            ));
+        }
+
+        let value = result.get_member("value").unwrap_or(Value::None);
+
+        Some(
+            match result
+                .get_member("done")
+                .is_some_and(|done| done.is_truthy())
+            {
+                true => Step::Done(value),
+                false => Step::Next(value),
+            },
+        )
+    }
+
+    pub(crate) fn close_iterator(&mut self, iterator: &Value) {
+        match iterator {
+            Value::Iterator(iteration) => Iteration::close(iteration),
+            iterator => {
+                if let (None, Some(method)) = (&self.error, iterator.get_member("return")) {
+                    self.call_back(Span::default(), &method, vec![]);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn spread(&mut self, span: Span, iterable: &Value) -> Option<Vec<Value>> {
+        let iterator = self.get_iterator(span, iterable)?;
+        let mut values = vec![];
+
+        while let Step::Next(value) = self.step(span, &iterator, Value::None)? {
+            values.push(value);
+        }
+
+        Some(values)
+    }
+}
@@ -1,35 +1,66 @@
 use tsr_lexer::globals::Positioned;
 use tsr_parser::ast::Expression;
 
-use crate::{value::Value, Runtime};
+use crate::{
+    value::{ErrorCode, Value},
+    Runtime,
+};
 
 pub mod array;
 pub mod arrow_function;
+pub mod assertion;
+pub mod async_function;
 pub mod binary;
 pub mod call;
+pub mod collection;
+pub mod generator;
+pub mod import;
 pub mod index;
+pub mod iterator;
 pub mod literal;
 pub mod new;
+pub mod object;
+pub mod promise;
+pub mod string;
 pub mod switch;
 
 impl Runtime {
     pub fn eval_expression(&mut self, expression: Positioned<Expression>) -> Value {
+        let span = expression.span;
+
         match expression.value {
             Expression::BinaryExpression(expression) => {
                 self.eval_binary_expression(expression.value)
             }
             Expression::IndexExpression(expression) => self.eval_index_expression(expression.value),
             Expression::MatchExpression(expression) => self.eval_match_expression(expression.value),
-            Expression::Block(block) => self.eval_program(block),
+            Expression::AsExpression(assertion) => self.eval_as_expression(*assertion),
+            Expression::SatisfiesExpression(assertion) => {
+                self.eval_satisfies_expression(*assertion)
+            }
+            Expression::NonNullExpression(expression) => self.eval_non_null_expression(*expression),
+            Expression::Block(block) => self.eval_code_block(block),
             Expression::Literal(literal) => self.eval_literal(literal.value),
             Expression::Array {
                 elements,
                 is_dynamic,
             } => self.eval_array(elements, is_dynamic),
             Expression::ArrowFunction(func) => self.eval_arrow_function(func.value),
+            Expression::Object(properties) => self.eval_object(properties),
             Expression::Null => Value::Null,
             Expression::FunctionCallExpression(call) => self.eval_call(*call),
             Expression::NewExpression(expression) => self.eval_new_expression(expression),
+            Expression::ImportExpression(specifier) => {
+                self.eval_import_expression(span, *specifier)
+            }
+            Expression::YieldExpression(expression) => self.eval_yield_expression(*expression),
+            Expression::AwaitExpression(argument) => self.eval_await_expression(*argument),
+            // Spread elements are taken apart by the arrays and calls they are written in
+            Expression::SpreadElement(_) => Value::error(
+                span,
+                ErrorCode::Type,
+                "spread is only valid in array literals and arguments",
+            ),
             Expression::Ident(name) => Value::Reference(vec![name.value.0], self.scope.clone()),
             Expression::This => Value::Reference(vec!["this".into()], self.scope.clone()),
         }
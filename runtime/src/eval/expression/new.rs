@@ -1,19 +1,32 @@
 use tsr_lexer::globals::Positioned;
 use tsr_parser::ast::NewExpression;
 
-use crate::{value::{ClassInstance, ErrorCode, Field, Value}, Runtime};
+use crate::{
+    value::{ClassInstance, ErrorCode, Field, Value},
+    Runtime,
+};
 
 impl Runtime {
     pub fn eval_new_expression(&mut self, expression: Positioned<NewExpression>) -> Value {
         let (span, expression) = expression.unpack();
-        let arguments = expression
-            .arguments
-            .into_iter()
-            .map(|argument| (argument.span, self.eval_expression(argument)))
-            .collect::<Vec<_>>();
+        let Some(arguments) = self.eval_elements(expression.arguments) else {
+            return self.error.clone().unwrap_or(Value::None);
+        };
 
         let class_name = self.eval_expression(*expression.expression);
 
+        // Built-in classes like `Map` are native functions building the instance themselves,
+        // except for `Promise`, whose executor calls back into the program
+        match self.resolve(span, class_name.clone()) {
+            Value::NativeFunction(constructor) if constructor.name == "Promise" => {
+                return self.construct_promise(span, arguments)
+            }
+            constructor @ Value::NativeFunction(_) => {
+                return self.call_value(span, &constructor, arguments, &[], None)
+            }
+            _ => {}
+        }
+
         if let Value::Reference(path, scope) = class_name {
             let class = self
                 .context
@@ -29,30 +42,31 @@ impl Runtime {
                 ..
             }) = class
             {
-                let constructor = constructors.into_iter().find(|constructor| {
-                    let arg_count = constructor
-                        .parameters
-                        .iter()
-                        .filter(|parameter| parameter.default.is_none())
-                        .count();
-
-                    arguments.len() >= arg_count
-                        && arguments.iter().enumerate().all(|(index, (_, argument))| {
-                            constructor
-                                .parameters
-                                .get(index)
-                                .is_some_and(|parameter| argument.is_type_of(&parameter.ty))
-                        })
-                });
-
-                if let Some(constructor) = constructor {
+                let has_constructors = !constructors.is_empty();
+                let type_arguments = expression
+                    .type_arguments
+                    .into_iter()
+                    .map(|ty| ty.value)
+                    .collect::<Vec<_>>();
+                let constructor = constructors
+                    .into_iter()
+                    .find(|constructor| constructor.accepts(self, &type_arguments, &arguments));
+
+                if constructor.is_some() || !has_constructors {
+                    // Constructors run in the scope of their class, which may be in a module
+                    let caller = self.scope.clone();
+
+                    if let Some(constructor) = &constructor {
+                        self.enter_scope(&constructor.scope);
+                    }
+
                     self.add_scope(format!("class-instance:{name}"));
 
                     self.set_variable(
                         "this",
-                        span.wrap(Value::ClassInstance(ClassInstance {
+                        span.wrap(Value::ClassInstance(ClassInstance::new(
                             name,
-                            fields: fields
+                            fields
                                 .into_iter()
                                 .map(|prop| {
                                     let value =
@@ -61,10 +75,18 @@ impl Runtime {
                                     Field { prop, value }
                                 })
                                 .collect(),
-                        })),
+                        ))),
                     );
 
-                    constructor.call(span, self, arguments, None);
+                    if let Some(constructor) = constructor {
+                        if let error @ Value::Error(..) =
+                            constructor.call(span, self, arguments, &type_arguments, None)
+                        {
+                            self.scope = caller;
+
+                            return error;
+                        }
+                    }
 
                     if let Some(Value::ClassInstance(instance)) = self
                         .context
@@ -75,7 +97,7 @@ impl Runtime {
                     {
                         let mut error = None;
 
-                        for field in &instance.fields {
+                        for field in instance.fields.read().iter() {
                             if field.value.is_none()
                                 && !field.prop.nullable
                                 && field.prop.init.is_none()
@@ -95,6 +117,7 @@ impl Runtime {
 
                         if error.is_some() {
                             self.error = error;
+                            self.scope = caller;
 
                             return Value::None;
                         }
@@ -102,7 +125,7 @@ impl Runtime {
 
                     let this = Value::Reference(vec!["this".into()], self.scope.clone());
 
-                    self.remove_scope();
+                    self.scope = caller;
 
                     return this;
                 }
@@ -111,4 +134,4 @@ impl Runtime {
 
         Value::None
     }
-}
\ No newline at end of file
+}
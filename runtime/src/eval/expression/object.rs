@@ -0,0 +1,37 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::{PropertyAssignment, PropertyName};
+
+use crate::{
+    value::{Properties, Value},
+    Runtime,
+};
+
+impl Runtime {
+    pub fn eval_object(&mut self, properties: Vec<Positioned<PropertyAssignment>>) -> Value {
+        let mut object = Properties::new();
+
+        for property in properties {
+            let property = property.value;
+            let name = match property.name.value {
+                PropertyName::LiteralPropertyName(literal) => self.eval_literal(literal.value),
+                PropertyName::ComputedPropertyName(expression) => {
+                    let span = expression.span;
+                    let value = self.eval_expression(expression);
+
+                    self.resolve(span, value)
+                }
+            };
+            let span = property.initializer.span;
+            let value = self.eval_expression(property.initializer);
+            let value = self.resolve(span, value);
+
+            if let Value::Error(..) = value {
+                return value;
+            }
+
+            object.insert(name, value);
+        }
+
+        Value::object(object)
+    }
+}
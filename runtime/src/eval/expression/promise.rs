@@ -0,0 +1,155 @@
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::{Block, PredefinedType};
+
+use super::array::lambda_callback;
+use crate::{
+    value::{
+        heap::Shared,
+        promise::{Outcome, Promise, Reaction},
+        ErrorCode, Value,
+    },
+    FunctionBuilder, Runtime,
+};
+
+pub(crate) const PROMISE_METHODS: &[&str] = &["then", "catch", "finally"];
+
+impl Runtime {
+    pub(crate) fn call_promise_method(
+        &mut self,
+        span: Span,
+        promise: Shared<Promise>,
+        name: &str,
+        mut args: Vec<(Span, Value)>,
+        lambda: Option<Block>,
+    ) -> Value {
+        if let Some(lambda) = lambda {
+            let parameters: &[&str] = match name {
+                "then" => &["value"],
+                "catch" => &["reason"],
+                _ => &[],
+            };
+
+            args.push((lambda.span, lambda_callback(parameters, lambda)));
+        }
+
+        // Like in JavaScript, handlers that aren't functions are left out
+        let mut handlers = args.into_iter().map(|(_, handler)| {
+            matches!(
+                handler,
+                Value::Function(_) | Value::NativeFunction(_) | Value::ArrowFunction(_)
+            )
+            .then_some(handler)
+        });
+        let mut handler = || handlers.next().flatten();
+        let derived = Promise::pending(&self.event_loop);
+
+        let reaction = match name {
+            "then" => {
+                let on_fulfilled = handler();
+
+                react(span, on_fulfilled, handler(), derived.clone())
+            }
+            "catch" => react(span, None, handler(), derived.clone()),
+            _ => finally(span, handler(), derived.clone()),
+        };
+
+        Promise::subscribe(&promise, reaction);
+
+        Value::Promise(derived)
+    }
+
+    fn call_handler(&mut self, span: Span, handler: &Value, arguments: Vec<Value>) -> Outcome {
+        match self.call_back(span, handler, arguments) {
+            Some(value) => Outcome::Fulfilled(value),
+            None => Outcome::Rejected(self.error.take().unwrap_or(Value::None)),
+        }
+    }
+
+    pub(crate) fn construct_promise(&mut self, span: Span, arguments: Vec<(Span, Value)>) -> Value {
+        let executor = arguments
+            .into_iter()
+            .next()
+            .map_or(Value::None, |(_, executor)| executor);
+
+        if !matches!(
+            executor,
+            Value::Function(_) | Value::NativeFunction(_) | Value::ArrowFunction(_)
+        ) {
+            return Value::error(
+                span,
+                ErrorCode::Type,
+                format!("promise resolver {} is not a function", executor.type_of()),
+            );
+        }
+
+        let promise = Promise::pending(&self.event_loop);
+        let settle = |name: &str, settled: fn(Value) -> Outcome| {
+            let promise = promise.clone();
+
+            FunctionBuilder::new(name)
+                .param_default("value", PredefinedType::Any, Value::None)
+                .returns(PredefinedType::Void)
+                .build(move |args| {
+                    Promise::settle(&promise, settled(args.get("value").unwrap_or(Value::None)))
+                })
+        };
+        let functions = vec![
+            settle("resolve", Outcome::Fulfilled),
+            settle("reject", Outcome::Rejected),
+        ];
+
+        if self.call_back(span, &executor, functions).is_none() {
+            let error = self.error.take().unwrap_or(Value::None);
+
+            Promise::settle(&promise, Outcome::Rejected(error));
+        }
+
+        Value::Promise(promise)
+    }
+}
+
+fn react(
+    span: Span,
+    on_fulfilled: Option<Value>,
+    on_rejected: Option<Value>,
+    derived: Shared<Promise>,
+) -> Reaction {
+    Box::new(move |runtime, outcome| {
+        let outcome = match (outcome, on_fulfilled, on_rejected) {
+            (Outcome::Fulfilled(value), Some(handler), _) => {
+                runtime.call_handler(span, &handler, vec![value])
+            }
+            (Outcome::Rejected(reason), _, Some(handler)) => {
+                runtime.call_handler(span, &handler, vec![reason.reason()])
+            }
+            (outcome, ..) => outcome,
+        };
+
+        Promise::settle(&derived, outcome);
+    })
+}
+
+fn finally(span: Span, callback: Option<Value>, derived: Shared<Promise>) -> Reaction {
+    Box::new(move |runtime, outcome| {
+        let Some(callback) = callback else {
+            return Promise::settle(&derived, outcome);
+        };
+
+        match runtime.call_handler(span, &callback, vec![]) {
+            Outcome::Fulfilled(Value::Promise(waited)) => Promise::subscribe(
+                &waited,
+                Box::new(move |_, result| {
+                    Promise::settle(
+                        &derived,
+                        match result {
+                            rejected @ Outcome::Rejected(_) => rejected,
+                            Outcome::Fulfilled(_) => outcome,
+                        },
+                    )
+                }),
+            ),
+            rejected @ Outcome::Rejected(_) => Promise::settle(&derived, rejected),
+            Outcome::Fulfilled(_) => Promise::settle(&derived, outcome),
+        }
+    })
+}
@@ -0,0 +1,242 @@
+use tsr_parser::ast::{ArraySize, PredefinedType, PrimaryType, Type};
+
+use super::array::relative_index;
+use crate::{value::Value, FunctionBuilder};
+
+/// UTF-16 code units of a string, which lengths and indices of strings count like in
+/// JavaScript.
+fn units(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+fn text(units: &[u16]) -> String {
+    String::from_utf16_lossy(units)
+}
+
+/// Index of the first occurrence of `search` in `units` at or after `from`.
+fn find(units: &[u16], search: &[u16], from: usize) -> Option<usize> {
+    (from..=units.len().saturating_sub(search.len()))
+        .find(|start| units[*start..].starts_with(search))
+}
+
+/// Pads `string` with `padding` repeated up to `length` code units, cut at the end.
+fn padding(string: &str, length: i64, padding: &str) -> String {
+    let missing = (length.max(0) as usize).saturating_sub(units(string).len());
+    let padding = units(padding);
+
+    match padding.is_empty() {
+        true => String::new(),
+        false => text(
+            &padding
+                .iter()
+                .copied()
+                .cycle()
+                .take(missing)
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+/// The code unit of a string at `index`, `undefined` past its end.
+pub(crate) fn string_index(string: &str, index: i64) -> Value {
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| units(string).get(index).copied())
+        .map_or(Value::None, |unit| Value::String(text(&[unit])))
+}
+
+/// A member of the `String` prototype bound to `string`.
+pub(crate) fn string_member(string: String, name: &str) -> Option<Value> {
+    let length = units(&string).len() as i64;
+    let method = |name: &str| FunctionBuilder::new(name);
+
+    Some(match name {
+        "length" => Value::Number(length),
+        "at" => method("at")
+            .param("index", PredefinedType::Number)
+            .returns(Type::union(vec![
+                PredefinedType::String.into(),
+                PredefinedType::Undefined.into(),
+            ]))
+            .build(move |args| {
+                let index = args.get_number("index");
+                let index = match index < 0 {
+                    true => length + index,
+                    false => index,
+                };
+
+                args.returns(string_index(&string, index));
+            }),
+        "charCodeAt" => method("charCodeAt")
+            .param_default("index", PredefinedType::Number, 0)
+            .returns(PredefinedType::Number)
+            .build(move |args| {
+                args.returns(
+                    usize::try_from(args.get_number("index"))
+                        .ok()
+                        .and_then(|index| units(&string).get(index).copied())
+                        .map_or(Value::Float(f64::NAN), |unit| Value::Number(unit as i64)),
+                );
+            }),
+        "slice" => method("slice")
+            .param_default("start", PredefinedType::Number, 0)
+            .param_default("end", PredefinedType::Number, length)
+            .returns(PredefinedType::String)
+            .build(move |args| {
+                let units = units(&string);
+                let start = relative_index(args.get_number("start"), units.len());
+                let end = relative_index(args.get_number("end"), units.len());
+
+                args.returns(text(&units[start..end.max(start)]));
+            }),
+        "substring" => method("substring")
+            .param_default("start", PredefinedType::Number, 0)
+            .param_default("end", PredefinedType::Number, length)
+            .returns(PredefinedType::String)
+            .build(move |args| {
+                let units = units(&string);
+                let clamp = |index: i64| index.clamp(0, units.len() as i64) as usize;
+                let (start, end) = (
+                    clamp(args.get_number("start")),
+                    clamp(args.get_number("end")),
+                );
+
+                args.returns(text(&units[start.min(end)..start.max(end)]));
+            }),
+        "indexOf" => method("indexOf")
+            .param("search", PredefinedType::String)
+            .param_default("position", PredefinedType::Number, 0)
+            .returns(PredefinedType::Number)
+            .build(move |args| {
+                let search = units(&args.get_string("search"));
+                let units = units(&string);
+                let from = relative_index(args.get_number("position").max(0), units.len());
+
+                args.returns(find(&units, &search, from).map_or(-1, |index| index as i64));
+            }),
+        "includes" => method("includes")
+            .param("search", PredefinedType::String)
+            .param_default("position", PredefinedType::Number, 0)
+            .returns(PredefinedType::Boolean)
+            .build(move |args| {
+                let search = units(&args.get_string("search"));
+                let units = units(&string);
+                let from = relative_index(args.get_number("position").max(0), units.len());
+
+                args.returns(find(&units, &search, from).is_some());
+            }),
+        "startsWith" => method("startsWith")
+            .param("search", PredefinedType::String)
+            .param_default("position", PredefinedType::Number, 0)
+            .returns(PredefinedType::Boolean)
+            .build(move |args| {
+                let search = units(&args.get_string("search"));
+                let units = units(&string);
+                let from = relative_index(args.get_number("position").max(0), units.len());
+
+                args.returns(units[from..].starts_with(&search));
+            }),
+        "endsWith" => method("endsWith")
+            .param("search", PredefinedType::String)
+            .param_default("end", PredefinedType::Number, length)
+            .returns(PredefinedType::Boolean)
+            .build(move |args| {
+                let search = units(&args.get_string("search"));
+                let units = units(&string);
+                let end = relative_index(args.get_number("end").max(0), units.len());
+
+                args.returns(units[..end].ends_with(&search));
+            }),
+        "replace" => method("replace")
+            .param("pattern", PredefinedType::String)
+            .param("replacement", PredefinedType::String)
+            .returns(PredefinedType::String)
+            .build(move |args| {
+                args.returns(string.replacen(
+                    &args.get_string("pattern"),
+                    &args.get_string("replacement"),
+                    1,
+                ));
+            }),
+        "replaceAll" => method("replaceAll")
+            .param("pattern", PredefinedType::String)
+            .param("replacement", PredefinedType::String)
+            .returns(PredefinedType::String)
+            .build(move |args| {
+                let pattern = args.get_string("pattern");
+                let replacement = args.get_string("replacement");
+
+                // Like in JavaScript, an empty pattern matches between every character
+                args.returns(match pattern.is_empty() {
+                    true => string.chars().fold(replacement.clone(), |replaced, char| {
+                        format!("{replaced}{char}{replacement}")
+                    }),
+                    false => string.replace(&pattern, &replacement),
+                });
+            }),
+        "trim" | "trimStart" | "trimEnd" => {
+            let trimmed = match name {
+                "trim" => string.trim(),
+                "trimStart" => string.trim_start(),
+                _ => string.trim_end(),
+            }
+            .to_string();
+
+            method(name)
+                .returns(PredefinedType::String)
+                .build(move |args| args.returns(trimmed.clone()))
+        }
+        "padStart" | "padEnd" => {
+            let start = name == "padStart";
+
+            method(name)
+                .param("length", PredefinedType::Number)
+                .param_default("padding", PredefinedType::String, String::from(" "))
+                .returns(PredefinedType::String)
+                .build(move |args| {
+                    let padding = padding(
+                        &string,
+                        args.get_number("length"),
+                        &args.get_string("padding"),
+                    );
+
+                    args.returns(match start {
+                        true => padding + &string,
+                        false => string.clone() + &padding,
+                    });
+                })
+        }
+        "toUpperCase" | "toLowerCase" => {
+            let converted = match name {
+                "toUpperCase" => string.to_uppercase(),
+                _ => string.to_lowercase(),
+            };
+
+            method(name)
+                .returns(PredefinedType::String)
+                .build(move |args| args.returns(converted.clone()))
+        }
+        "repeat" => method("repeat")
+            .param("count", PredefinedType::Number)
+            .returns(PredefinedType::String)
+            .build(move |args| {
+                args.returns(string.repeat(args.get_number("count").max(0) as usize));
+            }),
+        "split" => method("split")
+            .param("pattern", PredefinedType::String)
+            .returns(PrimaryType::ArrayType(
+                Box::new(PredefinedType::String.into()),
+                ArraySize::Dynamic,
+            ))
+            .build(move |args| {
+                args.returns(Value::array(
+                    string
+                        .split(&args.get_string("pattern"))
+                        .map(|part| Value::String(part.into()))
+                        .collect(),
+                    ArraySize::Dynamic,
+                ));
+            }),
+        _ => return None,
+    })
+}
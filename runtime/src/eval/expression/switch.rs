@@ -4,10 +4,18 @@ use crate::{value::Value, Runtime};
 
 impl Runtime {
     pub fn eval_match_expression(&mut self, expression: MatchExpression) -> Value {
+        let span = expression.target.span;
         let target = self.eval_expression(expression.target);
+        let target = self.resolve(span, target);
+
+        if let Value::Error(..) = target {
+            return target;
+        }
 
         for variant in expression.variants {
+            let span = variant.value.value.span;
             let value = self.eval_expression(variant.value.value);
+            let value = self.resolve(span, value);
 
             if target == value {
                 return self.eval_statement(variant.value.callback);
@@ -1,10 +1,11 @@
 use tsr_lexer::{globals::Positioned, token::Modifier};
 use tsr_parser::ast::{
-    ClassDeclaration, ClassElement, Literal, PrimaryType, PropertyMemberDeclaration, PropertyName,
+    ClassDeclaration, ClassElement, Ident, Literal, PrimaryType, PropertyMemberDeclaration,
+    PropertyName, PropertySignature, TypeMember,
 };
 
 use crate::{
-    value::{Function, Parameter, Property, Value, Visibility},
+    value::{Function, Parameter, ParameterDefault, Property, Value, Visibility},
     Runtime,
 };
 
@@ -12,6 +13,11 @@ impl Runtime {
     pub fn declare_class(&mut self, class: Positioned<ClassDeclaration>) -> Value {
         let (span, class) = class.unpack();
         let name = class.name.value.0;
+        let type_parameters = class
+            .type_parameters
+            .into_iter()
+            .map(|parameter| parameter.value)
+            .collect::<Vec<_>>();
         let extends = class
             .extends
             .into_iter()
@@ -47,6 +53,7 @@ impl Runtime {
                             Modifier::Protected => visibility = Visibility::Protected,
                             Modifier::Async => is_async = true,
                             Modifier::Static => is_static = true,
+                            Modifier::Readonly => {}
                         }
                     }
 
@@ -55,7 +62,9 @@ impl Runtime {
                         overloads: Vec::new(),
                         is_async,
                         is_static,
+                        is_generator: false,
                         name: "constructor".into(),
+                        type_parameters: type_parameters.clone(),
                         parameters: declaration
                             .parameters
                             .into_iter()
@@ -63,14 +72,12 @@ impl Runtime {
                                 name: param.value.name.value.0,
                                 nullable: param.value.nullable.value,
                                 ty: param.value.ty.value,
-                                default: param
-                                    .value
-                                    .default
-                                    .map(|expression| Box::new(self.eval_expression(expression))),
+                                default: param.value.default.map(ParameterDefault::Expression),
                             })
                             .collect(),
                         ty: PrimaryType::ThisType.into(),
-                        body: declaration.body,
+                        body: Some(declaration.body),
+                        scope: self.scope.clone(),
                     });
                 }
                 ClassElement::PropertyMemberDeclaration(declaration) => match declaration.value {
@@ -112,6 +119,7 @@ impl Runtime {
                                 Modifier::Protected => visibility = Visibility::Protected,
                                 Modifier::Async => is_async = true,
                                 Modifier::Static => is_static = true,
+                                Modifier::Readonly => {}
                             }
                         }
 
@@ -120,6 +128,7 @@ impl Runtime {
                             overloads: Vec::new(),
                             is_async,
                             is_static,
+                            is_generator: false,
                             name: match declaration.name.value {
                                 PropertyName::LiteralPropertyName(literal) => match literal.value {
                                     Literal::String(string) => string.value,
@@ -127,6 +136,13 @@ impl Runtime {
                                 },
                                 PropertyName::ComputedPropertyName(_) => todo!(),
                             },
+                            // Methods also see the type parameters of their class
+                            type_parameters: declaration
+                                .type_parameters
+                                .into_iter()
+                                .map(|parameter| parameter.value)
+                                .chain(type_parameters.iter().cloned())
+                                .collect(),
                             parameters: declaration
                                 .parameters
                                 .into_iter()
@@ -134,13 +150,12 @@ impl Runtime {
                                     name: param.value.name.value.0,
                                     nullable: param.value.nullable.value,
                                     ty: param.value.ty.value,
-                                    default: param.value.default.map(|expression| {
-                                        Box::new(self.eval_expression(expression))
-                                    }),
+                                    default: param.value.default.map(ParameterDefault::Expression),
                                 })
                                 .collect(),
                             ty: declaration.ty.value,
-                            body: declaration.body,
+                            body: Some(declaration.body),
+                            scope: self.scope.clone(),
                         });
                     }
                     PropertyMemberDeclaration::MemberAccessorDeclaration(_) => todo!(),
@@ -149,6 +164,25 @@ impl Runtime {
             }
         }
 
+        self.associate_type(
+            name.clone(),
+            type_parameters,
+            PrimaryType::ObjectType(
+                fields
+                    .iter()
+                    .map(|field| {
+                        TypeMember::PropertySignature(span.wrap(PropertySignature {
+                            modifiers: vec![],
+                            name: span.wrap(Ident(field.name.clone())),
+                            nullable: span.wrap(field.nullable),
+                            ty: span.wrap(field.ty.clone()),
+                        }))
+                    })
+                    .collect(),
+            )
+            .into(),
+        );
+
         self.set_variable(
             name.clone(),
             span.wrap(Value::Class {
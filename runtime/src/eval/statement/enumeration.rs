@@ -1,5 +1,5 @@
 use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::EnumDeclaration;
+use tsr_parser::ast::{EnumDeclaration, PredefinedType, Type};
 
 use crate::{
     value::{Enum, EnumMember, Value},
@@ -30,6 +30,22 @@ impl Runtime {
                 });
             }
         }
+        self.associate_type(
+            enumeration.name.value.0.clone(),
+            vec![],
+            Type::union(
+                members
+                    .iter()
+                    .map(|member| match member.init.as_ref() {
+                        Value::String(string) => {
+                            PredefinedType::StringLiteral(string.clone()).into()
+                        }
+                        value => value.value_type_of(),
+                    })
+                    .collect(),
+            ),
+        );
+
         let value = Value::Enum(Enum {
             name: enumeration.name.value.0.clone(),
             members,
@@ -1,23 +1,229 @@
-use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::ExportDeclaration;
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{
+    BindingPattern, ExportDeclaration, ExportDefaultElement, ExportListElement,
+    ExportSingleElement, ExportSpecifier,
+};
 
-use crate::{value::Value, Runtime};
+use crate::{
+    value::{native::Module, ErrorCode, Export, ExportSource, ExportValue, Value},
+    Runtime,
+};
 
 impl Runtime {
     pub fn eval_export(&mut self, export: Positioned<ExportDeclaration>) -> Value {
-        let (span, value) = export.unpack();
+        let (span, export) = export.unpack();
 
-        match value {
-            ExportDeclaration::Default(statement) => match statement.value {
-                tsr_parser::ast::ExportDefaultElement::FunctionDeclaration(declaration) => {
+        match export {
+            ExportDeclaration::Default(element) => match element.value {
+                ExportDefaultElement::FunctionDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, Some("default"));
                     self.declare_function(declaration)
                 }
-                tsr_parser::ast::ExportDefaultElement::ClassDeclaration(_) => todo!(),
-                tsr_parser::ast::ExportDefaultElement::Expression(_) => todo!(),
-                tsr_parser::ast::ExportDefaultElement::IdentifierReference(_) => todo!(),
+                ExportDefaultElement::ClassDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, Some("default"));
+                    self.declare_class(declaration)
+                }
+                ExportDefaultElement::Expression(expression) => {
+                    let expression_span = expression.span;
+                    let value = self.eval_expression(expression);
+
+                    match self.resolve(expression_span, value) {
+                        error @ Value::Error(..) => error,
+                        value => {
+                            self.exports.push(span.wrap(Export {
+                                source: ExportSource::CurrentModule,
+                                value: ExportValue::Value(value),
+                            }));
+
+                            Value::None
+                        }
+                    }
+                }
+                ExportDefaultElement::IdentifierReference(name) => {
+                    self.export_local(span, &name.value.0, Some("default"));
+
+                    Value::None
+                }
+            },
+            ExportDeclaration::Single(element) => match element.value {
+                ExportSingleElement::VariableStatement(statement) => {
+                    for declaration in &statement.value.declarations {
+                        for name in BindingPattern::names(&declaration.value.name) {
+                            self.export_local(span, &name.value.0, None);
+                        }
+                    }
+
+                    self.declare_variable(statement)
+                }
+                ExportSingleElement::FunctionDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, None);
+                    self.declare_function(declaration)
+                }
+                ExportSingleElement::ClassDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, None);
+                    self.declare_class(declaration)
+                }
+                ExportSingleElement::InterfaceDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, None);
+                    self.declare_interface(declaration)
+                }
+                ExportSingleElement::TypeAliasDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, None);
+                    self.declare_type_alias(declaration)
+                }
+                ExportSingleElement::EnumDeclaration(declaration) => {
+                    self.export_local(span, &declaration.value.name.value.0, None);
+                    self.declare_enum(declaration)
+                }
             },
-            ExportDeclaration::Single(_) => todo!(),
-            ExportDeclaration::List(_) => todo!(),
+            ExportDeclaration::List(element) => {
+                let (source, specifiers) = match element.value {
+                    ExportListElement::Namespace(module) => {
+                        self.exports.push(span.wrap(Export {
+                            source: ExportSource::Module(module.value),
+                            value: ExportValue::All,
+                        }));
+
+                        return Value::None;
+                    }
+                    ExportListElement::NamedExports(specifiers) => {
+                        (ExportSource::CurrentModule, specifiers.value)
+                    }
+                    ExportListElement::NamespaceExports(specifiers, module) => {
+                        (ExportSource::Module(module.value), specifiers.value)
+                    }
+                };
+
+                for specifier in specifiers {
+                    let ExportSpecifier {
+                        property_name,
+                        name,
+                    } = specifier.value;
+                    let value = match property_name {
+                        Some(local) => ExportValue::Reference(local.value.0, Some(name.value.0)),
+                        None => ExportValue::Reference(name.value.0, None),
+                    };
+
+                    self.exports.push(specifier.span.wrap(Export {
+                        source: source.clone(),
+                        value,
+                    }));
+                }
+
+                Value::None
+            }
+        }
+    }
+
+    fn export_local(&mut self, span: Span, name: &str, alias: Option<&str>) {
+        self.exports.push(span.wrap(Export {
+            source: ExportSource::CurrentModule,
+            value: ExportValue::Reference(name.into(), alias.map(Into::into)),
+        }));
+    }
+
+    /// Object with a property for every export of the evaluated program, so the host can
+    /// use what a module exports.
+    pub fn exports(&mut self) -> Value {
+        match self.module_exports(self.directory.display().to_string()) {
+            Some(module) => module.namespace(),
+            None => self.error.take().unwrap_or(Value::None),
+        }
+    }
+
+    /// Resolves the exports declared by the evaluated program to their current values and
+    /// to the types declared under their names, loading the modules re-exported from.
+    /// Failures are left in the error of the runtime.
+    pub(crate) fn module_exports(&mut self, name: String) -> Option<Module> {
+        let mut module = Module::new(name);
+        let mut declared = self.exports.clone();
+
+        // Star exports leave out names exported explicitly, wherever those are declared
+        declared.sort_by_key(|export| export.value.value == ExportValue::All);
+
+        for export in declared {
+            let (span, export) = export.unpack();
+            let source = match &export.source {
+                ExportSource::CurrentModule => None,
+                ExportSource::Module(specifier) => Some(self.import_module(span, specifier)?),
+            };
+
+            let (named, types) = match (export.value, source) {
+                (ExportValue::Value(value), _) => (vec![("default".into(), value)], vec![]),
+                (ExportValue::Reference(name, alias), None) => {
+                    let reference = Value::Reference(vec![name.clone()], self.scope.clone());
+                    let ty = self
+                        .context
+                        .lock()
+                        .unwrap()
+                        .get_type(&name, self.scope.clone())
+                        .cloned();
+                    let alias = alias.unwrap_or(name);
+
+                    match self.resolve(span, reference) {
+                        error @ Value::Error(..) => {
+                            self.error = Some(error);
+
+                            return None;
+                        }
+                        value => (
+                            vec![(alias.clone(), value)],
+                            ty.map(|ty| (alias, ty)).into_iter().collect(),
+                        ),
+                    }
+                }
+                (ExportValue::Reference(name, alias), Some(source)) => {
+                    let value = self.find_export(span, &source, &name)?;
+                    let alias = alias.unwrap_or(name.clone());
+
+                    (
+                        vec![(alias.clone(), value)],
+                        source
+                            .types
+                            .into_iter()
+                            .filter(|(export, _)| *export == name)
+                            .map(|(_, ty)| (alias.clone(), ty))
+                            .collect(),
+                    )
+                }
+                (ExportValue::All, Some(source)) => {
+                    let exported = |name: &String| {
+                        name != "default" && module.exports.iter().all(|(export, _)| export != name)
+                    };
+
+                    (
+                        source
+                            .exports
+                            .into_iter()
+                            .filter(|(name, _)| exported(name))
+                            .collect(),
+                        source
+                            .types
+                            .into_iter()
+                            .filter(|(name, _)| exported(name))
+                            .collect(),
+                    )
+                }
+                (ExportValue::All, None) => (vec![], vec![]),
+            };
+
+            for (name, value) in named {
+                if module.exports.iter().any(|(export, _)| *export == name) {
+                    self.error = Some(Value::error(
+                        span,
+                        ErrorCode::Declaration,
+                        format!("module exports \"{name}\" more than once"),
+                    ));
+
+                    return None;
+                }
+
+                module.exports.push((name, value));
+            }
+
+            module.types.extend(types);
         }
+
+        Some(module)
     }
 }
@@ -0,0 +1,62 @@
+use tsr_lexer::globals::Positioned;
+use tsr_parser::ast::ForOfStatement;
+
+use crate::{
+    value::{iterator::Step, Value},
+    Runtime,
+};
+
+impl Runtime {
+    /// Runs the body once per value of the iterable, closing the iterator when left early.
+    pub fn eval_for_of(&mut self, statement: Positioned<ForOfStatement>) -> Value {
+        let statement = statement.value;
+        let span = statement.iterable.span;
+        let iterable = self.eval_expression(statement.iterable);
+        let iterable = self.resolve(span, iterable);
+
+        if let Value::Error(..) = iterable {
+            return iterable;
+        }
+
+        let Some(iterator) = self.get_iterator(span, &iterable) else {
+            return self.error.clone().unwrap_or(Value::None);
+        };
+
+        loop {
+            let value = match self.step(span, &iterator, Value::None) {
+                Some(Step::Next(value)) => value,
+                Some(Step::Done(_)) => return Value::None,
+                None => return self.error.clone().unwrap_or(Value::None),
+            };
+
+            self.add_scope("for");
+
+            let value = match self.bind_pattern(statement.binding.clone(), span.wrap(value)) {
+                error @ Value::Error(..) => error,
+                _ => {
+                    let span = statement.body.span;
+                    let value = self.eval_statement(statement.body.clone());
+
+                    self.resolve(span, value)
+                }
+            };
+
+            self.clear_scope_variables();
+            self.remove_scope();
+
+            if let Some(error) = &self.error {
+                let error = error.clone();
+
+                self.close_iterator(&iterator);
+
+                return error;
+            }
+
+            if matches!(value, Value::Error(..)) || value.is_returned() {
+                self.close_iterator(&iterator);
+
+                return value;
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use tsr_lexer::{globals::Positioned, token::Modifier};
 use tsr_parser::ast::FunctionDeclaration;
 
-use crate::{value::{Function, Parameter, Value, Visibility}, Runtime};
+use crate::{
+    value::{Function, Parameter, ParameterDefault, Value, Visibility},
+    Runtime,
+};
 
 impl Runtime {
     pub fn declare_function(&mut self, function: Positioned<FunctionDeclaration>) -> Value {
@@ -19,39 +22,42 @@ impl Runtime {
                 Modifier::Protected => visibility = Visibility::Protected,
                 Modifier::Async => is_async = true,
                 Modifier::Static => is_static = true,
+                Modifier::Readonly => {}
             }
         }
 
-        if let Some(body) = function.body {
-            let parameters = function
-                .parameters
-                .into_iter()
-                .map(|param| Parameter {
-                    name: param.value.name.value.0,
-                    nullable: param.value.nullable.value,
-                    ty: param.value.ty.value,
-                    default: param
-                        .value
-                        .default
-                        .map(|expression| Box::new(self.eval_expression(expression))),
-                })
-                .collect();
+        let parameters = function
+            .parameters
+            .into_iter()
+            .map(|param| Parameter {
+                name: param.value.name.value.0,
+                nullable: param.value.nullable.value,
+                ty: param.value.ty.value,
+                default: param.value.default.map(ParameterDefault::Expression),
+            })
+            .collect();
 
-            self.set_variable(
-                function.name.value.0.clone(),
-                span.wrap(Value::Function(Function {
-                    visibility,
-                    overloads: Vec::default(),
-                    is_async,
-                    is_static,
-                    name: function.name.value.0,
-                    parameters,
-                    ty: function.ty.value,
-                    body,
-                })),
-            );
-        }
+        self.set_variable(
+            function.name.value.0.clone(),
+            span.wrap(Value::Function(Function {
+                visibility,
+                overloads: Vec::default(),
+                is_async,
+                is_static,
+                is_generator: function.generator,
+                name: function.name.value.0,
+                type_parameters: function
+                    .type_parameters
+                    .into_iter()
+                    .map(|parameter| parameter.value)
+                    .collect(),
+                parameters,
+                ty: function.ty.value,
+                body: function.body,
+                scope: self.scope.clone(),
+            })),
+        );
 
         Value::None
     }
-}
\ No newline at end of file
+}
@@ -5,6 +5,35 @@ use crate::{value::Value, Runtime};
 
 impl Runtime {
     pub fn eval_if(&mut self, statement: Positioned<IfStatement>) -> Value {
-        Value::None
+        let statement = statement.value;
+        let span = statement.expression.span;
+        let condition = self.eval_expression(statement.expression);
+        let condition = self.resolve(span, condition);
+
+        if let Value::Error(..) = condition {
+            return condition;
+        }
+
+        let branch = match condition.is_truthy() {
+            true => Some(statement.then_statement),
+            false => statement.else_statement,
+        };
+
+        match branch {
+            Some(branch) => {
+                let span = branch.span;
+
+                self.add_scope("if");
+
+                let value = self.eval_statement(branch);
+                let value = self.resolve(span, value);
+
+                self.clear_scope_variables();
+                self.remove_scope();
+
+                value
+            }
+            None => Value::None,
+        }
     }
 }
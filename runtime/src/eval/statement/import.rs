@@ -1,77 +1,286 @@
-use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::{ImportClause, ImportDeclaration, Literal};
+use std::path::Path;
 
-use crate::{value::{builders::ObjectBuilder, ErrorCode, Value}, Runtime};
+use tsr_lexer::{
+    globals::{Positioned, Span},
+    Lexer,
+};
+use tsr_parser::{
+    ast::{ImportClause, ImportDeclaration, Literal},
+    Parser,
+};
+
+use crate::{
+    loader::ModuleRecord,
+    value::{native::Module, ErrorCode, Value},
+    Runtime,
+};
 
 impl Runtime {
     pub fn eval_import(&mut self, import: Positioned<ImportDeclaration>) -> Value {
         let (span, import) = (import.span, import.value);
 
-        let Literal::String(module) = import.module_specifier.value else {
+        let Literal::String(specifier) = import.module_specifier.value else {
             unreachable!()
         };
 
-        if let Some(module) = self
-            .modules
-            .iter()
-            .find(|native| native.name == module.value)
-        {
-            if let Some(clause) = import.import_clause {
-                match clause.value {
-                    ImportClause::Named(_) => {}
-                    ImportClause::NamedImports(value) => {
-                        for specifier in value {
-                            let specifier = specifier.value;
-                            let (name_span, name) = specifier
-                                .property_name
-                                .map(|alias| alias.unpack())
-                                .unwrap_or(specifier.name.clone().unpack());
-
-                            if let Some(value) = module
-                                .exports
-                                .iter()
-                                .find(|export| export.0 == specifier.name.value.0)
-                            {
-                                self.set_variable(name.0, span.wrap(value.1.clone()));
-                            } else {
-                                self.error = Some(Value::error(
-                                    name_span,
-                                    ErrorCode::Reference,
-                                    format!(
-                                        "found \"{}\" while excepted one of next values: {}",
-                                        specifier.name.value,
-                                        module
-                                            .exports
-                                            .iter()
-                                            .map(|specifier| specifier.0.as_str())
-                                            .collect::<Vec<_>>()
-                                            .join(", ")
-                                    ),
-                                ));
-
-                                break;
-                            }
-                        }
+        let Some(module) = self.import_module(specifier.span, &specifier.value) else {
+            // Type-only imports are erased, their module may only exist for the checker
+            if import.is_type_only.value {
+                self.error = None;
+            }
+
+            return Value::None;
+        };
+
+        if let Some(clause) = import.import_clause {
+            let type_only = import.is_type_only.value;
+
+            self.import_clause(span, clause.value, &module, &specifier.value, type_only);
+        }
+
+        Value::None
+    }
+
+    /// Binds the names `clause` imports from `module`, failures are left in the error of the
+    /// runtime.
+    fn import_clause(
+        &mut self,
+        span: Span,
+        clause: ImportClause,
+        module: &Module,
+        specifier: &str,
+        type_only: bool,
+    ) {
+        match clause {
+            ImportClause::Named(name) => {
+                self.import_type(module, "default", &name.value.0);
+
+                if type_only {
+                    return;
+                }
+
+                match module
+                    .exports
+                    .iter()
+                    .find(|(export, _)| export == "default")
+                {
+                    Some((_, value)) => {
+                        self.set_variable(name.value.0, name.span.wrap(value.clone()));
                     }
-                    ImportClause::NamespaceImport(value) => {
-                        let mut object = ObjectBuilder::default();
+                    None => {
+                        self.error = Some(Value::error(
+                            name.span,
+                            ErrorCode::Reference,
+                            format!("module \"{specifier}\" has no default export"),
+                        ))
+                    }
+                }
+            }
+            ImportClause::NamedImports(value) => {
+                for specifier in value {
+                    let specifier = specifier.value;
+                    let imported = specifier.property_name.as_ref().unwrap_or(&specifier.name);
 
-                        for (key, value) in &module.exports {
-                            object = object.prop(key, value.clone());
-                        }
+                    self.import_type(module, &imported.value.0, &specifier.name.value.0);
 
-                        self.set_variable(value.value.0, value.span.wrap(object.build()));
+                    if type_only || specifier.is_type_only.value {
+                        continue;
                     }
+
+                    match self.find_export(imported.span, module, &imported.value.0) {
+                        Some(value) => self.set_variable(specifier.name.value.0, span.wrap(value)),
+                        None => break,
+                    };
                 }
             }
-        } else {
+            ImportClause::NamespaceImport(value) if !type_only => {
+                self.set_variable(value.value.0, value.span.wrap(module.namespace()));
+            }
+            ImportClause::NamespaceImport(_) => {}
+            ImportClause::Combined(name, bindings) => {
+                self.import_clause(
+                    span,
+                    ImportClause::Named(name),
+                    module,
+                    specifier,
+                    type_only,
+                );
+
+                if self.error.is_none() {
+                    self.import_clause(span, bindings.value, module, specifier, type_only);
+                }
+            }
+        }
+    }
+
+    /// Declares the type `module` exports as `name` under the name it is imported as.
+    fn import_type(&self, module: &Module, name: &str, local: &str) {
+        if let Some((_, (type_parameters, ty))) =
+            module.types.iter().find(|(export, _)| export == name)
+        {
+            self.associate_type(local.into(), type_parameters.clone(), ty.clone());
+        }
+    }
+
+    /// Finds the native module named `specifier` or loads it from a file.
+    pub(crate) fn import_module(&mut self, span: Span, specifier: &str) -> Option<Module> {
+        match self.modules.iter().find(|native| native.name == specifier) {
+            Some(module) => Some(module.clone()),
+            None => self.load_module(span, specifier),
+        }
+    }
+
+    /// The value `module` exports as `name`, errors list the names it does export.
+    pub(crate) fn find_export(&mut self, span: Span, module: &Module, name: &str) -> Option<Value> {
+        match module.exports.iter().find(|export| export.0 == name) {
+            Some((_, value)) => Some(value.clone()),
+            None => {
+                self.error = Some(Value::error(
+                    span,
+                    ErrorCode::Reference,
+                    format!(
+                        "found \"{}\" while excepted one of next values: {}",
+                        name,
+                        module
+                            .exports
+                            .iter()
+                            .map(|specifier| specifier.0.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+
+                None
+            }
+        }
+    }
+
+    /// Resolves `specifier` with the module loader and evaluates the module the first
+    /// time it is imported, later imports share the cached module. Failures are left in
+    /// the error of the runtime.
+    fn load_module(&mut self, span: Span, specifier: &str) -> Option<Module> {
+        let Some(path) = self.loader.resolve(specifier, &self.directory) else {
             self.error = Some(Value::error(
-                module.span,
+                span,
                 ErrorCode::Reference,
-                format!("failed to resolve module \"{}\"", module.value),
+                match self.modules.is_empty() {
+                    true => format!("failed to resolve module \"{specifier}\""),
+                    false => format!(
+                        "failed to resolve module \"{specifier}\", native modules are: {}",
+                        self.modules
+                            .iter()
+                            .map(|module| module.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                },
             ));
+
+            return None;
+        };
+
+        match self.records.read().unwrap().get(&path) {
+            Some(ModuleRecord::Evaluated(module)) => return Some(module.clone()),
+            Some(ModuleRecord::Evaluating) => {
+                self.error = Some(Value::error(
+                    span,
+                    ErrorCode::Reference,
+                    format!("circular import of module \"{specifier}\""),
+                ));
+
+                return None;
+            }
+            None => {}
         }
 
-        Value::None
+        self.records
+            .write()
+            .unwrap()
+            .insert(path.clone(), ModuleRecord::Evaluating);
+
+        let module = self.eval_module(span, specifier, &path);
+
+        match &module {
+            Some(module) => self
+                .records
+                .write()
+                .unwrap()
+                .insert(path, ModuleRecord::Evaluated(module.clone())),
+            None => self.records.write().unwrap().remove(&path),
+        };
+
+        module
+    }
+
+    /// Evaluates the module at `path` in a context of its own.
+    fn eval_module(&mut self, span: Span, specifier: &str, path: &Path) -> Option<Module> {
+        let error = |message: String| {
+            Value::error(
+                span,
+                ErrorCode::Reference,
+                format!("failed to load module \"{specifier}\": {message}"),
+            )
+        };
+        let program = match self.loader.load(path) {
+            Ok(source) => match Lexer::lex_tokens(source.as_bytes().into()) {
+                Ok((_, tokens)) => Parser::parse_tokens(&tokens)
+                    .map(|(_, program)| program)
+                    .map_err(|failure| {
+                        // Parsing stops at the first statement it can't read
+                        let position = match failure {
+                            nom::Err::Error(failure) | nom::Err::Failure(failure) => {
+                                failure.input.tok.first().map(|token| token.span)
+                            }
+                            nom::Err::Incomplete(_) => None,
+                        };
+
+                        error(match position {
+                            Some(span) => {
+                                format!(
+                                    "invalid syntax at line {}, column {}",
+                                    span.line, span.column
+                                )
+                            }
+                            None => "invalid syntax".into(),
+                        })
+                    }),
+                Err(_) => Err(error("invalid syntax".into())),
+            },
+            Err(reason) => Err(error(reason.to_string())),
+        };
+        let program = match program {
+            Ok(program) => program,
+            Err(error) => {
+                self.error = Some(error);
+
+                return None;
+            }
+        };
+
+        // The module sees the globals of the importer, but declares everything in a scope
+        // of its own, which its functions take along when they are called from outside
+        let mut runtime = Runtime {
+            directory: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            scope: vec!["root".into(), format!("module:{}", path.display())],
+            // Jobs queued by the module run on the loop it shares with the importer
+            ..self.fork()
+        };
+
+        // Unlike a program, a module leaves its jobs and timers to the loop of the importer
+        let value = runtime.eval_code_block(program);
+
+        if let error @ Value::Error(..) = runtime.returned(value) {
+            self.error = Some(error);
+
+            return None;
+        }
+
+        let module = runtime.module_exports(path.display().to_string());
+
+        if module.is_none() {
+            self.error = runtime.error;
+        }
+
+        module
     }
 }
@@ -1,5 +1,5 @@
 use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::{InterfaceDeclaration, PropertyName, TypeMember};
+use tsr_parser::ast::{InterfaceDeclaration, PrimaryType, PropertyName, TypeMember};
 
 use crate::{
     value::{
@@ -12,6 +12,33 @@ use crate::{
 impl Runtime {
     pub fn declare_interface(&mut self, interface: Positioned<InterfaceDeclaration>) -> Value {
         let (span, interface) = interface.unpack();
+        let object = PrimaryType::ObjectType(
+            interface
+                .members
+                .iter()
+                .map(|member| member.value.clone())
+                .collect(),
+        );
+
+        self.associate_type(
+            interface.name.value.0.clone(),
+            interface
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect(),
+            match interface.extends.is_empty() {
+                true => object.into(),
+                false => interface
+                    .extends
+                    .iter()
+                    .map(|extend| PrimaryType::TypeReference(extend.value.clone(), vec![]))
+                    .chain([object])
+                    .collect::<Vec<_>>()
+                    .into(),
+            },
+        );
+
         let value = Value::Interface {
             name: interface.name.value.0.clone(),
             extends: interface
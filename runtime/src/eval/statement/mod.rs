@@ -6,10 +6,12 @@ use crate::{value::Value, Runtime};
 pub mod class;
 pub mod enumeration;
 pub mod export;
+pub mod for_of;
 pub mod function;
 pub mod if_else;
 pub mod import;
 pub mod interface;
+pub mod namespace;
 pub mod type_alias;
 pub mod variable;
 
@@ -23,9 +25,20 @@ impl Runtime {
             Statement::EnumDeclaration(enumeration) => self.declare_enum(enumeration),
             Statement::ExportDeclaration(export) => self.eval_export(export),
             Statement::ClassDeclaration(class) => self.declare_class(class),
+            Statement::NamespaceDeclaration(namespace) => self.eval_namespace(namespace),
+            Statement::AmbientDeclaration(declaration) => self.eval_ambient(*declaration),
             Statement::VariableStatement(variable) => self.declare_variable(variable),
             Statement::IfStatement(statement) => self.eval_if(*statement),
-            Statement::ReturnStatement(statement) => self.eval_expression(statement),
+            Statement::ForOfStatement(statement) => self.eval_for_of(*statement),
+            Statement::ReturnStatement(statement) => {
+                let span = statement.span;
+                let value = self.eval_expression(statement);
+
+                match self.resolve(span, value) {
+                    error @ Value::Error(..) => error,
+                    value => Value::ReturnValue(Box::new(value)),
+                }
+            }
             Statement::Expression(expression) => self.eval_expression(expression),
         }
     }
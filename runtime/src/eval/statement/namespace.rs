@@ -0,0 +1,225 @@
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{
+    AmbientDeclaration, BindingPattern, NamespaceDeclaration, PredefinedType, PrimaryType, Type,
+};
+
+use crate::{
+    value::{ErrorCode, Properties, Value},
+    Runtime,
+};
+
+impl Runtime {
+    /// Evaluates the body of a namespace in a scope of its own and merges what it exports
+    /// into the object bound to the namespace name, so a namespace may be declared in parts.
+    pub fn eval_namespace(&mut self, declaration: Positioned<NamespaceDeclaration>) -> Value {
+        let (span, declaration) = declaration.unpack();
+        let path = declaration
+            .name
+            .into_iter()
+            .map(|name| name.value.0)
+            .collect::<Vec<_>>();
+
+        // Exports inside the body belong to the namespace rather than the module
+        let exports = std::mem::take(&mut self.exports);
+
+        self.add_scope(format!("namespace:{}", path.join(".")));
+
+        let members = match self.eval_code_block(declaration.body) {
+            error @ Value::Error(..) => Err(error),
+            _ => self
+                .module_exports(path.join("."))
+                .map(|module| module.exports)
+                .ok_or_else(|| self.error.clone().unwrap_or(Value::None)),
+        };
+
+        // The members stay declared in the scope, where the functions of the namespace
+        // find their siblings when called
+        self.remove_scope();
+        self.exports = exports;
+
+        let members = match members {
+            Ok(members) => members,
+            Err(error) => return error,
+        };
+
+        let existing = self
+            .context
+            .lock()
+            .unwrap()
+            .get(&path[0], self.scope.clone())
+            .map(|variable| variable.value.clone());
+
+        match existing {
+            None | Some(Value::Object(_)) => {
+                let namespace = Self::merge_namespace(existing, &path[1..], members);
+
+                self.set_variable(&path[0], span.wrap(namespace))
+            }
+            Some(value) => Value::error(
+                span,
+                ErrorCode::Declaration,
+                format!(
+                    "namespace \"{}\" cannot merge with {}",
+                    path[0],
+                    value.type_of()
+                ),
+            ),
+        }
+    }
+
+    /// Adds `members` to the object nested at `path` inside `namespace`, an existing object
+    /// is extended in place so every alias of it sees the members.
+    fn merge_namespace(
+        namespace: Option<Value>,
+        path: &[String],
+        members: Vec<(String, Value)>,
+    ) -> Value {
+        let namespace = match namespace {
+            Some(namespace @ Value::Object(_)) => namespace,
+            _ => Value::object(Properties::new()),
+        };
+        let Value::Object(properties) = &namespace else {
+            unreachable!()
+        };
+
+        match path.split_first() {
+            Some((name, path)) => {
+                let key = Value::String(name.clone());
+                let nested = properties.read().get(&key).cloned();
+                let nested = Self::merge_namespace(nested, path, members);
+
+                properties.write().insert(key, nested);
+            }
+            None => properties.write().extend(
+                members
+                    .into_iter()
+                    .map(|(name, value)| (Value::String(name), value)),
+            ),
+        }
+
+        namespace
+    }
+
+    /// Checks an ambient declaration against the binding the host registered for it,
+    /// ambient declarations declare nothing themselves. A mismatch stops the program.
+    pub fn eval_ambient(&mut self, declaration: Positioned<AmbientDeclaration>) -> Value {
+        let value = self.check_ambient(declaration);
+
+        if let Value::Error(..) = value {
+            self.error = Some(value.clone());
+        }
+
+        value
+    }
+
+    fn check_ambient(&mut self, declaration: Positioned<AmbientDeclaration>) -> Value {
+        match declaration.value {
+            AmbientDeclaration::VariableStatement(variable) => {
+                for declaration in variable.value.declarations {
+                    let (span, declaration) = declaration.unpack();
+                    // Names taken out of a pattern aren't given a type of their own
+                    let ty = match (&declaration.name.value, declaration.ty) {
+                        (BindingPattern::Ident(_), Some(ty)) => Some(ty.value),
+                        _ => None,
+                    };
+                    let ty = match (ty, declaration.nullable.value) {
+                        (Some(ty), true) => Type::union(vec![ty, PredefinedType::Undefined.into()]),
+                        (Some(ty), false) => ty,
+                        (None, _) => PredefinedType::Any.into(),
+                    };
+
+                    for name in BindingPattern::names(&declaration.name) {
+                        if let error @ Value::Error(..) =
+                            self.check_host_binding(span, &[name.value.0], &ty)
+                        {
+                            return error;
+                        }
+                    }
+                }
+
+                Value::None
+            }
+            AmbientDeclaration::FunctionDeclaration(function) => {
+                let (span, function) = function.unpack();
+                let ty = function.ty();
+
+                self.check_host_binding(span, &[function.name.value.0], &ty)
+            }
+            AmbientDeclaration::NamespaceDeclaration(namespace) => {
+                let (span, namespace) = namespace.unpack();
+                let ty =
+                    PrimaryType::ObjectType(AmbientDeclaration::members(&namespace.body.value));
+                let path = namespace
+                    .name
+                    .into_iter()
+                    .map(|name| name.value.0)
+                    .collect::<Vec<_>>();
+
+                self.check_host_binding(span, &path, &ty.into())
+            }
+            AmbientDeclaration::ModuleDeclaration(module) => {
+                let (span, module) = module.unpack();
+                let ty: Type =
+                    PrimaryType::ObjectType(AmbientDeclaration::members(&module.body.value)).into();
+
+                let Some(native) = self
+                    .modules
+                    .iter()
+                    .find(|native| native.name == module.name.value)
+                else {
+                    return Value::error(
+                        module.name.span,
+                        ErrorCode::Reference,
+                        format!(
+                            "module \"{}\" is declared but not provided by the host",
+                            module.name.value
+                        ),
+                    );
+                };
+
+                match native.namespace().check_type(&ty, self) {
+                    Ok(()) => Value::None,
+                    Err(mismatch) => Value::error(
+                        span,
+                        ErrorCode::Type,
+                        format!(
+                            "declaration of module \"{}\" does not match the host: {mismatch}",
+                            module.name.value
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Checks the value the host bound at `path` against the declared type.
+    fn check_host_binding(&self, span: Span, path: &[String], ty: &Type) -> Value {
+        let name = path.join(".");
+        let mut value = self
+            .context
+            .lock()
+            .unwrap()
+            .get(&path[0], self.scope.clone())
+            .map(|variable| variable.value.clone());
+
+        for key in &path[1..] {
+            value = value.and_then(|value| value.get_member(key));
+        }
+
+        match value {
+            Some(value) => match value.check_type(ty, self) {
+                Ok(()) => Value::None,
+                Err(mismatch) => Value::error(
+                    span,
+                    ErrorCode::Type,
+                    format!("declaration of \"{name}\" does not match the host: {mismatch}"),
+                ),
+            },
+            None => Value::error(
+                span,
+                ErrorCode::Reference,
+                format!("\"{name}\" is declared but not provided by the host"),
+            ),
+        }
+    }
+}
@@ -6,6 +6,16 @@ use crate::{value::Value, Runtime};
 impl Runtime {
     pub fn declare_type_alias(&mut self, type_alias: Positioned<TypeAliasDeclaration>) -> Value {
         let (span, type_alias) = type_alias.unpack();
+        self.associate_type(
+            type_alias.name.value.0.clone(),
+            type_alias
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.value.clone())
+                .collect(),
+            type_alias.ty.value.clone(),
+        );
+
         let value = Value::TypeAlias {
             name: type_alias.name.value.0.clone(),
             ty: type_alias.ty.value,
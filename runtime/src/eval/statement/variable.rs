@@ -1,17 +1,16 @@
 use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::VariableStatement;
+use tsr_parser::ast::{ArraySize, BindingPattern, VariableStatement};
 
 use crate::{
-    value::{ErrorCode, Value},
+    value::{iterator::Step, ErrorCode, Value},
     Runtime,
 };
 
 impl Runtime {
     pub fn declare_variable(&mut self, variables: Positioned<VariableStatement>) -> Value {
-        let (span, variables) = variables.unpack();
         let mut last = Value::None;
 
-        for variable in variables.declarations {
+        for variable in variables.value.declarations {
             let (span, variable) = variable.unpack();
 
             if !variable.nullable.value && variable.initializer.is_none() {
@@ -19,30 +18,106 @@ impl Runtime {
             }
 
             let (value_span, value) = if let Some(expression) = variable.initializer {
-                (
-                    expression.span,
-                    match self.eval_expression(expression) {
-                        Value::Reference(path, scope) => todo!(),
-                        value => value,
-                    },
-                )
+                let span = expression.span;
+                let value = self.eval_expression(expression);
+
+                (span, self.resolve(span, value))
             } else {
                 (span, Value::None)
             };
 
             if let Some(ty) = variable.ty {
-                if !value.is_type_of(&ty.value) {
+                if let Err(mismatch) = value.check_type(&ty.value, self) {
                     return Value::error(
                         value_span,
                         ErrorCode::Type,
-                        format!("expected {}, but got {}", ty.value, value.value_type_of()),
+                        format!(
+                            "expected {}, but got {}{}",
+                            ty.value,
+                            value.value_type_of(),
+                            mismatch.note()
+                        ),
                     );
                 }
             }
 
-            last = self.set_variable(variable.name.value.0, value_span.wrap(value));
+            last = self.bind_pattern(variable.name, value_span.wrap(value));
+
+            if let Value::Error(..) = last {
+                return last;
+            }
         }
 
         last
     }
+
+    /// Binds the names of `pattern` in the current scope.
+    pub fn bind_pattern(
+        &mut self,
+        pattern: Positioned<BindingPattern>,
+        value: Positioned<Value>,
+    ) -> Value {
+        let (elements, rest) = match pattern.value {
+            BindingPattern::Ident(name) => return self.set_variable(name.0, value),
+            BindingPattern::Array { elements, rest } => (elements, rest),
+        };
+        let (span, value) = value.unpack();
+
+        let Some(iterator) = self.get_iterator(span, &value) else {
+            return self.error.clone().unwrap_or(Value::None);
+        };
+        let mut done = false;
+
+        for element in elements {
+            let value = match done {
+                true => Value::None,
+                false => match self.step(span, &iterator, Value::None) {
+                    Some(Step::Next(value)) => value,
+                    Some(Step::Done(_)) => {
+                        done = true;
+
+                        Value::None
+                    }
+                    None => return self.error.clone().unwrap_or(Value::None),
+                },
+            };
+
+            // Holes skip a value
+            let Some(element) = element else {
+                continue;
+            };
+
+            if let error @ Value::Error(..) = self.bind_pattern(element, span.wrap(value)) {
+                self.close_iterator(&iterator);
+
+                return error;
+            }
+        }
+
+        match rest {
+            Some(rest) => {
+                let mut values = vec![];
+
+                while !done {
+                    match self.step(span, &iterator, Value::None) {
+                        Some(Step::Next(value)) => values.push(value),
+                        Some(Step::Done(_)) => done = true,
+                        None => return self.error.clone().unwrap_or(Value::None),
+                    }
+                }
+
+                self.set_variable(
+                    rest.value.0,
+                    rest.span.wrap(Value::array(values, ArraySize::Dynamic)),
+                )
+            }
+            None => {
+                if !done {
+                    self.close_iterator(&iterator);
+                }
+
+                Value::None
+            }
+        }
+    }
 }
@@ -0,0 +1,382 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::{self, Debug, Formatter},
+    rc::{Rc, Weak},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tsr_lexer::globals::Span;
+
+use crate::{
+    clock::{Clock, SystemClock},
+    value::{
+        heap::Shared,
+        promise::{Outcome, Promise},
+        ErrorCode, Value,
+    },
+    Runtime,
+};
+
+/// Work queued on the event loop, like the reactions of a settled promise.
+pub type Job = Box<dyn FnOnce(&mut Runtime)>;
+
+enum Settlement {
+    Fulfilled(Box<dyn FnOnce() -> Value + Send>),
+    Rejected(Box<dyn FnOnce() -> Value + Send>),
+    /// The resolver was dropped without settling the promise
+    Abandoned,
+}
+
+type Message = (usize, Settlement);
+
+#[derive(Clone)]
+struct Timer {
+    callback: Value,
+    arguments: Vec<Value>,
+    interval: Option<Duration>,
+}
+
+enum Wait {
+    No,
+    For(Duration),
+    Forever,
+}
+
+struct Queue {
+    jobs: VecDeque<Job>,
+    hosted: HashMap<usize, Shared<Promise>>,
+    next_id: usize,
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    unhandled: Vec<Shared<Promise>>,
+    timers: BTreeMap<(Duration, usize), Timer>,
+    next_timer_id: usize,
+    clock: Arc<dyn Clock>,
+    failures: Vec<Value>,
+}
+
+/// The microtask queue of a runtime, shared by its clones and the modules it imports.
+#[derive(Clone)]
+pub struct EventLoop(Rc<RefCell<Queue>>);
+
+impl Default for EventLoop {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self(Rc::new(RefCell::new(Queue {
+            jobs: VecDeque::new(),
+            hosted: HashMap::new(),
+            next_id: 0,
+            sender,
+            receiver,
+            unhandled: vec![],
+            timers: BTreeMap::new(),
+            next_timer_id: 1,
+            clock: Arc::new(SystemClock::default()),
+            failures: vec![],
+        })))
+    }
+}
+
+impl Debug for EventLoop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventLoop").finish_non_exhaustive()
+    }
+}
+
+impl EventLoop {
+    /// A pending promise for scripts and the resolver the host settles it with.
+    pub fn promise(&self) -> (Value, PromiseResolver) {
+        let promise = Promise::pending(self);
+        let mut queue = self.0.borrow_mut();
+        let id = queue.next_id;
+
+        queue.next_id += 1;
+        queue.hosted.insert(id, promise.clone());
+
+        (
+            Value::Promise(promise),
+            PromiseResolver {
+                id,
+                sender: Some(queue.sender.clone()),
+            },
+        )
+    }
+
+    pub(crate) fn downgrade(&self) -> WeakEventLoop {
+        WeakEventLoop(Rc::downgrade(&self.0))
+    }
+
+    /// Whether timers are pending or promises are still waiting on the host.
+    pub fn is_waiting(&self) -> bool {
+        let queue = self.0.borrow();
+
+        !queue.timers.is_empty() || !queue.hosted.is_empty()
+    }
+
+    pub(crate) fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.0.borrow_mut().clock = clock;
+    }
+
+    pub(crate) fn add_timer(
+        &self,
+        callback: Value,
+        arguments: Vec<Value>,
+        delay: Duration,
+        repeat: bool,
+    ) -> usize {
+        let mut queue = self.0.borrow_mut();
+        let id = queue.next_timer_id;
+        let due = queue.clock.now() + delay;
+
+        queue.next_timer_id += 1;
+        queue.timers.insert(
+            (due, id),
+            Timer {
+                callback,
+                arguments,
+                interval: repeat.then_some(delay),
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn clear_timer(&self, id: usize) {
+        self.0
+            .borrow_mut()
+            .timers
+            .retain(|(_, timer), _| *timer != id);
+    }
+
+    pub(crate) fn now(&self) -> Duration {
+        self.0.borrow().clock.now()
+    }
+
+    fn due_timer(&self, deadline: Option<Duration>) -> Option<Timer> {
+        let mut queue = self.0.borrow_mut();
+        let now = queue.clock.now();
+        let entry = queue
+            .timers
+            .first_entry()
+            .filter(|entry| entry.key().0 <= deadline.unwrap_or(now))?;
+        let ((_, id), timer) = entry.remove_entry();
+
+        if let Some(interval) = timer.interval {
+            // Like in Node, ticks an interval missed are skipped rather than run late
+            queue.timers.insert((now + interval, id), timer.clone());
+        }
+
+        Some(timer)
+    }
+
+    fn wait_for_timer(&self) -> bool {
+        let Some((due, clock)) = ({
+            let queue = self.0.borrow();
+
+            queue
+                .timers
+                .first_key_value()
+                .map(|((due, _), _)| (*due, queue.clock.clone()))
+        }) else {
+            return false;
+        };
+
+        self.receive(Wait::For(due.saturating_sub(clock.now())))
+            || clock.sleep(due.saturating_sub(clock.now()))
+    }
+
+    fn next_job(&self) -> Option<Job> {
+        self.0.borrow_mut().jobs.pop_front()
+    }
+
+    fn receive(&self, wait: Wait) -> bool {
+        let (id, settlement) = {
+            let queue = self.0.borrow();
+
+            if queue.hosted.is_empty() {
+                return false;
+            }
+
+            let message = match wait {
+                Wait::No => queue.receiver.try_recv().ok(),
+                Wait::For(timeout) => queue.receiver.recv_timeout(timeout).ok(),
+                Wait::Forever => queue.receiver.recv().ok(),
+            };
+
+            match message {
+                Some(message) => message,
+                None => return false,
+            }
+        };
+        let Some(promise) = self.0.borrow_mut().hosted.remove(&id) else {
+            return true;
+        };
+
+        Promise::settle(
+            &promise,
+            match settlement {
+                Settlement::Fulfilled(value) => Outcome::Fulfilled(value()),
+                Settlement::Rejected(reason) => Outcome::Rejected(reason()),
+                Settlement::Abandoned => Outcome::Rejected(Value::error(
+                    Span::default(),
+                    ErrorCode::Type,
+                    "the host dropped the promise without settling it",
+                )),
+            },
+        );
+
+        true
+    }
+
+    fn fail(&self, error: Value) {
+        self.0.borrow_mut().failures.push(error);
+    }
+
+    fn take_error(&self) -> Option<Value> {
+        let (failures, unhandled) = {
+            let mut queue = self.0.borrow_mut();
+
+            (
+                std::mem::take(&mut queue.failures),
+                std::mem::take(&mut queue.unhandled),
+            )
+        };
+
+        failures.into_iter().next().or_else(|| {
+            unhandled
+                .into_iter()
+                .find_map(|promise| match promise.read().outcome() {
+                    Some(Outcome::Rejected(reason)) => {
+                        Some(Value::rejection(Span::default(), reason.clone()))
+                    }
+                    _ => None,
+                })
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct WeakEventLoop(Weak<RefCell<Queue>>);
+
+impl WeakEventLoop {
+    pub(crate) fn enqueue(&self, job: Job) {
+        if let Some(queue) = self.0.upgrade() {
+            queue.borrow_mut().jobs.push_back(job);
+        }
+    }
+
+    pub(crate) fn reject_unhandled(&self, promise: &Shared<Promise>) {
+        if let Some(queue) = self.0.upgrade() {
+            queue.borrow_mut().unhandled.push(promise.clone());
+        }
+    }
+
+    pub(crate) fn handle(&self, promise: &Shared<Promise>) {
+        if let Some(queue) = self.0.upgrade() {
+            queue
+                .borrow_mut()
+                .unhandled
+                .retain(|unhandled| unhandled != promise);
+        }
+    }
+}
+
+/// Settles a promise given to scripts by [`EventLoop::promise`], from any thread.
+pub struct PromiseResolver {
+    id: usize,
+    sender: Option<Sender<Message>>,
+}
+
+impl PromiseResolver {
+    pub fn resolve<T: Into<Value> + Send + 'static>(mut self, value: T) {
+        self.send(Settlement::Fulfilled(Box::new(move || value.into())));
+    }
+
+    pub fn reject<T: Into<Value> + Send + 'static>(mut self, reason: T) {
+        self.send(Settlement::Rejected(Box::new(move || reason.into())));
+    }
+
+    fn send(&mut self, settlement: Settlement) {
+        if let Some(sender) = self.sender.take() {
+            // The runtime may be gone already, then nobody waits for the promise
+            let _ = sender.send((self.id, settlement));
+        }
+    }
+}
+
+impl Drop for PromiseResolver {
+    fn drop(&mut self) {
+        self.send(Settlement::Abandoned);
+    }
+}
+
+impl Runtime {
+    /// Runs the queued jobs, along with the jobs they queue, until none are left.
+    pub fn run_microtasks(&mut self) {
+        while let Some(job) = self.event_loop.next_job() {
+            job(self);
+        }
+    }
+
+    /// Runs the event loop until nothing is left to do, waiting for timers and the host.
+    pub fn run_event_loop(&mut self) -> Value {
+        while self.turn_event_loop(None) {}
+
+        self.event_loop.take_error().unwrap_or(Value::None)
+    }
+
+    /// Runs everything ready by now without waiting, for hosts driving the loop themselves.
+    pub fn run_until_idle(&mut self) -> Value {
+        let deadline = self.event_loop.now();
+
+        while self.turn_event_loop(Some(deadline)) {}
+
+        self.event_loop.take_error().unwrap_or(Value::None)
+    }
+
+    /// Whether timers are pending or promises still wait on the host.
+    pub fn is_waiting(&self) -> bool {
+        self.event_loop.is_waiting()
+    }
+
+    pub(crate) fn turn_event_loop(&mut self, deadline: Option<Duration>) -> bool {
+        if let Some(job) = self.event_loop.next_job() {
+            job(self);
+
+            return true;
+        }
+
+        if let Some(timer) = self.event_loop.due_timer(deadline) {
+            if self
+                .call_back(Span::default(), &timer.callback, timer.arguments)
+                .is_none()
+            {
+                let error = self.error.take().unwrap_or(Value::None);
+
+                self.event_loop.fail(error);
+            }
+
+            return true;
+        }
+
+        match deadline {
+            None => {
+                self.event_loop.receive(Wait::No)
+                    || self.event_loop.wait_for_timer()
+                    || self.event_loop.receive(Wait::Forever)
+            }
+            Some(_) => self.event_loop.receive(Wait::No),
+        }
+    }
+
+    /// A pending promise for scripts and the resolver the host settles it with.
+    pub fn promise(&self) -> (Value, PromiseResolver) {
+        self.event_loop.promise()
+    }
+}
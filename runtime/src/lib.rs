@@ -1,24 +1,35 @@
 pub use self::value::builders::FunctionBuilder;
 use self::{
-    environment::{Context, Environment, Scope},
+    clock::Clock,
+    environment::{Context, Environment, Scope, Variable},
+    event_loop::{EventLoop, PromiseResolver},
+    loader::{FileLoader, ModuleLoader, ModuleRecord},
     value::{
         native::{Module, NativeModule},
-        ErrorCode, Function, NativeFunction, Parameter, Signature, Value, Visibility,
+        ErrorCode, Export, Function, NativeFunction, Parameter, Signature, Value, Visibility,
     },
 };
 use std::{
     collections::HashMap,
+    path::PathBuf,
+    rc::Rc,
     sync::{Arc, RwLock},
 };
-use tsr_lexer::globals::Positioned;
-use tsr_parser::ast::{Block, Type};
+use tsr_lexer::globals::{Positioned, Span};
+use tsr_parser::ast::{Block, Type, TypeParameter};
 
 pub mod api;
+pub mod clock;
+pub mod coroutine;
 pub mod environment;
 pub mod eval;
+pub mod event_loop;
+pub mod loader;
+pub mod types;
 pub mod value;
 
 pub type FunctionTuple = (Visibility, bool, bool, bool, String, Vec<Parameter>, Type);
+pub type TypeDefinition = (Vec<TypeParameter>, Type);
 
 macro_rules! try_unpack {
     ($variant:path, $value:expr) => {
@@ -35,6 +46,7 @@ pub struct FArguments {
     context: Context,
     scope: Scope,
     returns: Option<Value>,
+    event_loop: EventLoop,
 }
 
 impl FArguments {
@@ -63,6 +75,16 @@ impl FArguments {
         self.returns = Some(value.into());
     }
 
+    /// A pending promise to return to the script and the resolver settling it.
+    pub fn promise(&self) -> (Value, PromiseResolver) {
+        self.event_loop.promise()
+    }
+
+    /// The event loop of the runtime calling the function.
+    pub fn event_loop(&self) -> &EventLoop {
+        &self.event_loop
+    }
+
     pub fn get_interface<N: AsRef<str>>(
         &self,
         name: N,
@@ -117,6 +139,15 @@ impl FArguments {
         try_unpack!(Value::String, self.get(name)).unwrap()
     }
 
+    /// A numeric argument as a float, whether it was given as an integer or not.
+    pub fn get_float<N: AsRef<str>>(&self, name: N) -> f64 {
+        match self.get(name) {
+            Some(Value::Number(number)) => number as f64,
+            Some(Value::Float(float)) => float,
+            _ => f64::NAN,
+        }
+    }
+
     pub fn get_number_opt<N: AsRef<str>>(&self, name: N) -> Option<i64> {
         try_unpack!(Value::Number, self.get(name))
     }
@@ -132,33 +163,69 @@ impl FArguments {
 
 #[derive(Debug, Clone)]
 pub struct Runtime {
-    types: Arc<RwLock<HashMap<String, Type>>>,
     context: Context,
-    modules: Vec<Module>,
+    modules: Rc<Vec<Module>>,
+    loader: Arc<dyn ModuleLoader>,
+    records: Arc<RwLock<HashMap<PathBuf, ModuleRecord>>>,
+    /// Directory of the module being evaluated, relative imports are resolved against it
+    directory: PathBuf,
+    /// Exports declared by the program, resolved once it has been evaluated
+    exports: Vec<Positioned<Export>>,
     error: Option<Value>,
     scope: Scope,
+    coroutine: Option<Rc<coroutine::Suspender>>,
+    task: Option<Rc<coroutine::Suspender>>,
+    rejected: Option<(Value, Value)>,
+    event_loop: EventLoop,
 }
 
 impl Default for Runtime {
     fn default() -> Self {
-        Self {
-            types: Arc::new(RwLock::new(HashMap::new())),
-            context: Environment::new(),
-            modules: Default::default(),
-            scope: vec!["root".into()],
-            error: None,
-        }
+        Self::new(Environment::new())
     }
 }
 
 impl Runtime {
     pub fn new(context: Context) -> Self {
         Self {
-            types: Arc::new(RwLock::new(HashMap::new())),
             context,
             modules: Default::default(),
+            loader: Arc::new(FileLoader),
+            records: Default::default(),
+            directory: PathBuf::from("."),
+            exports: vec![],
             scope: vec!["root".into()],
             error: None,
+            coroutine: None,
+            task: None,
+            rejected: None,
+            event_loop: EventLoop::default(),
+        }
+    }
+
+    /// A runtime with the standard globals installed.
+    pub fn with_std() -> Self {
+        let runtime = Self::default();
+
+        api::prelude::install(&runtime);
+
+        runtime
+    }
+
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            modules: self.modules.clone(),
+            loader: self.loader.clone(),
+            records: self.records.clone(),
+            directory: self.directory.clone(),
+            exports: vec![],
+            error: None,
+            scope: self.scope.clone(),
+            coroutine: None,
+            task: None,
+            rejected: None,
+            event_loop: self.event_loop.clone(),
         }
     }
 
@@ -172,35 +239,88 @@ impl Runtime {
         self.scope.pop();
     }
 
+    /// Moves into `scope` unless the current scope is already inside of it, returning the
+    /// scope to go back to with [`Runtime::leave_scope`].
+    fn enter_scope(&mut self, scope: &Scope) -> Option<Scope> {
+        match self.scope.starts_with(scope) {
+            true => None,
+            false => Some(std::mem::replace(&mut self.scope, scope.clone())),
+        }
+    }
+
+    fn leave_scope(&mut self, caller: Option<Scope>) {
+        if let Some(scope) = caller {
+            self.scope = scope;
+        }
+    }
+
     fn clear_scope_variables(&mut self) {
         self.context
             .lock()
             .unwrap()
             .remove_by_scope(self.scope.clone());
+
+        // Generators cleared with the scope are unwound now that the context is unlocked
+        coroutine::close_dropped();
     }
 
-    fn clear_scope_variables_filtered(&mut self, variables: &[String]) {
+    /// Declares the type `name` in the current scope.
+    fn associate_type(&self, name: String, type_parameters: Vec<TypeParameter>, ty: Type) {
         self.context
             .lock()
             .unwrap()
-            .remove_by_scope_filtered(self.scope.clone(), variables);
+            .set_type(name, self.scope.clone(), (type_parameters, ty));
     }
 
-    // fn associate_type(&self, name: String, ty: Type) {
-    //     self.types.write().unwrap().insert(name, ty);
-    // }
+    /// Looks up the type declared under `name` by an alias, interface, class or enum visible
+    /// from the current scope and instantiates it with the given type arguments.
+    pub fn resolve_type(&self, name: &str, arguments: &[Type]) -> Option<Type> {
+        let (type_parameters, ty) = self
+            .context
+            .lock()
+            .unwrap()
+            .get_type(name, self.scope.clone())
+            .cloned()?;
 
-    // fn resolve_type(&self, name: String) -> Option<Type> {
-    //     self.types.read().unwrap().get(&name).cloned()
-    // }
+        Some(ty.substitute(&TypeParameter::bind(
+            &type_parameters,
+            TypeParameter::zip(&type_parameters, arguments),
+        )))
+    }
+
+    /// Finds the method `name` declared by the class `class`.
+    pub fn get_class_method(&self, class: &str, name: &str) -> Option<Function> {
+        match self.context.lock().unwrap().get(class, self.scope.clone()) {
+            Some(Variable {
+                value: Value::Class { methods, .. },
+                ..
+            }) => methods.iter().find(|method| method.name == name).cloned(),
+            _ => None,
+        }
+    }
 
     pub fn add_module<M: NativeModule>(&mut self, module: &'static M) {
-        self.modules.push(module.build_module());
+        Rc::make_mut(&mut self.modules).push(module.build_module());
+    }
+
+    /// Replaces the [`FileLoader`] used for imports that aren't native modules.
+    pub fn set_loader<L: ModuleLoader + 'static>(&mut self, loader: L) {
+        self.loader = Arc::new(loader);
+    }
+
+    /// Sets the directory relative imports of the program are resolved against.
+    pub fn set_directory<P: Into<PathBuf>>(&mut self, directory: P) {
+        self.directory = directory.into();
+    }
+
+    /// Replaces the clock timers are scheduled with.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.event_loop.set_clock(Arc::new(clock));
     }
 
     pub fn set_variable<N: AsRef<str>>(&self, name: N, value: Positioned<Value>) -> Value {
         let (span, value) = value.unpack();
-        let name = [name.as_ref()];
+        let name = name.as_ref();
 
         if let Value::Error { .. } = value {
             return value;
@@ -209,11 +329,14 @@ impl Runtime {
         let mut context = self.context.lock().unwrap();
 
         if let Some(Value::Function(function)) = context
-            .get_mut(name[0], self.scope.clone())
+            .get_declared_mut(name, &self.scope)
             .map(|variable| &mut variable.value)
         {
             if let Value::Function(func) = value {
-                function.overloads.push(func);
+                // Signatures declared before collect into the overloads of the latest one
+                let overloads = function.signatures();
+
+                *function = Function { overloads, ..func };
             } else {
                 return Value::error(
                     span,
@@ -222,7 +345,7 @@ impl Runtime {
                 );
             }
         } else {
-            context.set(&name, self.scope.clone(), value);
+            context.declare(name, self.scope.clone(), value);
         }
 
         Value::None
@@ -231,7 +354,11 @@ impl Runtime {
     fn eval_code_block(&mut self, mut block: Block) -> Value {
         match block.value.len() {
             0 => Value::None,
-            1 => self.eval_statement(block.value.remove(0)),
+            1 => {
+                let value = self.eval_statement(block.value.remove(0));
+
+                self.error.clone().unwrap_or(value)
+            }
             _ => {
                 let statement = block.value.remove(0);
                 let value = self.eval_statement(statement);
@@ -259,18 +386,54 @@ impl Runtime {
     }
 
     pub fn eval_program(&mut self, program: Block) -> Value {
-        let value = self.eval_code_block(program);
+        let value = match self.eval_code_block(program) {
+            error @ Value::Error(..) => error,
+            // Like in JavaScript, jobs queued by the program run once it has been evaluated
+            value => match self.run_event_loop() {
+                Value::None => value,
+                error => error,
+            },
+        };
+
+        // Cycles left behind by the program would never be freed otherwise
+        Self::collect_garbage();
+        coroutine::close_dropped();
 
         self.returned(value)
     }
+
+    /// Frees objects, arrays and instances kept alive only by references among themselves,
+    /// returns how many were freed. Reference counting frees everything else on its own.
+    pub fn collect_garbage() -> usize {
+        value::heap::collect_cycles()
+    }
 }
 
 impl Runtime {
-    fn is_array(&self, value: Value) -> bool {
+    /// Dereferences a [`Value::Reference`] produced by identifiers and member
+    /// accesses, returning any other value untouched.
+    fn resolve(&self, span: Span, value: Value) -> Value {
         match value {
-            Value::Array(..) => true,
-            Value::Reference(path, scope) => todo!(),
-            _ => false,
+            Value::Reference(path, scope) => {
+                let variable = self
+                    .context
+                    .lock()
+                    .unwrap()
+                    .get(&path[0], scope)
+                    .map(|variable| variable.value.clone());
+
+                match variable {
+                    Some(value) => path[1..].iter().fold(value, |value, key| {
+                        self.get_property(span, value, Value::String(key.clone()))
+                    }),
+                    None => Value::error(
+                        span,
+                        ErrorCode::Reference,
+                        format!("\"{}\" is not defined", path[0]),
+                    ),
+                }
+            }
+            value => value,
         }
     }
 }
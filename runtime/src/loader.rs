@@ -0,0 +1,67 @@
+use std::{
+    fmt::Debug,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::value::native::Module;
+
+/// Finds and reads the source of modules imported by path, native modules registered
+/// with [`Runtime::add_module`](crate::Runtime::add_module) are looked up before it.
+pub trait ModuleLoader: Debug + Send + Sync {
+    /// Resolves `specifier` imported from a module in `directory` to the path identifying
+    /// the module, `None` when no module matches.
+    fn resolve(&self, specifier: &str, directory: &Path) -> Option<PathBuf>;
+
+    /// Reads the source of a module returned by [`ModuleLoader::resolve`].
+    fn load(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Loads modules from the file system. Relative specifiers may leave out the `.ts` or
+/// `.tsx` extension or point to a directory with an `index.ts` file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FileLoader;
+
+impl FileLoader {
+    const EXTENSIONS: [&'static str; 2] = ["ts", "tsx"];
+}
+
+impl ModuleLoader for FileLoader {
+    fn resolve(&self, specifier: &str, directory: &Path) -> Option<PathBuf> {
+        if !["./", "../", "/"]
+            .iter()
+            .any(|prefix| specifier.starts_with(prefix))
+        {
+            return None;
+        }
+
+        let base = directory.join(specifier);
+        let with_extension = Self::EXTENSIONS.iter().map(|extension| {
+            let mut path = base.clone().into_os_string();
+
+            path.push(format!(".{extension}"));
+            PathBuf::from(path)
+        });
+        let index = Self::EXTENSIONS
+            .iter()
+            .map(|extension| base.join(format!("index.{extension}")));
+
+        std::iter::once(base.clone())
+            .chain(with_extension)
+            .chain(index)
+            .find(|path| path.is_file())
+            .and_then(|path| path.canonicalize().ok())
+    }
+
+    fn load(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+}
+
+/// State of a module loaded from a [`ModuleLoader`], cached by its resolved path.
+#[derive(Debug, Clone)]
+pub enum ModuleRecord {
+    /// The module is being evaluated, importing it again means the imports are circular
+    Evaluating,
+    Evaluated(Module),
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use tsr_lexer::{globals::Span, token::Modifier};
+use tsr_parser::ast::{
+    ConditionalType, Ident, IndexSignature, IntersectionOrPrimaryType, Literal, MappedType,
+    PredefinedType, PrimaryType, PropertyName, PropertySignature, Type, TypeMember,
+    UnionOrIntersectionOrPrimaryType,
+};
+
+use crate::{value::Value, Runtime};
+
+impl Runtime {
+    /// Reduces type operators (`typeof`, `keyof`, indexed access, mapped and conditional
+    /// types) to the type they stand for, any other type is returned unchanged.
+    pub fn evaluate_type(&self, ty: &Type) -> Type {
+        if let Type::ConditionalType(conditional) = ty {
+            return self.evaluate_conditional(conditional);
+        }
+
+        match ty.as_primary() {
+            Some(PrimaryType::ReadonlyType(ty)) => self.evaluate_type(&(**ty).clone().into()),
+            Some(PrimaryType::TypeQuery(path)) => {
+                let path = path.iter().map(|name| name.0.clone()).collect();
+
+                match self.resolve(Span::default(), Value::Reference(path, self.scope.clone())) {
+                    Value::Error(..) => PredefinedType::Any.into(),
+                    value => value.value_type_of(),
+                }
+            }
+            Some(PrimaryType::KeyOfType(ty)) => self.keys_of(&(**ty).clone().into()),
+            Some(PrimaryType::IndexedAccessType(ty, index)) => {
+                let object = (**ty).clone().into();
+                let index = self.evaluate_type(index);
+
+                Type::union(
+                    index
+                        .union_members()
+                        .iter()
+                        .map(|index| self.index_type(&object, index))
+                        .collect(),
+                )
+            }
+            Some(PrimaryType::MappedType(mapped)) => self.evaluate_mapped(mapped),
+            Some(primary) => primary.clone().into(),
+            None => ty.clone(),
+        }
+    }
+
+    /// Returns the members of an object-like type, following type references and
+    /// intersections.
+    pub fn members_of(&self, ty: &Type) -> Option<Vec<TypeMember>> {
+        match self.resolve_reference(&self.evaluate_type(ty)) {
+            Type::UnionOrIntersectionOrPrimaryType(
+                UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                    IntersectionOrPrimaryType::IntersectionType(types),
+                ),
+            ) => Some(
+                types
+                    .into_iter()
+                    .filter_map(|ty| self.members_of(&ty.into()))
+                    .flatten()
+                    .collect(),
+            ),
+            ty => match ty.as_primary() {
+                Some(PrimaryType::ObjectType(members)) => Some(members.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Checks whether `source` extends `target`, as used by conditional types.
+    pub fn type_extends(&self, source: &Type, target: &Type) -> bool {
+        let source = self.evaluate_type(source);
+        let target = self.evaluate_type(target);
+
+        if source == target {
+            return true;
+        }
+
+        match (source.as_predefined(), target.as_predefined()) {
+            (_, Some(PredefinedType::Any | PredefinedType::Unknown))
+            | (Some(PredefinedType::Any | PredefinedType::Never), _) => return true,
+            (Some(source), Some(target)) => {
+                return matches!(
+                    (source, target),
+                    (PredefinedType::StringLiteral(_), PredefinedType::String)
+                        | (
+                            PredefinedType::NumberLiteral(_),
+                            PredefinedType::Number | PredefinedType::Float
+                        )
+                        | (PredefinedType::BooleanLiteral(_), PredefinedType::Boolean)
+                        | (PredefinedType::Undefined, PredefinedType::Void)
+                )
+            }
+            _ => {}
+        }
+
+        let sources = source.union_members();
+
+        if sources.len() > 1 {
+            return sources
+                .iter()
+                .all(|source| self.type_extends(source, &target));
+        }
+
+        let targets = target.union_members();
+
+        if targets.len() > 1 {
+            return targets
+                .iter()
+                .any(|target| self.type_extends(&source, target));
+        }
+
+        if let (
+            Some(PrimaryType::TypeReference(name, generics)),
+            Some(PrimaryType::TypeReference(target_name, target_generics)),
+        ) = (source.as_primary(), target.as_primary())
+        {
+            if name == target_name && generics.len() == target_generics.len() {
+                return generics
+                    .iter()
+                    .zip(target_generics)
+                    .all(|(source, target)| self.type_extends(source, target));
+            }
+        }
+
+        let resolved_source = self.resolve_reference(&source);
+        let resolved_target = self.resolve_reference(&target);
+
+        if resolved_source != source || resolved_target != target {
+            return self.type_extends(&resolved_source, &resolved_target);
+        }
+
+        match (source.as_primary(), target.as_primary()) {
+            (Some(PrimaryType::ArrayType(source, _)), Some(PrimaryType::ArrayType(target, _))) => {
+                self.type_extends(&(**source).clone().into(), &(**target).clone().into())
+            }
+            (Some(PrimaryType::TupleType(sources)), Some(PrimaryType::ArrayType(target, _))) => {
+                let target = (**target).clone().into();
+
+                sources
+                    .iter()
+                    .all(|source| self.type_extends(source, &target))
+            }
+            (Some(PrimaryType::TupleType(sources)), Some(PrimaryType::TupleType(targets))) => {
+                sources.len() == targets.len()
+                    && sources
+                        .iter()
+                        .zip(targets)
+                        .all(|(source, target)| self.type_extends(source, target))
+            }
+            (_, Some(PrimaryType::ObjectType(_))) => {
+                let (Some(members), Some(targets)) =
+                    (self.members_of(&source), self.members_of(&target))
+                else {
+                    return false;
+                };
+
+                targets.iter().all(|target| match target {
+                    TypeMember::PropertySignature(target) => members.iter().any(|member| {
+                        matches!(
+                            member,
+                            TypeMember::PropertySignature(member)
+                                if member.value.name.value == target.value.name.value
+                                    && self.type_extends(&member.value.ty.value, &target.value.ty.value)
+                        )
+                    }) || target.value.nullable.value,
+                    TypeMember::MethodSignature(target) => members.iter().any(|member| {
+                        matches!(
+                            member,
+                            TypeMember::MethodSignature(member)
+                                if member.value.0.value == target.value.0.value
+                        )
+                    }),
+                    _ => true,
+                })
+            }
+            (Some(PrimaryType::ObjectType(_)), _) => false,
+            _ => matches!(
+                (&source, &target),
+                (Type::FunctionType(..), Type::FunctionType(..))
+                    | (Type::ConstructorType(..), Type::ConstructorType(..))
+            ),
+        }
+    }
+
+    fn resolve_reference(&self, ty: &Type) -> Type {
+        match ty.as_primary() {
+            Some(PrimaryType::TypeReference(name, generics)) => {
+                match self.resolve_type(&name.0, generics) {
+                    Some(resolved) => self.resolve_reference(&self.evaluate_type(&resolved)),
+                    None => ty.clone(),
+                }
+            }
+            _ => ty.clone(),
+        }
+    }
+
+    fn keys_of(&self, ty: &Type) -> Type {
+        let keys = self
+            .members_of(ty)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|member| match member {
+                TypeMember::PropertySignature(property) => {
+                    Some(PredefinedType::StringLiteral(property.value.name.value.0).into())
+                }
+                TypeMember::MethodSignature(method) => match method.value.0.value {
+                    PropertyName::LiteralPropertyName(literal) => match literal.value {
+                        Literal::String(name) => {
+                            Some(PredefinedType::StringLiteral(name.value).into())
+                        }
+                        _ => None,
+                    },
+                    PropertyName::ComputedPropertyName(_) => None,
+                },
+                TypeMember::IndexSignature(signature) => Some(signature.value.1.value),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        match keys.is_empty() {
+            true => PredefinedType::Never.into(),
+            false => Type::union(keys),
+        }
+    }
+
+    fn index_type(&self, object: &Type, index: &Type) -> Type {
+        let object = self.resolve_reference(&self.evaluate_type(object));
+
+        match (object.as_primary(), index.as_predefined()) {
+            (
+                Some(PrimaryType::ArrayType(element, _)),
+                Some(PredefinedType::Number | PredefinedType::NumberLiteral(_)),
+            ) => (**element).clone().into(),
+            (Some(PrimaryType::TupleType(types)), Some(PredefinedType::NumberLiteral(index))) => {
+                types
+                    .get(*index as usize)
+                    .cloned()
+                    .unwrap_or(PredefinedType::Undefined.into())
+            }
+            (Some(PrimaryType::TupleType(types)), Some(PredefinedType::Number)) => {
+                Type::union(types.clone())
+            }
+            (_, Some(PredefinedType::StringLiteral(name))) => {
+                let members = self.members_of(&object).unwrap_or_default();
+
+                members
+                    .iter()
+                    .find_map(|member| match member {
+                        TypeMember::PropertySignature(property)
+                            if &property.value.name.value.0 == name =>
+                        {
+                            Some(property.value.ty.value.clone())
+                        }
+                        _ => None,
+                    })
+                    .or_else(|| {
+                        members.iter().find_map(|member| match member {
+                            TypeMember::IndexSignature(signature) => {
+                                Some(signature.value.2.value.clone())
+                            }
+                            _ => None,
+                        })
+                    })
+                    .unwrap_or(PredefinedType::Any.into())
+            }
+            _ => PredefinedType::Any.into(),
+        }
+    }
+
+    fn evaluate_mapped(&self, mapped: &MappedType) -> Type {
+        let span = Span::default();
+        let modifiers = match mapped.readonly {
+            true => vec![span.wrap(Modifier::Readonly)],
+            false => vec![],
+        };
+
+        PrimaryType::ObjectType(
+            self.evaluate_type(&mapped.constraint)
+                .union_members()
+                .into_iter()
+                .map(|key| {
+                    let ty = mapped
+                        .ty
+                        .substitute(&HashMap::from([(mapped.parameter.0.clone(), key.clone())]));
+
+                    match key.as_predefined() {
+                        Some(PredefinedType::StringLiteral(name)) => {
+                            TypeMember::PropertySignature(span.wrap(PropertySignature {
+                                modifiers: modifiers.clone(),
+                                name: span.wrap(Ident(name.clone())),
+                                nullable: span.wrap(mapped.optional),
+                                ty: span.wrap(ty),
+                            }))
+                        }
+                        _ => TypeMember::IndexSignature(span.wrap(IndexSignature(
+                            span.wrap(mapped.parameter.clone()),
+                            span.wrap(key),
+                            span.wrap(ty),
+                        ))),
+                    }
+                })
+                .collect(),
+        )
+        .into()
+    }
+
+    /// Conditional types distribute over unions in their checked type, a checked type of
+    /// `any` takes both branches.
+    fn evaluate_conditional(&self, conditional: &ConditionalType) -> Type {
+        let check = self.evaluate_type(&conditional.check);
+        let checks = check.union_members();
+
+        if checks.len() > 1 {
+            return Type::union(
+                checks
+                    .into_iter()
+                    .map(|check| {
+                        self.evaluate_conditional(&ConditionalType {
+                            check,
+                            ..conditional.clone()
+                        })
+                    })
+                    .collect(),
+            );
+        }
+
+        if matches!(check.as_predefined(), Some(PredefinedType::Any)) {
+            return Type::union(vec![
+                self.evaluate_type(&conditional.true_type),
+                self.evaluate_type(&conditional.false_type),
+            ]);
+        }
+
+        let mut bindings = HashMap::new();
+
+        conditional.extends.infer(&check, &[], &mut bindings);
+        conditional
+            .extends
+            .infer(&self.resolve_reference(&check), &[], &mut bindings);
+
+        match self.type_extends(&check, &conditional.extends.substitute(&bindings)) {
+            true => self.evaluate_type(&conditional.true_type.substitute(&bindings)),
+            false => self.evaluate_type(&conditional.false_type),
+        }
+    }
+}
@@ -3,14 +3,16 @@ use std::sync::Arc;
 
 use crate::value::NativeFunction;
 use crate::value::Parameter;
+use crate::value::ParameterDefault;
 use crate::value::Visibility;
 use crate::FArguments;
 use crate::Type;
+use super::Properties;
 use super::Value;
 
 #[derive(Default)]
 pub struct ObjectBuilder {
-    properties: HashMap<Value, Value>,
+    properties: Properties,
 }
 
 impl ObjectBuilder {
@@ -24,7 +26,7 @@ impl ObjectBuilder {
     }
 
     pub fn build(self) -> Value {
-        Value::Object(self.properties)
+        Value::object(self.properties)
     }
 }
 
@@ -32,7 +34,9 @@ pub struct FunctionBuilder {
     visibility: Visibility,
     name: String,
     parameters: Vec<Parameter>,
+    rest: Option<Box<Parameter>>,
     ty: Type,
+    properties: HashMap<String, Value>,
 }
 
 impl FunctionBuilder {
@@ -43,7 +47,9 @@ impl FunctionBuilder {
             visibility: Default::default(),
             name: name.into(),
             parameters: Default::default(),
+            rest: None,
             ty: Default::default(),
+            properties: Default::default(),
         }
     }
 
@@ -67,12 +73,33 @@ impl FunctionBuilder {
             name: name.into(),
             nullable: false,
             ty: ty.into(),
-            default: Some(Box::new(default.into())),
+            default: Some(ParameterDefault::Value(default.into())),
         });
 
         self
     }
 
+    /// Collects the arguments left over into an array named `name`, each checked against `ty`.
+    pub fn rest<N: AsRef<str>, T: Into<Type>>(mut self, name: N, ty: T) -> Self {
+        let name: &str = name.as_ref();
+
+        self.rest = Some(Box::new(Parameter {
+            name: name.into(),
+            nullable: true,
+            ty: ty.into(),
+            default: None,
+        }));
+
+        self
+    }
+
+    /// Sets a property on the function itself, like `Symbol.iterator` on `Symbol`.
+    pub fn prop<N: AsRef<str>, V: Into<Value>>(mut self, name: N, value: V) -> Self {
+        self.properties.insert(name.as_ref().into(), value.into());
+
+        self
+    }
+
     pub fn returns<T: Into<Type>>(mut self, ty: T) -> Self {
         self.ty = ty.into();
 
@@ -85,6 +112,8 @@ impl FunctionBuilder {
             ty: self.ty,
             name: self.name,
             parameters: self.parameters,
+            rest: self.rest,
+            properties: self.properties,
             body: Arc::new(body),
         })
     }
@@ -0,0 +1,168 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    sync::{RwLock, Weak},
+};
+
+use super::{heap::Trace, Value};
+
+#[derive(Clone, Debug)]
+struct Key(Value);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Value::Float(first), Value::Float(second)) => {
+                first == second || (first.is_nan() && second.is_nan())
+            }
+            (first, second) => first == second,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Entries of a `Map` or `Set` in the order they were first inserted.
+#[derive(Default, Debug)]
+pub struct Entries {
+    entries: Vec<(Value, Value)>,
+    index: HashMap<Key, usize>,
+}
+
+impl Entries {
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.index
+            .get(&Key(key.clone()))
+            .map(|position| &self.entries[*position].1)
+    }
+
+    pub fn has(&self, key: &Value) -> bool {
+        self.index.contains_key(&Key(key.clone()))
+    }
+
+    /// Sets the value of `key`, which keeps its position when it is already there.
+    pub fn insert(&mut self, key: Value, value: Value) {
+        match self.index.get(&Key(key.clone())) {
+            Some(position) => self.entries[*position].1 = value,
+            None => {
+                self.index.insert(Key(key.clone()), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    /// Removes `key`, returns whether it was there.
+    pub fn remove(&mut self, key: &Value) -> bool {
+        let Some(position) = self.index.remove(&Key(key.clone())) else {
+            return false;
+        };
+
+        self.entries.remove(position);
+
+        for moved in self.index.values_mut() {
+            if *moved > position {
+                *moved -= 1;
+            }
+        }
+
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Value, Value)> {
+        self.entries.iter()
+    }
+}
+
+impl FromIterator<(Value, Value)> for Entries {
+    fn from_iter<I: IntoIterator<Item = (Value, Value)>>(entries: I) -> Self {
+        let mut collected = Self::default();
+
+        for (key, value) in entries {
+            collected.insert(key, value);
+        }
+
+        collected
+    }
+}
+
+impl Trace for Entries {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        for (key, value) in &self.entries {
+            visit(key);
+            visit(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        Entries::clear(self)
+    }
+}
+
+/// Entries of a `WeakMap`, which don't keep their keys alive.
+#[derive(Default, Debug)]
+pub struct WeakEntries {
+    entries: HashMap<usize, (Weak<RwLock<dyn Trace>>, Value)>,
+}
+
+impl WeakEntries {
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.entries
+            .get(&key.heap_id()?)
+            .filter(|(key, _)| key.strong_count() > 0)
+            .map(|(_, value)| value)
+    }
+
+    pub fn has(&self, key: &Value) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Sets the value of `key`, returns false when it can't be a key.
+    pub fn insert(&mut self, key: &Value, value: Value) -> bool {
+        let Some((id, weak)) = key.downgrade() else {
+            return false;
+        };
+
+        // Entries of keys freed since are dropped along the way
+        self.entries.retain(|_, (key, _)| key.strong_count() > 0);
+        self.entries.insert(id, (weak, value));
+
+        true
+    }
+
+    pub fn remove(&mut self, key: &Value) -> bool {
+        let removed = key.heap_id().and_then(|id| self.entries.remove(&id));
+
+        removed.is_some_and(|(key, _)| key.strong_count() > 0)
+    }
+}
+
+impl Trace for WeakEntries {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        for (_, value) in self.entries.values() {
+            visit(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear()
+    }
+}
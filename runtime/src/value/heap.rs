@@ -0,0 +1,243 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak},
+};
+
+use indexmap::IndexMap;
+
+use super::{Field, Properties, Value};
+
+/// Storage of an object, array or class instance. Clones share the storage, so mutations
+/// through any alias are visible to all of them and equality compares identity, like
+/// references in JavaScript.
+pub struct Shared<T: Trace + 'static>(Arc<RwLock<T>>);
+
+impl<T: Trace + 'static> Shared<T> {
+    pub fn new(value: T) -> Self {
+        let shared = Arc::new(RwLock::new(value));
+        let traced: Arc<RwLock<dyn Trace>> = shared.clone();
+
+        HEAP.with(|heap| heap.borrow_mut().track(Arc::downgrade(&traced)));
+
+        Self(shared)
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap()
+    }
+
+    /// Reference to the storage that doesn't keep it alive.
+    pub fn downgrade(&self) -> Weak<RwLock<dyn Trace>> {
+        let traced: Arc<RwLock<dyn Trace>> = self.0.clone();
+
+        Arc::downgrade(&traced)
+    }
+
+    /// Address of the storage, the same for every alias.
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl<T: Trace + 'static> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Trace + 'static> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Trace + Debug + 'static> Debug for Shared<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let id = self.id();
+
+        // Storage that refers back to itself is only printed once
+        if FORMATTING.with(|formatting| formatting.borrow().contains(&id)) {
+            return write!(f, "[Circular]");
+        }
+
+        FORMATTING.with(|formatting| formatting.borrow_mut().push(id));
+
+        let result = match self.0.try_read() {
+            Ok(value) => value.fmt(f),
+            Err(_) => write!(f, "<locked>"),
+        };
+
+        FORMATTING.with(|formatting| formatting.borrow_mut().pop());
+
+        result
+    }
+}
+
+/// Contents of heap storage that can refer to other storage, walked to find cycles.
+pub trait Trace {
+    fn trace(&self, visit: &mut dyn FnMut(&Value));
+
+    /// Drops the references held, used to break cycles nothing else refers to.
+    fn clear(&mut self);
+}
+
+impl Trace for Vec<Value> {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        self.iter().for_each(visit);
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+impl Trace for Properties {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        for (key, value) in self {
+            visit(key);
+            visit(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        IndexMap::clear(self)
+    }
+}
+
+impl Trace for Vec<Field> {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        self.iter().for_each(|field| visit(&field.value));
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+impl Value {
+    /// Identity of the heap storage the value refers to, `None` for primitives.
+    pub fn heap_id(&self) -> Option<usize> {
+        match self {
+            Value::Array(elements, _) => Some(elements.id()),
+            Value::Object(properties) => Some(properties.id()),
+            Value::ClassInstance(instance) => Some(instance.fields.id()),
+            Value::Map(entries) | Value::Set(entries) => Some(entries.id()),
+            Value::WeakMap(entries) => Some(entries.id()),
+            Value::Iterator(iteration) => Some(iteration.id()),
+            Value::Promise(promise) => Some(promise.id()),
+            _ => None,
+        }
+    }
+
+    /// Identity and weak reference of the heap storage the value refers to.
+    pub fn downgrade(&self) -> Option<(usize, Weak<RwLock<dyn Trace>>)> {
+        let weak = match self {
+            Value::Array(elements, _) => elements.downgrade(),
+            Value::Object(properties) => properties.downgrade(),
+            Value::ClassInstance(instance) => instance.fields.downgrade(),
+            Value::Map(entries) | Value::Set(entries) => entries.downgrade(),
+            Value::WeakMap(entries) => entries.downgrade(),
+            Value::Iterator(iteration) => iteration.downgrade(),
+            Value::Promise(promise) => promise.downgrade(),
+            _ => return None,
+        };
+
+        Some((self.heap_id()?, weak))
+    }
+}
+
+thread_local! {
+    static HEAP: RefCell<Heap> = RefCell::new(Heap::default());
+
+    /// Ids of the storage being formatted with `Debug`, outermost first.
+    static FORMATTING: RefCell<Vec<usize>> = const { RefCell::new(vec![]) };
+}
+
+#[derive(Default)]
+struct Heap {
+    tracked: Vec<Weak<RwLock<dyn Trace>>>,
+    /// Storage alive after the last pruning, the list is pruned again once it doubles
+    live: usize,
+}
+
+impl Heap {
+    fn track(&mut self, storage: Weak<RwLock<dyn Trace>>) {
+        self.tracked.push(storage);
+
+        if self.tracked.len() > (self.live * 2).max(64) {
+            self.tracked.retain(|storage| storage.strong_count() > 0);
+            self.live = self.tracked.len();
+        }
+    }
+}
+
+/// Frees storage that is only referred to by storage it refers to itself, returns how
+/// many were freed. Storage referred to from anywhere else, like variables or the host,
+/// stays alive along with everything reachable from it.
+pub fn collect_cycles() -> usize {
+    let storages = HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+
+        heap.tracked.retain(|storage| storage.strong_count() > 0);
+        heap.live = heap.tracked.len();
+        heap.tracked
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>()
+    });
+    let ids = storages
+        .iter()
+        .enumerate()
+        .map(|(index, storage)| (Arc::as_ptr(storage) as *const () as usize, index))
+        .collect::<HashMap<_, _>>();
+    let children = storages
+        .iter()
+        .map(|storage| {
+            let mut children = vec![];
+
+            storage.read().unwrap().trace(&mut |value| {
+                if let Some(index) = value.heap_id().and_then(|id| ids.get(&id)) {
+                    children.push(*index);
+                }
+            });
+
+            children
+        })
+        .collect::<Vec<_>>();
+
+    let mut internal = vec![0; storages.len()];
+
+    for child in children.iter().flatten() {
+        internal[*child] += 1;
+    }
+
+    // One reference to each storage is held by `storages` itself
+    let mut pending = (0..storages.len())
+        .filter(|index| Arc::strong_count(&storages[*index]) - 1 > internal[*index])
+        .collect::<Vec<_>>();
+    let mut reachable = pending.iter().copied().collect::<HashSet<_>>();
+
+    while let Some(index) = pending.pop() {
+        for child in &children[index] {
+            if reachable.insert(*child) {
+                pending.push(*child);
+            }
+        }
+    }
+
+    let garbage = (0..storages.len())
+        .filter(|index| !reachable.contains(index))
+        .collect::<Vec<_>>();
+
+    for index in &garbage {
+        storages[*index].write().unwrap().clear();
+    }
+
+    garbage.len()
+}
@@ -0,0 +1,296 @@
+use owo_colors::{OwoColorize, Style};
+
+use super::{promise::Outcome, Enum, Value};
+
+/// Options of [`Value::inspect`], named after those of Node's `util.inspect`.
+#[derive(Debug, Clone, Copy)]
+pub struct InspectOptions {
+    /// Puts every entry of objects and arrays on a line of its own
+    pub pretty: bool,
+    /// Colours values with ANSI escape codes
+    pub colors: bool,
+    /// Nesting shown before objects and arrays are abbreviated to `[Object]` and `[Array]`
+    pub depth: usize,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            pretty: false,
+            colors: false,
+            depth: 2,
+        }
+    }
+}
+
+impl Value {
+    /// Formats the value the way Node's `util.inspect` does.
+    pub fn inspect(&self, options: InspectOptions) -> String {
+        Inspector {
+            options,
+            seen: vec![],
+        }
+        .inspect(self, 0)
+    }
+}
+
+enum Key {
+    /// Elements of arrays and sets go without
+    None,
+    /// `name: value`, the name quoted when it isn't an identifier
+    Property(String),
+    /// `[symbol]: value`
+    Symbol(String),
+    /// `key => value`, for maps whose keys are any value
+    Value(Box<Value>),
+}
+
+struct Inspector {
+    options: InspectOptions,
+    /// Heap ids of the objects being formatted, seeing one again inside itself means it
+    /// is circular
+    seen: Vec<usize>,
+}
+
+impl Inspector {
+    fn paint<T: ToString>(&self, text: T, style: Style) -> String {
+        match self.options.colors {
+            true => text.to_string().style(style).to_string(),
+            false => text.to_string(),
+        }
+    }
+
+    fn special<T: ToString>(&self, text: T) -> String {
+        self.paint(text, Style::new().cyan())
+    }
+
+    fn inspect(&mut self, value: &Value, depth: usize) -> String {
+        match value {
+            Value::Number(number) => self.paint(number, Style::new().yellow()),
+            Value::Float(float) if float.is_infinite() => {
+                let sign = if *float < 0.0 { "-" } else { "" };
+
+                self.paint(format!("{sign}Infinity"), Style::new().yellow())
+            }
+            Value::Float(float) => self.paint(float, Style::new().yellow()),
+            Value::Boolean(boolean) => self.paint(boolean, Style::new().yellow()),
+            Value::String(string) => self.paint(quote(string), Style::new().green()),
+            Value::Null => self.paint("null", Style::new().bold()),
+            Value::None => self.paint("undefined", Style::new().dimmed()),
+            Value::ReturnValue(value) => self.inspect(value, depth),
+            Value::Function(function) => self.special(function_tag(&function.name)),
+            Value::NativeFunction(function) => self.special(function_tag(&function.name)),
+            Value::ArrowFunction(_) => self.special(function_tag("")),
+            Value::Class { name, extends, .. } => self.special(match extends.first() {
+                Some(parent) => format!("[class {name} extends {parent}]"),
+                None => format!("[class {name}]"),
+            }),
+            Value::Interface { name, .. } => self.special(format!("[interface {name}]")),
+            Value::TypeAlias { name, ty } => self.special(format!("[type {name} = {ty}]")),
+            Value::Reference(path, _) => self.special(format!("[Reference: {}]", path.join("."))),
+            Value::Error(span, code, message) => format!(
+                "[{code:?}Exception]:{}:{}: {message}",
+                span.line, span.column
+            ),
+            Value::Symbol(symbol) => self.paint(symbol, Style::new().green()),
+            Value::Array(elements, _) => self.entries(
+                value,
+                depth,
+                None,
+                elements
+                    .read()
+                    .iter()
+                    .map(|element| (Key::None, element.clone()))
+                    .collect(),
+            ),
+            Value::Map(entries) => {
+                let entries = entries.read();
+
+                self.entries(
+                    value,
+                    depth,
+                    Some(&format!("Map({})", entries.len())),
+                    entries
+                        .iter()
+                        .map(|(key, value)| (Key::Value(Box::new(key.clone())), value.clone()))
+                        .collect(),
+                )
+            }
+            Value::Set(entries) => {
+                let entries = entries.read();
+
+                self.entries(
+                    value,
+                    depth,
+                    Some(&format!("Set({})", entries.len())),
+                    entries
+                        .iter()
+                        .map(|(value, _)| (Key::None, value.clone()))
+                        .collect(),
+                )
+            }
+            // Entries of weak maps may be gone at any time, Node doesn't list them either
+            Value::WeakMap(_) => self.special("WeakMap { <items unknown> }"),
+            Value::Iterator(iteration) => {
+                self.special(format!("Object [{}] {{}}", iteration.read().name()))
+            }
+            Value::Promise(promise) => {
+                let outcome = promise.read().outcome().cloned();
+                let state = match outcome {
+                    None => self.special("<pending>"),
+                    Some(Outcome::Fulfilled(value)) => self.inspect(&value, depth + 1),
+                    Some(Outcome::Rejected(reason)) => format!(
+                        "{} {}",
+                        self.special("<rejected>"),
+                        self.inspect(&reason.reason(), depth + 1)
+                    ),
+                };
+
+                format!("Promise {{ {state} }}")
+            }
+            Value::Object(properties) => {
+                let properties = properties.read();
+                let mut properties = properties.iter().collect::<Vec<_>>();
+
+                // Integer keys come first and symbols last like in Node, the other keys
+                // keep the order they were added in
+                properties.sort_by_key(|(key, _)| match key {
+                    Value::Number(index) => (0, *index),
+                    Value::Symbol(_) => (2, 0),
+                    _ => (1, 0),
+                });
+
+                let properties = properties
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let key = match key {
+                            Value::String(key) => Key::Property(key.clone()),
+                            Value::Symbol(symbol) => Key::Symbol(symbol.to_string()),
+                            key => Key::Property(key.inspect(InspectOptions::default())),
+                        };
+
+                        (key, value.clone())
+                    })
+                    .collect::<Vec<_>>();
+
+                self.entries(value, depth, None, properties)
+            }
+            Value::ClassInstance(instance) => self.entries(
+                value,
+                depth,
+                Some(&instance.name),
+                instance
+                    .fields
+                    .read()
+                    .iter()
+                    .map(|field| (Key::Property(field.prop.name.clone()), field.value.clone()))
+                    .collect(),
+            ),
+            Value::Enum(Enum { name, members }) => self.entries(
+                value,
+                depth,
+                Some(name),
+                members
+                    .iter()
+                    .map(|member| (Key::Property(member.name.clone()), *member.init.clone()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn entries(
+        &mut self,
+        value: &Value,
+        depth: usize,
+        prefix: Option<&str>,
+        entries: Vec<(Key, Value)>,
+    ) -> String {
+        let (open, close, kind) = match value {
+            Value::Array(..) => ("[", "]", "Array"),
+            _ => ("{", "}", "Object"),
+        };
+        let prefixed = |body: String| match prefix {
+            Some(prefix) => format!("{prefix} {body}"),
+            None => body,
+        };
+
+        if entries.is_empty() {
+            return prefixed(format!("{open}{close}"));
+        }
+
+        let id = value.heap_id();
+
+        if id.is_some_and(|id| self.seen.contains(&id)) {
+            return self.special("[Circular]");
+        }
+
+        if depth > self.options.depth {
+            return self.special(format!("[{}]", prefix.unwrap_or(kind)));
+        }
+
+        self.seen.extend(id);
+
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                let value = self.inspect(&value, depth + 1);
+
+                match key {
+                    Key::None => value,
+                    Key::Property(key) => format!("{}: {value}", property_key(&key)),
+                    Key::Symbol(symbol) => format!("[{symbol}]: {value}"),
+                    Key::Value(key) => format!("{} => {value}", self.inspect(&key, depth + 1)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if id.is_some() {
+            self.seen.pop();
+        }
+
+        prefixed(match self.options.pretty {
+            true => format!(
+                "{open}\n{}\n{}{close}",
+                entries
+                    .iter()
+                    .map(|entry| format!("{}{entry}", "  ".repeat(depth + 1)))
+                    .collect::<Vec<_>>()
+                    .join(",\n"),
+                "  ".repeat(depth)
+            ),
+            false => format!("{open} {} {close}", entries.join(", ")),
+        })
+    }
+}
+
+fn function_tag(name: &str) -> String {
+    match name.is_empty() {
+        true => "[Function (anonymous)]".into(),
+        false => format!("[Function: {name}]"),
+    }
+}
+
+/// Keys that aren't identifiers are quoted like strings.
+fn property_key(key: &str) -> String {
+    let identifier = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_' || first == '$')
+        && key
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_' || char == '$');
+
+    match identifier {
+        true => key.into(),
+        false => quote(key),
+    }
+}
+
+fn quote(string: &str) -> String {
+    let escaped = string
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('\n', "\\n");
+
+    format!("'{escaped}'")
+}
@@ -0,0 +1,142 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter,
+};
+
+use tsr_lexer::globals::Span;
+use tsr_parser::ast::ArraySize;
+
+use super::{
+    heap::{Shared, Trace},
+    ErrorCode, Value,
+};
+use crate::coroutine::{Coroutine, Resume, Suspend};
+
+pub enum Iteration {
+    Native(Box<dyn Iterator<Item = Value>>),
+    Generator(Coroutine),
+    /// A generator whose body is being run
+    Running,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Step {
+    Next(Value),
+    Done(Value),
+}
+
+impl Iteration {
+    /// Runs the iterator to its next value, `sent` is what a generator's `yield` evaluates to.
+    pub fn step(iteration: &Shared<Iteration>, sent: Value) -> Step {
+        // The body may refer to its own generator, so the storage isn't locked while it runs
+        let mut current = match &mut *iteration.write() {
+            Iteration::Native(values) => {
+                return values.next().map_or(Step::Done(Value::None), Step::Next)
+            }
+            current => std::mem::replace(current, Iteration::Running),
+        };
+
+        let step = match &mut current {
+            Iteration::Generator(coroutine) => match coroutine.resume(Resume::Next(Box::new(sent)))
+            {
+                Suspend::Yield(value) => Step::Next(value),
+                Suspend::Complete(value) => Step::Done(value),
+            },
+            _ => Step::Done(Value::error(
+                Span::default(),
+                ErrorCode::Type,
+                "generator is already running",
+            )),
+        };
+
+        *iteration.write() = current;
+
+        step
+    }
+
+    pub fn close(iteration: &Shared<Iteration>) {
+        let current = std::mem::replace(
+            &mut *iteration.write(),
+            Iteration::Native(Box::new(iter::empty())),
+        );
+
+        match current {
+            Iteration::Running => *iteration.write() = current,
+            Iteration::Generator(mut coroutine) => coroutine.close(),
+            Iteration::Native(_) => {}
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Iteration::Native(_) => "Iterator",
+            Iteration::Generator(_) | Iteration::Running => "Generator",
+        }
+    }
+}
+
+impl Trace for Iteration {
+    fn trace(&self, _: &mut dyn FnMut(&Value)) {}
+
+    fn clear(&mut self) {
+        *self = Iteration::Native(Box::new(iter::empty()));
+    }
+}
+
+impl Debug for Iteration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Iteration::Native(_) => f.write_str("Native"),
+            Iteration::Generator(coroutine) => coroutine.fmt(f),
+            Iteration::Running => f.write_str("Running"),
+        }
+    }
+}
+
+impl Value {
+    /// Exposes a Rust iterator to scripts, which consume it lazily.
+    pub fn iterator<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = Value>,
+        I::IntoIter: 'static,
+    {
+        Value::Iterator(Shared::new(Iteration::Native(Box::new(
+            values.into_iter().fuse(),
+        ))))
+    }
+
+    /// Iterator over the values of a built-in iterable.
+    pub fn values(&self) -> Option<Value> {
+        Some(match self {
+            Value::Array(elements, _) => {
+                let elements = elements.clone();
+
+                Value::iterator((0..).map_while(move |index| elements.read().get(index).cloned()))
+            }
+            Value::String(string) => Value::iterator(
+                string
+                    .chars()
+                    .map(|char| Value::String(char.into()))
+                    .collect::<Vec<_>>(),
+            ),
+            Value::Set(entries) => Value::iterator(
+                entries
+                    .read()
+                    .iter()
+                    .map(|(value, _)| value.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            Value::Map(entries) => Value::iterator(
+                entries
+                    .read()
+                    .iter()
+                    .map(|(key, value)| {
+                        Value::array(vec![key.clone(), value.clone()], ArraySize::Fixed(2))
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Value::Iterator(_) => self.clone(),
+            _ => return None,
+        })
+    }
+}
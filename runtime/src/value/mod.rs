@@ -1,17 +1,36 @@
 pub mod builders;
+pub mod collection;
+pub mod heap;
+pub mod inspect;
+pub mod iterator;
 pub mod native;
-
-use super::{environment::Scope, FArguments, Runtime};
-use owo_colors::{colors, Color};
+pub mod promise;
+pub mod symbol;
+
+use self::{
+    collection::{Entries, WeakEntries},
+    heap::Shared,
+    inspect::InspectOptions,
+    iterator::Iteration,
+    promise::Promise,
+    symbol::Symbol,
+};
+use super::{
+    coroutine,
+    environment::Scope,
+    eval::expression::{array::ARRAY_METHODS, string::string_member},
+    FArguments, Runtime,
+};
+use indexmap::IndexMap;
 use std::{
     collections::HashMap,
     fmt::{Display, Formatter},
     hash::{Hash, Hasher},
 };
-use tsr_lexer::globals::Span;
+use tsr_lexer::globals::{Positioned, Span};
 use tsr_parser::ast::{
-    ArraySize, Block, IntersectionOrPrimaryType, Literal, PredefinedType, PrimaryType, Type,
-    UnionOrIntersectionOrPrimaryType,
+    self, ArraySize, Block, Ident, IntersectionOrPrimaryType, Literal, PredefinedType, PrimaryType,
+    Type, TypeMember, TypeParameter, UnionOrIntersectionOrPrimaryType,
 };
 
 pub trait Args {
@@ -59,68 +78,108 @@ impl<A: Display, B: Display, C: Display, D: Display, E: Display> Args for (A, B,
     }
 }
 
-fn format_message(message: &str, args: impl Args) -> String {
-    let args = args.formatted();
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ErrorCode {
+    /// Caused while type-checking
+    Type = 0x1250,
+    /// Value not exists
+    Reference = 0x1350,
+    /// Required value has not been declared or user trying to access uninitialized variable
+    Declaration = 0x1450,
+    /// Caused for features that currently not implemented
+    Implementing = 0x1950,
+}
 
-    let mut inside_fmt = false;
-    let pieces = message.split(|character| {
-        if character == '{' {
-            inside_fmt = true;
+/// Describes where a value stopped matching the type it was checked against.
+#[derive(PartialEq, Clone, Debug)]
+pub struct TypeMismatch {
+    /// Property names and `[index]` segments leading to the mismatched value, outermost first
+    pub path: Vec<String>,
+    pub expected: Box<Type>,
+    /// `None` when the value is missing
+    pub found: Option<Box<Type>>,
+    /// Name of the type reference that isn't declared, when that is why the value didn't match
+    pub unknown: Option<String>,
+}
 
-            true
-        } else if character == '}' && inside_fmt {
-            inside_fmt = false;
+impl TypeMismatch {
+    pub fn new(expected: Type, found: Option<Type>) -> Self {
+        Self {
+            path: vec![],
+            expected: Box::new(expected),
+            found: found.map(Box::new),
+            unknown: None,
+        }
+    }
 
-            true
-        } else {
-            inside_fmt
+    /// The value couldn't be checked against `expected`, which refers to the undeclared
+    /// type `name`.
+    fn unknown(expected: Type, found: Type, name: &str) -> Self {
+        Self {
+            unknown: Some(name.into()),
+            ..Self::new(expected, Some(found))
         }
-    });
+    }
 
-    pieces
-        .enumerate()
-        .map(|(index, string)| {
-            if let Some(arg) = args.get(index) {
-                format!("{string}{arg}")
-            } else {
-                string.to_string()
+    fn inside<S: Into<String>>(mut self, segment: S) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+
+    /// Joins the path into an accessor like `user.tags[0]`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+
+        for segment in &self.path {
+            if !path.is_empty() && !segment.starts_with('[') {
+                path.push('.');
             }
-        })
-        .collect::<String>()
+
+            path.push_str(segment);
+        }
+
+        path
+    }
+
+    /// `": <mismatch>"` for nested mismatches and undeclared types, empty when the value
+    /// itself didn't match.
+    pub fn note(&self) -> String {
+        match self.path.is_empty() && self.unknown.is_none() {
+            true => String::new(),
+            false => format!(": {self}"),
+        }
+    }
 }
 
-/* macro_rules! errors {
-    ($( $error_name:ident: $error_code:literal => $error_message:expr );*;) => {
-        #[derive(PartialEq, Clone, Copy, Debug)]
-        pub enum ErrorCode {
-            $(
-                $error_name = $error_code
-            ),*
+impl Display for TypeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.unknown, &self.found) {
+            (Some(name), _) => write!(f, "type \"{name}\" is not defined")?,
+            (None, Some(found)) => write!(f, "{} expected but {found} given", self.expected)?,
+            (None, None) => return write!(f, "property \"{}\" is missing", self.path()),
         }
 
-        impl ErrorCode {
-            pub fn format(&self, args: impl Args) -> (ErrorCode, String) {
-                match self {
-                    $(
-                        ErrorCode::$error_name => (*self, format_message($error_message, args))
-                    ),*
-                }
-            }
+        match self.path.is_empty() {
+            true => Ok(()),
+            false => write!(f, " at \"{}\"", self.path()),
         }
-    };
+    }
 }
- */
 
-#[derive(PartialEq, Clone, Copy, Debug)]
-pub enum ErrorCode {
-    /// Caused while type-checking
-    Type = 0x1250,
-    /// Value not exists
-    Reference = 0x1350,
-    /// Required value has not been declared or user trying to access uninitialized variable
-    Declaration = 0x1450,
-    /// Caused for features that currently not implemented
-    Implementing = 0x1950,
+/// Types of the values the runtime builds itself, which aren't declared by programs.
+const BUILT_IN_TYPES: [&str; 8] = [
+    "Map",
+    "Set",
+    "WeakMap",
+    "Promise",
+    "Generator",
+    "Iterator",
+    "IterableIterator",
+    "Iterable",
+];
+
+fn is_any(ty: &Type) -> bool {
+    matches!(ty.as_predefined(), Some(PredefinedType::Any))
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -150,8 +209,12 @@ pub enum ExportSource {
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum ExportValue {
+    /// Default export of an expression
     Value(Value),
+    /// A binding of the source, exported under an alias when one is given
     Reference(String, Option<String>),
+    /// Every named export of the source, `export * from`
+    All,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -208,17 +271,21 @@ pub enum Signature {
     Method(MethodSignature),
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug, Default)]
 pub enum Visibility {
+    #[default]
     Public,
     Private,
     Protected,
 }
 
-impl Default for Visibility {
-    fn default() -> Self {
-        Self::Public
-    }
+/// Value given to a parameter that a call leaves out.
+#[derive(PartialEq, Clone, Debug)]
+pub enum ParameterDefault {
+    /// Known when the function is built, used by native functions
+    Value(Value),
+    /// Evaluated in the callee scope on every call, after the earlier parameters are bound
+    Expression(Positioned<ast::Expression>),
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -226,7 +293,7 @@ pub struct Parameter {
     pub name: String,
     pub nullable: bool,
     pub ty: Type,
-    pub default: Option<Box<Value>>,
+    pub default: Option<ParameterDefault>,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -234,7 +301,7 @@ pub struct ArrowParameter {
     pub name: String,
     pub nullable: bool,
     pub ty: Option<Type>,
-    pub default: Option<Box<Value>>,
+    pub default: Option<ParameterDefault>,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -243,6 +310,8 @@ pub struct ArrowFunction {
     pub parameters: Vec<ArrowParameter>,
     pub ty: Option<Type>,
     pub body: Block,
+    /// See [`Function::scope`]
+    pub scope: Scope,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -251,10 +320,18 @@ pub struct Function {
     pub overloads: Vec<Function>,
     pub is_async: bool,
     pub is_static: bool,
+    /// `function*`, calls return a generator running the body as it is iterated
+    pub is_generator: bool,
     pub name: String,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
     pub ty: Type,
-    pub body: Block,
+    /// `None` for overload signatures declared without an implementation.
+    pub body: Option<Block>,
+    /// Scope the function was declared in. Calls from outside of it, like from the
+    /// importer of a module, run inside it so the body sees what was declared next to the
+    /// function. Empty for lambdas, which run in the scope they are called from.
+    pub scope: Scope,
 }
 
 use derivative::Derivative;
@@ -266,7 +343,12 @@ pub struct NativeFunction {
     pub visibility: Visibility,
     pub name: String,
     pub parameters: Vec<Parameter>,
+    /// Parameter collecting the arguments left after `parameters` into an array, its type
+    /// is the type of each of them
+    pub rest: Option<Box<Parameter>>,
     pub ty: Type,
+    /// Properties of the function itself, like `Symbol.iterator`
+    pub properties: HashMap<String, Value>,
 
     #[derivative(Debug = "ignore")]
     pub body: Arc<dyn Fn(&mut FArguments)>,
@@ -277,6 +359,7 @@ impl PartialEq for NativeFunction {
         self.visibility == other.visibility
             && self.name == other.name
             && self.parameters == other.parameters
+            && self.rest == other.rest
             && self.ty == other.ty
     }
 }
@@ -301,15 +384,24 @@ pub struct Field {
     pub value: Value,
 }
 
+/// Properties of an object, kept in the order they were added like in JavaScript.
+pub type Properties = IndexMap<Value, Value>;
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Value {
     Reference(Vec<String>, Scope),
-    Array(Vec<Value>, ArraySize),
-    Object(HashMap<Value, Value>),
+    Array(Shared<Vec<Value>>, ArraySize),
+    Object(Shared<Properties>),
+    Map(Shared<Entries>),
+    Set(Shared<Entries>),
+    WeakMap(Shared<WeakEntries>),
+    Iterator(Shared<Iteration>),
+    Promise(Shared<Promise>),
     Number(i64),
     Float(f64),
     Boolean(bool),
     String(String),
+    Symbol(Symbol),
     ReturnValue(Box<Value>),
     ArrowFunction(ArrowFunction),
     NativeFunction(NativeFunction),
@@ -367,45 +459,219 @@ impl Enum {
 #[derive(PartialEq, Clone, Debug)]
 pub struct ClassInstance {
     pub name: String,
-    pub fields: Vec<Field>,
+    /// Shared by every alias of the instance
+    pub fields: Shared<Vec<Field>>,
 }
 
 impl ClassInstance {
-    pub fn get_field_moved<N: AsRef<str>>(self, name: N) -> Option<Field> {
-        self.fields
-            .into_iter()
-            .find(|field| field.prop.name == name.as_ref())
+    pub fn new<N: AsRef<str>>(name: N, fields: Vec<Field>) -> Self {
+        Self {
+            name: name.as_ref().into(),
+            fields: Shared::new(fields),
+        }
     }
 
-    pub fn get_field<N: AsRef<str>>(&self, name: N) -> Option<&Field> {
+    pub fn get_field<N: AsRef<str>>(&self, name: N) -> Option<Field> {
         self.fields
+            .read()
             .iter()
             .find(|field| field.prop.name == name.as_ref())
+            .cloned()
     }
 
-    pub fn get_field_mut<N: AsRef<str>>(&mut self, name: N) -> Option<&mut Field> {
-        self.fields
+    /// Assigns a declared field, returns whether the instance has one named `name`.
+    pub fn set_field<N: AsRef<str>>(&self, name: N, value: Value) -> bool {
+        match self
+            .fields
+            .write()
             .iter_mut()
             .find(|field| field.prop.name == name.as_ref())
+        {
+            Some(field) => {
+                field.value = value;
+
+                true
+            }
+            None => false,
+        }
     }
 }
 
 impl Function {
+    /// Binds the type parameters to the explicit type arguments or to the types inferred
+    /// from the argument values, also returns the constraints each parameter must satisfy.
+    fn instantiate(
+        &self,
+        type_arguments: &[Type],
+        args: &[(Span, Value)],
+    ) -> (HashMap<String, Type>, HashMap<String, Type>) {
+        if self.type_parameters.is_empty() {
+            return Default::default();
+        }
+
+        let mut bindings = TypeParameter::zip(&self.type_parameters, type_arguments);
+
+        if type_arguments.is_empty() {
+            let names = self
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.name.value.0.clone())
+                .collect::<Vec<_>>();
+
+            for (parameter, (_, value)) in self.parameters.iter().zip(args) {
+                if !value.is_none() {
+                    parameter
+                        .ty
+                        .infer(&value.value_type_of(), &names, &mut bindings);
+                }
+            }
+        }
+
+        let bindings = TypeParameter::bind(&self.type_parameters, bindings);
+        // Parameters without a constraint accept anything
+        let constraints = self
+            .type_parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.value.0.clone(),
+                    parameter
+                        .constraint
+                        .as_ref()
+                        .map_or(PredefinedType::Any.into(), |constraint| {
+                            constraint.value.substitute(&bindings)
+                        }),
+                )
+            })
+            .collect();
+
+        (bindings, constraints)
+    }
+
+    /// Overload signatures declared so far, in declaration order.
+    pub fn signatures(&self) -> Vec<Function> {
+        let mut signatures = self.overloads.clone();
+
+        if self.body.is_none() {
+            signatures.push(Function {
+                overloads: vec![],
+                ..self.clone()
+            });
+        }
+
+        signatures
+    }
+
+    /// Whether the arguments fit the parameters of this signature.
+    pub(crate) fn accepts(
+        &self,
+        runtime: &Runtime,
+        type_arguments: &[Type],
+        args: &[(Span, Value)],
+    ) -> bool {
+        let required = self
+            .parameters
+            .iter()
+            .filter(|parameter| parameter.default.is_none() && !parameter.nullable)
+            .count();
+        let (bindings, _) = self.instantiate(type_arguments, args);
+
+        (required..=self.parameters.len()).contains(&args.len())
+            && self
+                .parameters
+                .iter()
+                .zip(args)
+                .all(|(parameter, (_, value))| {
+                    value.is_type_of(&parameter.ty.substitute(&bindings), runtime)
+                })
+    }
+
+    /// Calls the implementation, when the function is overloaded the first overload
+    /// accepting the arguments also decides the type of the result.
     pub fn call(
         &self,
         span: Span,
         runtime: &mut Runtime,
         args: Vec<(Span, Value)>,
+        type_arguments: &[Type],
         lambda: Option<Block>,
+    ) -> Value {
+        let Some(body) = &self.body else {
+            return Value::error(
+                span,
+                ErrorCode::Declaration,
+                format!("function \"{}\" has no implementation", self.name),
+            );
+        };
+
+        if self.overloads.is_empty() {
+            return self.call_body(span, runtime, args, type_arguments, lambda, body);
+        }
+
+        let Some(overload) = self
+            .overloads
+            .iter()
+            .find(|overload| overload.accepts(runtime, type_arguments, &args))
+        else {
+            return Value::error(
+                span,
+                ErrorCode::Type,
+                format!(
+                    "no overload of {} accepts ({}), candidates: {}",
+                    self.name,
+                    args.iter()
+                        .map(|(_, value)| value.value_type_of().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    self.overloads
+                        .iter()
+                        .map(|overload| format!(
+                            "{}{}",
+                            overload.name,
+                            Value::Function(overload.clone()).value_type_of()
+                        ))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ),
+            );
+        };
+        let (bindings, _) = overload.instantiate(type_arguments, &args);
+        let ty = overload.ty.substitute(&bindings);
+        let value = self.call_body(span, runtime, args, &[], lambda, body);
+
+        match &value {
+            Value::Error(..) | Value::None => value,
+            returned => match returned.check_type(&ty, runtime) {
+                Ok(()) => value,
+                Err(mismatch) => Value::error(
+                    span,
+                    ErrorCode::Type,
+                    format!(
+                        "{} expected but {} returned{}",
+                        ty,
+                        returned.value_type_of(),
+                        mismatch.note()
+                    ),
+                ),
+            },
+        }
+    }
+
+    fn call_body(
+        &self,
+        span: Span,
+        runtime: &mut Runtime,
+        args: Vec<(Span, Value)>,
+        type_arguments: &[Type],
+        lambda: Option<Block>,
+        body: &Block,
     ) -> Value {
         let arg_count = self
             .parameters
             .iter()
-            .filter(|parameter| parameter.default.is_none())
+            .filter(|parameter| parameter.default.is_none() && !parameter.nullable)
             .count();
 
-        println!("{} < {arg_count}", args.len());
-
         if args.len() < arg_count {
             return Value::error(
                 span,
@@ -418,13 +684,44 @@ impl Function {
             );
         }
 
-        let zipped = self.parameters.clone().into_iter().zip(args);
+        let (bindings, constraints) = self.instantiate(type_arguments, &args);
+        let mut args = args.into_iter();
+
+        if self.is_async && self.is_generator {
+            return Value::error(
+                span,
+                ErrorCode::Implementing,
+                "async generators are not supported",
+            );
+        }
+
+        // The variables of generators and async functions outlive the call, so they get a
+        // scope of their own
+        runtime.add_scope(match (self.is_generator, self.is_async) {
+            (true, _) => coroutine::scope("generator", &self.name),
+            (_, true) => coroutine::scope("async", &self.name),
+            _ => format!("func:{}", self.name),
+        });
+
+        // Type parameters stand for the types they are bound to inside the body
+        for (name, ty) in &bindings {
+            runtime.associate_type(name.clone(), vec![], ty.clone());
+        }
+
+        for argument in self.parameters.clone() {
+            let (span, value) = runtime.argument(span, &mut args, argument.default.as_ref());
+
+            if let Value::Error(..) = value {
+                runtime.clear_scope_variables();
+                runtime.remove_scope();
 
-        runtime.add_scope(format!("func:{}", self.name));
+                return value;
+            }
 
-        for (argument, (span, value)) in zipped {
-            if value.is_none() && lambda.is_some() {
-                if let Type::FunctionType(_, params, ty) = argument.ty.clone() {
+            let ty = argument.ty.substitute(&bindings);
+
+            if let (true, Some(lambda)) = (value.is_none(), lambda.clone()) {
+                if let Type::FunctionType(_, params, ty) = ty {
                     let value = span.wrap(Value::ArrowFunction(ArrowFunction {
                         is_async: false,
                         parameters: params
@@ -433,13 +730,12 @@ impl Function {
                                 name: param.name.value.0,
                                 nullable: param.nullable.value,
                                 ty: Some(param.ty.value),
-                                default: param
-                                    .default
-                                    .map(|default| Box::new(runtime.eval_expression(default))),
+                                default: param.default.map(ParameterDefault::Expression),
                             })
                             .collect(),
                         ty: Some(*ty),
-                        body: lambda.unwrap(),
+                        body: lambda,
+                        scope: vec![],
                     }));
 
                     runtime.set_variable(&argument.name, value);
@@ -447,28 +743,65 @@ impl Function {
                     break;
                 }
             } else {
-                if !value.is_type_of(&argument.ty) {
-                    return Value::error(
-                        span,
-                        ErrorCode::Type,
-                        format!(
-                            "{} expected but {} given",
-                            argument.ty,
-                            value.value_type_of()
-                        ),
-                    );
+                let constraint = argument.ty.substitute(&constraints);
+
+                let optional = argument.nullable && value.is_none();
+
+                for ty in [&ty, &constraint] {
+                    if let (false, Err(mismatch)) = (optional, value.check_type(ty, runtime)) {
+                        return Value::error(
+                            span,
+                            ErrorCode::Type,
+                            format!(
+                                "{} expected but {} given{}",
+                                ty,
+                                value.value_type_of(),
+                                mismatch.note()
+                            ),
+                        );
+                    }
                 }
 
                 runtime.set_variable(argument.name, span.wrap(value));
             }
         }
 
-        let value = runtime.eval_code_block(self.body.clone());
+        if self.is_generator || self.is_async {
+            let value = match self.is_generator {
+                true => runtime.start_generator(body.clone()),
+                false => runtime.start_async(body.clone()),
+            };
+
+            runtime.remove_scope();
+
+            return value;
+        }
+
+        let value = runtime.eval_code_block(body.clone());
+        let value = runtime.returned(value);
+        let value = runtime.resolve(span, value);
 
         runtime.clear_scope_variables();
         runtime.remove_scope();
 
-        value
+        let ty = self.ty.substitute(&bindings);
+
+        match value.clone() {
+            Value::Error(..) | Value::None => value,
+            returned => match returned.check_type(&ty, runtime) {
+                Ok(()) => value,
+                Err(mismatch) => Value::error(
+                    span,
+                    ErrorCode::Type,
+                    format!(
+                        "{} expected but {} returned{}",
+                        ty,
+                        returned.value_type_of(),
+                        mismatch.note()
+                    ),
+                ),
+            },
+        }
     }
 }
 
@@ -500,7 +833,7 @@ impl From<bool> for Value {
 
 impl<K: Into<Value>, V: Into<Value>> From<HashMap<K, V>> for Value {
     fn from(value: HashMap<K, V>) -> Self {
-        Value::Object(
+        Value::object(
             value
                 .into_iter()
                 .map(|(key, value)| (key.into(), value.into()))
@@ -511,7 +844,7 @@ impl<K: Into<Value>, V: Into<Value>> From<HashMap<K, V>> for Value {
 
 impl<T: Into<Value>> From<Vec<T>> for Value {
     fn from(value: Vec<T>) -> Self {
-        Value::Array(
+        Value::array(
             value.into_iter().map(|element| element.into()).collect(),
             ArraySize::Dynamic,
         )
@@ -523,6 +856,16 @@ impl Value {
         Self::Error(span, code, message.into())
     }
 
+    /// Allocates a new array holding `elements`.
+    pub fn array(elements: Vec<Value>, size: ArraySize) -> Self {
+        Self::Array(Shared::new(elements), size)
+    }
+
+    /// Allocates a new object holding `properties`.
+    pub fn object(properties: Properties) -> Self {
+        Self::Object(Shared::new(properties))
+    }
+
     pub fn is_primitive(&self) -> bool {
         match self {
             Value::ReturnValue(value) => value.is_primitive(),
@@ -541,151 +884,50 @@ impl Value {
     pub fn is_array(&self) -> bool {
         matches!(self, Value::Array(..))
     }
-}
-
-impl Display for Value {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let alternate = f.alternate();
 
+    /// Property of an object or field of a class instance.
+    pub fn get_member<N: AsRef<str>>(&self, name: N) -> Option<Value> {
         match self {
-            Value::Array(elements, _) => {
-                let mut list = &mut f.debug_list();
-
-                for element in elements {
-                    list = match alternate {
-                        true => list.entry(&format_args!("{element:#}")),
-                        false => list.entry(&format_args!("{element}")),
-                    }
-                }
-
-                list.finish()
-            }
-            Value::Object(properties) => {
-                let mut structure = &mut f.debug_struct("Object");
-
-                for (name, value) in properties {
-                    let name = match name {
-                        Value::String(value) => value.clone(),
-                        value => value.to_string(),
-                    };
-
-                    structure = match alternate {
-                        true => structure.field(&name, &format_args!("{value:#}")),
-                        false => structure.field(&name, &format_args!("{value}")),
-                    }
-                }
-
-                structure.finish()
-            }
-            Value::Number(number) => number.fmt(f),
-            Value::Float(float) => float.fmt(f),
-            Value::Boolean(boolean) => boolean.fmt(f),
-            Value::String(string) => write!(f, "{string:?}"),
-            Value::ReturnValue(value) => value.fmt(f),
-            Value::Function(function) => write!(
-                f,
-                "[Function {}({}) => {}]",
-                function.name,
-                function.parameters.len(),
-                function.ty
-            ),
-            Value::Interface {
-                name,
-                extends: _,
-                signatures,
-            } => {
-                let mut structure = &mut f.debug_struct(name);
-
-                for signature in signatures {
-                    structure = structure.field(
-                        match signature {
-                            Signature::Property(signature) => &signature.name,
-                            Signature::Call(_) => name,
-                            Signature::Construct(_) => "new",
-                            Signature::Index(signature) => &signature.name,
-                            Signature::Method(signature) => name,
-                        },
-                        signature,
-                    );
-                }
-
-                structure.finish()
-            }
-            Value::Class {
-                name,
-                extends: _,
-                implements: _,
-                constructors,
-                fields,
-                methods,
-            } => {
-                let mut structure = &mut f.debug_struct(name);
-
-                for constructor in constructors.clone() {
-                    structure = structure.field("constructor", &Value::Function(constructor));
-                }
-
-                for field in fields {
-                    structure = structure.field(&field.name, &field.init);
-                }
-
-                for method in methods {
-                    structure = structure.field(&method.name, &Value::Function(method.clone()));
-                }
-
-                structure.finish()
-            }
-            Value::Null => write!(f, "null"),
-            Value::None => write!(f, ""),
-            Value::Error(span, code, message) => write!(
-                f,
-                "[{code:?}Exception]:{}:{}: {message}",
-                span.line, span.line
-            ),
-            Value::ArrowFunction(function) => {
-                write!(f, "[Function anonymous({})]", function.parameters.len())
-            }
-            Value::Enum(Enum { name, members }) => {
-                let mut structure = &mut f.debug_struct(name);
-
-                for member in members {
-                    structure = structure.field(&member.name, &member.init);
-                }
+            Value::Object(properties) => properties
+                .read()
+                .get(&Value::String(name.as_ref().into()))
+                .cloned(),
+            Value::ClassInstance(instance) => instance.get_field(name).map(|field| field.value),
+            _ => None,
+        }
+    }
 
-                structure.finish()
+    /// Member of a string or an array checked against an object type, like `length`.
+    fn built_in_member(&self, name: &str) -> Option<Value> {
+        match self {
+            Value::String(string) => string_member(string.clone(), name),
+            Value::Array(elements, _) if name == "length" => {
+                Some(Value::Number(elements.read().len() as i64))
             }
-            Value::TypeAlias { name, ty } => f.debug_tuple(name).field(ty).finish(),
-            Value::NativeFunction(func) => write!(
-                f,
-                "[NativeFunction {}({})]",
-                func.name,
-                func.parameters.len()
-            ),
-            Value::ClassInstance(_) => todo!(),
-            Value::Reference(reference, _) => write!(f, "[Reference({})]", reference.join(".")),
+            _ => None,
         }
     }
 }
 
+impl Display for Value {
+    /// Formats like [`Value::inspect`] without colours, `{:#}` puts entries on their own lines.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.inspect(InspectOptions {
+            pretty: f.alternate(),
+            ..Default::default()
+        }))
+    }
+}
+
 impl Value {
     pub fn format<T: Into<String>>(&self, file: T, source: T) -> String {
         let file: String = file.into();
         let source: String = source.into();
 
         match &self {
-            Value::Error(span, code, message) => format!(
-                "==> {red}{code:?}Exception{reset} at {green}{file}:{line}:{column}{reset}\n{gray}{pline:>3} |{reset}\n{gray}{line:>3} |{reset} {data}\n{gray}... |{reset}{nspace}{red}{length} {message}{reset}",
-                pline = span.line - 1,
-                line = span.line,
-                column = span.column,
-                length = "-".repeat(span.end - span.start),
-                data = source.lines().collect::<Vec<_>>()[(span.line - 1) as usize],
-                nspace = " ".repeat(span.column + 2 - (span.line.to_string().len())),
-                gray = colors::css::DimGray::ANSI_FG,
-                green = colors::css::LightSeaGreen::ANSI_FG,
-                red = colors::css::IndianRed::ANSI_FG,
-                reset = colors::Default::ANSI_FG
-            ),
+            Value::Error(span, code, message) => {
+                span.report(&format!("{code:?}Exception"), &file, &source, message)
+            }
             _ => format!("{self}"),
         }
     }
@@ -700,167 +942,613 @@ impl Value {
         matches!(self, Value::None)
     }
 
-    pub fn is_type_of(&self, ty: &Type) -> bool {
+    /// Checks the value against `ty`, type references are resolved through the types
+    /// declared in `runtime`.
+    pub fn is_type_of(&self, ty: &Type, runtime: &Runtime) -> bool {
+        self.check_type(ty, runtime).is_ok()
+    }
+
+    /// Like [`Value::is_type_of`], but reports where the value stopped matching.
+    pub fn check_type(&self, ty: &Type, runtime: &Runtime) -> Result<(), TypeMismatch> {
+        self.check_within(ty, runtime, &mut vec![])
+    }
+
+    /// Checks the value against `ty`, `seen` holds the objects being checked against a type
+    /// reference further up, like an object holding itself against an interface holding
+    /// itself, which are assumed to match.
+    fn check_within(
+        &self,
+        ty: &Type,
+        runtime: &Runtime,
+        seen: &mut Vec<(usize, Type)>,
+    ) -> Result<(), TypeMismatch> {
+        let mismatch = || Err(TypeMismatch::new(ty.clone(), Some(self.value_type_of())));
+
         match ty {
             Type::UnionOrIntersectionOrPrimaryType(ty) => match ty {
-                UnionOrIntersectionOrPrimaryType::UnionType(types) => types.iter().any(|ty| {
-                    self.is_type_of(&Type::UnionOrIntersectionOrPrimaryType(
-                        UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(ty.clone()),
-                    ))
-                }),
+                UnionOrIntersectionOrPrimaryType::UnionType(types) => {
+                    let mut nearest = None;
+
+                    for ty in types {
+                        match self.check_within(&ty.clone().into(), runtime, seen) {
+                            Ok(()) => return Ok(()),
+                            Err(error) if !error.path.is_empty() && nearest.is_none() => {
+                                nearest = Some(error)
+                            }
+                            Err(_) => {}
+                        }
+                    }
+
+                    match nearest {
+                        Some(error) if types.len() == 1 => Err(error),
+                        _ => mismatch(),
+                    }
+                }
                 UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(ty) => match ty {
-                    IntersectionOrPrimaryType::IntersectionType(types) => types.iter().all(|ty| {
-                        self.is_type_of(&Type::UnionOrIntersectionOrPrimaryType(
-                            UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
-                                IntersectionOrPrimaryType::PrimaryType(ty.clone()),
-                            ),
-                        ))
-                    }),
-                    IntersectionOrPrimaryType::PrimaryType(ty) => match &ty {
-                        PrimaryType::ParenthesizedType(ty) => self.is_type_of(ty),
-                        PrimaryType::PredefinedType(ty) => match ty {
-                            PredefinedType::Any => true,
-                            PredefinedType::Number => matches!(self, Value::Number(_)),
-                            PredefinedType::Float => matches!(self, Value::Float(_)),
-                            PredefinedType::Boolean => matches!(self, Value::Boolean(_)),
-                            PredefinedType::String => matches!(self, Value::String(_)),
-                            PredefinedType::StringLiteral(literal) => match self {
-                                Value::String(string) => string == literal,
-                                _ => false,
+                    IntersectionOrPrimaryType::IntersectionType(types) => types
+                        .iter()
+                        .try_for_each(|ty| self.check_within(&ty.clone().into(), runtime, seen)),
+                    IntersectionOrPrimaryType::PrimaryType(primary) => match &primary {
+                        PrimaryType::ParenthesizedType(ty) => self.check_within(ty, runtime, seen),
+                        PrimaryType::PredefinedType(predefined) => {
+                            let matches = match predefined {
+                                PredefinedType::Any => true,
+                                PredefinedType::Number => matches!(self, Value::Number(_)),
+                                PredefinedType::Float => matches!(self, Value::Float(_)),
+                                PredefinedType::Boolean => matches!(self, Value::Boolean(_)),
+                                PredefinedType::String => matches!(self, Value::String(_)),
+                                PredefinedType::StringLiteral(literal) => match self {
+                                    Value::String(string) => string == literal,
+                                    _ => false,
+                                },
+                                PredefinedType::NumberLiteral(literal) => {
+                                    matches!(self, Value::Number(number) if number == literal)
+                                }
+                                PredefinedType::BooleanLiteral(literal) => {
+                                    matches!(self, Value::Boolean(boolean) if boolean == literal)
+                                }
+                                PredefinedType::Symbol => matches!(self, Value::Symbol(_)),
+                                PredefinedType::Void | PredefinedType::Undefined => {
+                                    matches!(self, Value::None)
+                                }
+                                PredefinedType::Null => matches!(self, Value::Null),
+                                PredefinedType::Unknown => true,
+                                PredefinedType::Never => false,
+                            };
+
+                            match matches {
+                                true => Ok(()),
+                                false => mismatch(),
+                            }
+                        }
+                        PrimaryType::TypeReference(name, generics) => match self {
+                            Value::ClassInstance(instance) if instance.name == name.0 => Ok(()),
+                            Value::Map(entries) if name.0 == "Map" => {
+                                entries.read().iter().try_for_each(|(key, value)| {
+                                    let path = format!("[{key}]");
+
+                                    if let Some(ty) = generics.first() {
+                                        key.check_within(ty, runtime, seen)
+                                            .map_err(|error| error.inside(path.clone()))?;
+                                    }
+
+                                    match generics.get(1) {
+                                        Some(ty) => value
+                                            .check_within(ty, runtime, seen)
+                                            .map_err(|error| error.inside(path)),
+                                        None => Ok(()),
+                                    }
+                                })
+                            }
+                            Value::Set(entries) if name.0 == "Set" => entries
+                                .read()
+                                .iter()
+                                .try_for_each(|(value, _)| match generics.first() {
+                                    Some(ty) => value
+                                        .check_within(ty, runtime, seen)
+                                        .map_err(|error| error.inside(format!("[{value}]"))),
+                                    None => Ok(()),
+                                }),
+                            Value::WeakMap(_) if name.0 == "WeakMap" => Ok(()),
+                            Value::Promise(_) if name.0 == "Promise" => Ok(()),
+                            Value::Iterator(_)
+                                if matches!(
+                                    name.0.as_str(),
+                                    "Generator" | "Iterator" | "IterableIterator" | "Iterable"
+                                ) =>
+                            {
+                                Ok(())
+                            }
+                            value => match runtime.resolve_type(&name.0, generics) {
+                                Some(resolved) => {
+                                    let key: Option<(usize, Type)> =
+                                        value.heap_id().map(|id| (id, primary.clone().into()));
+
+                                    if key.as_ref().is_some_and(|key| seen.contains(key)) {
+                                        return Ok(());
+                                    }
+
+                                    seen.extend(key.clone());
+
+                                    let checked = value.check_within(&resolved, runtime, seen);
+
+                                    if key.is_some() {
+                                        seen.pop();
+                                    }
+
+                                    checked.map_err(|error| match error.path.is_empty() {
+                                        true => TypeMismatch::new(
+                                            primary.clone().into(),
+                                            error.found.map(|found| *found),
+                                        ),
+                                        false => error,
+                                    })
+                                }
+                                None if BUILT_IN_TYPES.contains(&name.0.as_str()) => mismatch(),
+                                None => Err(TypeMismatch::unknown(
+                                    primary.clone().into(),
+                                    self.value_type_of(),
+                                    &name.0,
+                                )),
                             },
-                            PredefinedType::Symbol => true,
-                            PredefinedType::Void => matches!(self, Value::None),
-                            PredefinedType::Null => matches!(self, Value::Null),
-                        },
-                        PrimaryType::TypeReference(_, _) => true,
-                        PrimaryType::ObjectType(members) => match self {
-                            Value::Object(_fields) => true /* match members[0] {
-                                TypeMember::PropertySignature(_) => true,
-                                TypeMember::CallSignature(_) => true,
-                                TypeMember::ConstructSignature(_) => true,
-                                TypeMember::IndexSignature(_) => true,
-                                TypeMember::MethodSignature(_) => true,
-                            } */,
-                            _ => false,
                         },
-                        PrimaryType::ArrayType(ty, size) => match self {
-                            Value::Array(elements, _) => match size {
-                                ArraySize::Fixed(size) => {
-                                    elements.len() == *size
-                                        && elements.iter().all(|element| {
-                                            element.is_type_of(&(*ty.clone()).into())
-                                        })
+                        PrimaryType::ObjectType(members) => {
+                            if !matches!(
+                                self,
+                                Value::Object(_)
+                                    | Value::String(_)
+                                    | Value::Array(..)
+                                    | Value::ClassInstance(_)
+                                    | Value::Function(_)
+                                    | Value::ArrowFunction(_)
+                                    | Value::NativeFunction(_)
+                                    | Value::Class { .. }
+                            ) {
+                                return mismatch();
+                            }
+
+                            members
+                                .iter()
+                                .try_for_each(|member| self.check_member(member, runtime, seen))
+                        }
+                        PrimaryType::ArrayType(element, size) => match self {
+                            Value::Array(elements, _) => {
+                                let elements = elements.read();
+
+                                if let ArraySize::Fixed(size) = size {
+                                    if elements.len() != *size {
+                                        return mismatch();
+                                    }
                                 }
-                                ArraySize::Dynamic => elements
-                                    .iter()
-                                    .all(|element| element.is_type_of(&(*ty.clone()).into())),
-                            },
-                            _ => false,
+
+                                elements.iter().enumerate().try_for_each(|(index, value)| {
+                                    value
+                                        .check_within(&(**element).clone().into(), runtime, seen)
+                                        .map_err(|error| error.inside(format!("[{index}]")))
+                                })
+                            }
+                            _ => mismatch(),
                         },
                         PrimaryType::TupleType(types) => match self {
-                            Value::Array(elements, _) => {
-                                elements.len() == types.len()
-                                    && elements
-                                        .iter()
-                                        .zip(types)
-                                        .all(|(element, ty)| element.is_type_of(ty))
+                            Value::Array(elements, _) if elements.read().len() == types.len() => {
+                                elements.read().iter().zip(types).enumerate().try_for_each(
+                                    |(index, (value, ty))| {
+                                        value
+                                            .check_within(ty, runtime, seen)
+                                            .map_err(|error| error.inside(format!("[{index}]")))
+                                    },
+                                )
                             }
-                            _ => false,
+                            _ => mismatch(),
                         },
-                        PrimaryType::TypeQuery => true,
-                        PrimaryType::ThisType => true,
+                        PrimaryType::ReadonlyType(ty) => {
+                            self.check_within(&(**ty).clone().into(), runtime, seen)
+                        }
+                        PrimaryType::TypeQuery(_)
+                        | PrimaryType::KeyOfType(_)
+                        | PrimaryType::IndexedAccessType(..)
+                        | PrimaryType::MappedType(_) => {
+                            self.check_evaluated(&primary.clone().into(), runtime, seen)
+                        }
+                        PrimaryType::InferType(_) => Ok(()),
+                        PrimaryType::ThisType => Ok(()),
                     },
                 },
             },
-            Type::FunctionType(_generics, params, ty) => match self {
-                Value::Function(_) => todo!(),
-                Value::ArrowFunction(func) => {
-                    func.parameters.len() == params.len()
-                        && params.iter().enumerate().all(|(index, param)| {
-                            match func.parameters[index].ty.as_ref() {
-                                Some(ty) => ty == &param.ty.value,
-                                None => true,
-                            }
-                        })
-                        && (func.ty.is_none()
-                            || func
-                                .ty
-                                .as_ref()
-                                .is_some_and(|func_ty| func_ty == ty.as_ref()))
+            Type::FunctionType(_, parameters, ty) => {
+                match self
+                    .signature()
+                    .is_some_and(|(required, signature, returns)| {
+                        required <= parameters.len()
+                            && signature.iter().zip(parameters).all(|(own, parameter)| {
+                                own.as_ref().is_none_or(|own| {
+                                    is_any(own)
+                                        || is_any(&parameter.ty.value)
+                                        || own == &parameter.ty.value
+                                })
+                            })
+                            && returns.as_ref().is_none_or(|returns| {
+                                is_any(returns)
+                                    || is_any(ty)
+                                    || matches!(ty.as_predefined(), Some(PredefinedType::Void))
+                                    || returns == ty.as_ref()
+                            })
+                    }) {
+                    true => Ok(()),
+                    false => mismatch(),
                 }
-                _ => false,
-            },
-            Type::ConstructorType(_, _, _) => match self {
-                Value::Function(_) => todo!(),
-                _ => false,
+            }
+            Type::ConstructorType(_, parameters, _) => match self {
+                Value::Class { constructors, .. }
+                    if constructors.is_empty()
+                        || constructors.iter().any(|constructor| {
+                            constructor
+                                .parameters
+                                .iter()
+                                .filter(|parameter| {
+                                    !parameter.nullable && parameter.default.is_none()
+                                })
+                                .count()
+                                <= parameters.len()
+                        }) =>
+                {
+                    Ok(())
+                }
+                _ => mismatch(),
             },
+            Type::ConditionalType(_) => self.check_evaluated(ty, runtime, seen),
         }
     }
 
-    pub fn value_type_of(&self) -> Type {
+    /// Checks the value against the type that a type operator in `ty` evaluates to.
+    fn check_evaluated(
+        &self,
+        ty: &Type,
+        runtime: &Runtime,
+        seen: &mut Vec<(usize, Type)>,
+    ) -> Result<(), TypeMismatch> {
+        self.check_within(&runtime.evaluate_type(ty), runtime, seen)
+            .map_err(|error| match error.path.is_empty() {
+                true => TypeMismatch::new(ty.clone(), error.found.map(|found| *found)),
+                false => error,
+            })
+    }
+
+    fn check_member(
+        &self,
+        member: &TypeMember,
+        runtime: &Runtime,
+        seen: &mut Vec<(usize, Type)>,
+    ) -> Result<(), TypeMismatch> {
+        match member {
+            TypeMember::PropertySignature(signature) => {
+                let signature = &signature.value;
+                let name = &signature.name.value.0;
+
+                match self.get_member(name).or_else(|| self.built_in_member(name)) {
+                    Some(Value::None | Value::Null) if signature.nullable.value => Ok(()),
+                    Some(value) => value
+                        .check_within(&signature.ty.value, runtime, seen)
+                        .map_err(|error| error.inside(name)),
+                    None if signature.nullable.value => Ok(()),
+                    None => Err(TypeMismatch::new(signature.ty.value.clone(), None).inside(name)),
+                }
+            }
+            TypeMember::MethodSignature(signature) => {
+                let ast::MethodSignature(name, optional, signature) = &signature.value;
+                let name = name.value.to_string();
+                let ty = Type::FunctionType(
+                    signature.value.0.iter().map(|p| p.value.clone()).collect(),
+                    signature.value.1.iter().map(|p| p.value.clone()).collect(),
+                    Box::new(signature.value.2.value.clone()),
+                );
+                let method = match self {
+                    Value::ClassInstance(instance) => runtime
+                        .get_class_method(&instance.name, &name)
+                        .map(Value::Function),
+                    // Array methods are run by the runtime, they only exist when called
+                    Value::Array(..) if ARRAY_METHODS.contains(&name.as_str()) => return Ok(()),
+                    value => value
+                        .get_member(&name)
+                        .or_else(|| value.built_in_member(&name)),
+                };
+
+                match method {
+                    Some(method) => method
+                        .check_within(&ty, runtime, seen)
+                        .map_err(|error| error.inside(&name)),
+                    None if optional.value => Ok(()),
+                    None => Err(TypeMismatch::new(ty, None).inside(&name)),
+                }
+            }
+            TypeMember::CallSignature(signature) => self.check_within(
+                &Type::FunctionType(
+                    signature.value.0.iter().map(|p| p.value.clone()).collect(),
+                    signature.value.1.iter().map(|p| p.value.clone()).collect(),
+                    Box::new(signature.value.2.value.clone()),
+                ),
+                runtime,
+                seen,
+            ),
+            TypeMember::ConstructSignature(signature) => self.check_within(
+                &Type::ConstructorType(
+                    signature.value.0.iter().map(|p| p.value.clone()).collect(),
+                    signature.value.1.iter().map(|p| p.value.clone()).collect(),
+                    Box::new(signature.value.2.value.clone()),
+                ),
+                runtime,
+                seen,
+            ),
+            TypeMember::IndexSignature(signature) => {
+                let ast::IndexSignature(_, key_type, ty) = &signature.value;
+                let entries: Vec<(Value, Value)> = match self {
+                    Value::Object(properties) => properties
+                        .read()
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                    Value::ClassInstance(instance) => instance
+                        .fields
+                        .read()
+                        .iter()
+                        .map(|field| (Value::String(field.prop.name.clone()), field.value.clone()))
+                        .collect(),
+                    _ => vec![],
+                };
+
+                entries.into_iter().try_for_each(|(key, value)| {
+                    let path = match &key {
+                        Value::String(key) => key.clone(),
+                        key => key.to_string(),
+                    };
+
+                    key.check_within(&key_type.value, runtime, seen)
+                        .map_err(|error| error.inside(format!("[{path}]")))?;
+                    value
+                        .check_within(&ty.value, runtime, seen)
+                        .map_err(|error| error.inside(path))
+                })
+            }
+        }
+    }
+
+    /// Required parameter count, parameter types and return type of a function value.
+    fn signature(&self) -> Option<(usize, Vec<Option<Type>>, Option<Type>)> {
         match self {
+            Value::Function(Function { parameters, ty, .. })
+            | Value::NativeFunction(NativeFunction { parameters, ty, .. }) => Some((
+                parameters
+                    .iter()
+                    .filter(|parameter| !parameter.nullable && parameter.default.is_none())
+                    .count(),
+                parameters
+                    .iter()
+                    .map(|parameter| Some(parameter.ty.clone()))
+                    .collect(),
+                Some(ty.clone()),
+            )),
+            Value::ArrowFunction(function) => Some((
+                function
+                    .parameters
+                    .iter()
+                    .filter(|parameter| !parameter.nullable && parameter.default.is_none())
+                    .count(),
+                function
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.ty.clone())
+                    .collect(),
+                function.ty.clone(),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn value_type_of(&self) -> Type {
+        self.value_type_within(&mut vec![])
+    }
+
+    /// Type of the value, `seen` holds the objects being described further up, which are
+    /// described as `any` where they contain themselves.
+    fn value_type_within(&self, seen: &mut Vec<usize>) -> Type {
+        let id = self.heap_id();
+
+        if id.is_some_and(|id| seen.contains(&id)) {
+            return PredefinedType::Any.into();
+        }
+
+        seen.extend(id);
+
+        let ty = match self {
             Value::Array(elements, size) => {
-                let elements = elements
+                let elements = elements.read();
+                // Mixed arrays get a union of their element types
+                let ty = match elements.is_empty() {
+                    true => PredefinedType::Any.into(),
+                    false => Type::union(
+                        elements
+                            .iter()
+                            .map(|value| value.value_type_within(seen))
+                            .collect(),
+                    ),
+                };
+                let ty = match ty {
+                    Type::UnionOrIntersectionOrPrimaryType(
+                        UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
+                            IntersectionOrPrimaryType::PrimaryType(ty),
+                        ),
+                    ) => ty,
+                    ty => PrimaryType::ParenthesizedType(Box::new(ty)),
+                };
+
+                PrimaryType::ArrayType(Box::new(ty), *size).into()
+            }
+            Value::Object(properties) => {
+                // Sorted by name so that the same object always prints the same type
+                let mut properties = properties
+                    .read()
                     .iter()
-                    .map(|element| element.value_type_of())
+                    .map(|(name, value)| {
+                        let name = match name {
+                            Value::String(name) => name.clone(),
+                            name => name.to_string(),
+                        };
+
+                        (name, value.clone())
+                    })
                     .collect::<Vec<_>>();
 
-                let ty = if elements.iter().all(|ty| ty == &elements[0]) {
-                    elements
-                        .get(0)
-                        .cloned()
-                        .unwrap_or(PredefinedType::Any.into())
-                } else {
-                    panic!("all elements in array must be one type");
+                properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                PrimaryType::ObjectType(
+                    properties
+                        .into_iter()
+                        .map(|(name, value)| {
+                            let span = Span::default();
+
+                            TypeMember::PropertySignature(span.wrap(ast::PropertySignature {
+                                modifiers: vec![],
+                                name: span.wrap(Ident(name)),
+                                nullable: span.wrap(false),
+                                ty: span.wrap(value.value_type_within(seen)),
+                            }))
+                        })
+                        .collect(),
+                )
+                .into()
+            }
+            Value::Map(entries) => {
+                let entries = entries.read();
+                let union = |types: Vec<Type>| match types.is_empty() {
+                    true => PredefinedType::Any.into(),
+                    false => Type::union(types),
                 };
 
-                if let Type::UnionOrIntersectionOrPrimaryType(
-                    UnionOrIntersectionOrPrimaryType::IntersectionOrPrimaryType(
-                        IntersectionOrPrimaryType::PrimaryType(ty),
+                PrimaryType::TypeReference(
+                    Ident("Map".into()),
+                    vec![
+                        union(
+                            entries
+                                .iter()
+                                .map(|(key, _)| key.value_type_within(seen))
+                                .collect(),
+                        ),
+                        union(
+                            entries
+                                .iter()
+                                .map(|(_, value)| value.value_type_within(seen))
+                                .collect(),
+                        ),
+                    ],
+                )
+                .into()
+            }
+            Value::Set(entries) => {
+                let entries = entries.read();
+                let ty = match entries.is_empty() {
+                    true => PredefinedType::Any.into(),
+                    false => Type::union(
+                        entries
+                            .iter()
+                            .map(|(value, _)| value.value_type_within(seen))
+                            .collect(),
                     ),
-                ) = ty
-                {
-                    PrimaryType::ArrayType(Box::new(ty), *size).into()
-                } else {
-                    panic!("can't infer type");
-                }
+                };
+
+                PrimaryType::TypeReference(Ident("Set".into()), vec![ty]).into()
             }
-            Value::Object(_) => todo!(),
+            Value::WeakMap(_) => PrimaryType::TypeReference(
+                Ident("WeakMap".into()),
+                vec![PredefinedType::Any.into(), PredefinedType::Any.into()],
+            )
+            .into(),
+            Value::Iterator(iteration) => PrimaryType::TypeReference(
+                Ident(iteration.read().name().into()),
+                vec![PredefinedType::Any.into()],
+            )
+            .into(),
+            Value::Promise(_) => PrimaryType::TypeReference(
+                Ident("Promise".into()),
+                vec![PredefinedType::Any.into()],
+            )
+            .into(),
             Value::Number(_) => PredefinedType::Number.into(),
             Value::Float(_) => PredefinedType::Float.into(),
             Value::Boolean(_) => PredefinedType::Boolean.into(),
             Value::String(_) => PredefinedType::String.into(),
-            Value::ReturnValue(value) => value.value_type_of(),
-            Value::ArrowFunction(func) => PredefinedType::Any.into(),
-            Value::Function(_) => todo!(),
-            Value::Interface {
-                name,
-                extends,
-                signatures,
-            } => todo!(),
-            Value::Class {
-                name,
-                extends,
-                implements,
-                constructors,
-                fields,
-                methods,
-            } => todo!(),
+            Value::Symbol(_) => PredefinedType::Symbol.into(),
+            Value::ReturnValue(value) => value.value_type_within(seen),
+            Value::ArrowFunction(ArrowFunction { parameters, ty, .. }) => {
+                let span = Span::default();
+
+                Type::FunctionType(
+                    vec![],
+                    parameters
+                        .iter()
+                        .map(|parameter| ast::Parameter {
+                            name: span.wrap(Ident(parameter.name.clone())),
+                            nullable: span.wrap(parameter.nullable),
+                            ty: span
+                                .wrap(parameter.ty.clone().unwrap_or(PredefinedType::Any.into())),
+                            default: None,
+                        })
+                        .collect(),
+                    Box::new(ty.clone().unwrap_or(PredefinedType::Any.into())),
+                )
+            }
+            Value::Function(Function { parameters, ty, .. })
+            | Value::NativeFunction(NativeFunction { parameters, ty, .. }) => {
+                let span = Span::default();
+
+                Type::FunctionType(
+                    vec![],
+                    parameters
+                        .iter()
+                        .map(|parameter| ast::Parameter {
+                            name: span.wrap(Ident(parameter.name.clone())),
+                            nullable: span.wrap(parameter.nullable),
+                            ty: span.wrap(parameter.ty.clone()),
+                            default: None,
+                        })
+                        .collect(),
+                    Box::new(ty.clone()),
+                )
+            }
+            // Declarations are described by the name they were declared with
+            Value::Interface { name, .. } | Value::TypeAlias { name, .. } => {
+                PrimaryType::TypeReference(Ident(name.clone()), vec![]).into()
+            }
+            Value::Class { name, .. } | Value::Enum(Enum { name, .. }) => {
+                PrimaryType::TypeQuery(vec![Ident(name.clone())]).into()
+            }
             Value::Null => PredefinedType::Null.into(),
             Value::None => PredefinedType::Void.into(),
-            Value::Error(span, code, message) => PredefinedType::Void.into(),
-            Value::Enum(_) => todo!(),
-            Value::TypeAlias { name, ty } => todo!(),
-            Value::NativeFunction(_) => todo!(),
-            Value::ClassInstance(_) => PrimaryType::ThisType.into(),
-            Value::Reference(..) => todo!(),
+            Value::Error(..) => PredefinedType::Void.into(),
+            Value::ClassInstance(instance) => {
+                PrimaryType::TypeReference(Ident(instance.name.clone()), vec![]).into()
+            }
+            Value::Reference(path, _) => {
+                PrimaryType::TypeQuery(path.iter().cloned().map(Ident).collect()).into()
+            }
+        };
+
+        if id.is_some() {
+            seen.pop();
         }
+
+        ty
     }
 
     pub fn type_of(&self) -> &str {
         match self {
             Value::Array(..) => "array",
             Value::Object(_) => "object",
+            Value::Map(_) => "Map",
+            Value::Set(_) => "Set",
+            Value::WeakMap(_) => "WeakMap",
+            Value::Iterator(iteration) => iteration.read().name(),
+            Value::Promise(_) => "Promise",
+            Value::Symbol(_) => "symbol",
             Value::Number(_) => "number",
             Value::Float(_) => "float",
             Value::Boolean(_) => "boolean",
@@ -875,9 +1563,38 @@ impl Value {
             Value::ArrowFunction(_) => "LinearFunction",
             Value::Enum(Enum { name, .. }) => name,
             Value::TypeAlias { name, .. } => name,
-            Value::NativeFunction(_) => todo!(),
-            Value::ClassInstance(_) => todo!(),
-            Value::Reference(..) => todo!(),
+            Value::NativeFunction(_) => "Function",
+            Value::ClassInstance(instance) => &instance.name,
+            Value::Reference(..) => "Reference",
+        }
+    }
+
+    /// Name reported by the `typeof` operator.
+    pub fn type_of_operator(&self) -> &str {
+        match self {
+            Value::Number(_) | Value::Float(_) => "number",
+            Value::Boolean(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Symbol(_) => "symbol",
+            Value::ReturnValue(value) => value.type_of_operator(),
+            Value::Function(_)
+            | Value::ArrowFunction(_)
+            | Value::NativeFunction(_)
+            | Value::Class { .. } => "function",
+            Value::None => "undefined",
+            _ => "object",
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Number(number) => *number != 0,
+            Value::Float(number) => *number != 0.0 && !number.is_nan(),
+            Value::Boolean(boolean) => *boolean,
+            Value::String(string) => !string.is_empty(),
+            Value::ReturnValue(value) => value.is_truthy(),
+            Value::Null | Value::None | Value::Error(..) => false,
+            _ => true,
         }
     }
 }
@@ -890,7 +1607,17 @@ impl Hash for Value {
             Value::Number(number) => number.hash(state),
             Value::Boolean(boolean) => boolean.hash(state),
             Value::String(string) => string.hash(state),
-            _ => "".hash(state),
+            // Zeros of either sign are equal, and every NaN hashes alike for `SameValueZero`
+            Value::Float(float) => match float {
+                float if *float == 0.0 => 0f64.to_bits().hash(state),
+                float if float.is_nan() => f64::NAN.to_bits().hash(state),
+                float => float.to_bits().hash(state),
+            },
+            Value::Symbol(symbol) => symbol.hash(state),
+            value => match value.heap_id() {
+                Some(id) => id.hash(state),
+                None => std::mem::discriminant(value).hash(state),
+            },
         }
     }
 }
@@ -1,4 +1,5 @@
-use super::Value;
+use super::{builders::ObjectBuilder, Function, NativeFunction, Parameter, Value};
+use crate::TypeDefinition;
 
 pub trait NativeObject {
     fn build_object(&'static self) -> Value;
@@ -6,12 +7,19 @@ pub trait NativeObject {
 
 pub trait NativeModule {
     fn build_module(&'static self) -> Module;
+
+    /// Source of a `.d.ts` file describing the exports of the module.
+    fn declaration(&'static self) -> String {
+        self.build_module().declaration()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Module {
     pub name: String,
     pub(crate) exports: Vec<(String, Value)>,
+    /// Types declared under the names of the exports, imports declare them too
+    pub(crate) types: Vec<(String, TypeDefinition)>,
 }
 
 impl Module {
@@ -21,6 +29,7 @@ impl Module {
         Self {
             name: name.into(),
             exports: Default::default(),
+            types: Default::default(),
         }
     }
 
@@ -29,5 +38,80 @@ impl Module {
 
         self.exports.push((name.into(), value))
     }
+
+    /// Sets the value bound by `import name from` this module.
+    pub fn export_default(&mut self, value: Value) {
+        self.export("default", value)
+    }
+
+    /// Object with a property for every export, bound by `import * as name`.
+    pub fn namespace(&self) -> Value {
+        self.exports
+            .iter()
+            .fold(ObjectBuilder::default(), |object, (key, value)| {
+                object.prop(key, value.clone())
+            })
+            .build()
+    }
+
+    /// Source of a `.d.ts` file with a `declare module` block for the module, functions
+    /// keep their signatures and other values are typed by their current value.
+    pub fn declaration(&self) -> String {
+        let mut lines = vec![];
+
+        for (name, value) in &self.exports {
+            if name != "default" {
+                lines.push(format!("export {}", declare(name, value)));
+            }
+        }
+
+        if let Some((_, value)) = self.exports.iter().find(|(name, _)| name == "default") {
+            match self
+                .exports
+                .iter()
+                .find(|(name, export)| name != "default" && export == value)
+            {
+                Some((name, _)) => lines.push(format!("export default {name};")),
+                None => {
+                    lines.push(declare("_default", value));
+                    lines.push("export default _default;".into());
+                }
+            }
+        }
+
+        format!(
+            "declare module \"{}\" {{\n{}}}\n",
+            self.name,
+            lines
+                .iter()
+                .map(|line| format!("    {line}\n"))
+                .collect::<String>()
+        )
+    }
 }
 
+/// Declaration of `value` under `name`, as written inside a `declare` block.
+fn declare(name: &str, value: &Value) -> String {
+    let signature = |parameters: &[Parameter]| {
+        parameters
+            .iter()
+            .map(|parameter| {
+                let optional = match parameter.nullable || parameter.default.is_some() {
+                    true => "?",
+                    false => "",
+                };
+
+                format!("{}{optional}: {}", parameter.name, parameter.ty)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match value {
+        Value::NativeFunction(NativeFunction { parameters, ty, .. })
+        | Value::Function(Function { parameters, ty, .. }) => {
+            format!("function {name}({}): {ty};", signature(parameters))
+        }
+        value => format!("const {name}: {};", value.value_type_of()),
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    mem,
+};
+
+use tsr_lexer::globals::Span;
+
+use super::{
+    heap::{Shared, Trace},
+    ErrorCode, Properties, Value,
+};
+use crate::{
+    event_loop::{EventLoop, WeakEventLoop},
+    Runtime,
+};
+
+/// How a promise settled.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Outcome {
+    Fulfilled(Value),
+    /// The reason, any value or the error a function failed with
+    Rejected(Value),
+}
+
+/// Runs once the promise it was added to settles, as a job of the event loop.
+pub type Reaction = Box<dyn FnOnce(&mut Runtime, Outcome)>;
+
+pub enum State {
+    Pending(Vec<Reaction>),
+    Settled(Box<Outcome>),
+}
+
+/// State of a promise, along with the event loop its reactions are queued on.
+pub struct Promise {
+    state: State,
+    event_loop: WeakEventLoop,
+}
+
+impl Promise {
+    /// A promise settled later, by [`Promise::settle`].
+    pub fn pending(event_loop: &EventLoop) -> Shared<Promise> {
+        Shared::new(Promise {
+            state: State::Pending(vec![]),
+            event_loop: event_loop.downgrade(),
+        })
+    }
+
+    /// A promise settled with `outcome` from the start, like `Promise.resolve(value)`.
+    pub fn settled(event_loop: &EventLoop, outcome: Outcome) -> Shared<Promise> {
+        let promise = Self::pending(event_loop);
+
+        Self::settle(&promise, outcome);
+
+        promise
+    }
+
+    /// `value` itself if it is a promise, otherwise a promise fulfilled with it.
+    pub fn from_value(event_loop: &EventLoop, value: Value) -> Shared<Promise> {
+        match value {
+            Value::Promise(promise) => promise,
+            value => Self::settled(event_loop, Outcome::Fulfilled(value)),
+        }
+    }
+
+    pub fn outcome(&self) -> Option<&Outcome> {
+        match &self.state {
+            State::Pending(_) => None,
+            State::Settled(outcome) => Some(outcome),
+        }
+    }
+
+    /// Settles a pending promise and queues its reactions.
+    pub fn settle(promise: &Shared<Promise>, outcome: Outcome) {
+        if let Outcome::Fulfilled(Value::Promise(inner)) = outcome {
+            if inner == *promise {
+                return Self::settle(
+                    promise,
+                    Outcome::Rejected(Value::error(
+                        Span::default(),
+                        ErrorCode::Type,
+                        "a promise can't be resolved with itself",
+                    )),
+                );
+            }
+
+            let promise = promise.clone();
+
+            return Self::subscribe(
+                &inner,
+                Box::new(move |_, outcome| Self::settle(&promise, outcome)),
+            );
+        }
+
+        let (reactions, event_loop) = {
+            let mut current = promise.write();
+
+            let State::Pending(reactions) = &mut current.state else {
+                return;
+            };
+            let reactions = mem::take(reactions);
+
+            current.state = State::Settled(Box::new(outcome.clone()));
+
+            (reactions, current.event_loop.clone())
+        };
+
+        if reactions.is_empty() {
+            if let Outcome::Rejected(_) = outcome {
+                event_loop.reject_unhandled(promise);
+            }
+        }
+
+        for reaction in reactions {
+            let outcome = outcome.clone();
+
+            event_loop.enqueue(Box::new(move |runtime| reaction(runtime, outcome)));
+        }
+    }
+
+    /// Adds a reaction to the promise, queued right away when it has already settled.
+    pub fn subscribe(promise: &Shared<Promise>, reaction: Reaction) {
+        let mut current = promise.write();
+
+        match &mut current.state {
+            State::Pending(reactions) => reactions.push(reaction),
+            State::Settled(outcome) => {
+                let outcome = (**outcome).clone();
+
+                current.event_loop.handle(promise);
+                current
+                    .event_loop
+                    .enqueue(Box::new(move |runtime| reaction(runtime, outcome)));
+            }
+        }
+    }
+}
+
+impl Trace for Promise {
+    fn trace(&self, visit: &mut dyn FnMut(&Value)) {
+        if let State::Settled(outcome) = &self.state {
+            let (Outcome::Fulfilled(value) | Outcome::Rejected(value)) = &**outcome;
+
+            visit(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.state = State::Pending(vec![]);
+    }
+}
+
+impl Debug for Promise {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.state {
+            State::Pending(_) => f.write_str("Pending"),
+            State::Settled(outcome) => outcome.fmt(f),
+        }
+    }
+}
+
+impl Value {
+    /// Error a rejection fails the program with when the reason isn't an error already.
+    pub fn rejection(span: Span, reason: Value) -> Value {
+        match reason {
+            error @ Value::Error(..) => error,
+            Value::String(message) => Value::error(span, ErrorCode::Type, message),
+            reason => Value::error(span, ErrorCode::Type, reason.to_string()),
+        }
+    }
+
+    /// The reason of a rejection as handlers receive it.
+    pub fn reason(self) -> Value {
+        match self {
+            Value::Error(_, code, message) => Value::object(Properties::from([
+                (
+                    Value::String("name".into()),
+                    Value::String(format!("{code:?}Error")),
+                ),
+                (Value::String("message".into()), Value::String(message)),
+            ])),
+            reason => reason,
+        }
+    }
+}
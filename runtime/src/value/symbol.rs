@@ -0,0 +1,100 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        LazyLock, Mutex,
+    },
+};
+
+// Ids below are taken by the well-known symbols
+const WELL_KNOWN: usize = 16;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(WELL_KNOWN);
+
+static REGISTRY: LazyLock<Mutex<HashMap<String, Symbol>>> = LazyLock::new(Default::default);
+
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    id: usize,
+    pub description: Option<String>,
+}
+
+impl Symbol {
+    pub fn new(description: Option<String>) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            description,
+        }
+    }
+
+    fn well_known(id: usize, name: &str) -> Self {
+        Self {
+            id,
+            description: Some(format!("Symbol.{name}")),
+        }
+    }
+
+    pub fn iterator() -> Self {
+        Self::well_known(0, "iterator")
+    }
+
+    pub fn async_iterator() -> Self {
+        Self::well_known(1, "asyncIterator")
+    }
+
+    pub fn has_instance() -> Self {
+        Self::well_known(2, "hasInstance")
+    }
+
+    pub fn to_primitive() -> Self {
+        Self::well_known(3, "toPrimitive")
+    }
+
+    pub fn to_string_tag() -> Self {
+        Self::well_known(4, "toStringTag")
+    }
+
+    /// Like `Symbol.for`.
+    pub fn registered(key: &str) -> Self {
+        REGISTRY
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_insert_with(|| Self::new(Some(key.to_string())))
+            .clone()
+    }
+
+    /// Like `Symbol.keyFor`.
+    pub fn key(&self) -> Option<String> {
+        REGISTRY
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, symbol)| *symbol == self)
+            .map(|(key, _)| key.clone())
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Symbol({})",
+            self.description.as_deref().unwrap_or_default()
+        )
+    }
+}
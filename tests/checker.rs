@@ -0,0 +1,694 @@
+use tsr_checker::Checker;
+use tsr_lexer::Lexer;
+use tsr_parser::Parser;
+
+fn check(code: &str) -> Vec<String> {
+    let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+    Checker::with_std()
+        .check_program(&ast)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn accepts_well_typed_program() {
+    let diagnostics = check(
+        r#"
+        interface Point { x: number; y: number; }
+        type Name = string;
+
+        function add(a: number, b: number): number {
+            return a + b;
+        }
+
+        class Named implements Point {
+            x: number = 0;
+            y: number = 0;
+            name: Name = "origin";
+        }
+
+        const point: Point = new Named();
+        const sum: number = add(point.x, 2);
+        let label: Name = "sum";
+        label = label + "!";
+        console.log(label.length);
+        "#,
+    );
+
+    assert_eq!(diagnostics, Vec::<String>::new());
+}
+
+#[test]
+fn reports_assignability_errors() {
+    let diagnostics = check(
+        r#"
+        interface Point { x: number; y: number; }
+
+        let count: number = "one";
+        const point: Point = 1;
+        const partial: Point = { x: 1 };
+        const text: { length: number; toUpperCase(): string; } = "abc";
+        const list: { length: number; push(item: number): number; } = [0...];
+        const sized: { size: number } = "abc";
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"Point\"",
+            "type \"{ x: number }\" is not assignable to type \"Point\"",
+            "type \"string\" is not assignable to type \"{ size: number }\"",
+        ]
+    );
+}
+
+#[test]
+fn reports_call_errors() {
+    let diagnostics = check(
+        r#"
+        function greet(name: string): string {
+            return "hello " + name;
+        }
+
+        greet(1);
+        greet("a", "b");
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"number\" is not assignable to type \"string\"",
+            "expected 1 arguments, but got 2",
+        ]
+    );
+}
+
+#[test]
+fn reports_names_and_members() {
+    let diagnostics = check(
+        r#"
+        interface Shape { area: number; }
+        class Square implements Shape { side: number = 1; }
+
+        const limit = 1;
+        limit = 2;
+        const shape: Unknown = missing;
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "class \"Square\" incorrectly implements interface \"Shape\"",
+            "cannot assign to \"limit\" because it is a constant",
+            "cannot find name \"Unknown\"",
+            "cannot find name \"missing\"",
+        ]
+    );
+}
+
+#[test]
+fn instantiates_generics() {
+    let diagnostics = check(
+        r#"
+        interface Point { x: number; }
+        interface Box<T> { value: T; }
+        type Pair<A, B = number> = { first: A; second: B; };
+
+        function identity<T>(value: T): T {
+            return value;
+        }
+
+        function apply<T, U>(value: T, map: (value: T) => U): U {
+            return map(value);
+        }
+
+        function measure<T extends string>(value: T): number {
+            return value.length;
+        }
+
+        function count<T extends { length: number }>(value: T): number {
+            return value.length;
+        }
+
+        const one: number = identity<number>(1);
+        const two: string = identity(2);
+        const wrong: number = identity<number>("one");
+        const length: number = apply("text", (text) => text.length);
+        const size: number = measure(3);
+        const letters: number = count("abc");
+        const items: number = count([1, 2]);
+        const none: number = count(5);
+        const box: Box<Point> = { value: { x: 1 } };
+        const bad: Box<Point> = { value: 1 };
+        const pair: Pair<Point> = { first: { x: 1 }, second: "2" };
+        const extra: Box<Point, Point> = { value: { x: 1 } };
+        identity<number, string>(1);
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" does not satisfy the constraint \"string\"",
+            "type \"number\" does not satisfy the constraint \"{ length: number }\"",
+            "type \"{ value: number }\" is not assignable to type \"Box<Point>\"",
+            "type \"{ first: { x: number }, second: string }\" is not assignable to type \"Pair<Point>\"",
+            "expected 1 type arguments, but got 2",
+            "expected 1 type arguments, but got 2",
+        ]
+    );
+}
+
+#[test]
+fn evaluates_type_operators() {
+    let diagnostics = check(
+        r#"
+        interface Point { x: number; y: string; }
+        type Keys = keyof Point;
+        type Y = Point["y"];
+        type Flags<T> = { readonly [K in keyof T]?: boolean };
+        type Unwrap<T> = T extends (infer U)[] ? U : T;
+        type Small = 1 | 2 | true;
+
+        const origin = { x: 0, y: "0" };
+        const key: Keys = "x";
+        const wrongKey: Keys = "z";
+        const y: Y = 1;
+        const flags: Flags<Point> = { x: true };
+        const element: Unwrap<string[]> = "a";
+        const plain: Unwrap<number> = "a";
+        const copy: typeof origin = { x: 1, y: "1" };
+        const small: Small = 3;
+        const top: unknown = 1;
+        const missing: Point["z"] = 1;
+        type Dir = "up" | "down";
+        const tagged: { kind: "a" } = { kind: "a" };
+        const dirs: Dir[] = ["up", "down"];
+        const wrongDirs: Dir[] = ["left"];
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"\"z\"\" is not assignable to type \"Keys\"",
+            "type \"number\" is not assignable to type \"Y\"",
+            "type \"string\" is not assignable to type \"Unwrap<number>\"",
+            "type \"3\" is not assignable to type \"Small\"",
+            "property \"z\" does not exist on type \"Point\"",
+            "type \"string[1]\" is not assignable to type \"Dir[]\"",
+        ]
+    );
+}
+
+#[test]
+fn narrows_types_in_conditions() {
+    let diagnostics = check(
+        r#"
+        interface Circle { kind: "circle"; radius: number; }
+        interface Square { kind: "square"; size: number; }
+        type Shape = Circle | Square;
+        class Dog { bark(): string { return "woof"; } }
+
+        function length(value: string | null): number {
+            if (value === null) {
+                return 0;
+            }
+
+            const text: string = value;
+
+            return 1;
+        }
+
+        function describe(value: string | number): string {
+            if (typeof value === "string") {
+                const text: string = value;
+                const wrong: number = value;
+            } else {
+                const count: number = value;
+            }
+
+            return "";
+        }
+
+        function area(shape: Shape): number {
+            if (shape.kind === "circle") {
+                const radius: number = shape.radius;
+                const size: number = shape.size;
+            }
+
+            return match (shape.kind) {
+                when ("square") => shape.size,
+                when ("circle") => shape.radius,
+            };
+        }
+
+        const square: Shape = { kind: "square", size: 4 };
+        const triangle: Shape = { kind: "triangle", size: 3 };
+        const total: number = area(square) + area({ kind: "circle", radius: 1 });
+
+        function speak(pet: Dog | string): string {
+            if (pet instanceof Dog) {
+                const dog: Dog = pet;
+            }
+
+            if ("bark" in pet) {
+                const dog: Dog = pet;
+            }
+
+            if (pet !== null && typeof pet === "string") {
+                const name: string = pet;
+            }
+
+            return "";
+        }
+
+        function nullable(value: string | null): string {
+            const text: string = value;
+
+            if (value) {
+                const present: string = value;
+            }
+
+            return "";
+        }
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"string\" is not assignable to type \"number\"",
+            "property \"size\" does not exist on type \"Circle\"",
+            "type \"{ kind: string, size: number }\" is not assignable to type \"Shape\"",
+            "type \"string | null\" is not assignable to type \"string\"",
+        ]
+    );
+}
+
+#[test]
+fn checks_type_assertions() {
+    let diagnostics = check(
+        r#"
+        interface Point { x: number; y: number; }
+
+        const value: string | null = "a";
+        const text: string = value!;
+        const wide: any = 1;
+        const point = wide as Point;
+        const x: number = point.x;
+        const wrong = "a" as number;
+        const checked = { x: 1, y: 2 } satisfies Point;
+        const missing = { x: 1 } satisfies Point;
+        const nullable: string = value as string | null;
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "conversion of type \"string\" to type \"number\" may be a mistake",
+            "type \"{ x: number }\" is not assignable to type \"Point\"",
+            "type \"string | null\" is not assignable to type \"string\"",
+        ]
+    );
+}
+
+#[test]
+fn resolves_overloads() {
+    let diagnostics = check(
+        r#"
+        function parse(value: string): number;
+        function parse(value: number): string;
+        function parse(value: any): any {
+            return value;
+        }
+
+        const count: number = parse("1");
+        const text: string = parse(1);
+        const wrong: string = parse("1");
+        parse(true);
+
+        function broken(value: string): boolean;
+        function broken(value: number): number {
+            return value;
+        }
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"number\" is not assignable to type \"string\"",
+            "no overload matches this call",
+            "this overload signature is not compatible with its implementation",
+        ]
+    );
+}
+
+#[test]
+fn checks_exports() {
+    let diagnostics = check(
+        r#"
+        interface Shape { sides: number; }
+        const square: Shape = { sides: 4 };
+
+        export { square, Shape, square as box, missing };
+        export * from "./shapes";
+        export default square;
+        "#,
+    );
+
+    assert_eq!(diagnostics, vec!["cannot find name \"missing\""]);
+}
+
+#[test]
+fn resolves_type_only_imports() {
+    let diagnostics = check(
+        r#"
+        import type { Shape } from "./shapes";
+        import type Scene from "./scene";
+        import { type Point, distance } from "./points";
+
+        const shape: Shape = { sides: 4 };
+        const point: Point = { x: 1 };
+        distance(point, point);
+        Scene;
+        "#,
+    );
+
+    assert_eq!(diagnostics, vec!["cannot find name \"Scene\""]);
+}
+
+#[test]
+fn checks_dynamic_imports() {
+    let diagnostics = check(
+        r#"
+        const shapes = await import("./shapes");
+        const sides: number = shapes.sides;
+        import(1);
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec!["type \"number\" is not assignable to type \"string\""]
+    );
+}
+
+#[test]
+fn checks_namespaces_and_ambient_declarations() {
+    let diagnostics = check(
+        r#"
+        declare const version: number;
+        declare function log(data: string): void;
+        declare namespace host {
+            const name: string;
+            function exit(code: number): void;
+        }
+
+        namespace Shapes {
+            const hidden = 2;
+            export const sides = hidden * 2;
+        }
+        namespace Shapes.Polygons {
+            export function area(sides: number): number {
+                return sides * version;
+            }
+        }
+
+        const sides: number = Shapes.sides;
+        const area: number = Shapes.Polygons.area(sides);
+        const name: number = host.name;
+        log(host.exit);
+        hidden;
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"(code: number) => void\" is not assignable to type \"string\"",
+            "cannot find name \"hidden\"",
+        ]
+    );
+}
+
+#[test]
+fn types_imports_with_loaded_declarations() {
+    let parse = |code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+
+        Parser::parse_tokens(&tokens).unwrap().1
+    };
+    let mut checker = Checker::default();
+
+    let diagnostics = checker.load_declarations(&parse(
+        r#"
+        declare module "greeter" {
+            export function greet(name: string): string;
+            export default greet;
+        }
+        declare const version: number;
+        const local = 1;
+        "#,
+    ));
+
+    assert_eq!(
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>(),
+        vec!["only declarations are allowed in a declaration file"]
+    );
+
+    let diagnostics = checker.check_program(&parse(
+        r#"
+        import hello from "greeter";
+        import { greet, wave } from "greeter";
+        import * as greeter from "greeter";
+        import { default as welcome } from "greeter";
+        import hi, { greet as wave2 } from "greeter";
+        import hey, * as all from "greeter";
+        import { anything } from "elsewhere";
+
+        const message: string = hello("you");
+        const count: number = greet("me");
+        greeter.greet(version);
+        welcome(version);
+        const waved: number = wave2(hi(hey("x")));
+        all.greet(1);
+        anything(1);
+        "#,
+    ));
+
+    assert_eq!(
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>(),
+        vec![
+            "module \"greeter\" has no exported member \"wave\"",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"string\"",
+        ]
+    );
+}
+
+#[test]
+fn types_array_methods() {
+    let diagnostics = check(
+        r#"
+        const numbers = [3, 1, 2...];
+        const fixed = [1, 2];
+        const frozen: readonly number[] = [1, 2];
+
+        numbers.push(4);
+        const last: number | undefined = numbers.pop();
+        const labels: string[] = numbers.map((value) => 'label');
+        const doubled: number[] = numbers.map() { value * 2 };
+        const sum: number = numbers.reduce((total, value) => total + value, 0);
+        const found: number | undefined = numbers.find((value) => value > 1);
+        const joined: string = numbers.filter((value) => value > 1).join(", ");
+
+        fixed.push(3);
+        frozen.sort();
+        numbers.includes("a");
+        const wrong: number[] = numbers.map((value) => 'label');
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "property \"push\" does not exist on type \"number[2]\"",
+            "property \"sort\" does not exist on type \"readonly number[]\"",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"string[]\" is not assignable to type \"number[]\"",
+        ]
+    );
+}
+
+#[test]
+fn types_string_methods() {
+    let diagnostics = check(
+        r#"
+        const text = "Hello";
+        const length: number = text.length;
+        const last: string | undefined = text.at(-1);
+        const part: string = text.slice(1).toUpperCase().padStart(8, "*");
+        const found: boolean = text.includes("ell") && text.endsWith("o");
+        const parts: string[] = text.split("l");
+
+        text.repeat("2");
+        const position: string = text.indexOf("l");
+        text.shout();
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "property \"shout\" does not exist on type \"string\"",
+        ]
+    );
+}
+
+#[test]
+fn checks_generators_and_iteration() {
+    let diagnostics = check(
+        r#"
+        function* numbers(): Generator<number> {
+            yield 1;
+            yield* [2, 3];
+            yield "four";
+        }
+
+        declare const pair: [number, string];
+        const [first, second, third] = pair;
+        const name: string = first;
+        const missing: string = third;
+        for (const value of numbers()) {
+            const label: string = value;
+        }
+        for (const [key, count] of [["a", 1]]) {
+            const total: number = key;
+        }
+        for (const value of 5) { }
+        const spread: number[] = [...numbers(), ..."ab"];
+        yield 1;
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"undefined\" is not assignable to type \"string\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"(string | number)\" is not assignable to type \"number\"",
+            "type \"number\" is not iterable",
+            "type \"(number | string)[]\" is not assignable to type \"number[]\"",
+            "yield is only valid in generator functions",
+        ]
+    );
+}
+
+#[test]
+fn checks_async_functions() {
+    let diagnostics = check(
+        r#"
+        async function load(id: number): Promise<string> {
+            const other: number = await Promise.resolve(id);
+            return id;
+        }
+        async function forward(): Promise<string> {
+            return load(1);
+        }
+        async function untyped(): any {
+            return await load(2);
+        }
+        async function wrong(): number {
+            return 1;
+        }
+        function sync(): number {
+            return await load(3);
+        }
+
+        const name: string = await load(4);
+        const count: number = await load(5);
+        const pending: Promise<string> = load(6);
+        const doubled = async (value: number) => value * 2;
+        const result: Promise<string> = doubled(1);
+        load(7).then((value) => value.length).finally(() => 0);
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"number\" is not a valid return type for an async function",
+            "await is only valid in async functions and at the top level",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"string\" is not assignable to type \"number\"",
+            "type \"Promise<number>\" is not assignable to type \"Promise<string>\"",
+        ]
+    );
+}
+
+#[test]
+fn types_standard_globals() {
+    let diagnostics = check(
+        r#"
+        const scores = new Map<string, number>();
+        scores.set("a", 1);
+        const score: number | undefined = scores.get("a");
+        const seen = new Set<number | float>();
+        seen.add(Number("12") + Math.floor(2.5) + Number.MAX_SAFE_INTEGER);
+        const ratio: float = Math.sqrt(2) * Math.PI;
+        const text: string | undefined = JSON.stringify({ score });
+        const settled: Promise<number> = Promise.resolve(1);
+        const timer: number = setTimeout(() => console.log(Math.max(1, 2)), 10);
+        clearTimeout(timer);
+        for (const [name, value] of scores) {
+            console.log(name.length, value * 2, Object.keys(seen), Array.isArray(seen));
+        }
+
+        const label: string = scores.get("a");
+        const parsed: string = parseInt("42");
+        const rounded: number = Math.random();
+        scores.set(1, 1);
+        JSON.parse(1);
+        Math.round();
+        const Map = 1;
+        "#,
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            "type \"number | undefined\" is not assignable to type \"string\"",
+            "type \"number | float\" is not assignable to type \"string\"",
+            "type \"float\" is not assignable to type \"number\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "type \"number\" is not assignable to type \"string\"",
+            "expected 1 arguments, but got 0",
+        ]
+    );
+}
@@ -1,11 +1,30 @@
-use std::{fs, io};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use tsr_lexer::{globals::Span, Lexer};
-use tsr_parser::{ast::PredefinedType, Parser};
+use tsr_parser::{
+    ast::{ArraySize, PredefinedType},
+    Parser,
+};
 use tsr_runtime::{
-    api::{reflection::Reflection, util::Util},
-    value::{builders::ObjectBuilder, Value},
-    FunctionBuilder, Runtime,
+    api::{prelude::table, reflection::Reflection, util::Util},
+    clock::VirtualClock,
+    loader::{FileLoader, ModuleLoader},
+    value::{
+        builders::ObjectBuilder,
+        inspect::InspectOptions,
+        native::{Module, NativeModule},
+        Properties, Value,
+    },
+    FArguments, FunctionBuilder, Runtime,
 };
 
 #[test]
@@ -16,29 +35,7 @@ fn main() -> io::Result<()> {
 
     let (_, tokens) = Lexer::lex_tokens(code.into()).unwrap();
     let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
-    let mut runtime = Runtime::default();
-
-    runtime.set_variable(
-        "console",
-        Span::default().wrap(
-            ObjectBuilder::default()
-                .prop(
-                    "log",
-                    FunctionBuilder::new("log")
-                        .param("data", PredefinedType::Any)
-                        .returns(PredefinedType::Void)
-                        .build(|args| {
-                            if let Some(data) = args.get("data") {
-                                match data {
-                                    Value::String(data) => println!("{data}"),
-                                    data => println!("{data:#}"),
-                                }
-                            }
-                        }),
-                )
-                .build(),
-        ),
-    );
+    let mut runtime = Runtime::with_std();
 
     runtime.add_module(&Reflection);
     runtime.add_module(&Util);
@@ -48,3 +45,1741 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+fn eval(code: &str) -> Value {
+    let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+
+    Runtime::default().eval_program(ast)
+}
+
+/// Evaluates `code` with the standard globals installed.
+fn eval_std(code: &str) -> Value {
+    let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+
+    Runtime::with_std().eval_program(ast)
+}
+
+fn error_message(value: Value) -> String {
+    match value {
+        Value::Error(_, _, message) => message,
+        value => panic!("expected an error, got {value:?}"),
+    }
+}
+
+#[test]
+fn resolves_interface_references() {
+    let program = r#"
+        interface Point { x: number; y: number; }
+
+        function getX(point: Point): number {
+            point.x
+        }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} getX({{ x: 1, y: 2 }});")),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} getX({{ x: 1 }});"))),
+        "Point expected but { x: number } given: property \"y\" is missing"
+    );
+}
+
+#[test]
+fn resolves_aliases_enums_and_classes() {
+    assert_eq!(
+        error_message(eval("type Id = string; const id: Id = 1;")),
+        "expected Id, but got number"
+    );
+    assert_eq!(
+        error_message(eval(
+            "enum Kind { A = 'a', B = 'b' } const kind: Kind = 'c';"
+        )),
+        "expected Kind, but got string"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            class Counter { count: number = 1; }
+            function read(counter: Counter): number { counter.count }
+            read(new Counter());
+            "#
+        ),
+        Value::Number(1)
+    );
+}
+
+#[test]
+fn reports_unknown_types_and_scopes_aliases() {
+    assert_eq!(
+        error_message(eval("const x: Missng = 5;")),
+        "expected Missng, but got number: type \"Missng\" is not defined"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            type Id = string;
+            function local(): number { type Id = number; const inner: Id = 1; inner }
+            const outer: Id = 'a';
+            [local(), outer];
+            "#
+        )
+        .to_string(),
+        "[ 1, 'a' ]"
+    );
+    assert_eq!(
+        error_message(eval(
+            "if (true) { type Local = number; } const leaked: Local = 1;"
+        )),
+        "expected Local, but got number: type \"Local\" is not defined"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            class Box<T> { value: T; constructor(value: T) { this.value = value; } }
+            function first<T>(values: T[]): T { const value: T = values[0]; value }
+            [new Box<number>(3).value, first([4])];
+            "#
+        )
+        .to_string(),
+        "[ 3, 4 ]"
+    );
+}
+
+#[test]
+fn checks_object_members_structurally() {
+    let program = r#"
+        interface User {
+            name: string;
+            nickname?: string;
+            address: { city: string; };
+            greet(greeting: string): string;
+        }
+
+        function city(user: User): string {
+            user.address.city
+        }
+    "#;
+    let greet = "greet: (greeting: string): string => greeting";
+
+    assert_eq!(
+        eval(&format!(
+            "{program} city({{ name: 'a', address: {{ city: 'b' }}, {greet} }});"
+        )),
+        Value::String("b".into())
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "{program} city({{ name: 'a', address: {{ city: 1 }}, {greet} }});"
+        ))),
+        "User expected but { address: { city: number }, greet: (greeting: string) => string, name: string } given: string expected but number given at \"address.city\""
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "{program} city({{ name: 'a', address: {{ city: 'b' }}, greet: 1 }});"
+        ))),
+        "User expected but { address: { city: string }, greet: number, name: string } given: (greeting: string) => string expected but number given at \"greet\""
+    );
+    // Strings and arrays have members of their own
+    assert_eq!(
+        eval(
+            "const text: { length: number; toUpperCase(): string; } = 'abc'; \
+             const list: { length: number; push(item: number): number; } = [1]; \
+             [text.length, list.length];"
+        )
+        .to_string(),
+        "[ 3, 1 ]"
+    );
+    assert_eq!(
+        error_message(eval("const list: { size: number } = [1];")),
+        "expected { size: number }, but got number[1]: property \"size\" is missing"
+    );
+}
+
+#[test]
+fn checks_types_of_cyclic_values() {
+    let node = "const node = { value: 1, next: null }; node.next = node;";
+    let list = "const list = [1]; list[0] = list;";
+
+    assert_eq!(
+        error_message(eval(&format!("{node} const n: number = node;"))),
+        "expected number, but got { next: any, value: number }"
+    );
+    assert_eq!(
+        eval(&format!(
+            "interface Node {{ value: number; next: Node; }} {node} const n: Node = node; n.value + 0;"
+        )),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "interface Node {{ value: string; next: Node; }} {node} const n: Node = node;"
+        ))),
+        "expected Node, but got { next: any, value: number }: string expected but number given at \"value\""
+    );
+    assert_eq!(
+        error_message(eval(&format!("{list} const n: number = list;"))),
+        "expected number, but got any[1]"
+    );
+    assert_eq!(
+        eval(&format!(
+            "type Nested = Nested[]; {list} const n: Nested = list; 1;"
+        )),
+        Value::Number(1)
+    );
+    assert_eq!(
+        format!("{:?}", eval(&format!("{node} [node];"))),
+        "Array([Object({String(\"value\"): Number(1), String(\"next\"): Object([Circular])})], Fixed(1))"
+    );
+}
+
+#[test]
+fn checks_index_signatures() {
+    assert!(!matches!(
+        eval("const scores: { [name: string]: number } = { a: 1, b: 2 };"),
+        Value::Error(..)
+    ));
+    assert_eq!(
+        error_message(eval(
+            "const scores: { [name: string]: number } = { a: 1, b: 'c' };"
+        )),
+        "expected { [name: string]: number }, but got { a: number, b: string }: number expected but string given at \"b\""
+    );
+}
+
+#[test]
+fn checks_class_methods_against_method_signatures() {
+    let program = r#"
+        interface Named { name(): string; }
+        class Cat { name(): string { 'cat' } }
+        class Dog { name(loud: boolean): string { 'dog' } }
+
+        function describe(named: Named): number { 1 }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} describe(new Cat());")),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} describe(new Dog());"))),
+        "Named expected but Dog given: () => string expected but (loud: boolean) => string given at \"name\""
+    );
+}
+
+#[test]
+fn instantiates_generic_functions() {
+    let program = r#"
+        function identity<T>(value: T): T { value }
+        function unwrap<T extends number = number>(box: { value: T }): T { box.value }
+        function count<T extends { length: number }>(value: T): number { value.length }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} identity<number>(1);")),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} identity<number>('a');"))),
+        "number expected but string given"
+    );
+    assert_eq!(
+        eval(&format!("{program} identity('a');")),
+        Value::String("a".into())
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} unwrap({{ value: 'a' }});"))),
+        "{ value: number } expected but { value: string } given: number expected but string given at \"value\""
+    );
+    assert_eq!(
+        eval(&format!("{program} [count('abc'), count([1, 2])];")).to_string(),
+        "[ 3, 2 ]"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} count(5);"))),
+        "{ length: number } expected but number given"
+    );
+}
+
+#[test]
+fn instantiates_generic_types() {
+    let program = r#"
+        interface Point { x: number; }
+        interface Box<T> { value: T; }
+        type Pair<A, B = Point> = { first: A; second: B; };
+
+        function unbox(box: Box<Point>): number { 1 }
+        function pair(pair: Pair<Point>): number { 2 }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} unbox({{ value: {{ x: 1 }} }});")),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} unbox({{ value: 1 }});"))),
+        "Box<Point> expected but { value: number } given: Point expected but number given at \"value\""
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "{program} pair({{ first: {{ x: 1 }}, second: 2 }});"
+        ))),
+        "Pair<Point> expected but { first: { x: number }, second: number } given: Point expected but number given at \"second\""
+    );
+}
+
+#[test]
+fn evaluates_type_operators() {
+    let program = r#"
+        interface Point { x: number; y: string; }
+        type Unwrap<T> = T extends (infer U)[] ? U : T;
+
+        const origin = { x: 0, y: "0" };
+    "#;
+
+    assert_eq!(
+        error_message(eval(&format!("{program} const key: keyof Point = 'z';"))),
+        "expected keyof Point, but got string"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} const y: Point['y'] = 1;"))),
+        "expected Point[\"y\"], but got number"
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "{program} const copy: typeof origin = {{ x: 1, y: 2 }};"
+        ))),
+        "expected typeof origin, but got { x: number, y: number }: string expected but number given at \"y\""
+    );
+    assert_eq!(
+        error_message(eval(&format!(
+            "{program} const element: Unwrap<string[]> = 1;"
+        ))),
+        "expected Unwrap<string[]>, but got number"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} const one: 1 | true = 2;"))),
+        "expected 1 | true, but got number"
+    );
+    assert!(!matches!(
+        eval(&format!(
+            "{program} const flags: {{ readonly [K in keyof Point]?: boolean }} = {{ x: true }}; const plain: Unwrap<number> = 1; const element: Unwrap<string[]> = 'a';"
+        )),
+        Value::Error(..)
+    ));
+}
+
+#[test]
+fn narrows_values_in_conditions() {
+    let program = r#"
+        interface Circle { kind: "circle"; radius: number; }
+        interface Square { kind: "square"; size: number; }
+        class Dog { bark(): string { return "woof"; } }
+
+        function describe(value: string | number | null): string {
+            if (value === null) {
+                return "null";
+            }
+
+            if (typeof value === "string") {
+                return "string";
+            }
+
+            return "number";
+        }
+
+        function area(shape: Circle | Square): number {
+            match (shape.kind) {
+                when ("circle") => shape.radius * shape.radius * 3,
+                when ("square") => shape.size * shape.size,
+            }
+        }
+
+        function speak(pet: Dog | string): string {
+            if (pet instanceof Dog && "bark" in pet) {
+                return "woof";
+            }
+
+            return pet;
+        }
+    "#;
+
+    for (call, expected) in [
+        ("describe(null)", Value::String("null".into())),
+        ("describe('a')", Value::String("string".into())),
+        ("describe(1)", Value::String("number".into())),
+        ("area({ kind: 'circle', radius: 2 })", Value::Number(12)),
+        ("area({ kind: 'square', size: 3 })", Value::Number(9)),
+        ("speak(new Dog())", Value::String("woof".into())),
+        ("speak('meow')", Value::String("meow".into())),
+        ("typeof speak", Value::String("function".into())),
+        ("!(1 > 2) && 2 >= 2", Value::Boolean(true)),
+    ] {
+        assert_eq!(eval(&format!("{program} {call};")), expected, "{call}");
+    }
+}
+
+#[test]
+fn evaluates_type_assertions() {
+    let program = r#"
+        interface Point { x: number; y: number; }
+
+        const point: any = { x: 1, y: 2 };
+        const empty: string | null = null;
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} (point as Point).x;")),
+        Value::Number(1)
+    );
+    assert_eq!(eval(&format!("{program} 2 as float;")), Value::Float(2.0));
+    assert_eq!(
+        error_message(eval(&format!("{program} 'a' as number;"))),
+        "cannot convert string to number"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} {{ x: 1 }} satisfies Point;"))),
+        "Point expected but { x: number } given: property \"y\" is missing"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} empty!;"))),
+        "non-null assertion failed: value is null or undefined"
+    );
+}
+
+#[test]
+fn describes_types_of_all_values() {
+    let program = r#"
+        class Point {}
+        enum Color { Red }
+        interface Shape { kind: string; }
+    "#;
+
+    for (code, expected) in [
+        ("const mixed: number[] = [1, 'a'];", "expected number[], but got (number | string)[2]: number expected but string given at \"[1]\""),
+        ("const empty: number = [];", "expected number, but got any[0]"),
+        ("const nested: number = [[1], ['a']];", "expected number, but got (number[1] | string[1])[2]"),
+        ("const arrow: number = (x: number): string => 'a';", "expected number, but got (x: number) => string"),
+        ("const point: number = Point;", "expected number, but got typeof Point"),
+        ("const color: number = Color;", "expected number, but got typeof Color"),
+    ] {
+        assert_eq!(error_message(eval(&format!("{program} {code}"))), expected, "{code}");
+    }
+}
+
+#[test]
+fn resolves_overloads() {
+    let program = r#"
+        function pick(value: string): string;
+        function pick(value: number): number;
+        function pick(value: any): any {
+            return value;
+        }
+
+        function lie(value: string): number;
+        function lie(value: any): any {
+            return value;
+        }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} pick('a');")),
+        Value::String("a".into())
+    );
+    assert_eq!(eval(&format!("{program} pick(1);")), Value::Number(1));
+    assert_eq!(
+        error_message(eval(&format!("{program} pick(true);"))),
+        "no overload of pick accepts (boolean), candidates: pick(value: string) => string; pick(value: number) => number"
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} lie('a');"))),
+        "number expected but string returned"
+    );
+}
+
+#[test]
+fn evaluates_defaults_at_call_time() {
+    let program = r#"
+        let calls = 0;
+
+        function count(): number {
+            calls++;
+            return calls;
+        }
+
+        function scale(a: number, b: number = a * 2): number {
+            return a + b;
+        }
+
+        function tick(at: number = count()): number {
+            return at;
+        }
+
+        function maybe(value?: string): boolean {
+            return typeof value == "undefined";
+        }
+
+        const double = (a: number, b: number = a * 2): number => a + b;
+    "#;
+
+    assert_eq!(eval(&format!("{program} scale(1);")), Value::Number(3));
+    assert_eq!(eval(&format!("{program} scale(1, 1);")), Value::Number(2));
+    assert_eq!(
+        eval(&format!("{program} tick(); tick();")),
+        Value::Number(2)
+    );
+    assert_eq!(eval(&format!("{program} maybe();")), Value::Boolean(true));
+    assert_eq!(eval(&format!("{program} double(2);")), Value::Number(6));
+}
+
+/// Serves modules from memory and counts how often each one is read.
+#[derive(Debug, Default)]
+struct MemoryLoader {
+    files: HashMap<PathBuf, String>,
+    loads: Arc<AtomicUsize>,
+}
+
+impl MemoryLoader {
+    fn new(files: &[(&str, &str)]) -> Self {
+        Self {
+            files: files
+                .iter()
+                .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+                .collect(),
+            loads: Default::default(),
+        }
+    }
+}
+
+impl ModuleLoader for MemoryLoader {
+    fn resolve(&self, specifier: &str, _: &Path) -> Option<PathBuf> {
+        let path = PathBuf::from(format!("{}.ts", specifier.trim_start_matches("./")));
+
+        self.files.contains_key(&path).then_some(path)
+    }
+
+    fn load(&self, path: &Path) -> io::Result<String> {
+        self.loads.fetch_add(1, Ordering::SeqCst);
+
+        Ok(self.files[path].clone())
+    }
+}
+
+fn eval_with_loader(code: &str, loader: MemoryLoader) -> Value {
+    let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+    let mut runtime = Runtime::default();
+
+    runtime.add_module(&Greeter);
+    runtime.set_loader(loader);
+    runtime.eval_program(ast)
+}
+
+#[test]
+fn loads_file_modules() {
+    let directory = std::env::temp_dir().join(format!("tsr-modules-{}", std::process::id()));
+
+    fs::create_dir_all(directory.join("shapes")).unwrap();
+    fs::write(directory.join("math.ts"), "const pi = 3;").unwrap();
+    fs::write(directory.join("view.tsx"), "const view = 1;").unwrap();
+    fs::write(directory.join("shapes/index.ts"), "const sides = 4;").unwrap();
+
+    for (specifier, file) in [
+        ("./math", "math.ts"),
+        ("./math.ts", "math.ts"),
+        ("./view", "view.tsx"),
+        ("./shapes", "shapes/index.ts"),
+        ("./shapes/../math", "math.ts"),
+    ] {
+        assert_eq!(
+            FileLoader.resolve(specifier, &directory),
+            directory.join(file).canonicalize().ok(),
+            "{specifier}"
+        );
+    }
+
+    assert_eq!(FileLoader.resolve("./missing", &directory), None);
+    assert_eq!(FileLoader.resolve("math", &directory), None);
+
+    fs::remove_dir_all(directory).unwrap();
+
+    let loader = MemoryLoader::new(&[("a.ts", "import {} from './b';"), ("b.ts", "const b = 1;")]);
+    let loads = loader.loads.clone();
+
+    assert_eq!(
+        eval_with_loader("import {} from './a'; import {} from './b'; 1;", loader),
+        Value::Number(1)
+    );
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import {} from './a';",
+            MemoryLoader::new(&[
+                ("a.ts", "import {} from './b';"),
+                ("b.ts", "import {} from './a';")
+            ])
+        )),
+        "circular import of module \"./a\""
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import {} from './c';",
+            MemoryLoader::default()
+        )),
+        "failed to resolve module \"./c\", native modules are: greeter"
+    );
+}
+
+#[test]
+fn evaluates_exports() {
+    let loader = || {
+        MemoryLoader::new(&[
+            (
+                "shapes.ts",
+                r#"
+                export const sides = 4;
+                export function area(size: number): number {
+                    return size * size;
+                }
+                const hidden = 1;
+                export { hidden as secret };
+                export default 7;
+                "#,
+            ),
+            (
+                "index.ts",
+                r#"
+                import { sides } from './shapes';
+                export * from './shapes';
+                export { area as square } from './shapes';
+                export default sides;
+                "#,
+            ),
+        ])
+    };
+
+    assert_eq!(
+        eval_with_loader("import { square } from './index'; square(3);", loader()),
+        Value::Number(9)
+    );
+    assert_eq!(
+        eval_with_loader(
+            "import * as shapes from './index'; shapes.secret + 0;",
+            loader()
+        ),
+        Value::Number(1)
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import { missing } from './index';",
+            loader()
+        )),
+        "found \"missing\" while excepted one of next values: square, default, sides, area, secret"
+    );
+
+    let code = "export const a = 1; export { a as b }; export default a + 1;";
+    let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+    let mut runtime = Runtime::default();
+
+    runtime.eval_program(ast);
+
+    // Objects compare by identity, their contents are compared through the inspector
+    assert_eq!(
+        runtime.exports().to_string(),
+        ObjectBuilder::default()
+            .prop("a", 1)
+            .prop("b", 1)
+            .prop("default", 2)
+            .build()
+            .to_string()
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import {} from './twice';",
+            MemoryLoader::new(&[("twice.ts", "export const a = 1; export { a };")])
+        )),
+        "module exports \"a\" more than once"
+    );
+
+    let loader = || {
+        MemoryLoader::new(&[
+            ("d1.ts", "const v = 5; export { v as default };"),
+            (
+                "d2.ts",
+                "export { default } from './d1'; export { default as five } from './d1';",
+            ),
+            ("broken.ts", "export const a = 1;\n  export const = 2;"),
+        ])
+    };
+
+    assert_eq!(
+        eval_with_loader(
+            r#"
+            import value from './d1';
+            import { default as named } from './d1';
+            import { five } from './d2';
+            import again from './d2';
+            [value, named, five, again];
+            "#,
+            loader()
+        )
+        .to_string(),
+        "[ 5, 5, 5, 5 ]"
+    );
+    assert_eq!(
+        eval_with_loader(
+            r#"
+            import value, { five } from './d2';
+            import again, * as all from './d2';
+            [value, five, again, all.five];
+            "#,
+            loader()
+        )
+        .to_string(),
+        "[ 5, 5, 5, 5 ]"
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import value, { five } from './named';",
+            MemoryLoader::new(&[("named.ts", "export const five = 5;")])
+        )),
+        "module \"./named\" has no default export"
+    );
+    assert_eq!(
+        error_message(eval_with_loader("import {} from './broken';", loader())),
+        "failed to load module \"./broken\": invalid syntax at line 2, column 3"
+    );
+}
+
+#[test]
+fn keeps_the_scope_of_modules() {
+    let loader = || {
+        MemoryLoader::new(&[(
+            "circle.ts",
+            r#"
+            const secret = 2;
+            function scale(size: number): number {
+                return size * secret;
+            }
+            export function area(radius: number): number {
+                return Math.floor(Math.PI * scale(radius));
+            }
+            export const reveal = (): number => secret;
+            export class Counter {
+                count: number;
+                constructor() {
+                    this.count = scale(10);
+                }
+            }
+            "#,
+        )])
+    };
+    let eval = |code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+        let mut runtime = Runtime::with_std();
+
+        runtime.set_loader(loader());
+        runtime.eval_program(ast)
+    };
+
+    // Exports see what is private to their module, the importer keeps its own names
+    assert_eq!(
+        eval("const secret = 99; import { area } from './circle'; area(3) + secret;"),
+        Value::Number(117)
+    );
+    assert_eq!(
+        eval("import { reveal } from './circle'; const secret = 99; reveal();"),
+        Value::Number(2)
+    );
+    assert_eq!(
+        eval("import { Counter } from './circle'; new Counter().count + 0;"),
+        Value::Number(20)
+    );
+    assert_eq!(
+        error_message(eval("import { area } from './circle'; scale(1);")),
+        "\"scale\" is not defined"
+    );
+}
+
+#[test]
+fn imports_modules_without_running_the_event_loop() {
+    let clock = VirtualClock::default();
+    let mut runtime = Runtime::with_std();
+    let run = |runtime: &mut Runtime, code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+
+        runtime.eval_program(ast)
+    };
+
+    runtime.set_clock(clock.clone());
+    runtime.set_loader(MemoryLoader::new(&[(
+        "ticker.ts",
+        r#"
+        export const ticks = [0...];
+        ticks.pop();
+        setInterval(() => ticks.push(ticks.length + 1), 1000);
+        "#,
+    )]));
+
+    // Jobs and timers of the importer wait for the program, even across an import
+    assert_eq!(
+        run(
+            &mut runtime,
+            r#"
+            const order = [0...];
+            order.pop();
+            setTimeout(() => order.push('timer'));
+            Promise.resolve('job').then((job) => order.push(job));
+            import { ticks } from './ticker';
+            order.push('import');
+            const pending = await import('./ticker');
+            order.push('dynamic import');
+            order.join(' ') + '';
+            "#
+        ),
+        Value::String("import job dynamic import".into())
+    );
+    assert_eq!(
+        run(&mut runtime, "order.join(' ') + '';"),
+        Value::String("import job dynamic import timer".into())
+    );
+    assert!(runtime.is_waiting());
+
+    // The interval of the module runs on the loop of the importer
+    for _ in 0..2 {
+        clock.advance(Duration::from_millis(1000));
+        assert_eq!(runtime.run_until_idle(), Value::None);
+    }
+    assert_eq!(run(&mut runtime, "ticks.length + 0;"), Value::Number(2));
+}
+
+struct Greeter;
+
+#[tsr_macro::native_module("greeter")]
+impl Greeter {
+    #[func(default, args = [("name", PredefinedType::String)], returns = PredefinedType::String)]
+    fn greet(&self, args: &FArguments) -> impl Into<Value> {
+        format!("hello {}", args.get_string("name"))
+    }
+}
+
+#[test]
+fn imports_defaults_and_erases_types() {
+    let loader = || {
+        MemoryLoader::new(&[
+            ("shapes.ts", "export const sides = 4; export default 7;"),
+            ("plain.ts", "export const sides = 4;"),
+            (
+                "point.ts",
+                "export interface Point { x: number; } type Id = number; export type Name = string;",
+            ),
+        ])
+    };
+
+    for (code, expected) in [
+        ("import seven from './shapes'; seven + 0;", Value::Number(7)),
+        (
+            "import { sides as count } from './shapes'; count + 0;",
+            Value::Number(4),
+        ),
+        (
+            "import { type Shape, sides } from './shapes'; sides + 0;",
+            Value::Number(4),
+        ),
+        (
+            "import type { Shape } from './missing'; 1;",
+            Value::Number(1),
+        ),
+        (
+            "import { Point, type Name as Label } from './point'; const point: Point = { x: 1 }; const label: Label = 'a'; point.x + 0;",
+            Value::Number(1),
+        ),
+        (
+            "import type { Point } from './point'; type Id = string; const id: Id = 'a'; const point: Point = { x: 2 }; point.x + 0;",
+            Value::Number(2),
+        ),
+        (
+            "import hello from 'greeter'; hello('you');",
+            Value::String("hello you".into()),
+        ),
+        (
+            "import { greet } from 'greeter'; greet('me');",
+            Value::String("hello me".into()),
+        ),
+    ] {
+        assert_eq!(eval_with_loader(code, loader()), expected, "{code}");
+    }
+
+    assert_eq!(
+        error_message(eval_with_loader("import shape from './plain';", loader())),
+        "module \"./plain\" has no default export"
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            "import { Point } from './point'; const point: Point = { x: 'a' };",
+            loader()
+        )),
+        "expected Point, but got { x: string }: number expected but string given at \"x\""
+    );
+}
+
+#[test]
+fn imports_modules_dynamically() {
+    let loader = || MemoryLoader::new(&[("shapes.ts", "export const sides = 4;")]);
+
+    assert_eq!(
+        eval_with_loader(
+            "const shapes = await import('./shapes'); shapes.sides + 0;",
+            loader()
+        ),
+        Value::Number(4)
+    );
+    assert_eq!(
+        eval_with_loader(
+            "const name = 'greet' + 'er'; const greeter = await import(name); greeter.greet('you');",
+            loader()
+        ),
+        Value::String("hello you".into())
+    );
+    assert_eq!(
+        error_message(eval_with_loader("await import('./circles');", loader())),
+        "failed to resolve module \"./circles\", native modules are: greeter"
+    );
+    assert_eq!(
+        error_message(eval_with_loader("await import(1);", loader())),
+        "string expected but number given"
+    );
+}
+
+#[test]
+fn merges_namespaces() {
+    let program = r#"
+        namespace Shapes {
+            const hidden = 2;
+            export const sides = hidden * 2;
+        }
+        namespace Shapes {
+            export function double(value: number): number {
+                return value * 2;
+            }
+            export function twice(): number {
+                return double(sides) + hidden;
+            }
+        }
+        namespace Shapes.Polygons {
+            export const triangle = 3;
+        }
+    "#;
+
+    assert_eq!(
+        eval(&format!("{program} Shapes.double(Shapes.sides);")),
+        Value::Number(8)
+    );
+    assert_eq!(
+        eval(&format!(
+            "{program} Shapes.Polygons.triangle + Shapes.sides;"
+        )),
+        Value::Number(7)
+    );
+    assert_eq!(
+        eval(&format!("{program} Shapes.twice();")),
+        Value::Number(10)
+    );
+    assert_eq!(
+        error_message(eval(&format!("{program} hidden + 0;"))),
+        "\"hidden\" is not defined"
+    );
+    assert_eq!(
+        error_message(eval(
+            "const Shapes = 1; namespace Shapes { export const sides = 4; }"
+        )),
+        "namespace \"Shapes\" cannot merge with number"
+    );
+}
+
+#[test]
+fn checks_ambient_declarations_against_the_host() {
+    let loader = MemoryLoader::default;
+
+    assert_eq!(
+        eval_with_loader(
+            r#"
+            declare module "greeter" {
+                export function greet(name: string): string;
+            }
+            import { greet } from "greeter";
+            greet("you");
+            "#,
+            loader()
+        ),
+        Value::String("hello you".into())
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            r#"declare module "greeter" { export function greet(name: number): string; } 1;"#,
+            loader()
+        )),
+        "declaration of module \"greeter\" does not match the host: \
+         (name: number) => string expected but (name: string) => string given at \"greet\""
+    );
+    assert_eq!(
+        error_message(eval_with_loader(
+            r#"declare module "clock" { export function now(): number; } 1;"#,
+            loader()
+        )),
+        "module \"clock\" is declared but not provided by the host"
+    );
+
+    let program = |code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+        let mut runtime = Runtime::default();
+
+        runtime.set_variable("version", Span::default().wrap(Value::Number(2)));
+        runtime.set_variable(
+            "host",
+            Span::default().wrap(
+                ObjectBuilder::default()
+                    .prop("name", Value::String("tsr".into()))
+                    .build(),
+            ),
+        );
+        runtime.eval_program(ast)
+    };
+
+    assert_eq!(
+        program("declare const version: number; declare namespace host { const name: string; } version + 1;"),
+        Value::Number(3)
+    );
+    assert_eq!(
+        error_message(program("declare const version: string; version;")),
+        "declaration of \"version\" does not match the host: string expected but number given"
+    );
+    assert_eq!(
+        error_message(program("declare function log(data: any): void; 1;")),
+        "\"log\" is declared but not provided by the host"
+    );
+}
+
+#[test]
+fn emits_declarations_of_native_modules() {
+    let declaration = Greeter.declaration();
+
+    assert_eq!(
+        declaration,
+        "declare module \"greeter\" {\n    export function greet(name: string): string;\n    export default greet;\n}\n"
+    );
+    assert_eq!(
+        eval_with_loader(&format!("{declaration} 1;"), MemoryLoader::default()),
+        Value::Number(1)
+    );
+
+    let mut config = Module::new("config");
+
+    config.export("version", Value::Number(2));
+    config.export_default(Value::String("tsr".into()));
+
+    assert_eq!(
+        config.declaration(),
+        "declare module \"config\" {\n    export const version: number;\n    const _default: string;\n    export default _default;\n}\n"
+    );
+}
+
+#[test]
+fn inspects_values() {
+    let program = r#"
+        class Point {
+            x: number = 1;
+            label: string = "it's";
+        }
+        const nested = { a: { b: { c: { d: 1 } } }, "two words": [1, 2] };
+    "#;
+
+    let (_, tokens) = Lexer::lex_tokens(
+        format!("{program} import {{ inspect }} from 'util'; [inspect(nested), inspect(new Point()), inspect(new Point(), true), inspect(Point), inspect(inspect)];")
+            .as_bytes()
+            .into(),
+    )
+    .unwrap();
+    let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+    let mut runtime = Runtime::default();
+
+    runtime.add_module(&Util);
+
+    let Value::Array(inspected, _) = runtime.eval_program(ast) else {
+        panic!("expected an array");
+    };
+
+    assert_eq!(
+        *inspected.read(),
+        [
+            "{ a: { b: { c: [Object] } }, 'two words': [ 1, 2 ] }",
+            "Point { x: 1, label: 'it\\'s' }",
+            "Point {\n  x: 1,\n  label: 'it\\'s'\n}",
+            "[class Point]",
+            "[Function: inspect]",
+        ]
+        .map(|string| Value::String(string.into()))
+    );
+    assert_eq!(
+        eval(&format!("{program} nested;")).to_string(),
+        "[Reference: nested]"
+    );
+    assert_eq!(
+        Value::Number(1).inspect(InspectOptions {
+            colors: true,
+            ..Default::default()
+        }),
+        "\u{1b}[33m1\u{1b}[0m"
+    );
+}
+
+#[test]
+fn shares_objects_between_aliases() {
+    assert_eq!(
+        eval("const a = { x: 1 }; const b = a; b.x = 2; a.x + 0;"),
+        Value::Number(2)
+    );
+    assert_eq!(
+        eval("const a = { inner: { x: 1 } }; const b = a.inner; b.x = 2; a.inner.x + 0;"),
+        Value::Number(2)
+    );
+    assert_eq!(
+        eval("const a = [1, 2]; const b = a; a === b;"),
+        Value::Boolean(true)
+    );
+    assert_eq!(
+        eval("const a = { x: 1 }; const b = { x: 1 }; a === b;"),
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        eval(
+            "class Point { x: number = 1; } const p = new Point(); const q = p; q.x = 3; p.x + 0;"
+        ),
+        Value::Number(3)
+    );
+
+    // A cycle outlives every alias of it until it is collected
+    let first = Value::object(Properties::new());
+    let second = Value::object(Properties::from([(
+        Value::String("first".into()),
+        first.clone(),
+    )]));
+
+    if let Value::Object(properties) = &first {
+        properties
+            .write()
+            .insert(Value::String("second".into()), second.clone());
+    }
+
+    let kept = Value::object(Properties::new());
+
+    drop((first, second));
+
+    assert_eq!(Runtime::collect_garbage(), 2);
+    assert_eq!(Runtime::collect_garbage(), 0);
+    assert_eq!(kept.to_string(), "{}");
+}
+
+#[test]
+fn assigns_elements_and_calls_array_methods() {
+    assert_eq!(
+        eval("const grid = [[1, 2], [3, 4]]; grid[1][0] = 7; grid[1][0] + 0;"),
+        Value::Number(7)
+    );
+    assert_eq!(
+        eval("const list = [1...]; list[3] = 4; list.length + 0;"),
+        Value::Number(4)
+    );
+    assert_eq!(
+        error_message(eval("const pair = [1, 2]; pair[2] = 3;")),
+        "index 2 is out of bounds of an array of fixed size 2"
+    );
+    assert_eq!(
+        error_message(eval("const pair = [1, 2]; pair.push(3);")),
+        "\"push\" cannot change the length of an array of fixed size 2"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            const numbers = [3, 1, 2...];
+            numbers.push(5, 4);
+            numbers.pop();
+            const removed = numbers.splice(1, 1);
+            [
+                numbers.join("-"),
+                removed.join(),
+                numbers.map((value) => value * 2).join(),
+                numbers.map() { value + index }.join(),
+                numbers.filter((value) => value > 2).join(),
+                numbers.reduce((total, value) => total + value, 10),
+                numbers.reduce() { accumulator * value },
+                numbers.find((value) => value > 2),
+                numbers.some((value) => value > 4),
+                numbers.every((value) => value > 1),
+                numbers.slice(-2).join(),
+                numbers.includes(5),
+                numbers.indexOf(9),
+                [[1, [2]], 3].flat().length,
+                numbers.flatMap((value) => [value, value]).join(),
+                numbers.sort().join(),
+                numbers.sort((first, second) => second - first).join()
+            ].join(" | ");
+            "#
+        ),
+        Value::String(
+            "3-2-5 | 1 | 6,4,10 | 3,3,7 | 3,5 | 20 | 30 | 3 | true | true | 2,5 | true | -1 | 3 | 3,3,2,2,5,5 | 2,3,5 | 5,3,2"
+                .into()
+        )
+    );
+    assert_eq!(
+        eval("const items = [1, 2]; let total = 0; items.forEach((item) => { total = total + item; }); total + 0;"),
+        Value::Number(3)
+    );
+}
+
+#[test]
+fn calls_string_methods() {
+    assert_eq!(
+        eval(
+            r#"
+            const text = "  Hello, wörld  ";
+            const word = "héllo😀";
+            [
+                word.length,
+                word[1],
+                word.at(-2),
+                word.charCodeAt(1),
+                word.slice(1, -2),
+                word.substring(3, 1),
+                text.indexOf("l"),
+                text.indexOf("l", 5),
+                text.includes("wö"),
+                text.trim().startsWith("Hello"),
+                text.trimEnd().endsWith("wörld"),
+                text.trimStart(),
+                "a-b-c".replace("-", "+"),
+                "a-b-c".replaceAll("-", "+"),
+                "7".padStart(3, "0"),
+                "ab".padEnd(5, "xy"),
+                word.toUpperCase(),
+                "ABC".toLowerCase(),
+                "ab".repeat(3),
+                "a,b".split(",").length
+            ];
+            "#
+        )
+        .to_string(),
+        "[ 7, 'é', '\u{fffd}', 233, 'éllo', 'él', 4, 5, true, true, true, 'Hello, wörld  ', 'a+b-c', 'a+b+c', '007', 'abxyx', 'HÉLLO😀', 'abc', 'ababab', 2 ]"
+    );
+    assert_eq!(eval("const word = 'ab'; word[2];"), Value::None);
+}
+
+#[test]
+fn evaluates_standard_globals() {
+    let eval = eval_std;
+
+    assert_eq!(
+        eval(
+            r#"
+            [
+                Math.max(1, 5, 3),
+                Math.max(1, 2.5),
+                Math.min(),
+                Math.floor(2.7),
+                Math.round(-2.5),
+                Math.sqrt(16),
+                Math.pow(2, 10),
+                parseInt("42px"),
+                parseInt("ff", 16),
+                parseFloat("3.25abc"),
+                Number.isNaN(parseInt("x")),
+                Number.isInteger(4.0)
+            ];
+            "#
+        )
+        .to_string(),
+        "[ 5, 2.5, Infinity, 2, -2, 4, 1024, 42, 255, 3.25, true, true ]"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            [
+                Number("12"),
+                Number(" 2.5 "),
+                Number("0x1f"),
+                Number(""),
+                Number(true),
+                Number(null),
+                Number("12px"),
+                Number("NaN"),
+                Number("-Infinity"),
+                Number(),
+                Number.MAX_SAFE_INTEGER
+            ];
+            "#
+        )
+        .to_string(),
+        "[ 12, 2.5, 31, 0, 1, 0, NaN, NaN, -Infinity, 0, 9007199254740991 ]"
+    );
+    assert_eq!(
+        eval(
+            r#"
+            const point = { y: 2, x: 1 };
+            const merged = Object.assign({ z: 0 }, point, { z: 3 });
+            [Object.keys(point), Object.entries(point), merged, Array.isArray(point), Array.from("ab")];
+            "#
+        )
+        .to_string(),
+        "[ [ 'y', 'x' ], [ [ 'y', 2 ], [ 'x', 1 ] ], { z: 3, y: 2, x: 1 }, false, [ 'a', 'b' ] ]"
+    );
+    assert_eq!(
+        eval(r#"JSON.stringify(JSON.parse('{"b": [1, 2.5, null], "a": "é"}'));"#),
+        Value::String(r#"{"b":[1,2.5,null],"a":"é"}"#.into())
+    );
+    assert_eq!(
+        eval("JSON.stringify({ zeta: 1, alpha: { c: 1, b: 2 } });"),
+        Value::String(r#"{"zeta":1,"alpha":{"c":1,"b":2}}"#.into())
+    );
+    assert_eq!(
+        error_message(eval("JSON.parse('[1, }');")),
+        "unexpected token } in JSON at position 4"
+    );
+    assert_eq!(
+        error_message(eval(
+            "const node = { name: 'root' }; node.self = node; JSON.stringify(node);"
+        )),
+        "converting circular structure to JSON"
+    );
+}
+
+#[test]
+fn formats_console_tables() {
+    let rows = Value::array(
+        vec![
+            Value::object(Properties::from([(
+                Value::String("a".into()),
+                Value::Number(1),
+            )])),
+            Value::Number(2),
+        ],
+        ArraySize::Dynamic,
+    );
+
+    assert_eq!(
+        table(&rows),
+        [
+            "┌─────────┬───┬────────┐",
+            "│ (index) │ a │ Values │",
+            "├─────────┼───┼────────┤",
+            "│ 0       │ 1 │        │",
+            "│ 1       │   │ 2      │",
+            "└─────────┴───┴────────┘",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn evaluates_collections_and_symbols() {
+    assert_eq!(
+        eval_std(
+            r#"
+            const key = { id: 1 };
+            const map = new Map([[1, 'one'], ['1', 'string one']]);
+            map.set(key, 'object').set(NaN, 'nan').set(1, 'uno');
+            [map, map.get(1), map.get(key), map.get({ id: 1 }), map.get(NaN), map.size];
+            "#
+        )
+        .to_string(),
+        "[ Map(4) { 1 => 'uno', '1' => 'string one', { id: 1 } => 'object', NaN => 'nan' }, 'uno', 'object', undefined, 'nan', 4 ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            const numbers = new Set([3, 1, 3, 2]);
+            numbers.delete(1);
+            let sum = 0;
+            numbers.forEach() { sum = sum + value };
+            [numbers, numbers.has(3), numbers.has(1), Array.from(numbers), [sum]];
+            "#
+        )
+        .to_string(),
+        "[ Set(2) { 3, 2 }, true, false, [ 3, 2 ], [ 5 ] ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            const first = Symbol('id');
+            const registered = Symbol.for('app');
+            [first == Symbol('id'), first == first, first.description, typeof first, registered == Symbol.for('app'), Symbol.keyFor(registered), Symbol.iterator];
+            "#
+        )
+        .to_string(),
+        "[ false, true, 'id', 'symbol', true, 'app', Symbol(Symbol.iterator) ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            const tag = Symbol('tag');
+            const make = () => ({ a: 1 });
+            const labels = make();
+            labels[tag] = 'symbol';
+            labels[1] = 'one';
+            make()[tag] = 'dropped';
+            [labels, labels[tag], labels[1]];
+            "#
+        )
+        .to_string(),
+        "[ { '1': 'one', a: 1, [Symbol(tag)]: 'symbol' }, 'symbol', 'one' ]"
+    );
+    assert_eq!(
+        error_message(eval_std("const list = [1]; list[Symbol('x')] = 2; list;")),
+        "can't set property Symbol(x) of array"
+    );
+    assert_eq!(
+        error_message(eval_std("const list = [1]; 5 = 2; list;")),
+        "invalid left-hand side in assignment"
+    );
+    assert_eq!(
+        error_message(eval_std("const id: symbol = 'id';")),
+        "expected symbol, but got string"
+    );
+    assert_eq!(
+        error_message(eval_std("const map: Map<string, number> = new Map([['a', 'b']]);")),
+        "expected Map<string, number>, but got Map<string, string>: number expected but string given at \"['a']\""
+    );
+    assert_eq!(
+        error_message(eval_std(
+            "const cache = new WeakMap(); cache.set('key', 1);"
+        )),
+        "invalid value used as weak map key: string"
+    );
+}
+
+#[test]
+fn iterates_generators_and_iterables() {
+    assert_eq!(
+        eval(
+            r#"
+            function* naturals(start: number): Generator<number> {
+                yield start;
+                yield* naturals(start + 1);
+            }
+            const [first, , third, ...rest] = [1, 2, 3, 4, 5];
+            const [a, b, c] = naturals(0);
+            [first, third, rest, a, b, c];
+            "#
+        )
+        .to_string(),
+        "[ 1, 3, [ 4, 5 ], 0, 1, 2 ]"
+    );
+    // Generators dropped while suspended are unwound once their scope is cleared
+    assert_eq!(
+        eval_std(
+            r#"
+            function* pair(): Generator<number> {
+                yield 1;
+                yield 2;
+            }
+            let total = 0;
+            for (const step of Array.from({ length: 50 })) {
+                const started = pair();
+                total = total + started.next().value;
+            }
+            total + 0;
+            "#
+        ),
+        Value::Number(50)
+    );
+    assert_eq!(
+        eval(
+            r#"
+            function* count(start: number): Generator<number> {
+                yield start;
+                yield* [start + 1, start + 2];
+                const sent = yield 0;
+                return sent;
+            }
+            const counter = count(5);
+            [counter.next(), counter.next(), counter.next(), counter.next(), counter.next(42), counter.next()];
+            "#
+        )
+        .to_string(),
+        "[ { value: 5, done: false }, { value: 6, done: false }, { value: 7, done: false }, { value: 0, done: false }, { value: 42, done: true }, { value: undefined, done: true } ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            const out = [0...];
+            out.pop();
+            for (const value of [1, 2]) { out.push(value * 2); }
+            for (const [key, value] of new Map([['a', 1]])) { out.push(key); out.push(value); }
+            for (const char of "hi") { out.push(char); }
+            for (const value of new Set([7, 7, 8])) { out.push(value); }
+            const state = { n: 0 };
+            const countdown = {
+                [Symbol.iterator]: () => {
+                    return { next: () => { state.n = state.n + 1; return { value: state.n, done: state.n > 2 }; } };
+                }
+            };
+            [out, [...countdown, ...'ab']];
+            "#
+        )
+        .to_string(),
+        "[ [ 2, 4, 'a', 1, 'h', 'i', 7, 8 ], [ 1, 2, 'a', 'b' ] ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            function* letters(): Generator<string> { yield 'a'; yield 'b'; yield 'c'; }
+            function add(a: number, b: number, c: number): number { return a + b + c; }
+            function first(): string { for (const letter of letters()) { return letter; } return ''; }
+            const closed = letters();
+            closed.next();
+            [add(...[1, 2], 3), Array.from(letters()), first(), closed.return('z'), closed.next()];
+            "#
+        )
+        .to_string(),
+        "[ 6, [ 'a', 'b', 'c' ], 'a', { value: 'z', done: true }, { value: undefined, done: true } ]"
+    );
+
+    let program = |code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+        let mut runtime = Runtime::with_std();
+
+        runtime.set_variable(
+            "range",
+            Span::default().wrap(
+                FunctionBuilder::new("range")
+                    .param("end", PredefinedType::Number)
+                    .returns(PredefinedType::Any)
+                    .build(|args| {
+                        let end = args.get_number("end");
+
+                        args.returns(Value::iterator((0..end).map(Value::Number)));
+                    }),
+            ),
+        );
+        runtime.eval_program(ast)
+    };
+
+    assert_eq!(
+        program("[...range(3), Array.from(range(2))];").to_string(),
+        "[ 0, 1, 2, [ 0, 1 ] ]"
+    );
+    assert_eq!(
+        error_message(eval("for (const value of 5) { }")),
+        "number is not iterable"
+    );
+    assert_eq!(
+        error_message(eval("yield 1;")),
+        "yield is only valid in generator functions"
+    );
+    assert_eq!(
+        error_message(eval(
+            "function* broken(): Generator<number> { yield 1; missing(); } const it = broken(); it.next(); it.next();"
+        )),
+        "\"missing\" is not defined"
+    );
+}
+
+#[test]
+fn runs_async_functions_and_promises() {
+    assert_eq!(
+        eval_std(
+            r#"
+            const log = [0...];
+            log.pop();
+            Promise.resolve(1).then((value) => log.push(value));
+            async function step(): Promise<void> { log.push('a'); await null; log.push('b'); }
+            step();
+            log.push('sync');
+            await null;
+            [log];
+            "#
+        )
+        .to_string(),
+        "[ [ 'a', 'sync', 1, 'b' ] ]"
+    );
+    assert_eq!(
+        eval_std(
+            r#"
+            async function add(a: number, b: number): Promise<number> {
+                const first = await Promise.resolve(a);
+                return first + b;
+            }
+            const twice = async () => (await add(1, 2)) * 2;
+            async function fail(): Promise<number> { await Promise.reject('no'); return 1; }
+            const done = [0...];
+            done.pop();
+            const kept = await Promise.resolve(5).finally(() => done.push('finally'));
+            [
+                await twice(),
+                await fail().catch((reason) => reason),
+                await new Promise((resolve, reject) => reject('early')).then((value) => value, (reason) => [reason]),
+                kept,
+                done,
+                await Promise.all([1, add(1, 1), new Promise((resolve) => resolve(3))]),
+                await Promise.race([Promise.resolve('a'), 'b']),
+                Promise.resolve(1),
+                new Promise((resolve) => 1)
+            ];
+            "#
+        )
+        .to_string(),
+        "[ 6, 'no', [ 'early' ], 5, [ 'finally' ], [ 1, 2, 3 ], 'a', Promise { 1 }, Promise { <pending> } ]"
+    );
+
+    let program = |code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+        let mut runtime = Runtime::with_std();
+
+        runtime.set_variable(
+            "fetch",
+            Span::default().wrap(
+                FunctionBuilder::new("fetch")
+                    .param("id", PredefinedType::Number)
+                    .returns(PredefinedType::Any)
+                    .build(|args| {
+                        let id = args.get_number("id");
+                        let (promise, resolver) = args.promise();
+
+                        // Settled later by the host, like a task of an async Rust runtime
+                        std::thread::spawn(move || match id {
+                            0 => drop(resolver),
+                            id => resolver.resolve(format!("item {id}")),
+                        });
+
+                        args.returns(promise);
+                    }),
+            ),
+        );
+        runtime.eval_program(ast)
+    };
+
+    assert_eq!(
+        program("const item = fetch(1); [await item, await Promise.all([fetch(2), fetch(3)])];")
+            .to_string(),
+        "[ 'item 1', [ 'item 2', 'item 3' ] ]"
+    );
+    assert_eq!(
+        error_message(program("await fetch(0);")),
+        "the host dropped the promise without settling it"
+    );
+    assert_eq!(
+        error_message(eval_std("Promise.reject('unhandled'); 1;")),
+        "unhandled"
+    );
+    assert_eq!(
+        error_message(eval_std("await new Promise((resolve) => 1);")),
+        "awaited promise can never settle"
+    );
+    assert_eq!(
+        error_message(eval_std("Promise(1);")),
+        "Promise constructor cannot be invoked without \"new\""
+    );
+}
+
+#[test]
+fn runs_timers_on_a_virtual_clock() {
+    let clock = VirtualClock::default();
+    let mut runtime = Runtime::with_std();
+    let run = |runtime: &mut Runtime, code: &str| {
+        let (_, tokens) = Lexer::lex_tokens(code.as_bytes().into()).unwrap();
+        let (_, ast) = Parser::parse_tokens(&tokens).unwrap();
+
+        runtime.eval_program(ast)
+    };
+
+    runtime.set_clock(clock.clone());
+
+    assert_eq!(
+        run(
+            &mut runtime,
+            r#"
+            const log = [0...];
+            log.pop();
+            let ticks = 0;
+            const ticker = setInterval(() => {
+                ticks = ticks + 1;
+                log.push(ticks);
+                if (ticks == 3) { clearInterval(ticker); }
+            }, 100);
+            setTimeout((name) => log.push(name), 150, 'later');
+            setTimeout(() => log.push('soon'));
+            const skipped = setTimeout(() => log.push('skipped'), 50);
+            clearTimeout(skipped);
+            Promise.resolve('job').then((job) => log.push(job));
+            log.push('sync');
+            [log];
+            "#
+        )
+        .to_string(),
+        "[ [ 'sync', 'job', 'soon' ] ]"
+    );
+    assert!(runtime.is_waiting());
+
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(runtime.run_until_idle(), Value::None);
+    assert_eq!(
+        run(&mut runtime, "[log];").to_string(),
+        "[ [ 'sync', 'job', 'soon', 1, 'later' ] ]"
+    );
+
+    // Ticks the interval missed are skipped, it runs again an interval after it last ran
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(runtime.run_until_idle(), Value::None);
+    assert!(runtime.is_waiting());
+    assert_eq!(
+        run(&mut runtime, "[log];").to_string(),
+        "[ [ 'sync', 'job', 'soon', 1, 'later', 2 ] ]"
+    );
+
+    clock.advance(Duration::from_millis(100));
+    assert_eq!(runtime.run_until_idle(), Value::None);
+    assert!(!runtime.is_waiting());
+    assert_eq!(
+        run(&mut runtime, "[log];").to_string(),
+        "[ [ 'sync', 'job', 'soon', 1, 'later', 2, 3 ] ]"
+    );
+
+    // A clock moved past several ticks runs an interval once
+    run(
+        &mut runtime,
+        "const spins = [0...]; spins.pop(); const spinner = setInterval(() => spins.push(1), 0);",
+    );
+    clock.advance(Duration::from_millis(5));
+    assert_eq!(runtime.run_until_idle(), Value::None);
+    assert_eq!(
+        run(&mut runtime, "clearInterval(spinner); spins.length + 0;"),
+        Value::Number(1)
+    );
+
+    run(&mut runtime, "setTimeout(() => JSON.parse('{'), 10);");
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(
+        error_message(runtime.run_until_idle()),
+        "unexpected end of JSON input"
+    );
+    assert_eq!(
+        error_message(run(
+            &mut runtime,
+            "await new Promise((resolve) => setTimeout(resolve, 10));"
+        )),
+        "awaited promise waits on timers the clock doesn't advance"
+    );
+    assert_eq!(
+        error_message(eval_std("setTimeout(5, 10);")),
+        "number is not a function"
+    );
+}